@@ -1,16 +1,103 @@
-//! Simple aerobraking pass integrator using exponential atmospheres.
+//! Simple aerobraking pass integrator using exponential or layered atmospheres.
 
+use solar_core::angles::acos_clamped;
 use thiserror::Error;
 
+/// Sutton–Graves convective heating coefficient for air, in SI units (kg^0.5 / m). Also the
+/// default for other nitrogen-dominated atmospheres.
+pub const SUTTON_GRAVES_K: f64 = 1.7415e-4;
+/// Sutton–Graves coefficient for the CO₂-dominated atmospheres of Mars and Venus.
+pub const SUTTON_GRAVES_K_CO2: f64 = 1.8980e-4;
+
+/// Density profile of a planetary atmosphere.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AtmosphereModel {
+    /// Single exponential, `rho0 * exp(-h / scale_height)`.
+    Exponential { rho0: f64, scale_height: f64 },
+    /// Piecewise exponential: each `(alt_m, rho, scale_height)` layer starts at `alt_m` with
+    /// density `rho` and decays with its own scale height up to the next layer. Altitudes below
+    /// the lowest layer extrapolate that layer downwards.
+    Layered { layers: Vec<(f64, f64, f64)> },
+}
+
+impl AtmosphereModel {
+    /// Density (kg/m³) at `altitude_m` above the reference radius.
+    pub fn density(&self, altitude_m: f64) -> f64 {
+        match self {
+            AtmosphereModel::Exponential { rho0, scale_height } => {
+                rho0 * f64::exp(-altitude_m / scale_height)
+            }
+            AtmosphereModel::Layered { layers } => {
+                let layer = layers
+                    .iter()
+                    .filter(|(base, _, _)| *base <= altitude_m)
+                    .max_by(|a, b| a.0.total_cmp(&b.0))
+                    .or_else(|| layers.iter().min_by(|a, b| a.0.total_cmp(&b.0)));
+                match layer {
+                    Some((base, rho, scale_height)) => {
+                        rho * f64::exp(-(altitude_m - base) / scale_height)
+                    }
+                    None => 0.0,
+                }
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), AerobrakeError> {
+        let layers = match self {
+            AtmosphereModel::Exponential { rho0, scale_height } => {
+                vec![(0.0, *rho0, *scale_height)]
+            }
+            AtmosphereModel::Layered { layers } => layers.clone(),
+        };
+        if layers
+            .iter()
+            .any(|(_, _, scale_height)| *scale_height <= 0.0)
+        {
+            return Err(AerobrakeError::InvalidScaleHeight);
+        }
+        if layers.is_empty() || layers.iter().any(|(_, rho, _)| *rho <= 0.0) {
+            return Err(AerobrakeError::MissingAtmosphere);
+        }
+        Ok(())
+    }
+}
+
 /// Properties describing the target planet and its atmosphere.
 #[derive(Debug, Clone)]
 pub struct PlanetEntryContext {
     pub mu_m3_s2: f64,
     pub radius_m: f64,
-    pub surface_density_kg_m3: f64,
-    pub scale_height_m: f64,
+    pub atmosphere: AtmosphereModel,
     pub target_periapsis_altitude_m: f64,
     pub exit_altitude_m: f64,
+    /// Sutton–Graves coefficient of the atmosphere's gas mix (kg^0.5 / m).
+    pub sutton_graves_k: f64,
+}
+
+impl PlanetEntryContext {
+    /// Context for a single-exponential atmosphere, as described by the body catalog, heated
+    /// with the air coefficient [`SUTTON_GRAVES_K`].
+    pub fn exponential(
+        mu_m3_s2: f64,
+        radius_m: f64,
+        surface_density_kg_m3: f64,
+        scale_height_m: f64,
+        target_periapsis_altitude_m: f64,
+        exit_altitude_m: f64,
+    ) -> Self {
+        Self {
+            mu_m3_s2,
+            radius_m,
+            atmosphere: AtmosphereModel::Exponential {
+                rho0: surface_density_kg_m3,
+                scale_height: scale_height_m,
+            },
+            target_periapsis_altitude_m,
+            exit_altitude_m,
+            sutton_graves_k: SUTTON_GRAVES_K,
+        }
+    }
 }
 
 /// Aerodynamic characteristics of the vehicle during entry.
@@ -18,6 +105,12 @@ pub struct PlanetEntryContext {
 pub struct VehicleEntryContext {
     pub ballistic_coefficient_kg_m2: f64,
     pub lift_to_drag: Option<f64>,
+    /// Effective nose radius for stagnation-point heating.
+    pub nose_radius_m: f64,
+    /// Bank angle (rad) as a function of altitude (m), linearly interpolated and held constant
+    /// beyond the end points. `Some` switches to a lifting trajectory integration in which
+    /// `lift_to_drag` steers the vehicle instead of being folded into the ballistic coefficient.
+    pub bank_angle_schedule: Option<Vec<(f64, f64)>>,
 }
 
 /// Request to simulate a single aerobraking pass.
@@ -37,6 +130,16 @@ pub struct AerobrakeResult {
     pub peak_deceleration_m_s2: f64,
     pub periapsis_altitude_m: f64,
     pub integration_steps: usize,
+    /// Orbital period of the post-pass orbit; `None` while the vehicle remains unbound.
+    pub pass_duration_s: Option<f64>,
+    /// Time spent inside the sensible atmosphere during the pass.
+    pub atmospheric_time_s: f64,
+    /// Peak stagnation-point convective heat flux (Sutton–Graves).
+    pub peak_heat_flux_w_m2: f64,
+    /// Stagnation-point heat flux integrated over the pass.
+    pub integrated_heat_load_j_m2: f64,
+    /// Flight-path angle when leaving the atmosphere (positive climbing).
+    pub exit_flight_path_angle_rad: f64,
 }
 
 #[derive(Debug, Error)]
@@ -45,25 +148,43 @@ pub enum AerobrakeError {
     InvalidScaleHeight,
     #[error("ballistic coefficient must be positive")]
     InvalidBallisticCoefficient,
+    #[error("nose radius must be positive")]
+    InvalidNoseRadius,
     #[error("hyperbolic periapsis lies below the planetary surface")]
     PeriapsisBelowSurface,
     #[error("insufficient atmospheric data for aerobraking")]
     MissingAtmosphere,
+    #[error("lifting trajectory reached the planetary surface")]
+    SurfaceImpact,
+    #[error("lifting trajectory did not leave the atmosphere")]
+    NoAtmosphericExit,
 }
 
+/// Fixed RK4 step for lifting passes.
+const LIFTING_STEP_S: f64 = 0.5;
+/// Longest lifting pass integrated before giving up on an atmospheric exit.
+const MAX_LIFTING_PASS_S: f64 = 4.0 * 3_600.0;
+
 /// Simulate a ballistic aerobraking pass and estimate drag impulse.
 ///
 /// The integrator assumes a two-body hyperbolic fly-by and integrates
 /// drag along the unperturbed trajectory using an exponential atmosphere.
+/// Stagnation heating follows Sutton–Graves, `q = k √(ρ / r_n) v³`, with `k` taken from
+/// [`PlanetEntryContext::sutton_graves_k`].
+///
+/// With a bank-angle schedule the pass is instead integrated as a planar point-mass trajectory
+/// from the atmospheric interface, with the vertical lift component `L cos σ` bending the path;
+/// `periapsis_altitude_m` then reports the lowest altitude actually reached.
 pub fn simulate_ballistic_pass(
     request: &AerobrakeRequest,
 ) -> Result<AerobrakeResult, AerobrakeError> {
-    if request.planet.scale_height_m <= 0.0 {
-        return Err(AerobrakeError::InvalidScaleHeight);
-    }
+    request.planet.atmosphere.validate()?;
     if request.vehicle.ballistic_coefficient_kg_m2 <= 0.0 {
         return Err(AerobrakeError::InvalidBallisticCoefficient);
     }
+    if request.vehicle.nose_radius_m <= 0.0 {
+        return Err(AerobrakeError::InvalidNoseRadius);
+    }
 
     let mu = request.planet.mu_m3_s2;
     let radius = request.planet.radius_m;
@@ -83,14 +204,15 @@ pub fn simulate_ballistic_pass(
             peak_deceleration_m_s2: 0.0,
             periapsis_altitude_m: h_target,
             integration_steps: 0,
+            pass_duration_s: None,
+            atmospheric_time_s: 0.0,
+            peak_heat_flux_w_m2: 0.0,
+            integrated_heat_load_j_m2: 0.0,
+            exit_flight_path_angle_rad: 0.0,
         });
     }
 
-    let scale_height = request.planet.scale_height_m;
-    let rho0 = request.planet.surface_density_kg_m3;
-    if rho0 <= 0.0 {
-        return Err(AerobrakeError::MissingAtmosphere);
-    }
+    let atmosphere = &request.planet.atmosphere;
 
     // Hyperbolic orbit parameters.
     let a = -mu / (v_inf * v_inf);
@@ -99,14 +221,20 @@ pub fn simulate_ballistic_pass(
     let p = h_ang * h_ang / mu;
 
     let r_exit = radius + h_exit;
+    if let Some(schedule) = &request.vehicle.bank_angle_schedule {
+        return simulate_lifting_pass(request, schedule, v_inf, h_ang, r_exit);
+    }
     let cos_f_max = ((p / r_exit) - 1.0) / e;
-    let f_max = cos_f_max.clamp(-1.0, 1.0).acos();
+    let f_max = acos_clamped(cos_f_max);
 
     let steps = 800; // dense sampling for smooth integrals
     let df = 2.0 * f_max / steps as f64;
     let mut delta_v_drag = 0.0_f64;
     let mut peak_q = 0.0_f64;
     let mut peak_accel = 0.0_f64;
+    let mut atmospheric_time = 0.0_f64;
+    let mut peak_heat_flux = 0.0_f64;
+    let mut heat_load = 0.0_f64;
 
     let mut beta = request.vehicle.ballistic_coefficient_kg_m2;
     if let Some(ld) = request.vehicle.lift_to_drag {
@@ -127,7 +255,7 @@ pub fn simulate_ballistic_pass(
             continue;
         }
         let h = r - radius;
-        let rho = rho0 * f64::exp(-h / scale_height);
+        let rho = atmosphere.density(h);
         if rho < 1.0e-12 {
             continue;
         }
@@ -137,13 +265,36 @@ pub fn simulate_ballistic_pass(
         let dynamic_pressure = 0.5 * rho * v * v;
         peak_q = peak_q.max(dynamic_pressure);
         peak_accel = peak_accel.max(a_drag);
+        let heat_flux = request.planet.sutton_graves_k
+            * (rho / request.vehicle.nose_radius_m).sqrt()
+            * v
+            * v
+            * v;
+        peak_heat_flux = peak_heat_flux.max(heat_flux);
 
         let dt = (r * r / h_ang) * df;
         delta_v_drag += a_drag * dt;
+        atmospheric_time += dt;
+        heat_load += heat_flux * dt;
     }
 
     let final_v_inf = (v_inf - delta_v_drag).max(0.0);
 
+    // Energy of the post-pass orbit, treating the drag impulse as applied at periapsis.
+    let v_periapsis = (v_inf * v_inf + 2.0 * mu / rp).sqrt();
+    let v_after = (v_periapsis - delta_v_drag).max(0.0);
+    let energy = 0.5 * v_after * v_after - mu / rp;
+    let pass_duration = if energy < 0.0 {
+        let sma = -mu / (2.0 * energy);
+        Some(2.0 * std::f64::consts::PI * (sma.powi(3) / mu).sqrt())
+    } else {
+        None
+    };
+
+    // The unperturbed conic leaves the atmosphere mirroring its entry angle.
+    let v_exit = (mu * (2.0 / r_exit - 1.0 / a)).sqrt();
+    let exit_flight_path_angle = acos_clamped(h_ang / (r_exit * v_exit));
+
     Ok(AerobrakeResult {
         delta_v_drag_m_s: delta_v_drag,
         final_vinf_m_s: final_v_inf,
@@ -151,5 +302,148 @@ pub fn simulate_ballistic_pass(
         peak_deceleration_m_s2: peak_accel,
         periapsis_altitude_m: h_target,
         integration_steps: steps + 1,
+        pass_duration_s: pass_duration,
+        atmospheric_time_s: atmospheric_time,
+        peak_heat_flux_w_m2: peak_heat_flux,
+        integrated_heat_load_j_m2: heat_load,
+        exit_flight_path_angle_rad: exit_flight_path_angle,
     })
 }
+
+/// Planar entry state: radius, speed and flight-path angle.
+#[derive(Clone, Copy)]
+struct EntryState {
+    r: f64,
+    v: f64,
+    gamma: f64,
+}
+
+/// Bank angle at `altitude_m`, interpolated from an altitude-sorted schedule.
+fn bank_angle_at(schedule: &[(f64, f64)], altitude_m: f64) -> f64 {
+    match schedule {
+        [] => 0.0,
+        [(_, bank)] => *bank,
+        _ => {
+            let (first, last) = (schedule[0], schedule[schedule.len() - 1]);
+            if altitude_m <= first.0 {
+                return first.1;
+            }
+            if altitude_m >= last.0 {
+                return last.1;
+            }
+            let upper = schedule.partition_point(|(alt, _)| *alt < altitude_m);
+            let (lo, hi) = (schedule[upper - 1], schedule[upper]);
+            let span = hi.0 - lo.0;
+            if span <= 0.0 {
+                return hi.1;
+            }
+            lo.1 + (hi.1 - lo.1) * (altitude_m - lo.0) / span
+        }
+    }
+}
+
+/// Integrate a lifting pass from the atmospheric interface on the approach hyperbola until the
+/// vehicle climbs back out through `r_exit`.
+fn simulate_lifting_pass(
+    request: &AerobrakeRequest,
+    schedule: &[(f64, f64)],
+    v_inf: f64,
+    h_ang: f64,
+    r_exit: f64,
+) -> Result<AerobrakeResult, AerobrakeError> {
+    let mu = request.planet.mu_m3_s2;
+    let radius = request.planet.radius_m;
+    let atmosphere = &request.planet.atmosphere;
+    let beta = request.vehicle.ballistic_coefficient_kg_m2;
+    let lift_to_drag = request.vehicle.lift_to_drag.unwrap_or(0.0);
+    let mut schedule = schedule.to_vec();
+    schedule.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let density = |r: f64| atmosphere.density(r - radius);
+    let derivative = |state: EntryState| {
+        let rho = density(state.r);
+        let drag = 0.5 * rho * state.v * state.v / beta;
+        let lift = lift_to_drag * drag * bank_angle_at(&schedule, state.r - radius).cos();
+        let g = mu / (state.r * state.r);
+        EntryState {
+            r: state.v * state.gamma.sin(),
+            v: -drag - g * state.gamma.sin(),
+            gamma: lift / state.v + (state.v / state.r - g / state.v) * state.gamma.cos(),
+        }
+    };
+    let advance = |state: EntryState, k: EntryState, dt: f64| EntryState {
+        r: state.r + k.r * dt,
+        v: state.v + k.v * dt,
+        gamma: state.gamma + k.gamma * dt,
+    };
+
+    let v_entry = (v_inf * v_inf + 2.0 * mu / r_exit).sqrt();
+    let mut state = EntryState {
+        r: r_exit,
+        v: v_entry,
+        gamma: -acos_clamped(h_ang / (r_exit * v_entry)),
+    };
+
+    let dt = LIFTING_STEP_S;
+    let mut elapsed = 0.0_f64;
+    let mut steps = 0_usize;
+    let mut delta_v_drag = 0.0_f64;
+    let mut peak_q = 0.0_f64;
+    let mut peak_accel = 0.0_f64;
+    let mut peak_heat_flux = 0.0_f64;
+    let mut heat_load = 0.0_f64;
+    let mut min_r = state.r;
+
+    while elapsed < MAX_LIFTING_PASS_S {
+        let rho = density(state.r);
+        let dynamic_pressure = 0.5 * rho * state.v * state.v;
+        let drag = dynamic_pressure / beta;
+        let heat_flux = request.planet.sutton_graves_k
+            * (rho / request.vehicle.nose_radius_m).sqrt()
+            * state.v.powi(3);
+        peak_q = peak_q.max(dynamic_pressure);
+        peak_accel = peak_accel.max(drag * (1.0 + lift_to_drag * lift_to_drag).sqrt());
+        peak_heat_flux = peak_heat_flux.max(heat_flux);
+        delta_v_drag += drag * dt;
+        heat_load += heat_flux * dt;
+
+        let k1 = derivative(state);
+        let k2 = derivative(advance(state, k1, 0.5 * dt));
+        let k3 = derivative(advance(state, k2, 0.5 * dt));
+        let k4 = derivative(advance(state, k3, dt));
+        state = EntryState {
+            r: state.r + dt / 6.0 * (k1.r + 2.0 * k2.r + 2.0 * k3.r + k4.r),
+            v: state.v + dt / 6.0 * (k1.v + 2.0 * k2.v + 2.0 * k3.v + k4.v),
+            gamma: state.gamma + dt / 6.0 * (k1.gamma + 2.0 * k2.gamma + 2.0 * k3.gamma + k4.gamma),
+        };
+        elapsed += dt;
+        steps += 1;
+        min_r = min_r.min(state.r);
+
+        if state.r <= radius || state.v <= 0.0 {
+            return Err(AerobrakeError::SurfaceImpact);
+        }
+        if state.r >= r_exit && state.gamma > 0.0 {
+            let energy = 0.5 * state.v * state.v - mu / state.r;
+            let pass_duration = (energy < 0.0).then(|| {
+                let sma = -mu / (2.0 * energy);
+                2.0 * std::f64::consts::PI * (sma.powi(3) / mu).sqrt()
+            });
+            return Ok(AerobrakeResult {
+                delta_v_drag_m_s: delta_v_drag,
+                final_vinf_m_s: (2.0 * energy).max(0.0).sqrt(),
+                peak_dynamic_pressure_pa: peak_q,
+                peak_deceleration_m_s2: peak_accel,
+                periapsis_altitude_m: min_r - radius,
+                integration_steps: steps,
+                pass_duration_s: pass_duration,
+                atmospheric_time_s: elapsed,
+                peak_heat_flux_w_m2: peak_heat_flux,
+                integrated_heat_load_j_m2: heat_load,
+                exit_flight_path_angle_rad: state.gamma,
+            });
+        }
+    }
+
+    Err(AerobrakeError::NoAtmosphericExit)
+}
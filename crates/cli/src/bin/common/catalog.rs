@@ -0,0 +1,71 @@
+use anyhow::anyhow;
+use solar_travel_calculator::config::{PlanetConfig, VehicleConfig};
+use solar_travel_calculator::ephemeris;
+use solar_travel_calculator::propulsion::{PropulsionMode, Vehicle as PropulsionVehicle};
+use solar_travel_calculator::transfer::vehicle as transfer_vehicle;
+
+/// Look up a catalog body and confirm its `spice_name` is known to the loaded kernels.
+pub(super) fn find_body(planets: &[PlanetConfig], name: &str) -> anyhow::Result<PlanetConfig> {
+    let upper = name.to_uppercase();
+    let body = planets
+        .iter()
+        .find(|p| p.name.to_uppercase() == upper)
+        .cloned()
+        .ok_or_else(|| anyhow!("Planet/moon '{}' not found in catalog", name))?;
+    ephemeris::resolve_body(&body.spice_name)
+        .map_err(|err| anyhow!("catalog entry '{}' has a bad spice_name: {err}", body.name))?;
+    Ok(body)
+}
+
+/// Resolve the origin/destination pair, rejecting a transfer from a body to itself.
+pub(super) fn find_body_pair(
+    planets: &[PlanetConfig],
+    from: &str,
+    to: &str,
+) -> anyhow::Result<(PlanetConfig, PlanetConfig)> {
+    let origin = find_body(planets, from)?;
+    let destination = find_body(planets, to)?;
+    if origin
+        .spice_name
+        .eq_ignore_ascii_case(&destination.spice_name)
+    {
+        return Err(anyhow!(
+            "Origin and destination are both '{}'; choose two different bodies",
+            origin.name
+        ));
+    }
+    Ok((origin, destination))
+}
+
+/// Catalog entry for the planet `body` orbits, or `None` for planets and unknown parents.
+pub(super) fn find_parent(planets: &[PlanetConfig], body: &PlanetConfig) -> Option<PlanetConfig> {
+    let parent = body.parent_spice.as_ref()?.to_uppercase();
+    planets
+        .iter()
+        .find(|p| p.spice_name.to_uppercase() == parent)
+        .cloned()
+}
+
+/// The requested vehicle, or the first impulsive one in the catalog; window datasets only
+/// model impulsive burns.
+pub(super) fn select_impulsive_vehicle(
+    catalog: &[VehicleConfig],
+    requested: Option<&str>,
+) -> anyhow::Result<PropulsionVehicle> {
+    if requested.is_some() {
+        let vehicle = transfer_vehicle::select(catalog, requested)?;
+        if !matches!(vehicle.propulsion, PropulsionMode::Impulsive { .. }) {
+            return Err(anyhow!(
+                "Vehicle '{}' is not impulsive; window datasets require impulsive propulsion",
+                vehicle.name
+            ));
+        }
+        return Ok(vehicle);
+    }
+
+    catalog
+        .iter()
+        .filter_map(|cfg| transfer_vehicle::from_config(cfg).ok())
+        .find(|vehicle| matches!(vehicle.propulsion, PropulsionMode::Impulsive { .. }))
+        .ok_or_else(|| anyhow!("No impulsive vehicle found in catalog"))
+}
@@ -1,19 +1,66 @@
 //! Utility binary to download commonly used SPICE kernels into `data/spice/`.
 //!
 //! The download set is intentionally small to keep onboarding fast. Additional
-//! kernels can be added by extending the catalog in `ephemeris::kernels`.
+//! kernels can be added by extending the catalog in `ephemeris::kernels`. Pass
+//! `--scenario` or `--bodies` to fetch only the kernels those bodies depend on.
 
+use clap::Parser;
+use solar_travel_calculator::config::{PlanetConfig, load_planets, load_scenario};
 use solar_travel_calculator::ephemeris;
 use solar_travel_calculator::ephemeris::kernels::KERNEL_CATALOG;
 use solar_travel_calculator::importer::{self, KernelStatus};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Concurrent kernel downloads; NAIF throttles aggressive clients, so keep this small.
+const DOWNLOAD_WORKERS: usize = 4;
+
+#[derive(Parser)]
+#[command(author, version, about = "Download SPICE kernels into data/spice")]
+struct Cli {
+    /// Only fetch kernels needed by the origin and destination of this scenario manifest
+    #[arg(long, conflicts_with = "bodies")]
+    scenario: Option<PathBuf>,
+
+    /// Only fetch kernels needed by these catalog bodies (comma-separated or repeated)
+    #[arg(long, value_delimiter = ',')]
+    bodies: Vec<String>,
+}
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let statuses = importer::download_kernels(KERNEL_CATALOG)?;
+    let cli = Cli::parse();
+    let descriptors = match selected_bodies(&cli)? {
+        Some(bodies) => {
+            let unknown = importer::unknown_kernel_dependencies(&bodies);
+            if !unknown.is_empty() {
+                eprintln!(
+                    "[warn] no catalog entry for kernel dependencies: {}; skipping",
+                    unknown.join(", ")
+                );
+            }
+            importer::required_kernels_for(&bodies)
+        }
+        None => KERNEL_CATALOG.to_vec(),
+    };
+
+    let statuses = importer::download_kernels_parallel(&descriptors, DOWNLOAD_WORKERS)?;
     for status in statuses {
         match status {
-            KernelStatus::Downloaded(path) => println!("[downloaded] {}", path.display()),
+            KernelStatus::Downloaded { path, attempts } if attempts > 1 => {
+                println!(
+                    "[downloaded] {} (after {attempts} attempts)",
+                    path.display()
+                )
+            }
+            KernelStatus::Downloaded { path, .. } => println!("[downloaded] {}", path.display()),
             KernelStatus::AlreadyPresent(path) => println!("[skip] {}", path.display()),
+            KernelStatus::ChecksumMismatch {
+                path,
+                expected,
+                actual,
+            } => eprintln!(
+                "[checksum mismatch] {} (expected {expected}, got {actual}); file discarded",
+                path.display()
+            ),
         }
     }
 
@@ -26,7 +73,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     summary.descriptor.filename,
                     summary.descriptor.kind.label(),
                     format_size(summary.file_size_bytes),
-                    summary.descriptor.description,
+                    summary.descriptor.describe_with_coverage(),
                     display_path(&summary.path)
                 );
             }
@@ -37,6 +84,34 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Bodies whose kernels were requested, or `None` to fetch the whole catalog.
+fn selected_bodies(cli: &Cli) -> Result<Option<Vec<PlanetConfig>>, Box<dyn std::error::Error>> {
+    if let Some(path) = &cli.scenario {
+        let scenario = load_scenario(path)?;
+        return Ok(Some(vec![
+            scenario.origin().clone(),
+            scenario.destination().clone(),
+        ]));
+    }
+    if cli.bodies.is_empty() {
+        return Ok(None);
+    }
+
+    let planets = load_planets("configs/bodies")?;
+    let bodies = cli
+        .bodies
+        .iter()
+        .map(|name| {
+            planets
+                .iter()
+                .find(|p| p.name.eq_ignore_ascii_case(name.trim()))
+                .cloned()
+                .ok_or_else(|| format!("Planet/moon '{name}' not found in catalog"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Some(bodies))
+}
+
 fn display_path(path: &Path) -> String {
     path.display().to_string()
 }
@@ -1,17 +1,34 @@
 use clap::{Parser, ValueEnum};
-use solar_travel_calculator::config::{PlanetConfig, load_planets, load_vehicle_configs};
+use solar_travel_calculator::config::{
+    AerobrakeSetting, ConstantsSet, PlanetConfig, load_constants, load_planets, load_scenario,
+    load_vehicle_configs,
+};
 use solar_travel_calculator::ephemeris;
-use solar_travel_calculator::propulsion::{PropulsionMode, Vehicle as PropulsionVehicle};
-use solar_travel_calculator::transfer::mission::porkchop::{
-    WINDOW_DATASET_VERSION, WindowDataset, WindowError, WindowSuggestion, analyze_departure,
-    compute_window_dataset, load_window_dataset, save_window_dataset,
+use solar_travel_calculator::orbits::{capture_delta_v, escape_delta_v};
+use solar_travel_calculator::propulsion::PropulsionMode;
+use solar_travel_calculator::transfer::mission::flyby::{
+    FlybyScreenRequest, FlybyScreening, screen_single_flyby,
 };
+use solar_travel_calculator::transfer::mission::porkchop::{WindowSuggestion, analyze_departure};
+use solar_travel_calculator::transfer::mission::synodic::next_launch_window;
 use solar_travel_calculator::transfer::vehicle as transfer_vehicle;
 use solar_travel_calculator::transfer::{
-    AerobrakingOption, ArrivalConfig, DepartureConfig, InterplanetaryConfig, MissionConfig,
-    plan_mission,
+    AerobrakingOption, ArrivalConfig, DepartureConfig, GM_MISMATCH_TOLERANCE, InterplanetaryConfig,
+    MissionConfig, gm_mismatches, kernel_constants, plan_mission,
+};
+use std::path::PathBuf;
+
+#[path = "common/catalog.rs"]
+#[allow(dead_code)] // select_impulsive_vehicle is only needed by the window binaries
+mod catalog;
+#[path = "mission/window_cache.rs"]
+mod window_cache;
+
+use catalog::{find_body, find_body_pair, find_parent};
+use window_cache::{
+    WindowCacheKey, compute_and_store_dataset, load_cached_dataset, state_cache_dir,
+    window_cache_path,
 };
-use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(
@@ -20,17 +37,21 @@ use std::path::{Path, PathBuf};
     about = "Mission planner CLI (continuous thrust ready)"
 )]
 struct Cli {
+    /// Scenario manifest (TOML/YAML) bundling the mission, planets and vehicles
+    #[arg(long, conflicts_with_all = ["from", "to", "depart", "arrive", "vehicle"])]
+    scenario: Option<PathBuf>,
+
     /// Departure planet name (case-insensitive)
-    #[arg(long)]
-    from: String,
+    #[arg(long, required_unless_present = "scenario")]
+    from: Option<String>,
 
     /// Destination planet/moon name (case-insensitive)
-    #[arg(long)]
-    to: String,
+    #[arg(long, required_unless_present = "scenario")]
+    to: Option<String>,
 
     /// Departure epoch (TDB/UTC string accepted by SPICE)
-    #[arg(long)]
-    depart: String,
+    #[arg(long, required_unless_present = "scenario")]
+    depart: Option<String>,
 
     /// Optional arrival epoch (defaults to depart + solver prediction)
     #[arg(long)]
@@ -40,9 +61,13 @@ struct Cli {
     #[arg(long)]
     vehicle: Option<String>,
 
-    /// Aerobraking mode
-    #[arg(long, value_enum, default_value_t = AerobrakeMode::None)]
-    aerobrake: AerobrakeMode,
+    /// Aerobraking mode (defaults to the scenario's setting, else none)
+    #[arg(long, value_enum)]
+    aerobrake: Option<AerobrakeMode>,
+
+    /// Reject aerobrake passes whose peak stagnation heat flux exceeds this value (W/m²)
+    #[arg(long)]
+    max_heat_flux: Option<f64>,
 
     /// Parking altitude at origin in km (defaults to catalog)
     #[arg(long)]
@@ -52,9 +77,34 @@ struct Cli {
     #[arg(long)]
     dest_altitude: Option<f64>,
 
+    /// Origin parking-orbit inclination to the ecliptic in degrees; checks the departure asymptote
+    /// is reachable without a plane change
+    #[arg(long)]
+    origin_inclination: Option<f64>,
+
     /// Print coplanar circular Hohmann estimate (Δv, TOF)
     #[arg(long, default_value_t = false)]
     estimate_hohmann: bool,
+
+    /// Gravitational constants file (TOML/YAML); defaults to the GMs in the loaded kernels, else DE440 values
+    #[arg(long)]
+    constants: Option<PathBuf>,
+
+    /// Keep the catalog GMs even where the loaded kernels define their own
+    #[arg(long, default_value_t = false)]
+    catalog_gm: bool,
+
+    /// Print only the mission profile, skipping the advisory window suggestion and vehicle ranking
+    #[arg(long, default_value_t = false)]
+    quiet: bool,
+
+    /// Screen a single unpowered flyby of BODY against the direct transfer's arrival v_inf
+    #[arg(long, value_name = "BODY")]
+    assist_via: Option<String>,
+
+    /// Reuse sampled body states from data/ephem_cache, appending new samples on misses
+    #[arg(long, default_value_t = false)]
+    cache_states: bool,
 }
 
 #[derive(Copy, Clone, ValueEnum, Debug)]
@@ -64,21 +114,92 @@ enum AerobrakeMode {
     Full,
 }
 
-const WINDOW_CACHE_DIR: &str = "data/windows";
-const WINDOW_SPAN_DAYS: f64 = 3_650.0; // 10 years
-const WINDOW_STEP_DAYS: f64 = 10.0;
-const WINDOW_MIN_TOF_DAYS: f64 = 30.0;
-const WINDOW_MAX_TOF_DAYS: f64 = 1_200.0;
+impl From<AerobrakeSetting> for AerobrakeMode {
+    fn from(setting: AerobrakeSetting) -> Self {
+        match setting {
+            AerobrakeSetting::None => AerobrakeMode::None,
+            AerobrakeSetting::Partial => AerobrakeMode::Partial,
+            AerobrakeSetting::Full => AerobrakeMode::Full,
+        }
+    }
+}
+
 const WINDOW_THRESHOLD_FACTOR: f64 = 1.4;
+/// Plans costing more than this over the parking-orbit Hohmann estimate are flagged.
+const HOHMANN_EXCESS_WARN_PERCENT: f64 = 25.0;
+
+/// Mission request resolved from either the command line or a scenario manifest.
+struct MissionRequest {
+    from: String,
+    to: String,
+    depart: String,
+    arrive: Option<String>,
+    vehicle: Option<String>,
+    aerobrake: AerobrakeMode,
+}
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
-    let planets = load_planets("configs/bodies")?;
-    let vehicle_catalog = load_vehicle_configs("configs/vehicles")?;
+    let (planets, vehicle_catalog, scenario_constants, request) = match &cli.scenario {
+        Some(path) => {
+            let scenario = load_scenario(path)?;
+            let mission = scenario.mission;
+            let request = MissionRequest {
+                from: mission.from,
+                to: mission.to,
+                depart: mission.depart,
+                arrive: mission.arrive,
+                vehicle: mission.vehicle,
+                aerobrake: cli.aerobrake.unwrap_or(mission.aerobrake.into()),
+            };
+            (
+                scenario.planets,
+                scenario.vehicles,
+                Some(scenario.constants),
+                request,
+            )
+        }
+        None => {
+            // clap enforces these when no scenario is given.
+            let request = MissionRequest {
+                from: cli.from.clone().unwrap_or_default(),
+                to: cli.to.clone().unwrap_or_default(),
+                depart: cli.depart.clone().unwrap_or_default(),
+                arrive: cli.arrive.clone(),
+                vehicle: cli.vehicle.clone(),
+                aerobrake: cli.aerobrake.unwrap_or(AerobrakeMode::None),
+            };
+            (
+                load_planets("configs/bodies")?,
+                load_vehicle_configs("configs/vehicles")?,
+                None,
+                request,
+            )
+        }
+    };
+    let constants = match &cli.constants {
+        Some(path) => load_constants(path)?,
+        None => match scenario_constants {
+            Some(constants) => constants,
+            None if cli.catalog_gm => ConstantsSet::default(),
+            None => {
+                let constants = kernel_constants(&planets)?;
+                for mismatch in gm_mismatches(&planets, &constants, GM_MISMATCH_TOLERANCE) {
+                    eprintln!(
+                        "Warning: catalog GM for {} ({:.6} km³/s²) differs from the kernel value ({:.6} km³/s²) by {:.2e}; using the kernel value",
+                        mismatch.body,
+                        mismatch.catalog_mu_km3_s2,
+                        mismatch.kernel_mu_km3_s2,
+                        mismatch.relative_difference()
+                    );
+                }
+                constants
+            }
+        },
+    };
 
-    let origin = find_body(&planets, &cli.from)?;
-    let destination = find_body(&planets, &cli.to)?;
-    let vehicle = transfer_vehicle::select(&vehicle_catalog, cli.vehicle.as_deref())?;
+    let (origin, destination) = find_body_pair(&planets, &request.from, &request.to)?;
+    let vehicle = transfer_vehicle::select(&vehicle_catalog, request.vehicle.as_deref())?;
 
     let origin_altitude_km = cli
         .origin_altitude
@@ -87,28 +208,57 @@ fn main() -> anyhow::Result<()> {
         .dest_altitude
         .unwrap_or(destination.default_parking_altitude_km);
 
+    let departure_et = ephemeris::epoch_seconds(&request.depart)?;
+    ensure_epoch_covered(&origin, departure_et, "Departure")?;
+    if let Some(arrive) = &request.arrive {
+        ensure_epoch_covered(&destination, ephemeris::epoch_seconds(arrive)?, "Arrival")?;
+    }
+    let warm_start_tof_days = if request.arrive.is_none()
+        && matches!(vehicle.propulsion, PropulsionMode::Impulsive { .. })
+    {
+        cached_warm_start_tof_days(
+            &origin,
+            &destination,
+            &WindowCacheKey {
+                vehicle: &vehicle,
+                departure_et,
+                rpark_dep_km: origin.radius_km + origin_altitude_km,
+                rpark_arr_km: destination.radius_km + destination_altitude_km,
+                constants: &constants,
+            },
+        )
+    } else {
+        None
+    };
+
     let departure_cfg = DepartureConfig {
         origin_body: origin.spice_name.clone(),
         parking_altitude_km: origin_altitude_km,
-        departure_epoch: cli.depart.clone(),
+        departure_epoch: request.depart.clone(),
         required_v_infinity: None,
         propulsion_mode: vehicle.propulsion.clone(),
+        parking_inclination_deg: cli.origin_inclination,
     };
 
     let cruise_cfg = InterplanetaryConfig {
         departure_body: origin.spice_name.clone(),
         destination_body: destination.spice_name.clone(),
-        departure_epoch: cli.depart.clone(),
-        arrival_epoch: cli.arrive.clone(),
+        departure_epoch: request.depart.clone(),
+        arrival_epoch: request.arrive.clone(),
         propulsion_mode: vehicle.propulsion.clone(),
+        warm_start_tof_days,
+        parking_altitudes_km: Some((origin_altitude_km, destination_altitude_km)),
     };
 
     let arrival_cfg = ArrivalConfig {
         destination_body: destination.spice_name.clone(),
         target_parking_altitude_km: destination_altitude_km,
-        encounter_epoch: cli.arrive.clone().unwrap_or_else(|| cli.depart.clone()),
+        encounter_epoch: request
+            .arrive
+            .clone()
+            .unwrap_or_else(|| request.depart.clone()),
         propulsion_mode: vehicle.propulsion.clone(),
-        aerobraking: Some(match cli.aerobrake {
+        aerobraking: Some(match request.aerobrake {
             AerobrakeMode::None => AerobrakingOption::Disabled,
             AerobrakeMode::Partial => AerobrakingOption::Partial {
                 periapsis_altitude_km: destination
@@ -125,6 +275,7 @@ fn main() -> anyhow::Result<()> {
                     .unwrap_or(destination.default_parking_altitude_km),
             },
         }),
+        max_heat_flux_w_m2: cli.max_heat_flux,
     };
 
     let mission_config = MissionConfig {
@@ -134,12 +285,12 @@ fn main() -> anyhow::Result<()> {
         departure: departure_cfg,
         cruise: cruise_cfg,
         arrival: arrival_cfg,
+        constants: constants.clone(),
     };
 
     let profile = plan_mission(mission_config)?;
 
-    let departure_et = ephemeris::epoch_seconds(&cli.depart)?;
-    let arrival_et = if let Some(arrive) = &cli.arrive {
+    let arrival_et = if let Some(arrive) = &request.arrive {
         ephemeris::epoch_seconds(arrive)?
     } else {
         departure_et + profile.cruise.time_of_flight_days * 86_400.0
@@ -158,12 +309,22 @@ fn main() -> anyhow::Result<()> {
     let percent_c = peak_speed / 299_792.458 * 100.0;
 
     println!("=== Mission Profile ===");
-    println!("Departure epoch : {}", cli.depart);
+    println!("Departure epoch : {}", request.depart);
     println!("Arrival epoch   : {}", arrival_epoch_str);
     println!(
-        "Departure burn : Δv = {:.3} km/s, v_inf = {:.3} km/s",
-        profile.departure.delta_v_required, profile.departure.hyperbolic_excess_km_s
+        "Departure burn : Δv = {:.3} km/s (ideal {:.3}, gravity loss {:.3}), v_inf = {:.3} km/s",
+        profile.departure.delta_v_required,
+        profile.departure.ideal_delta_v_km_s,
+        profile.departure.gravity_loss_km_s(),
+        profile.departure.hyperbolic_excess_km_s
     );
+    if let Some(raan) = profile.departure.optimal_parking_raan_rad {
+        println!(
+            "Departure plane: best parking RAAN = {:.1}°, plane-change penalty = {:.3} km/s",
+            raan.to_degrees(),
+            profile.departure.plane_change_penalty_km_s
+        );
+    }
     println!(
         "Cruise         : TOF = {:.2} days ({}d {}h {}m), propellant used = {:.1} kg",
         profile.cruise.time_of_flight_days,
@@ -172,10 +333,44 @@ fn main() -> anyhow::Result<()> {
         m,
         profile.cruise.propellant_used_kg.unwrap_or(0.0)
     );
+    if let Some(burns) = profile.cruise.impulsive_burns {
+        println!(
+            "Impulsive burns: v_inf dep = {:.3} km/s, v_inf arr = {:.3} km/s, Δv dep = {:.3} km/s ({:.1} kg), Δv arr = {:.3} km/s ({:.1} kg)",
+            burns.vinf_depart_km_s,
+            burns.vinf_arrive_km_s,
+            burns.dv_depart_km_s,
+            burns.propellant_depart_kg,
+            burns.dv_arrive_km_s,
+            burns.propellant_arrive_kg
+        );
+        if burns.shortfall_km_s > 0.0 {
+            println!(
+                "Warning        : '{}' runs dry {:.3} km/s short of the required Δv ({:.1} kg of propellant on board)",
+                vehicle.name,
+                burns.shortfall_km_s,
+                vehicle.total_propellant_kg()
+            );
+        }
+    }
     println!(
         "Speeds         : start = {:.3} km/s, peak = {:.3} km/s ({:.6}% c), arrival = {:.3} km/s",
         depart_speed, peak_speed, percent_c, arrive_speed
     );
+    if let (Some(arc_km), Some(sweep_deg)) =
+        (profile.cruise.arc_length_km, profile.cruise.sweep_angle_deg)
+    {
+        println!(
+            "Path           : arc length = {:.3} AU, heliocentric sweep = {:.1}°",
+            arc_km / 149_597_870.7,
+            sweep_deg
+        );
+    }
+    if let Some(miss_km) = profile.cruise.miss_distance_km {
+        println!(
+            "Miss distance  : {:.0} km from {} at arrival",
+            miss_km, destination.name
+        );
+    }
     println!(
         "Arrival burn   : Δv = {:.3} km/s",
         profile.arrival.delta_v_required
@@ -195,6 +390,25 @@ fn main() -> anyhow::Result<()> {
         total_propulsive_dv, aerobrake_dv, total_delta_v
     );
 
+    // Compare every catalog vehicle that could fly the same burns.
+    let ranking = if cli.quiet {
+        Vec::new()
+    } else {
+        transfer_vehicle::rank(&vehicle_catalog, total_propulsive_dv)
+    };
+    if ranking.len() > 1 {
+        println!("Vehicle ranking (payload through Δv = {total_propulsive_dv:.3} km/s):");
+        for (i, ranked) in ranking.iter().enumerate() {
+            println!(
+                "  {}. {}: payload = {:.1} t, mass ratio = {:.3}",
+                i + 1,
+                ranked.vehicle.name,
+                ranked.payload_kg / 1_000.0,
+                ranked.mass_ratio
+            );
+        }
+    }
+
     if let Some(report) = &profile.arrival.aerobrake_report {
         println!(
             "Aerobrake      : Δv_drag = {:.3} km/s, v_inf_post = {:.3} km/s",
@@ -206,30 +420,59 @@ fn main() -> anyhow::Result<()> {
             report.peak_deceleration_m_s2,
             report.periapsis_altitude_m / 1_000.0
         );
+        let period = report
+            .pass_duration_s
+            .map(|s| format!("{:.2} h", s / 3_600.0))
+            .unwrap_or_else(|| "unbound".to_string());
+        println!(
+            "               : time in atmosphere = {:.1} s, post-pass period = {}",
+            report.atmospheric_time_s, period
+        );
+        println!(
+            "               : peak heat flux = {:.1} kW/m², heat load = {:.1} MJ/m²",
+            report.peak_heat_flux_w_m2 / 1_000.0,
+            report.integrated_heat_load_j_m2 / 1.0e6
+        );
     }
 
-    let total_dv_km_s = profile.departure.delta_v_required + profile.arrival.delta_v_required;
-    let rpark_dep_km = origin.radius_km + origin_altitude_km;
-    let rpark_arr_km = destination.radius_km + destination_altitude_km;
-    if let Some(suggestion) = compute_window_suggestion(
-        &planets,
-        &origin,
-        &destination,
-        &vehicle,
-        departure_et,
-        total_dv_km_s,
-        rpark_dep_km,
-        rpark_arr_km,
-    )? {
-        print_window_suggestion(&suggestion, departure_et, &origin.name, &destination.name);
+    if !cli.quiet {
+        let total_dv_km_s = profile.departure.delta_v_required + profile.arrival.delta_v_required;
+        let rpark_dep_km = origin.radius_km + origin_altitude_km;
+        let rpark_arr_km = destination.radius_km + destination_altitude_km;
+        let key = WindowCacheKey {
+            vehicle: &vehicle,
+            departure_et,
+            rpark_dep_km,
+            rpark_arr_km,
+            constants: &constants,
+        };
+        if let Some(suggestion) = compute_window_suggestion(
+            &planets,
+            &origin,
+            &destination,
+            &key,
+            total_dv_km_s,
+            cli.cache_states,
+        )? {
+            print_window_suggestion(&suggestion, departure_et, &origin.name, &destination.name);
+            // The dataset only spans its cached years; the synodic estimate works from any epoch.
+            if let Ok(window) = next_launch_window(&origin, &destination, departure_et, &constants)
+            {
+                println!(
+                    "      Next window: ~{} (Hohmann TOF {:.0} days, synodic period {:.0} days).",
+                    ephemeris::format_epoch_iso(window.depart_et)?,
+                    window.tof_days,
+                    window.synodic_period_days
+                );
+            }
+        }
     }
 
     if cli.estimate_hohmann {
         use solar_travel_calculator::impulsive::transfers::hohmann;
-        const MU_SUN: f64 = 1.327_124_400_18e11;
         let r1 = vector_norm(&profile.cruise.departure_state.position_km);
         let r2 = vector_norm(&profile.cruise.arrival_state.position_km);
-        let h = hohmann(r1, r2, MU_SUN);
+        let h = hohmann(r1, r2, constants.mu_sun_km3_s2);
         println!(
             "Hohmann est.   : Δv_total = {:.3} km/s (dv1={:.3}, dv2={:.3}), TOF = {:.2} days",
             h.dv_total_km_s,
@@ -237,26 +480,69 @@ fn main() -> anyhow::Result<()> {
             h.dv2_km_s,
             h.tof_seconds / 86_400.0
         );
+        // Put the heliocentric Hohmann burns on the same parking orbits as the plan so the two
+        // totals are comparable. Only the plan's burns count; aerobrake drag costs no propellant.
+        let hohmann_parking_dv = escape_delta_v(
+            constants.body_mu(&origin),
+            origin.radius_km + origin_altitude_km,
+            h.dv1_km_s.abs(),
+        ) + capture_delta_v(
+            constants.body_mu(&destination),
+            destination.radius_km + destination_altitude_km,
+            h.dv2_km_s.abs(),
+        );
+        if hohmann_parking_dv > 0.0 {
+            let excess_pct = (total_propulsive_dv / hohmann_parking_dv - 1.0) * 100.0;
+            let verdict = if excess_pct > HOHMANN_EXCESS_WARN_PERCENT {
+                " — well above the coplanar ideal (inclined/eccentric geometry or an off-window epoch)"
+            } else {
+                ""
+            };
+            println!(
+                "Hohmann vs plan: parking-orbit Δv = {:.3} km/s vs plan propulsive {:.3} km/s ({:+.1}%){}",
+                hohmann_parking_dv, total_propulsive_dv, excess_pct, verdict
+            );
+        }
     }
 
-    Ok(())
-}
+    if let Some(via) = &cli.assist_via {
+        let flyby_body = find_body(&planets, via)?;
+        let screening = screen_single_flyby(&FlybyScreenRequest {
+            origin: &origin,
+            flyby: &flyby_body,
+            destination: &destination,
+            departure_et,
+            direct_tof_days: profile.cruise.time_of_flight_days,
+            min_flyby_altitude_km: flyby_body.default_parking_altitude_km,
+            constants: &constants,
+        })?;
+        print_assist_screening(&screening, &flyby_body.name)?;
+    }
 
-fn find_body<'a>(planets: &'a [PlanetConfig], name: &str) -> anyhow::Result<PlanetConfig> {
-    let upper = name.to_uppercase();
-    planets
-        .iter()
-        .find(|p| p.name.to_uppercase() == upper)
-        .cloned()
-        .ok_or_else(|| anyhow::anyhow!("Planet/moon '{}' not found in catalog", name))
+    Ok(())
 }
 
-fn find_body_by_spice<'a>(planets: &'a [PlanetConfig], spice: &str) -> Option<PlanetConfig> {
-    let upper = spice.to_uppercase();
-    planets
-        .iter()
-        .find(|p| p.spice_name.to_uppercase() == upper)
-        .cloned()
+/// Fail with a readable message when `et` lies outside the loaded ephemeris for `body`.
+///
+/// Bodies without any SPK segments are left for SPICE to report.
+fn ensure_epoch_covered(body: &PlanetConfig, et: f64, label: &str) -> anyhow::Result<()> {
+    let Some(id) = ephemeris::body_id(&body.spice_name)? else {
+        return Ok(());
+    };
+    if ephemeris::is_covered(id, et)? {
+        return Ok(());
+    }
+    let coverage = ephemeris::kernel_coverage(id)?;
+    let (Some(first), Some(last)) = (coverage.first(), coverage.last()) else {
+        return Ok(());
+    };
+    Err(anyhow::anyhow!(
+        "{label} epoch {} is out of ephemeris range for {}: loaded kernels cover {} to {}",
+        ephemeris::format_epoch(et)?,
+        body.name,
+        ephemeris::format_epoch(first.start_et)?,
+        ephemeris::format_epoch(last.end_et)?
+    ))
 }
 
 fn format_duration(seconds: f64) -> (i64, i64, i64) {
@@ -276,98 +562,54 @@ fn compute_window_suggestion(
     planets: &[PlanetConfig],
     origin: &PlanetConfig,
     destination: &PlanetConfig,
-    vehicle: &PropulsionVehicle,
-    departure_et: f64,
+    key: &WindowCacheKey<'_>,
     total_dv_km_s: f64,
-    rpark_dep_km: f64,
-    rpark_arr_km: f64,
+    cache_states: bool,
 ) -> anyhow::Result<Option<WindowSuggestion>> {
-    if !matches!(vehicle.propulsion, PropulsionMode::Impulsive { .. }) {
+    if !matches!(key.vehicle.propulsion, PropulsionMode::Impulsive { .. }) {
         return Ok(None);
     }
 
-    let origin_parent = origin
-        .parent_spice
-        .as_ref()
-        .and_then(|spice| find_body_by_spice(planets, spice));
-    let destination_parent = destination
-        .parent_spice
-        .as_ref()
-        .and_then(|spice| find_body_by_spice(planets, spice));
+    let origin_parent = find_parent(planets, origin);
+    let destination_parent = find_parent(planets, destination);
 
-    let cache_path = window_cache_path(origin, destination, departure_et);
+    let cache_path = window_cache_path(origin, destination, key);
 
-    let dataset = if cache_path.exists() {
-        match load_window_dataset(&cache_path) {
-            Ok(dataset)
-                if dataset.version == WINDOW_DATASET_VERSION
-                    && departure_et >= dataset.depart_start_et
-                    && departure_et <= dataset.depart_end_et =>
-            {
-                dataset
-            }
-            _ => compute_and_store_dataset(
-                cache_path.as_path(),
-                origin,
-                origin_parent.as_ref(),
-                destination,
-                destination_parent.as_ref(),
-                vehicle,
-                rpark_dep_km,
-                rpark_arr_km,
-                departure_et,
-            )?,
+    let dataset = match load_cached_dataset(&cache_path, key) {
+        Some(dataset)
+            if key.departure_et >= dataset.depart_start_et
+                && key.departure_et <= dataset.depart_end_et =>
+        {
+            dataset
         }
-    } else {
-        compute_and_store_dataset(
+        _ => compute_and_store_dataset(
             cache_path.as_path(),
             origin,
             origin_parent.as_ref(),
             destination,
             destination_parent.as_ref(),
-            vehicle,
-            rpark_dep_km,
-            rpark_arr_km,
-            departure_et,
-        )?
+            key,
+            state_cache_dir(cache_states),
+        )?,
     };
 
     let suggestion = analyze_departure(
         &dataset,
-        departure_et,
+        key.departure_et,
         total_dv_km_s,
         WINDOW_THRESHOLD_FACTOR,
     );
     Ok(suggestion)
 }
 
-fn compute_and_store_dataset(
-    path: &Path,
+fn cached_warm_start_tof_days(
     origin: &PlanetConfig,
-    origin_parent: Option<&PlanetConfig>,
     destination: &PlanetConfig,
-    destination_parent: Option<&PlanetConfig>,
-    vehicle: &PropulsionVehicle,
-    rpark_dep_km: f64,
-    rpark_arr_km: f64,
-    depart_start_et: f64,
-) -> Result<WindowDataset, WindowError> {
-    let dataset = compute_window_dataset(
-        origin,
-        origin_parent,
-        destination,
-        destination_parent,
-        vehicle,
-        rpark_dep_km,
-        rpark_arr_km,
-        depart_start_et,
-        WINDOW_SPAN_DAYS,
-        WINDOW_STEP_DAYS,
-        WINDOW_MIN_TOF_DAYS,
-        WINDOW_MAX_TOF_DAYS,
-    )?;
-    save_window_dataset(path, &dataset)?;
-    Ok(dataset)
+    key: &WindowCacheKey<'_>,
+) -> Option<f64> {
+    let cache_path = window_cache_path(origin, destination, key);
+    let dataset = load_cached_dataset(&cache_path, key)?;
+    dataset.baseline_sample().map(|sample| sample.tof_days())
 }
 
 fn print_window_suggestion(
@@ -399,6 +641,45 @@ fn print_window_suggestion(
         suggestion.recommended.arrive_utc,
         suggestion.recommended.dv_total_km_s
     );
+    if let Some(faster) = &suggestion.faster {
+        println!(
+            "      Faster    : depart {} and arrive {} (TOF {:.0} days) with Δv_total ≈ {:.2} km/s.",
+            faster.depart_utc,
+            faster.arrive_utc,
+            faster.tof_days(),
+            faster.dv_total_km_s
+        );
+    }
+}
+
+fn print_assist_screening(screening: &FlybyScreening, flyby_name: &str) -> anyhow::Result<()> {
+    println!(
+        "Assist screen  : direct v_inf depart = {:.3} km/s, arrive = {:.3} km/s",
+        screening.direct_vinf_depart_km_s, screening.direct_vinf_arrive_km_s
+    );
+    let (Some(assist), Some(reduction)) = (&screening.assist, screening.vinf_reduction_km_s())
+    else {
+        println!(
+            "               : no unpowered {flyby_name} flyby found within the turn-angle limit"
+        );
+        return Ok(());
+    };
+    println!(
+        "               : via {} on {}: v_inf arrive = {:.3} km/s ({:+.3} km/s), arrive {}",
+        flyby_name,
+        ephemeris::format_epoch(assist.flyby_et)?,
+        assist.vinf_arrive_km_s,
+        -reduction,
+        ephemeris::format_epoch(assist.arrival_et)?
+    );
+    println!(
+        "               : flyby v_inf = {:.3} km/s, turn = {:.1}° of {:.1}° max, depart v_inf = {:.3} km/s",
+        assist.vinf_flyby_km_s,
+        assist.turn_angle_deg,
+        assist.max_turn_angle_deg,
+        assist.vinf_depart_km_s
+    );
+    Ok(())
 }
 
 fn describe_offset(delta_days: f64) -> String {
@@ -411,29 +692,3 @@ fn describe_offset(delta_days: f64) -> String {
         format!("≈{} days earlier", (-rounded) as i64)
     }
 }
-
-fn window_cache_path(
-    origin: &PlanetConfig,
-    destination: &PlanetConfig,
-    departure_et: f64,
-) -> PathBuf {
-    let origin_part = sanitize_filename_component(&origin.spice_name);
-    let destination_part = sanitize_filename_component(&destination.spice_name);
-    let depart_tag = format!("et{}", departure_et.round() as i64);
-    Path::new(WINDOW_CACHE_DIR).join(format!(
-        "{origin_part}__{destination_part}__{depart_tag}.json"
-    ))
-}
-
-fn sanitize_filename_component(input: &str) -> String {
-    input
-        .chars()
-        .map(|c| {
-            if c.is_ascii_alphanumeric() {
-                c.to_ascii_lowercase()
-            } else {
-                '_'
-            }
-        })
-        .collect()
-}
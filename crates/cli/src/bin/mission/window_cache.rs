@@ -0,0 +1,109 @@
+use solar_travel_calculator::config::{ConstantsSet, PlanetConfig};
+use solar_travel_calculator::ephemeris::cache::DEFAULT_STATE_CACHE_DIR;
+use solar_travel_calculator::propulsion::Vehicle as PropulsionVehicle;
+use solar_travel_calculator::transfer::mission::porkchop::{
+    TransferEndpoint, WINDOW_DATASET_VERSION, WindowDataset, WindowError, WindowSweep,
+    compute_window_dataset, load_window_dataset, save_window_dataset,
+};
+use std::path::{Path, PathBuf};
+
+pub(super) const WINDOW_CACHE_DIR: &str = "data/windows";
+pub(super) const WINDOW_SPAN_DAYS: f64 = 3_650.0; // 10 years
+pub(super) const WINDOW_STEP_DAYS: f64 = 10.0;
+pub(super) const WINDOW_MIN_TOF_DAYS: f64 = 30.0;
+pub(super) const WINDOW_MAX_TOF_DAYS: f64 = 1_200.0;
+
+/// Everything besides the body pair that a cached window dataset is computed for and keyed on.
+pub(super) struct WindowCacheKey<'a> {
+    pub(super) vehicle: &'a PropulsionVehicle,
+    pub(super) departure_et: f64,
+    pub(super) rpark_dep_km: f64,
+    pub(super) rpark_arr_km: f64,
+    pub(super) constants: &'a ConstantsSet,
+}
+
+pub(super) fn compute_and_store_dataset(
+    path: &Path,
+    origin: &PlanetConfig,
+    origin_parent: Option<&PlanetConfig>,
+    destination: &PlanetConfig,
+    destination_parent: Option<&PlanetConfig>,
+    key: &WindowCacheKey<'_>,
+    state_cache_dir: Option<&Path>,
+) -> Result<WindowDataset, WindowError> {
+    let sweep = WindowSweep {
+        depart_start_et: key.departure_et,
+        span_days: WINDOW_SPAN_DAYS,
+        step_days: WINDOW_STEP_DAYS,
+        min_tof_days: WINDOW_MIN_TOF_DAYS,
+        max_tof_days: WINDOW_MAX_TOF_DAYS,
+    };
+    let dataset = compute_window_dataset(
+        TransferEndpoint {
+            body: origin,
+            parent: origin_parent,
+            rpark_km: key.rpark_dep_km,
+        },
+        TransferEndpoint {
+            body: destination,
+            parent: destination_parent,
+            rpark_km: key.rpark_arr_km,
+        },
+        key.vehicle,
+        &sweep,
+        key.constants,
+        state_cache_dir,
+    )?;
+    save_window_dataset(path, &dataset)?;
+    Ok(dataset)
+}
+
+/// State cache directory to hand the window sweep when `--cache-states` is set.
+pub(super) fn state_cache_dir(enabled: bool) -> Option<&'static Path> {
+    enabled.then_some(Path::new(DEFAULT_STATE_CACHE_DIR))
+}
+
+/// Cache file for a pair, departure epoch, vehicle, parking radii (rounded to whole km) and
+/// constants fingerprint.
+pub(super) fn window_cache_path(
+    origin: &PlanetConfig,
+    destination: &PlanetConfig,
+    key: &WindowCacheKey<'_>,
+) -> PathBuf {
+    let origin_part = sanitize_filename_component(&origin.spice_name);
+    let destination_part = sanitize_filename_component(&destination.spice_name);
+    let depart_tag = format!("et{}", key.departure_et.round() as i64);
+    let vehicle_part = sanitize_filename_component(&key.vehicle.name);
+    let radii_tag = format!("r{:.0}_{:.0}", key.rpark_dep_km, key.rpark_arr_km);
+    let gm_tag = format!("gm{}", key.constants.fingerprint());
+    Path::new(WINDOW_CACHE_DIR).join(format!(
+        "{origin_part}__{destination_part}__{depart_tag}__{vehicle_part}__{radii_tag}__{gm_tag}.json"
+    ))
+}
+
+/// Load the dataset at `path` if it has the current schema and was computed for this vehicle,
+/// these parking radii and these constants; anything else should be recomputed.
+pub(super) fn load_cached_dataset(path: &Path, key: &WindowCacheKey<'_>) -> Option<WindowDataset> {
+    let dataset = load_window_dataset(path).ok()?;
+    (dataset.version == WINDOW_DATASET_VERSION
+        && dataset.matches_parameters(
+            &key.vehicle.name,
+            key.rpark_dep_km,
+            key.rpark_arr_km,
+            key.constants,
+        ))
+    .then_some(dataset)
+}
+
+fn sanitize_filename_component(input: &str) -> String {
+    input
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
@@ -1,20 +1,26 @@
 use anyhow::anyhow;
-use clap::Parser;
-use solar_travel_calculator::config::{PlanetConfig, load_planets, load_vehicle_configs};
+use clap::{Parser, ValueEnum};
+use solar_travel_calculator::config::{load_constants, load_planets, load_vehicle_configs};
+use solar_travel_calculator::ephemeris::cache::DEFAULT_STATE_CACHE_DIR;
 use solar_travel_calculator::ephemeris::{self, StateVector};
 use solar_travel_calculator::export::porkchop as export_porkchop;
 use solar_travel_calculator::propulsion::PropulsionMode;
+use solar_travel_calculator::transfer::kernel_constants;
 use solar_travel_calculator::transfer::mission::porkchop::{
-    self as porkchop_calc, PorkchopPath, PorkchopRequest, TimeWindow,
+    self as porkchop_calc, PorkchopMetric, PorkchopPath, PorkchopRequest, TimeWindow,
 };
 use solar_travel_calculator::transfer::vehicle as transfer_vehicle;
-use std::collections::HashMap;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+#[path = "common/catalog.rs"]
+#[allow(dead_code)] // select_impulsive_vehicle is only needed by the window binaries
+mod catalog;
 #[path = "porkchop/continuous.rs"]
 mod continuous;
 
+use catalog::{find_body_pair, find_parent};
+
 /// Generate porkchop data (CSV) for impulsive transfers by sweeping departure and arrival epochs.
 #[derive(Parser, Debug)]
 #[command(
@@ -63,36 +69,87 @@ struct Cli {
     #[arg(long, default_value_t = false)]
     long_path: bool,
 
+    /// Omit grid cells without a feasible transfer from the CSV
+    #[arg(long, default_value_t = false)]
+    feasible_only: bool,
+
     /// Output CSV file (use '-' for stdout)
     #[arg(long, default_value = "artifacts/pork.csv")]
     output: PathBuf,
 
+    /// Resolution in seconds for the depart/arrive ET columns (0 keeps full precision)
+    #[arg(long, default_value_t = export_porkchop::DEFAULT_ET_RESOLUTION_S)]
+    et_resolution_s: f64,
+
     /// Vehicle name from the vehicle catalog to size burns/propellant.
     #[arg(long, default_value = "Ion Tug Mk1")]
     vehicle: String,
+
+    /// Gravitational constants file (TOML/YAML); defaults to the GMs in the loaded kernels, else DE440 values
+    #[arg(long)]
+    constants: Option<PathBuf>,
+
+    /// Reuse sampled body states from data/ephem_cache, appending new samples on misses
+    #[arg(long, default_value_t = false)]
+    cache_states: bool,
+
+    /// Also consider multi-revolution Lambert arcs with up to this many full orbits
+    #[arg(long, default_value_t = 0)]
+    max_revolutions: u32,
+
+    /// Add the heliocentric plane change between origin and destination orbits to the capture burn
+    #[arg(long, default_value_t = false)]
+    plane_change_penalty: bool,
+
+    /// Cap the hourly telemetry sidecar of continuous-thrust runs at this many samples
+    #[arg(long)]
+    max_telemetry_samples: Option<usize>,
+
+    /// Also write a departure×arrival matrix of `--grid-metric` to this `.dat` file
+    #[arg(long)]
+    grid_output: Option<PathBuf>,
+
+    /// Also write the `--grid-metric` matrix in the compact binary grid format (readable by
+    /// `porkchop_plot`)
+    #[arg(long)]
+    binary_output: Option<PathBuf>,
+
+    /// Quantity written to `--grid-output` and `--binary-output`
+    #[arg(long, value_enum, default_value_t = GridMetric::DvTotal)]
+    grid_metric: GridMetric,
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum GridMetric {
+    DvTotal,
+    C3,
+    Tof,
+}
+
+impl From<GridMetric> for PorkchopMetric {
+    fn from(metric: GridMetric) -> Self {
+        match metric {
+            GridMetric::DvTotal => PorkchopMetric::DvTotal,
+            GridMetric::C3 => PorkchopMetric::C3,
+            GridMetric::Tof => PorkchopMetric::Tof,
+        }
+    }
 }
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
     let planets = load_planets("configs/bodies")?;
     let vehicle_catalog = load_vehicle_configs("configs/vehicles")?;
-    let spice_lookup: HashMap<String, PlanetConfig> = planets
-        .iter()
-        .map(|p| (p.spice_name.to_uppercase(), p.clone()))
-        .collect();
+    let constants = match &cli.constants {
+        Some(path) => load_constants(path)?,
+        None => kernel_constants(&planets)?,
+    };
     let vehicle = transfer_vehicle::select(&vehicle_catalog, Some(&cli.vehicle))?;
 
-    let origin = find_body(&planets, &cli.from)?;
-    let destination = find_body(&planets, &cli.to)?;
+    let (origin, destination) = find_body_pair(&planets, &cli.from, &cli.to)?;
 
-    let origin_parent = origin
-        .parent_spice
-        .as_ref()
-        .and_then(|ps| spice_lookup.get(&ps.to_uppercase()).cloned());
-    let destination_parent = destination
-        .parent_spice
-        .as_ref()
-        .and_then(|ps| spice_lookup.get(&ps.to_uppercase()).cloned());
+    let origin_parent = find_parent(&planets, &origin);
+    let destination_parent = find_parent(&planets, &destination);
 
     let rpark_dep = origin.radius_km
         + cli
@@ -109,6 +166,11 @@ fn main() -> anyhow::Result<()> {
         return Err(anyhow!("departure window end must be after start"));
     }
     let step_s = (cli.step_days.max(0.1)) * 86_400.0;
+    let departure_window = TimeWindow {
+        start_et: dep_start,
+        end_et: dep_end,
+        step_seconds: step_s,
+    };
 
     if matches!(vehicle.propulsion, PropulsionMode::Continuous { .. }) {
         return continuous::run_continuous_mode(
@@ -118,9 +180,7 @@ fn main() -> anyhow::Result<()> {
             origin_parent.as_ref(),
             &destination,
             destination_parent.as_ref(),
-            dep_start,
-            dep_end,
-            step_s,
+            &departure_window,
         );
     }
 
@@ -141,11 +201,6 @@ fn main() -> anyhow::Result<()> {
     let mut writer = export_porkchop::writer_for_path(&cli.output)?;
     export_porkchop::write_header(writer.as_mut())?;
 
-    let departure_window = TimeWindow {
-        start_et: dep_start,
-        end_et: dep_end,
-        step_seconds: step_s,
-    };
     let arrival_window = TimeWindow {
         start_et: arr_start,
         end_et: arr_end,
@@ -164,19 +219,29 @@ fn main() -> anyhow::Result<()> {
         arrival_window,
         long_path_only: cli.long_path,
         ignore_vehicle_limits: false,
+        include_infeasible: !cli.feasible_only,
+        constants: &constants,
+        state_cache_dir: cli
+            .cache_states
+            .then_some(Path::new(DEFAULT_STATE_CACHE_DIR)),
+        max_revolutions: cli.max_revolutions,
+        plane_change_penalty: cli.plane_change_penalty,
     };
 
-    let points = porkchop_calc::generate(&request)?;
+    let points = porkchop_calc::generate_parallel(&request)?;
 
-    for point in points {
+    for point in &points {
         let path_str = match point.lambert_path {
-            PorkchopPath::Short => "short",
-            PorkchopPath::Long => "long",
+            PorkchopPath::Short => "short".to_string(),
+            PorkchopPath::Long => "long".to_string(),
+            PorkchopPath::MultiRev { revolutions, left } => {
+                format!("{revolutions}rev-{}", if left { "left" } else { "right" })
+            }
             PorkchopPath::None => {
                 if cli.long_path {
-                    "long"
+                    "long".to_string()
                 } else {
-                    "none"
+                    "none".to_string()
                 }
             }
         };
@@ -196,26 +261,41 @@ fn main() -> anyhow::Result<()> {
             propellant_used_kg: point.propellant_used_kg,
             burn_time_s: point.burn_time_s,
             final_mass_kg: point.final_mass_kg,
-            path: path_str,
+            path: &path_str,
             feasible: point.feasible,
             origin_body: origin.spice_name.as_str(),
             dest_body: destination.spice_name.as_str(),
             rpark_dep_km: rpark_dep,
             rpark_arr_km: rpark_arr,
+            et_resolution_s: cli.et_resolution_s,
+            depart_index: export_porkchop::grid_index(point.depart_et, dep_start, step_s),
+            arrive_index: export_porkchop::grid_index(point.arrive_et, arr_start, step_s),
+            revolutions: match point.lambert_path {
+                PorkchopPath::MultiRev { revolutions, .. } => revolutions,
+                _ => 0,
+            },
         };
         record.write_to(writer.as_mut())?;
     }
 
     writer.flush()?;
 
-    Ok(())
-}
+    if let Some(grid_path) = &cli.grid_output {
+        let mut grid_writer = export_porkchop::writer_for_path(grid_path)?;
+        porkchop_calc::write_grid(grid_writer.as_mut(), &points, cli.grid_metric.into())?;
+        grid_writer.flush()?;
+    }
 
-fn find_body<'a>(planets: &'a [PlanetConfig], name: &str) -> anyhow::Result<PlanetConfig> {
-    let upper = name.to_uppercase();
-    planets
-        .iter()
-        .find(|p| p.name.to_uppercase() == upper)
-        .cloned()
-        .ok_or_else(|| anyhow::anyhow!("Body '{}' not found in catalog", name))
+    if let Some(binary_path) = &cli.binary_output {
+        let metric = PorkchopMetric::from(cli.grid_metric);
+        let metadata = export_porkchop::GridMetadata {
+            metric: metric.label().to_string(),
+            origin_body: origin.spice_name.clone(),
+            dest_body: destination.spice_name.clone(),
+        };
+        let grid = porkchop_calc::porkchop_grid(&points, metric);
+        export_porkchop::write_binary(binary_path, &grid, &metadata)?;
+    }
+
+    Ok(())
 }
@@ -1,18 +1,22 @@
 use super::Cli;
 use anyhow::anyhow;
 use solar_travel_calculator::config::PlanetConfig;
-use solar_travel_calculator::core::constants::G0;
+use solar_travel_calculator::core::constants::MU_SUN_KM3_S2;
 use solar_travel_calculator::ephemeris::{self, StateVector};
 use solar_travel_calculator::export::continuous as export_continuous;
+use solar_travel_calculator::impulsive::transfers::{hohmann_phase_angle, time_to_phase_alignment};
 use solar_travel_calculator::lowthrust::{
     ConstantAccelInputs, ContinuousTransferSummary, constant_accel_profile,
 };
+use solar_travel_calculator::mission::porkchop::TimeWindow;
 use solar_travel_calculator::propulsion::{PropulsionMode, Vehicle};
 
 const MIN_TOF_S: f64 = 600.0;
 const DISTANCE_TOLERANCE_M: f64 = 10_000.0;
 const MAX_SEARCH_TIME_S: f64 = 5.0 * 365.25 * 86_400.0;
 const MIN_REFINE_STEP_S: f64 = 1_800.0;
+/// Coarse samples scanned either side of the Hohmann-phased departure before widening.
+const GUESS_SCAN_RADIUS_STEPS: usize = 4;
 
 pub(super) fn run_continuous_mode(
     cli: &Cli,
@@ -21,14 +25,13 @@ pub(super) fn run_continuous_mode(
     origin_parent: Option<&PlanetConfig>,
     destination: &PlanetConfig,
     destination_parent: Option<&PlanetConfig>,
-    depart_start: f64,
-    depart_end: f64,
-    step_s: f64,
+    departure: &TimeWindow,
 ) -> anyhow::Result<()> {
     let PropulsionMode::Continuous {
         max_acceleration_m_s2,
         max_thrust_newtons,
         isp_seconds,
+        ..
     } = vehicle.propulsion
     else {
         return Err(anyhow!(
@@ -59,22 +62,22 @@ pub(super) fn run_continuous_mode(
         acceleration_m_s2: acceleration,
         isp_seconds,
         initial_mass_kg: vehicle.initial_mass_kg(),
-        dry_mass_kg: vehicle.dry_mass_kg,
+        dry_mass_kg: vehicle.initial_mass_kg() - vehicle.propellant_mass_kg,
     };
 
-    let coarse_step = step_s.max(43_200.0); // at least 12h resolution for departure scan
+    let coarse_step = departure.step_seconds.max(43_200.0); // at least 12h resolution for departure scan
 
     let best = find_best_departure_profile(
         &accel_inputs,
         &dep_target,
         &arr_target,
-        depart_start,
-        depart_end,
+        departure.start_et,
+        departure.end_et,
         coarse_step,
     )?;
 
-    let depart_utc = ephemeris::format_epoch(best.depart_et).unwrap_or_else(|_| "".to_string());
-    let arrive_utc = ephemeris::format_epoch(best.arrive_et).unwrap_or_else(|_| "".to_string());
+    let depart_utc = ephemeris::format_epoch_iso(best.depart_et).unwrap_or_else(|_| "".to_string());
+    let arrive_utc = ephemeris::format_epoch_iso(best.arrive_et).unwrap_or_else(|_| "".to_string());
 
     let metadata = export_continuous::Metadata {
         vehicle: &vehicle.name,
@@ -84,6 +87,7 @@ pub(super) fn run_continuous_mode(
         depart_utc: &depart_utc,
         arrive_et: best.arrive_et,
         arrive_utc: &arrive_utc,
+        max_samples: cli.max_telemetry_samples,
     };
 
     let samples: Vec<export_continuous::Sample> = best
@@ -103,6 +107,8 @@ pub(super) fn run_continuous_mode(
         burn_time_total_s: best.summary.burn_time_total_s,
         propellant_used_kg: best.summary.propellant_used_kg,
         final_mass_kg: best.summary.final_mass_kg,
+        mass_ratio: best.summary.mass_ratio,
+        payload_fraction: best.summary.payload_fraction,
         max_velocity_m_s: best.summary.max_velocity_m_s,
         max_velocity_fraction_c: best.summary.max_velocity_fraction_c,
         total_distance_m: best.summary.total_distance_m,
@@ -113,7 +119,7 @@ pub(super) fn run_continuous_mode(
     export_continuous::write_sidecars(&cli.output, &metadata, &telemetry)?;
 
     println!(
-        "Continuous profile ({})\n  Departure: {}\n  Arrival: {}\n  TOF: {:.2} days\n  Peak velocity: {:.2} km/s ({:.3}% c)\n  Total distance: {:.2} million km\n  Propellant used: {:.2} kg\n  Burn time (total): {:.2} hours",
+        "Continuous profile ({})\n  Departure: {}\n  Arrival: {}\n  TOF: {:.2} days\n  Peak velocity: {:.2} km/s ({:.3}% c)\n  Total distance: {:.2} million km\n  Propellant used: {:.2} kg\n  Final mass: {:.2} kg (mass ratio {:.3}, payload fraction {:.3})\n  Burn time (total): {:.2} hours",
         vehicle.name,
         depart_utc,
         arrive_utc,
@@ -122,6 +128,9 @@ pub(super) fn run_continuous_mode(
         telemetry.max_velocity_fraction_c * 100.0,
         telemetry.total_distance_m / 1.0e9,
         telemetry.propellant_used_kg,
+        telemetry.final_mass_kg,
+        telemetry.mass_ratio,
+        telemetry.payload_fraction,
         telemetry.burn_time_total_s / 3_600.0,
     );
 
@@ -148,16 +157,50 @@ fn find_best_departure_profile(
     depart_end: f64,
     coarse_step: f64,
 ) -> anyhow::Result<Candidate> {
+    let coarse_samples = sample_window(depart_start, depart_end, coarse_step);
+    let guess_idx =
+        hohmann_departure_guess(dep_target, arr_target, depart_start, depart_end).map(|guess| {
+            coarse_samples
+                .iter()
+                .position(|&et| et >= guess)
+                .unwrap_or(coarse_samples.len() - 1)
+        });
+
+    // Scan outward from the Hohmann-phased departure, widening only while nothing is feasible.
+    let (mut lo, mut hi) = match guess_idx {
+        Some(idx) => (
+            idx.saturating_sub(GUESS_SCAN_RADIUS_STEPS),
+            (idx + GUESS_SCAN_RADIUS_STEPS + 1).min(coarse_samples.len()),
+        ),
+        None => (0, coarse_samples.len()),
+    };
+    let mut scanned = lo..lo;
     let mut best: Option<Candidate> = None;
 
-    for depart_et in sample_window(depart_start, depart_end, coarse_step) {
-        if let Some(candidate) = evaluate_departure(accel_inputs, dep_target, arr_target, depart_et)
+    loop {
+        for &depart_et in coarse_samples[lo..hi]
+            .iter()
+            .enumerate()
+            .filter(|(offset, _)| !scanned.contains(&(lo + offset)))
+            .map(|(_, et)| et)
         {
-            match &best {
-                Some(current) if !is_better_candidate(&candidate, current) => {}
-                _ => best = Some(candidate),
+            if let Some(candidate) =
+                evaluate_departure(accel_inputs, dep_target, arr_target, depart_et)
+            {
+                match &best {
+                    Some(current) if !is_better_candidate(&candidate, current) => {}
+                    _ => best = Some(candidate),
+                }
             }
         }
+        scanned = lo..hi;
+
+        if best.is_some() || (lo == 0 && hi == coarse_samples.len()) {
+            break;
+        }
+        let radius = (hi - lo).max(1);
+        lo = lo.saturating_sub(radius);
+        hi = (hi + radius).min(coarse_samples.len());
     }
 
     let mut best =
@@ -170,16 +213,42 @@ fn find_best_departure_profile(
 
     for depart_et in sample_window(refine_start, refine_end, refine_step) {
         if let Some(candidate) = evaluate_departure(accel_inputs, dep_target, arr_target, depart_et)
+            && is_better_candidate(&candidate, &best)
         {
-            if is_better_candidate(&candidate, &best) {
-                best = candidate;
-            }
+            best = candidate;
         }
     }
 
     Ok(best)
 }
 
+/// Departure epoch at which the planets reach the Hohmann phase angle, if inside the window.
+///
+/// Uses the heliocentric radii and ecliptic longitudes at `depart_start` as circular orbits.
+fn hohmann_departure_guess(
+    dep_target: &str,
+    arr_target: &str,
+    depart_start: f64,
+    depart_end: f64,
+) -> Option<f64> {
+    let dep =
+        ephemeris::state_vector_et(dep_target, "SUN", "ECLIPJ2000", "NONE", depart_start).ok()?;
+    let arr =
+        ephemeris::state_vector_et(arr_target, "SUN", "ECLIPJ2000", "NONE", depart_start).ok()?;
+    let r1 = dep.position_km[0].hypot(dep.position_km[1]);
+    let r2 = arr.position_km[0].hypot(arr.position_km[1]);
+    if r1 <= 0.0 || r2 <= 0.0 {
+        return None;
+    }
+
+    let current =
+        arr.position_km[1].atan2(arr.position_km[0]) - dep.position_km[1].atan2(dep.position_km[0]);
+    let required = hohmann_phase_angle(r1, r2, MU_SUN_KM3_S2);
+    let wait = time_to_phase_alignment(current, required, r1, r2, MU_SUN_KM3_S2)?;
+    let guess = depart_start + wait;
+    (guess <= depart_end).then_some(guess)
+}
+
 fn evaluate_departure(
     accel_inputs: &ConstantAccelInputs,
     dep_target: &str,
@@ -307,7 +376,7 @@ fn propellant_time_limit(inputs: &ConstantAccelInputs) -> Option<f64> {
     if ratio <= 0.0 || ratio >= 1.0 {
         return None;
     }
-    let limit = -(inputs.isp_seconds * G0 / inputs.acceleration_m_s2) * ratio.ln();
+    let limit = -(inputs.exhaust_velocity_m_s() / inputs.acceleration_m_s2) * ratio.ln();
     if limit.is_finite() && limit > 0.0 {
         Some(limit)
     } else {
@@ -347,21 +416,17 @@ fn is_better_candidate(candidate: &Candidate, best: &Candidate) -> bool {
 }
 
 fn sample_window(start: f64, end: f64, step: f64) -> Vec<f64> {
-    let mut samples = Vec::new();
     if end < start {
-        return samples;
-    }
-    let step = step.max(1.0);
-    let mut t = start;
-    while t <= end + 1.0 {
-        samples.push(t);
-        t += step;
+        return Vec::new();
     }
-    if let Some(&last) = samples.last() {
-        if end - last > 1.0 {
-            samples.push(end);
-        }
-    } else {
+    let window = TimeWindow {
+        start_et: start,
+        end_et: end,
+        step_seconds: step.max(1.0),
+    };
+    let mut samples: Vec<f64> = window.epochs().collect();
+    // Refinement brackets rarely divide evenly; always probe the window edge too.
+    if samples.last().is_none_or(|&last| end - last > 1.0) {
         samples.push(end);
     }
     samples
@@ -1,8 +1,10 @@
-use chrono::NaiveDateTime;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use csv::ReaderBuilder;
+use plotters::coord::Shift;
 use plotters::coord::types::RangedCoordf64;
 use plotters::prelude::*;
+use solar_travel_calculator::ephemeris;
+use solar_travel_calculator::export::porkchop as export_porkchop;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -10,7 +12,7 @@ use std::path::{Path, PathBuf};
 #[command(
     author,
     version,
-    about = "Render a porkchop heatmap from CSV (dv_total or c3)"
+    about = "Render a porkchop heatmap from CSV or a binary grid (dv_total, c3, propellant, burn_time or tof)"
 )]
 struct Cli {
     #[arg(long, default_value = "artifacts/pork.csv")]
@@ -23,8 +25,134 @@ struct Cli {
     width: u32,
     #[arg(long, default_value_t = 900)]
     height: u32,
-    #[arg(long, default_value_t = 4.0)]
-    high_clip_factor: f64,
+    /// Clip the colour scale at this multiple of the best value (defaults per metric)
+    #[arg(long)]
+    high_clip_factor: Option<f64>,
+    /// Departure epoch (UTC) of a planned mission to mark on the plot
+    #[arg(long, requires = "mark_arrive")]
+    mark_depart: Option<String>,
+    /// Arrival epoch (UTC) of a planned mission to mark on the plot
+    #[arg(long, requires = "mark_depart")]
+    mark_arrive: Option<String>,
+    /// Output image format; inferred from the output extension when omitted (PNG output is
+    /// encoded by extension, so keep `.png` when forcing it)
+    #[arg(long, value_enum)]
+    format: Option<OutputFormat>,
+    /// Colour mapping: linear in the metric, or in log10 of it for wide dynamic ranges
+    #[arg(long, value_enum, default_value_t = ColorScale::Linear)]
+    scale: ColorScale,
+    /// What to draw over the plot area: the filled heatmap, contour lines, or both
+    #[arg(long, value_enum, default_value_t = PlotStyle::Both)]
+    style: PlotStyle,
+    /// Treat larger values as better: mark the maximum and clip the low end instead
+    #[arg(long, default_value_t = false)]
+    invert: bool,
+    /// Axis layout: departure vs arrival (default), arrival vs departure, or departure vs TOF
+    #[arg(long, value_enum, default_value_t = AxesLayout::DepartArrive)]
+    axes: AxesLayout,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Png,
+    Svg,
+}
+
+impl OutputFormat {
+    /// SVG for `.svg` outputs, PNG otherwise.
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("svg") => Self::Svg,
+            _ => Self::Png,
+        }
+    }
+}
+
+/// Smallest value taken before `log10` so zero or negative cells stay drawable.
+const LOG_SCALE_FLOOR: f64 = 1.0e-6;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum ColorScale {
+    Linear,
+    Log,
+}
+
+impl ColorScale {
+    /// Map a metric value into the space colours and contour levels are spread over.
+    fn apply(self, value: f64) -> f64 {
+        match self {
+            Self::Linear => value,
+            Self::Log => value.max(LOG_SCALE_FLOOR).log10(),
+        }
+    }
+
+    /// Legend tick label for a position in the scaled space.
+    fn tick_label(self, scaled: f64) -> String {
+        match self {
+            Self::Linear => format!("{scaled:.2}"),
+            Self::Log => {
+                // Three significant figures of the underlying value.
+                let value = 10f64.powf(scaled);
+                let decimals = (2.0 - scaled.floor()).clamp(0.0, 6.0) as usize;
+                format!("{value:.decimals$}")
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum PlotStyle {
+    /// Jet-coloured cells only
+    Filled,
+    /// Contour lines on a white background
+    Contours,
+    /// Filled cells with contour lines on top
+    Both,
+}
+
+impl PlotStyle {
+    fn fills(self) -> bool {
+        matches!(self, Self::Filled | Self::Both)
+    }
+
+    fn contours(self) -> bool {
+        matches!(self, Self::Contours | Self::Both)
+    }
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum AxesLayout {
+    /// Departure date on x, arrival date on y
+    DepartArrive,
+    /// Arrival date on x, departure date on y
+    ArriveDepart,
+    /// Departure date on x, time of flight (arrive − depart) on y
+    DepartTof,
+}
+
+impl AxesLayout {
+    /// Map a cell onto (x, y) in seconds: epochs for date axes, duration for TOF.
+    ///
+    /// TOF is rounded to whole seconds so cells sharing a nominal duration land in one row.
+    fn coordinates(self, cell: &Cell) -> (f64, f64) {
+        match self {
+            Self::DepartArrive => (cell.depart_et, cell.arrive_et),
+            Self::ArriveDepart => (cell.arrive_et, cell.depart_et),
+            Self::DepartTof => (cell.depart_et, (cell.arrive_et - cell.depart_et).round()),
+        }
+    }
+
+    fn descriptions(self) -> (&'static str, &'static str) {
+        match self {
+            Self::DepartArrive => ("Departure Date", "Arrival Date"),
+            Self::ArriveDepart => ("Arrival Date", "Departure Date"),
+            Self::DepartTof => ("Departure Date", "Time of Flight (days)"),
+        }
+    }
+
+    fn y_is_duration(self) -> bool {
+        matches!(self, Self::DepartTof)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -37,8 +165,18 @@ struct Cell {
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
     let metric_request = cli.metric.clone();
-    let (cells, mut dep_vals, mut arr_vals, metric_column) =
-        read_cells(cli.input.as_path(), &metric_request)?;
+    let (cells, metric_column) = read_cells(cli.input.as_path(), &metric_request)?;
+    let axes = cli.axes;
+    let mission_mark = match (&cli.mark_depart, &cli.mark_arrive) {
+        (Some(depart), Some(arrive)) => Some(axes.coordinates(&Cell {
+            depart_et: ephemeris::epoch_seconds(depart)?,
+            arrive_et: ephemeris::epoch_seconds(arrive)?,
+            metric_value: f64::NAN,
+        })),
+        _ => None,
+    };
+    let (mut x_vals, mut y_vals): (Vec<f64>, Vec<f64>) =
+        cells.iter().map(|cell| axes.coordinates(cell)).unzip();
 
     if cells.is_empty() {
         return Err(anyhow::anyhow!(
@@ -46,36 +184,89 @@ fn main() -> anyhow::Result<()> {
         ));
     }
 
-    dep_vals.sort_by(|a, b| a.partial_cmp(b).unwrap());
-    dep_vals.dedup();
-    arr_vals.sort_by(|a, b| a.partial_cmp(b).unwrap());
-    arr_vals.dedup();
+    // The porkchop exporter quantizes epochs, so cells on one grid line share
+    // bit-identical coordinates and exact dedup/lookup is sufficient.
+    x_vals.sort_by(f64::total_cmp);
+    x_vals.dedup();
+    y_vals.sort_by(f64::total_cmp);
+    y_vals.dedup();
 
-    if dep_vals.is_empty() || arr_vals.is_empty() {
+    if x_vals.is_empty() || y_vals.is_empty() {
         return Err(anyhow::anyhow!(
             "No feasible Lambert solutions in the provided CSV"
         ));
     }
 
-    if let Some(parent) = cli.output.parent() {
-        if !parent.as_os_str().is_empty() {
-            fs::create_dir_all(parent)?;
-        }
+    if let Some(parent) = cli.output.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
     }
     let output_str = cli
         .output
         .to_str()
         .ok_or_else(|| anyhow::anyhow!("Output path contains invalid UTF-8"))?;
-    let root = BitMapBackend::new(output_str, (cli.width, cli.height)).into_drawing_area();
+    let size = (cli.width, cli.height);
+    let data = PlotData {
+        cells,
+        metric_column,
+        mission_mark,
+        x_vals,
+        y_vals,
+    };
+    match cli
+        .format
+        .unwrap_or_else(|| OutputFormat::from_path(&cli.output))
+    {
+        OutputFormat::Png => render(
+            &cli,
+            BitMapBackend::new(output_str, size).into_drawing_area(),
+            data,
+        ),
+        OutputFormat::Svg => render(
+            &cli,
+            SVGBackend::new(output_str, size).into_drawing_area(),
+            data,
+        ),
+    }
+}
+
+/// Prepared cells and axes handed from `main` to the backend-generic renderer.
+struct PlotData {
+    cells: Vec<Cell>,
+    metric_column: String,
+    /// Planned mission point in axis coordinates (seconds), if one was requested.
+    mission_mark: Option<(f64, f64)>,
+    x_vals: Vec<f64>,
+    y_vals: Vec<f64>,
+}
+
+/// Draw the heatmap, contours, markers and legend onto `root`.
+fn render<DB: DrawingBackend>(
+    cli: &Cli,
+    root: DrawingArea<DB, Shift>,
+    data: PlotData,
+) -> anyhow::Result<()>
+where
+    DB::ErrorType: 'static,
+{
+    let PlotData {
+        cells,
+        metric_column,
+        mission_mark,
+        x_vals,
+        y_vals,
+    } = data;
+    let axes = cli.axes;
     root.fill(&WHITE)?;
 
-    let et_depart_min = *dep_vals.first().expect("depart range");
-    let et_depart_max = *dep_vals.last().expect("depart range");
-    let et_arrive_min = *arr_vals.first().expect("arrive range");
-    let et_arrive_max = *arr_vals.last().expect("arrive range");
+    let x_min = *x_vals.first().expect("x range");
+    let x_max = *x_vals.last().expect("x range");
+    let y_min = *y_vals.first().expect("y range");
+    let y_max = *y_vals.last().expect("y range");
 
-    let depart_span_days = (et_depart_max - et_depart_min) / 86_400.0;
-    let arrive_span_days = (et_arrive_max - et_arrive_min) / 86_400.0;
+    let x_span_days = (x_max - x_min) / 86_400.0;
+    let y_span_days = (y_max - y_min) / 86_400.0;
 
     let font_family = select_font_family();
     let caption_font = FontDesc::new(font_family, 24.0, FontStyle::Bold);
@@ -85,42 +276,56 @@ fn main() -> anyhow::Result<()> {
     let (plot_area, legend_area) =
         root.split_horizontally((cli.width as i32 - legend_width).max(200));
 
-    let dep_coords: Vec<f64> = dep_vals
-        .iter()
-        .map(|et| (et - et_depart_min) / 86_400.0)
-        .collect();
-    let arr_coords: Vec<f64> = arr_vals
-        .iter()
-        .map(|et| (et - et_arrive_min) / 86_400.0)
-        .collect();
+    let x_coords: Vec<f64> = x_vals.iter().map(|v| (v - x_min) / 86_400.0).collect();
+    let y_coords: Vec<f64> = y_vals.iter().map(|v| (v - y_min) / 86_400.0).collect();
 
-    let grid = build_grid(&cells, &dep_vals, &arr_vals);
+    let metric = PlotMetric::from_column(&metric_column);
+    let invert = cli.invert;
+    let grid = build_grid(&cells, &x_vals, &y_vals, axes, invert);
     let mut min_value = f64::INFINITY;
     let mut max_value = f64::NEG_INFINITY;
-    let mut min_pos: Option<(usize, usize)> = None;
-    for (arr_idx, row) in grid.iter().enumerate() {
-        for (dep_idx, &v) in row.iter().enumerate() {
+    let mut best_pos: Option<(usize, usize)> = None;
+    let mut best_value = f64::NAN;
+    for (y_idx, row) in grid.iter().enumerate() {
+        for (x_idx, &v) in row.iter().enumerate() {
             if v.is_finite() {
-                if v < min_value {
-                    min_value = v;
-                    min_pos = Some((dep_idx, arr_idx));
-                }
-                if v > max_value {
-                    max_value = v;
+                min_value = min_value.min(v);
+                max_value = max_value.max(v);
+                let better = if invert {
+                    v > best_value
+                } else {
+                    v < best_value
+                };
+                if best_value.is_nan() || better {
+                    best_value = v;
+                    best_pos = Some((x_idx, y_idx));
                 }
             }
         }
     }
 
-    let (min_dep_idx, min_arr_idx) =
-        min_pos.ok_or_else(|| anyhow::anyhow!("No feasible entries in the provided CSV"))?;
-    if !max_value.is_finite() {
-        max_value = min_value;
-    }
-    let mut high_clip = (min_value * cli.high_clip_factor).min(max_value);
-    if !high_clip.is_finite() || high_clip <= min_value {
-        high_clip = max_value.max(min_value * 1.001);
-    }
+    let (best_x_idx, best_y_idx) =
+        best_pos.ok_or_else(|| anyhow::anyhow!("No feasible entries in the provided CSV"))?;
+    // Colour range: clip the far end at `clip_factor` times the best value, keeping the
+    // interesting region around the optimum resolved.
+    let clip_factor = cli.high_clip_factor.or(metric.default_clip_factor());
+    let (min_value, high_clip) = match clip_factor {
+        Some(factor) if invert => ((max_value / factor).max(min_value), max_value),
+        Some(factor) => (min_value, (min_value * factor).min(max_value)),
+        None => (min_value, max_value),
+    };
+    let high_clip = if !high_clip.is_finite() || high_clip <= min_value {
+        max_value.max(min_value + min_value.abs().max(1.0) * 1.0e-3)
+    } else {
+        high_clip
+    };
+    // Colours, contour levels and the legend all live in the scaled space from here on.
+    let scale = cli.scale;
+    let min_value = scale.apply(min_value);
+    let high_clip = match scale.apply(high_clip) {
+        scaled if scaled > min_value => scaled,
+        _ => min_value + 1.0e-3,
+    };
 
     let grid_clamped: Vec<Vec<f64>> = grid
         .iter()
@@ -128,7 +333,7 @@ fn main() -> anyhow::Result<()> {
             row.iter()
                 .map(|v| {
                     if v.is_finite() {
-                        v.clamp(min_value, high_clip)
+                        scale.apply(*v).clamp(min_value, high_clip)
                     } else {
                         f64::NAN
                     }
@@ -147,58 +352,65 @@ fn main() -> anyhow::Result<()> {
     {
         let mut chart = ChartBuilder::on(&plot_area)
             .margin(20)
-            .caption("Porkchop heatmap".to_string(), caption_font)
+            .caption("Porkchop heatmap", caption_font)
             .x_label_area_size(60)
             .y_label_area_size(90)
-            .build_cartesian_2d(0.0..depart_span_days, 0.0..arrive_span_days)?;
-
+            .build_cartesian_2d(0.0..x_span_days, 0.0..y_span_days)?;
+
+        let (x_desc, y_desc) = axes.descriptions();
+        let y_formatter = |d: &f64| {
+            if axes.y_is_duration() {
+                format!("{:.0}", (y_min / 86_400.0) + d)
+            } else {
+                fmt_et_label(y_min + d * 86_400.0)
+            }
+        };
         chart
             .configure_mesh()
-            .x_desc("Departure Date")
-            .y_desc("Arrival Date")
+            .x_desc(x_desc)
+            .y_desc(y_desc)
             .label_style(label_font.clone())
             .x_labels(6)
             .y_labels(6)
-            .x_label_formatter(&|d| fmt_et_label(et_depart_min + d * 86_400.0))
-            .y_label_formatter(&|d| fmt_et_label(et_arrive_min + d * 86_400.0))
+            .x_label_formatter(&|d| fmt_et_label(x_min + d * 86_400.0))
+            .y_label_formatter(&y_formatter)
             .draw()?;
 
-        for (arr_idx, row) in grid.iter().enumerate() {
-            let (y0, y1) = cell_bounds(&arr_coords, arr_idx);
-            for (dep_idx, &value) in row.iter().enumerate() {
-                if !value.is_finite() {
-                    continue;
-                }
-                let (x0, x1) = cell_bounds(&dep_coords, dep_idx);
-                let clamped = value.clamp(min_value, high_clip);
-                let t = if (high_clip - min_value).abs() < f64::EPSILON {
-                    0.0
-                } else {
-                    (clamped - min_value) / (high_clip - min_value)
-                };
-                let color = jet_color(t);
-                chart.draw_series(std::iter::once(Rectangle::new(
-                    [(x0, y0), (x1, y1)],
-                    color.filled(),
-                )))?;
-            }
+        if cli.style.fills() {
+            draw_cells(
+                &mut chart,
+                &grid_clamped,
+                &x_coords,
+                &y_coords,
+                (min_value, high_clip),
+                invert,
+            )?;
         }
 
-        draw_contours(&mut chart, &grid_clamped, &dep_coords, &arr_coords, &levels)?;
+        if cli.style.contours() {
+            draw_contours(
+                &mut chart,
+                &grid_clamped,
+                &x_coords,
+                &y_coords,
+                &levels,
+                invert,
+            )?;
+        }
 
-        let x = dep_coords[min_dep_idx];
-        let y = arr_coords[min_arr_idx];
+        let x = x_coords[best_x_idx];
+        let y = y_coords[best_y_idx];
         chart.draw_series(std::iter::once(PathElement::new(
-            vec![(x, 0.0), (x, arrive_span_days)],
+            vec![(x, 0.0), (x, y_span_days)],
             ShapeStyle::from(&BLACK.mix(0.5)).stroke_width(1),
         )))?;
         chart.draw_series(std::iter::once(PathElement::new(
-            vec![(0.0, y), (depart_span_days, y)],
+            vec![(0.0, y), (x_span_days, y)],
             ShapeStyle::from(&BLACK.mix(0.5)).stroke_width(1),
         )))?;
         let marker_color = RGBColor(210, 100, 20);
-        let cross_half_width = depart_span_days * 0.02;
-        let cross_half_height = arrive_span_days * 0.02;
+        let cross_half_width = x_span_days * 0.02;
+        let cross_half_height = y_span_days * 0.02;
         chart.draw_series(std::iter::once(PathElement::new(
             vec![(x - cross_half_width, y), (x + cross_half_width, y)],
             ShapeStyle::from(&marker_color).stroke_width(3),
@@ -207,14 +419,36 @@ fn main() -> anyhow::Result<()> {
             vec![(x, y - cross_half_height), (x, y + cross_half_height)],
             ShapeStyle::from(&marker_color).stroke_width(3),
         )))?;
-        let (annotation_prefix, annotation_suffix) = metric_annotation(&metric_column);
-        let text = format!("{}{:.2}{}", annotation_prefix, min_value, annotation_suffix);
-        let text_pos = (x + 0.02 * depart_span_days, y + 0.02 * arrive_span_days);
+        let text = metric.annotate(best_value, invert);
+        let text_pos = (x + 0.02 * x_span_days, y + 0.02 * y_span_days);
         chart.draw_series(std::iter::once(Text::new(
             text,
             text_pos,
             label_font.clone().color(&marker_color),
         )))?;
+
+        if let Some((mark_x, mark_y)) = mission_mark {
+            let raw = ((mark_x - x_min) / 86_400.0, (mark_y - y_min) / 86_400.0);
+            let x = raw.0.clamp(0.0, x_span_days);
+            let y = raw.1.clamp(0.0, y_span_days);
+            let off_grid = x != raw.0 || y != raw.1;
+            let mission_color = RGBColor(200, 0, 160);
+            chart.draw_series(std::iter::once(Circle::new(
+                (x, y),
+                7,
+                ShapeStyle::from(&mission_color).stroke_width(3),
+            )))?;
+            let label = if off_grid {
+                "Mission (off grid)"
+            } else {
+                "Mission"
+            };
+            chart.draw_series(std::iter::once(Text::new(
+                label,
+                (x + 0.02 * x_span_days, y - 0.04 * y_span_days),
+                label_font.clone().color(&mission_color),
+            )))?;
+        }
     }
 
     {
@@ -232,7 +466,7 @@ fn main() -> anyhow::Result<()> {
             let t1 = (i + 1) as f64 / 300.0;
             let v0 = min_value + (high_clip - min_value) * t0;
             let v1 = min_value + (high_clip - min_value) * t1;
-            let color = jet_color(t0);
+            let color = jet_color(if invert { 1.0 - t0 } else { t0 });
             chart.draw_series(std::iter::once(Rectangle::new(
                 [(0.0, v0), (1.0, v1)],
                 color.filled(),
@@ -245,10 +479,10 @@ fn main() -> anyhow::Result<()> {
             .disable_y_mesh()
             .x_labels(0)
             .y_labels(6)
-            .y_desc(metric_axis_label(&metric_column))
+            .y_desc(metric.axis_label())
             .y_label_style(label_font.clone())
             .axis_desc_style(label_font.clone())
-            .y_label_formatter(&|v| format!("{v:.2}"))
+            .y_label_formatter(&|v| scale.tick_label(*v))
             .draw()?;
     }
 
@@ -266,10 +500,10 @@ fn select_font_family() -> FontFamily<'static> {
     }
 }
 
-fn read_cells(
-    path: &Path,
-    metric_name: &str,
-) -> anyhow::Result<(Vec<Cell>, Vec<f64>, Vec<f64>, String)> {
+fn read_cells(path: &Path, metric_name: &str) -> anyhow::Result<(Vec<Cell>, String)> {
+    if export_porkchop::is_binary_grid(path)? {
+        return read_binary_cells(path, metric_name);
+    }
     let mut rdr = ReaderBuilder::new().has_headers(true).from_path(path)?;
     let headers = rdr.headers()?.clone();
     let depart_idx = headers
@@ -292,8 +526,6 @@ fn read_cells(
         .ok_or_else(|| anyhow::anyhow!("Invalid metric column index"))?;
 
     let mut cells = Vec::new();
-    let mut dep_vals = Vec::new();
-    let mut arr_vals = Vec::new();
     for rec in rdr.records() {
         let r = rec?;
         let depart_et: f64 = r.get(depart_idx).unwrap_or("").parse().unwrap_or(f64::NAN);
@@ -303,27 +535,47 @@ fn read_cells(
             .unwrap_or("false")
             .eq_ignore_ascii_case("true");
         let metric_value: f64 = r.get(metric_idx).unwrap_or("").parse().unwrap_or(f64::NAN);
-        if depart_et.is_finite() && arrive_et.is_finite() {
-            if feasible && metric_value.is_finite() {
-                dep_vals.push(depart_et);
-                arr_vals.push(arrive_et);
+        if depart_et.is_finite() && arrive_et.is_finite() && feasible && metric_value.is_finite() {
+            cells.push(Cell {
+                depart_et,
+                arrive_et,
+                metric_value,
+            });
+        }
+    }
+    Ok((cells, metric_column))
+}
+
+/// Feasible cells of a binary grid, which holds the single metric it was exported with.
+fn read_binary_cells(path: &Path, metric_name: &str) -> anyhow::Result<(Vec<Cell>, String)> {
+    let (grid, metadata) = export_porkchop::read_binary(path)?;
+    let header = csv::StringRecord::from(vec![metadata.metric.as_str()]);
+    if resolve_metric_column(&header, metric_name).is_none() {
+        return Err(anyhow::anyhow!(
+            "Binary grid holds '{}', not '{}'",
+            metadata.metric,
+            metric_name
+        ));
+    }
+    let mut cells = Vec::new();
+    for (row, &depart_et) in grid.depart_et.iter().enumerate() {
+        for (col, &arrive_et) in grid.arrive_et.iter().enumerate() {
+            let idx = grid.index(row, col);
+            if grid.feasible[idx] && grid.values[idx].is_finite() {
                 cells.push(Cell {
                     depart_et,
                     arrive_et,
-                    metric_value,
+                    metric_value: grid.values[idx],
                 });
             }
         }
     }
-    Ok((cells, dep_vals, arr_vals, metric_column))
+    Ok((cells, metadata.metric))
 }
 
 fn fmt_et_label(et: f64) -> String {
-    match solar_travel_calculator::ephemeris::format_epoch(et) {
-        Ok(epoch) => match NaiveDateTime::parse_from_str(&epoch, "%Y %b %d %H:%M:%S%.f") {
-            Ok(dt) => dt.format("%Y-%m-%d").to_string(),
-            Err(_) => epoch,
-        },
+    match ephemeris::format_epoch_iso(et) {
+        Ok(epoch) => epoch.split('T').next().unwrap_or(&epoch).to_string(),
         Err(_) => format!("{et:.0}"),
     }
 }
@@ -339,33 +591,80 @@ fn jet_color(t_in: f64) -> RGBColor {
     RGBColor((r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8)
 }
 
-fn build_grid(cells: &[Cell], dep_vals: &[f64], arr_vals: &[f64]) -> Vec<Vec<f64>> {
-    let mut grid = vec![vec![f64::NAN; dep_vals.len()]; arr_vals.len()];
+/// Bin cells into a row-major grid indexed `[y][x]` according to the chosen axis layout.
+///
+/// Cells sharing a slot keep the best value: the smallest, or the largest when `invert` is set.
+fn build_grid(
+    cells: &[Cell],
+    x_vals: &[f64],
+    y_vals: &[f64],
+    axes: AxesLayout,
+    invert: bool,
+) -> Vec<Vec<f64>> {
+    let mut grid = vec![vec![f64::NAN; x_vals.len()]; y_vals.len()];
     for cell in cells {
-        let dep_idx = match dep_vals.binary_search_by(|x| x.partial_cmp(&cell.depart_et).unwrap()) {
+        let (x, y) = axes.coordinates(cell);
+        let x_idx = match x_vals.binary_search_by(|v| v.total_cmp(&x)) {
             Ok(idx) => idx,
             Err(_) => continue,
         };
-        let arr_idx = match arr_vals.binary_search_by(|x| x.partial_cmp(&cell.arrive_et).unwrap()) {
+        let y_idx = match y_vals.binary_search_by(|v| v.total_cmp(&y)) {
             Ok(idx) => idx,
             Err(_) => continue,
         };
-        let slot = &mut grid[arr_idx][dep_idx];
-        if !slot.is_finite() || cell.metric_value < *slot {
+        let slot = &mut grid[y_idx][x_idx];
+        let better = if invert {
+            cell.metric_value > *slot
+        } else {
+            cell.metric_value < *slot
+        };
+        if !slot.is_finite() || better {
             *slot = cell.metric_value;
         }
     }
     grid
 }
 
+/// Fill each finite grid cell with its jet colour over the `(low, high)` value range.
+fn draw_cells<DB: DrawingBackend>(
+    chart: &mut ChartContext<DB, Cartesian2d<RangedCoordf64, RangedCoordf64>>,
+    grid: &[Vec<f64>],
+    x_coords: &[f64],
+    y_coords: &[f64],
+    (low, high): (f64, f64),
+    invert: bool,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    for (y_idx, row) in grid.iter().enumerate() {
+        let (y0, y1) = cell_bounds(y_coords, y_idx);
+        for (x_idx, &value) in row.iter().enumerate() {
+            if !value.is_finite() {
+                continue;
+            }
+            let (x0, x1) = cell_bounds(x_coords, x_idx);
+            let t = if (high - low).abs() < f64::EPSILON {
+                0.0
+            } else {
+                (value.clamp(low, high) - low) / (high - low)
+            };
+            let color = jet_color(if invert { 1.0 - t } else { t });
+            chart.draw_series(std::iter::once(Rectangle::new(
+                [(x0, y0), (x1, y1)],
+                color.filled(),
+            )))?;
+        }
+    }
+    Ok(())
+}
+
 fn draw_contours<DB: DrawingBackend>(
     chart: &mut ChartContext<DB, Cartesian2d<RangedCoordf64, RangedCoordf64>>,
     grid: &[Vec<f64>],
-    dep_coords: &[f64],
-    arr_coords: &[f64],
+    x_coords: &[f64],
+    y_coords: &[f64],
     levels: &[f64],
+    invert: bool,
 ) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
-    if dep_coords.len() < 2 || arr_coords.len() < 2 {
+    if x_coords.len() < 2 || y_coords.len() < 2 {
         return Ok(());
     }
     let min_level = *levels.first().unwrap_or(&0.0);
@@ -376,9 +675,9 @@ fn draw_contours<DB: DrawingBackend>(
         } else {
             (level - min_level) / (max_level - min_level)
         };
-        let color = jet_color(t);
-        for i in 0..arr_coords.len() - 1 {
-            for j in 0..dep_coords.len() - 1 {
+        let color = jet_color(if invert { 1.0 - t } else { t });
+        for i in 0..y_coords.len() - 1 {
+            for j in 0..x_coords.len() - 1 {
                 let v0 = grid[i][j];
                 let v1 = grid[i][j + 1];
                 let v2 = grid[i + 1][j + 1];
@@ -387,10 +686,10 @@ fn draw_contours<DB: DrawingBackend>(
                     continue;
                 }
                 let coords = [
-                    (dep_coords[j], arr_coords[i]),
-                    (dep_coords[j + 1], arr_coords[i]),
-                    (dep_coords[j + 1], arr_coords[i + 1]),
-                    (dep_coords[j], arr_coords[i + 1]),
+                    (x_coords[j], y_coords[i]),
+                    (x_coords[j + 1], y_coords[i]),
+                    (x_coords[j + 1], y_coords[i + 1]),
+                    (x_coords[j], y_coords[i + 1]),
                 ];
                 for (p1, p2) in marching_square_segments([v0, v1, v2, v3], coords, level) {
                     chart.draw_series(std::iter::once(PathElement::new(
@@ -495,6 +794,9 @@ fn resolve_metric_column(headers: &csv::StringRecord, metric_name: &str) -> Opti
     let fallback = match metric_lower.as_str() {
         "dv_total" => "dv_total_km_s",
         "c3" => "c3_km2_s2",
+        "propellant" | "propellant_used" => "propellant_used_kg",
+        "burn_time" => "burn_time_s",
+        "tof" => "tof_days",
         other => other,
     };
     headers
@@ -502,19 +804,77 @@ fn resolve_metric_column(headers: &csv::StringRecord, metric_name: &str) -> Opti
         .position(|h| h.eq_ignore_ascii_case(fallback))
 }
 
-fn metric_annotation(metric_column: &str) -> (String, String) {
-    match metric_column.to_lowercase().as_str() {
-        "dv_total_km_s" => ("Δv = ".to_string(), " km/s".to_string()),
-        "c3_km2_s2" => ("C3 = ".to_string(), " km^2/s^2".to_string()),
-        other => (format!("{other} = "), "".to_string()),
-    }
+/// Known porkchop CSV metrics, with their display labels and units.
+#[derive(Debug, Clone, PartialEq)]
+enum PlotMetric {
+    DvTotal,
+    C3,
+    PropellantUsed,
+    BurnTime,
+    Tof,
+    /// Any other numeric column, labelled with its raw name.
+    Other(String),
 }
 
-fn metric_axis_label(metric_column: &str) -> String {
-    match metric_column.to_lowercase().as_str() {
-        "dv_total_km_s" => "Total Δv (km/s)".to_string(),
-        "c3_km2_s2" => "C3 (km^2/s^2)".to_string(),
-        other => other.to_string(),
+impl PlotMetric {
+    fn from_column(column: &str) -> Self {
+        match column.to_lowercase().as_str() {
+            "dv_total_km_s" => Self::DvTotal,
+            "c3_km2_s2" => Self::C3,
+            "propellant_used_kg" => Self::PropellantUsed,
+            "burn_time_s" => Self::BurnTime,
+            "tof_days" => Self::Tof,
+            _ => Self::Other(column.to_string()),
+        }
+    }
+
+    fn axis_label(&self) -> String {
+        match self {
+            Self::DvTotal => "Total Δv (km/s)".to_string(),
+            Self::C3 => "C3 (km^2/s^2)".to_string(),
+            Self::PropellantUsed => "Propellant used (kg)".to_string(),
+            Self::BurnTime => "Burn time (s)".to_string(),
+            Self::Tof => "Time of flight (days)".to_string(),
+            Self::Other(column) => column.clone(),
+        }
+    }
+
+    /// Prefix and suffix around the value in the best-cell annotation.
+    fn annotation(&self) -> (String, String) {
+        match self {
+            Self::DvTotal => ("Δv = ".to_string(), " km/s".to_string()),
+            Self::C3 => ("C3 = ".to_string(), " km^2/s^2".to_string()),
+            Self::PropellantUsed => ("Propellant = ".to_string(), " kg".to_string()),
+            Self::BurnTime => ("Burn = ".to_string(), " s".to_string()),
+            Self::Tof => ("TOF = ".to_string(), " d".to_string()),
+            Self::Other(column) => (format!("{column} = "), "".to_string()),
+        }
+    }
+
+    fn decimals(&self) -> usize {
+        match self {
+            Self::DvTotal | Self::C3 | Self::Other(_) => 2,
+            Self::PropellantUsed | Self::Tof => 1,
+            Self::BurnTime => 0,
+        }
+    }
+
+    /// Label for the best cell, flagged as a maximum when the scale is inverted.
+    fn annotate(&self, value: f64, invert: bool) -> String {
+        let (prefix, suffix) = self.annotation();
+        let extremum = if invert { "max " } else { "" };
+        format!("{extremum}{prefix}{value:.*}{suffix}", self.decimals())
+    }
+
+    /// Default colour clip as a multiple of the best value.
+    ///
+    /// Δv, C3, propellant and burn time blow up near the window edges, so they clip at 4× the
+    /// optimum; time of flight is bounded by the grid and shows its full range.
+    fn default_clip_factor(&self) -> Option<f64> {
+        match self {
+            Self::DvTotal | Self::C3 | Self::PropellantUsed | Self::BurnTime => Some(4.0),
+            Self::Tof | Self::Other(_) => None,
+        }
     }
 }
 
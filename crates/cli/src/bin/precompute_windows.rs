@@ -0,0 +1,104 @@
+use anyhow::anyhow;
+use clap::Parser;
+use solar_travel_calculator::config::{load_planets, load_vehicle_configs};
+use solar_travel_calculator::ephemeris;
+use solar_travel_calculator::transfer::kernel_constants;
+use std::time::Instant;
+
+#[path = "common/catalog.rs"]
+mod catalog;
+#[path = "mission/window_cache.rs"]
+mod window_cache;
+
+use catalog::{find_body_pair, find_parent, select_impulsive_vehicle};
+use window_cache::{
+    WindowCacheKey, compute_and_store_dataset, load_cached_dataset, state_cache_dir,
+    window_cache_path,
+};
+
+/// Precompute and cache departure-window datasets so the mission CLI never stalls on first use.
+#[derive(Parser, Debug)]
+#[command(
+    author,
+    version,
+    about = "Batch-compute window datasets for origin/destination pairs"
+)]
+struct Cli {
+    /// Origin/destination pair as ORIGIN:DESTINATION (repeatable, case-insensitive)
+    #[arg(long = "pair", required = true)]
+    pairs: Vec<String>,
+
+    /// Departure epoch the mission CLI will be run with (TDB/UTC string accepted by SPICE)
+    #[arg(long)]
+    depart: String,
+
+    /// Impulsive vehicle name from catalogs (defaults to the first impulsive vehicle)
+    #[arg(long)]
+    vehicle: Option<String>,
+
+    /// Recompute datasets even when a valid cache file already exists
+    #[arg(long, default_value_t = false)]
+    force: bool,
+
+    /// Reuse sampled body states from data/ephem_cache, appending new samples on misses
+    #[arg(long, default_value_t = false)]
+    cache_states: bool,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let planets = load_planets("configs/bodies")?;
+    let vehicle_catalog = load_vehicle_configs("configs/vehicles")?;
+    let vehicle = select_impulsive_vehicle(&vehicle_catalog, cli.vehicle.as_deref())?;
+    let departure_et = ephemeris::epoch_seconds(&cli.depart)?;
+    // Same GMs as the mission/porkchop/window commands that read these caches.
+    let constants = kernel_constants(&planets)?;
+
+    for pair in &cli.pairs {
+        let (from, to) = pair
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Pair '{}' must be ORIGIN:DESTINATION", pair))?;
+        let (origin, destination) = find_body_pair(&planets, from.trim(), to.trim())?;
+        let key = WindowCacheKey {
+            vehicle: &vehicle,
+            departure_et,
+            rpark_dep_km: origin.radius_km + origin.default_parking_altitude_km,
+            rpark_arr_km: destination.radius_km + destination.default_parking_altitude_km,
+            constants: &constants,
+        };
+        let cache_path = window_cache_path(&origin, &destination, &key);
+
+        if !cli.force && load_cached_dataset(&cache_path, &key).is_some() {
+            println!(
+                "{} → {}: cached at {}",
+                origin.name,
+                destination.name,
+                cache_path.display()
+            );
+            continue;
+        }
+
+        let origin_parent = find_parent(&planets, &origin);
+        let destination_parent = find_parent(&planets, &destination);
+        let started = Instant::now();
+        let dataset = compute_and_store_dataset(
+            &cache_path,
+            &origin,
+            origin_parent.as_ref(),
+            &destination,
+            destination_parent.as_ref(),
+            &key,
+            state_cache_dir(cli.cache_states),
+        )?;
+        println!(
+            "{} → {}: {} samples in {:.1}s, saved to {}",
+            origin.name,
+            destination.name,
+            dataset.samples.len(),
+            started.elapsed().as_secs_f64(),
+            cache_path.display()
+        );
+    }
+
+    Ok(())
+}
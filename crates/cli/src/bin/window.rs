@@ -0,0 +1,139 @@
+use anyhow::anyhow;
+use clap::Parser;
+use solar_travel_calculator::config::{load_constants, load_planets, load_vehicle_configs};
+use solar_travel_calculator::ephemeris::{self, cache::DEFAULT_STATE_CACHE_DIR};
+use solar_travel_calculator::transfer::kernel_constants;
+use solar_travel_calculator::transfer::mission::porkchop::{
+    TransferEndpoint, WindowSweep, compute_window_dataset, save_window_dataset,
+};
+use std::path::{Path, PathBuf};
+
+#[path = "common/catalog.rs"]
+mod catalog;
+
+use catalog::{find_body_pair, find_parent, select_impulsive_vehicle};
+
+/// Scan launch windows for one origin/destination pair and save the dataset as JSON.
+#[derive(Parser, Debug)]
+#[command(
+    author,
+    version,
+    about = "Launch-window search: best impulsive transfer per departure date"
+)]
+struct Cli {
+    /// Departure planet name (case-insensitive)
+    #[arg(long)]
+    from: String,
+
+    /// Destination planet/moon name (case-insensitive)
+    #[arg(long)]
+    to: String,
+
+    /// First departure epoch scanned (UTC/TDB string)
+    #[arg(long)]
+    depart_start: String,
+
+    /// Length of the departure scan in days
+    #[arg(long, default_value_t = 3_650.0)]
+    span_days: f64,
+
+    /// Departure/arrival grid step in days
+    #[arg(long, default_value_t = 10.0)]
+    step_days: f64,
+
+    /// Shortest time of flight considered, in days
+    #[arg(long, default_value_t = 30.0)]
+    min_tof: f64,
+
+    /// Longest time of flight considered, in days
+    #[arg(long, default_value_t = 1_200.0)]
+    max_tof: f64,
+
+    /// Impulsive vehicle name from catalogs (defaults to the first impulsive vehicle)
+    #[arg(long)]
+    vehicle: Option<String>,
+
+    /// Gravitational constants file (TOML/YAML); defaults to the GMs in the loaded kernels, else DE440 values
+    #[arg(long)]
+    constants: Option<PathBuf>,
+
+    /// Output JSON file for the window dataset
+    #[arg(long, default_value = "artifacts/window.json")]
+    output: PathBuf,
+
+    /// Reuse sampled body states from data/ephem_cache, appending new samples on misses
+    #[arg(long, default_value_t = false)]
+    cache_states: bool,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    if cli.max_tof < cli.min_tof {
+        return Err(anyhow!(
+            "--max-tof ({}) must not be shorter than --min-tof ({})",
+            cli.max_tof,
+            cli.min_tof
+        ));
+    }
+    let planets = load_planets("configs/bodies")?;
+    let vehicle_catalog = load_vehicle_configs("configs/vehicles")?;
+    let vehicle = select_impulsive_vehicle(&vehicle_catalog, cli.vehicle.as_deref())?;
+    let constants = match &cli.constants {
+        Some(path) => load_constants(path)?,
+        None => kernel_constants(&planets)?,
+    };
+    let (origin, destination) = find_body_pair(&planets, &cli.from, &cli.to)?;
+    let origin_parent = find_parent(&planets, &origin);
+    let destination_parent = find_parent(&planets, &destination);
+    let depart_start_et = ephemeris::epoch_seconds(&cli.depart_start)?;
+
+    let sweep = WindowSweep {
+        depart_start_et,
+        span_days: cli.span_days,
+        step_days: cli.step_days,
+        min_tof_days: cli.min_tof,
+        max_tof_days: cli.max_tof,
+    };
+
+    let dataset = compute_window_dataset(
+        TransferEndpoint {
+            body: &origin,
+            parent: origin_parent.as_ref(),
+            rpark_km: origin.radius_km + origin.default_parking_altitude_km,
+        },
+        TransferEndpoint {
+            body: &destination,
+            parent: destination_parent.as_ref(),
+            rpark_km: destination.radius_km + destination.default_parking_altitude_km,
+        },
+        &vehicle,
+        &sweep,
+        &constants,
+        cli.cache_states
+            .then_some(Path::new(DEFAULT_STATE_CACHE_DIR)),
+    )?;
+    save_window_dataset(&cli.output, &dataset)?;
+
+    println!(
+        "{} → {}: {} departure samples saved to {}",
+        origin.name,
+        destination.name,
+        dataset.samples.len(),
+        cli.output.display()
+    );
+    match dataset.baseline_sample() {
+        Some(best) => println!(
+            "Best departure : {} → {} (TOF = {:.1} days), Δv_total = {:.3} km/s (dep {:.3}, arr {:.3}), C3 = {:.3} km²/s²",
+            best.depart_utc,
+            best.arrive_utc,
+            best.tof_days(),
+            best.dv_total_km_s,
+            best.dv_depart_km_s,
+            best.dv_arrive_km_s,
+            best.c3_km2_s2
+        ),
+        None => println!("No feasible transfer found in the scanned window"),
+    }
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 //! Configuration models and loaders for the Solar Travel Calculator.
 
-use std::fs::File;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
@@ -19,6 +19,10 @@ pub struct PlanetConfig {
     pub default_parking_altitude_km: f64,
     pub surface_gravity_m_s2: f64,
     pub mass_kg: f64,
+    /// Second zonal harmonic (oblateness) referenced to `radius_km`; zero treats the body as a
+    /// sphere.
+    #[serde(default)]
+    pub j2: f64,
     pub atmosphere: Option<AtmosphereConfig>,
     #[serde(default)]
     pub entry_target: Option<EntryTargetConfig>,
@@ -26,12 +30,139 @@ pub struct PlanetConfig {
     pub kernel_dependencies: Vec<String>,
 }
 
+/// Relative tolerance between `surface_gravity_m_s2` and `mu / radius²`; rotation and oblateness
+/// put the gas giants several percent off the point-mass value.
+const SURFACE_GRAVITY_TOLERANCE: f64 = 0.1;
+
+impl PlanetConfig {
+    /// Check the record for physically inconsistent values, reporting every violation at once.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let positive = |value: f64| value.is_finite() && value > 0.0;
+        let mut issues = Vec::new();
+        if !positive(self.mu_km3_s2) {
+            issues.push(format!(
+                "mu_km3_s2 must be positive (got {})",
+                self.mu_km3_s2
+            ));
+        }
+        if !positive(self.radius_km) {
+            issues.push(format!(
+                "radius_km must be positive (got {})",
+                self.radius_km
+            ));
+        }
+        if self.soi_radius_km.is_nan() || self.soi_radius_km < self.radius_km {
+            issues.push(format!(
+                "soi_radius_km ({}) is smaller than radius_km ({})",
+                self.soi_radius_km, self.radius_km
+            ));
+        }
+        if positive(self.mu_km3_s2) && positive(self.radius_km) {
+            let expected = self.mu_km3_s2 / (self.radius_km * self.radius_km) * 1_000.0;
+            let relative = (self.surface_gravity_m_s2 - expected).abs() / expected;
+            if relative.is_nan() || relative > SURFACE_GRAVITY_TOLERANCE {
+                issues.push(format!(
+                    "surface_gravity_m_s2 ({}) differs from mu/radius² ({expected:.4}) by more than {:.0}%",
+                    self.surface_gravity_m_s2,
+                    SURFACE_GRAVITY_TOLERANCE * 100.0
+                ));
+            }
+        }
+        if let Some(atmosphere) = self.atmosphere.as_ref().filter(|a| a.exists) {
+            if !positive(atmosphere.scale_height_km) {
+                issues.push(format!(
+                    "atmosphere.scale_height_km must be positive (got {})",
+                    atmosphere.scale_height_km
+                ));
+            }
+            if !positive(atmosphere.surface_density_kg_m3) {
+                issues.push(format!(
+                    "atmosphere.surface_density_kg_m3 must be positive (got {})",
+                    atmosphere.surface_density_kg_m3
+                ));
+            }
+            if let Some(k) = atmosphere.sutton_graves_k.filter(|k| !positive(*k)) {
+                issues.push(format!(
+                    "atmosphere.sutton_graves_k must be positive (got {k})"
+                ));
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid {
+                body: self.name.clone(),
+                issues,
+            })
+        }
+    }
+}
+
+/// Partial planet record used to adjust a base catalog; absent fields keep the base value.
+///
+/// Nested tables (`atmosphere`, `entry_target`) replace the base table as a whole.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct PlanetOverride {
+    /// Name of the base record to modify (case-insensitive).
+    pub name: String,
+    pub spice_name: Option<String>,
+    pub parent_spice: Option<String>,
+    pub mu_km3_s2: Option<f64>,
+    pub radius_km: Option<f64>,
+    pub soi_radius_km: Option<f64>,
+    pub default_parking_altitude_km: Option<f64>,
+    pub surface_gravity_m_s2: Option<f64>,
+    pub mass_kg: Option<f64>,
+    pub j2: Option<f64>,
+    pub atmosphere: Option<AtmosphereConfig>,
+    pub entry_target: Option<EntryTargetConfig>,
+    pub kernel_dependencies: Option<Vec<String>>,
+}
+
+impl PlanetOverride {
+    /// Apply every field present in the override to `planet`.
+    pub fn apply(self, planet: &mut PlanetConfig) {
+        fn set<T>(target: &mut T, value: Option<T>) {
+            if let Some(value) = value {
+                *target = value;
+            }
+        }
+
+        set(&mut planet.spice_name, self.spice_name);
+        set(&mut planet.mu_km3_s2, self.mu_km3_s2);
+        set(&mut planet.radius_km, self.radius_km);
+        set(&mut planet.soi_radius_km, self.soi_radius_km);
+        set(
+            &mut planet.default_parking_altitude_km,
+            self.default_parking_altitude_km,
+        );
+        set(&mut planet.surface_gravity_m_s2, self.surface_gravity_m_s2);
+        set(&mut planet.mass_kg, self.mass_kg);
+        set(&mut planet.j2, self.j2);
+        set(&mut planet.kernel_dependencies, self.kernel_dependencies);
+        if self.parent_spice.is_some() {
+            planet.parent_spice = self.parent_spice;
+        }
+        if self.atmosphere.is_some() {
+            planet.atmosphere = self.atmosphere;
+        }
+        if self.entry_target.is_some() {
+            planet.entry_target = self.entry_target;
+        }
+    }
+}
+
 /// Atmospheric metadata for capture/aerobraking heuristics.
 #[derive(Debug, Deserialize, Clone)]
 pub struct AtmosphereConfig {
     pub exists: bool,
     pub scale_height_km: f64,
     pub surface_density_kg_m3: f64,
+    /// Sutton–Graves heating coefficient (kg^0.5 / m) for the gas mix; `None` uses the air value.
+    #[serde(default)]
+    pub sutton_graves_k: Option<f64>,
 }
 
 /// Entry targeting preferences for aerobraking passes.
@@ -52,6 +183,18 @@ pub struct VehicleConfig {
     pub propulsion: VehiclePropulsionConfig,
     #[serde(default)]
     pub aero: Option<VehicleAeroConfig>,
+    /// Lower stages (`[[stages]]`) in firing order; the top-level masses and propulsion describe
+    /// the final stage.
+    #[serde(default)]
+    pub stages: Vec<StageConfig>,
+}
+
+/// A jettisonable lower stage of a multi-stage vehicle.
+#[derive(Debug, Deserialize, Clone)]
+pub struct StageConfig {
+    pub dry_mass_kg: f64,
+    pub propellant_mass_kg: f64,
+    pub propulsion: VehiclePropulsionConfig,
 }
 
 /// Propulsion configuration in scenario manifests.
@@ -64,6 +207,10 @@ pub enum VehiclePropulsionConfig {
         isp_seconds: f64,
         #[serde(default)]
         max_acceleration_m_s2: Option<f64>,
+        #[serde(default)]
+        power_limited: bool,
+        #[serde(default)]
+        reference_distance_au: Option<f64>,
     },
     #[serde(rename = "impulsive")]
     Impulsive {
@@ -72,6 +219,13 @@ pub enum VehiclePropulsionConfig {
         #[serde(default)]
         max_thrust_newtons: Option<f64>,
     },
+    /// Throttleable engine with a piecewise `[fraction, throttle]` schedule.
+    #[serde(rename = "throttled")]
+    Throttled {
+        max_thrust_newtons: f64,
+        isp_seconds: f64,
+        throttle_profile: Vec<(f64, f64)>,
+    },
     #[serde(other)]
     Unsupported,
 }
@@ -93,6 +247,73 @@ pub struct VehicleAeroConfig {
     pub lift_to_drag: Option<f64>,
 }
 
+/// Heliocentric GM matching the DE440 ephemeris bundled as `de440s.bsp` (km³/s²).
+pub const DE440_MU_SUN_KM3_S2: f64 = solar_core::constants::MU_SUN_KM3_S2;
+
+/// Gravitational parameters consumed by the transfer solvers.
+///
+/// The defaults match DE440 and the body catalog. Override them when planning against a
+/// different ephemeris so the GMs stay consistent with the loaded SPK.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ConstantsSet {
+    #[serde(default = "default_mu_sun")]
+    pub mu_sun_km3_s2: f64,
+    /// Per-body GM overrides keyed by SPICE name (matched case-insensitively).
+    #[serde(default)]
+    pub body_mu_km3_s2: BTreeMap<String, f64>,
+}
+
+fn default_mu_sun() -> f64 {
+    DE440_MU_SUN_KM3_S2
+}
+
+impl Default for ConstantsSet {
+    fn default() -> Self {
+        Self {
+            mu_sun_km3_s2: DE440_MU_SUN_KM3_S2,
+            body_mu_km3_s2: BTreeMap::new(),
+        }
+    }
+}
+
+impl ConstantsSet {
+    /// GM for `body`, preferring an override over the catalog value.
+    pub fn body_mu(&self, body: &PlanetConfig) -> f64 {
+        self.body_mu_km3_s2
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(&body.spice_name))
+            .map(|(_, mu)| *mu)
+            .unwrap_or(body.mu_km3_s2)
+    }
+
+    /// Stable 64-bit FNV-1a digest of every GM in the set, as 16 hex digits.
+    ///
+    /// Two sets share a fingerprint only if the solar GM and all overrides (names compared
+    /// case-insensitively) are bit-identical, so caches keyed on it are never reused across
+    /// different constants.
+    pub fn fingerprint(&self) -> String {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut feed = |bytes: &[u8]| {
+            for &byte in bytes {
+                hash = (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3);
+            }
+        };
+        feed(&self.mu_sun_km3_s2.to_bits().to_le_bytes());
+        let mut overrides: Vec<(String, f64)> = self
+            .body_mu_km3_s2
+            .iter()
+            .map(|(name, mu)| (name.to_ascii_uppercase(), *mu))
+            .collect();
+        overrides.sort_by(|a, b| a.0.cmp(&b.0));
+        for (name, mu) in overrides {
+            feed(name.as_bytes());
+            feed(&[0]);
+            feed(&mu.to_bits().to_le_bytes());
+        }
+        format!("{hash:016x}")
+    }
+}
+
 /// Errors that can occur while loading configuration files.
 #[derive(Debug, Error)]
 pub enum ConfigError {
@@ -100,13 +321,59 @@ pub enum ConfigError {
     Io(#[from] std::io::Error),
     #[error("failed to parse YAML: {0}")]
     Parse(#[from] serde_yaml::Error),
+    #[error("failed to parse JSON: {0}")]
+    Json(#[from] serde_json::Error),
     #[error("failed to parse TOML: {0}")]
     Toml(#[from] toml::de::Error),
+    #[error("duplicate {kind} name '{name}'")]
+    DuplicateName { kind: &'static str, name: String },
+    #[error("invalid body '{body}': {}", issues.join("; "))]
+    Invalid { body: String, issues: Vec<String> },
+    #[error("override targets unknown {kind} '{name}'")]
+    UnknownOverride { kind: &'static str, name: String },
+    #[error("unresolved reference: mission.{field} = '{name}' is not in the {catalog} catalog")]
+    UnresolvedReference {
+        field: &'static str,
+        catalog: &'static str,
+        name: String,
+    },
 }
 
-/// Load planet configurations from a YAML file.
+/// Load planet configurations from a YAML/JSON/TOML file or a directory of them, rejecting
+/// physically inconsistent records.
 pub fn load_planets<P: AsRef<Path>>(path: P) -> Result<Vec<PlanetConfig>, ConfigError> {
-    let mut planets: Vec<PlanetConfig> = load_records(path)?;
+    prepare_planets(load_records(path)?)
+}
+
+/// Load a base planet catalog and apply partial records from `overrides`, matched by name.
+///
+/// Both paths accept anything [`load_planets`] does. Overrides are applied before validation
+/// and kernel inference, so the merged records are checked exactly like a plain catalog.
+pub fn load_planets_with_overrides<P: AsRef<Path>>(
+    base: P,
+    overrides: P,
+) -> Result<Vec<PlanetConfig>, ConfigError> {
+    let mut planets: Vec<PlanetConfig> = load_records(base)?;
+    let overrides: Vec<PlanetOverride> = load_records(overrides)?;
+    ensure_unique_names("override", overrides.iter().map(|o| o.name.as_str()))?;
+    for patch in overrides {
+        let planet = planets
+            .iter_mut()
+            .find(|p| p.name.eq_ignore_ascii_case(&patch.name))
+            .ok_or_else(|| ConfigError::UnknownOverride {
+                kind: "body",
+                name: patch.name.clone(),
+            })?;
+        patch.apply(planet);
+    }
+    prepare_planets(planets)
+}
+
+fn prepare_planets(mut planets: Vec<PlanetConfig>) -> Result<Vec<PlanetConfig>, ConfigError> {
+    ensure_unique_names("body", planets.iter().map(|p| p.name.as_str()))?;
+    for planet in &planets {
+        planet.validate()?;
+    }
     for planet in &mut planets {
         if planet.kernel_dependencies.is_empty() {
             planet.kernel_dependencies = infer_kernel_dependencies(&planet.spice_name);
@@ -115,9 +382,218 @@ pub fn load_planets<P: AsRef<Path>>(path: P) -> Result<Vec<PlanetConfig>, Config
     Ok(planets)
 }
 
-/// Load vehicle configurations from a YAML file.
+/// Load vehicle configurations from a YAML file (optionally a `---` separated multi-document
+/// stream), a JSON record or list, a single TOML record, or a directory mixing these formats.
 pub fn load_vehicle_configs<P: AsRef<Path>>(path: P) -> Result<Vec<VehicleConfig>, ConfigError> {
-    load_records(path)
+    prepare_vehicles(load_records(path)?)
+}
+
+fn prepare_vehicles(vehicles: Vec<VehicleConfig>) -> Result<Vec<VehicleConfig>, ConfigError> {
+    ensure_unique_names("vehicle", vehicles.iter().map(|v| v.name.as_str()))?;
+    Ok(vehicles)
+}
+
+/// Load a constants set from a TOML, JSON or YAML file.
+pub fn load_constants<P: AsRef<Path>>(path: P) -> Result<ConstantsSet, ConfigError> {
+    read_document(path.as_ref())
+}
+
+/// Aerobraking mode requested by a scenario's `[mission]` table.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AerobrakeSetting {
+    #[default]
+    None,
+    Partial,
+    Full,
+}
+
+/// The `[mission]` table of a scenario manifest.
+#[derive(Debug, Deserialize, Clone)]
+pub struct MissionManifest {
+    /// Departure body name (case-insensitive).
+    pub from: String,
+    /// Destination body name (case-insensitive).
+    pub to: String,
+    /// Departure epoch string accepted by SPICE.
+    pub depart: String,
+    #[serde(default)]
+    pub arrive: Option<String>,
+    /// Vehicle name; the planner's default selection applies when absent.
+    #[serde(default)]
+    pub vehicle: Option<String>,
+    #[serde(default)]
+    pub aerobrake: AerobrakeSetting,
+}
+
+/// A catalog given either inline or as a path (file or directory) relative to the manifest.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(untagged)]
+enum CatalogSource<T> {
+    Path(PathBuf),
+    Inline(Vec<T>),
+}
+
+impl<T> CatalogSource<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    fn resolve(self, base_dir: &Path) -> Result<Vec<T>, ConfigError> {
+        match self {
+            CatalogSource::Path(path) => load_records(base_dir.join(path)),
+            CatalogSource::Inline(records) => Ok(records),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ScenarioManifest {
+    mission: MissionManifest,
+    planets: CatalogSource<PlanetConfig>,
+    vehicles: CatalogSource<VehicleConfig>,
+    #[serde(default)]
+    constants: ConstantsSet,
+}
+
+/// A self-contained mission run: the mission request plus the catalogs it refers to.
+///
+/// Every name in `mission` is checked against the catalogs on load, so `origin`, `destination`
+/// and `vehicle` always resolve.
+#[derive(Debug, Clone)]
+pub struct Scenario {
+    pub mission: MissionManifest,
+    pub planets: Vec<PlanetConfig>,
+    pub vehicles: Vec<VehicleConfig>,
+    pub constants: ConstantsSet,
+    origin_index: usize,
+    destination_index: usize,
+    vehicle_index: Option<usize>,
+}
+
+impl Scenario {
+    /// Catalog entry for `mission.from`.
+    pub fn origin(&self) -> &PlanetConfig {
+        &self.planets[self.origin_index]
+    }
+
+    /// Catalog entry for `mission.to`.
+    pub fn destination(&self) -> &PlanetConfig {
+        &self.planets[self.destination_index]
+    }
+
+    /// Catalog entry for `mission.vehicle`, if one was named.
+    pub fn vehicle(&self) -> Option<&VehicleConfig> {
+        self.vehicle_index.map(|index| &self.vehicles[index])
+    }
+}
+
+/// Load a scenario manifest (TOML, JSON or YAML) bundling a `[mission]` table with its planet and
+/// vehicle catalogs.
+///
+/// `planets` and `vehicles` are either inline record lists or paths, resolved relative to the
+/// manifest, accepted by [`load_planets`] / [`load_vehicle_configs`]. An optional `constants`
+/// table follows the [`ConstantsSet`] layout.
+pub fn load_scenario<P: AsRef<Path>>(path: P) -> Result<Scenario, ConfigError> {
+    let path = path.as_ref();
+    let manifest: ScenarioManifest = read_document(path)?;
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+    let planets = prepare_planets(manifest.planets.resolve(base_dir)?)?;
+    let vehicles = prepare_vehicles(manifest.vehicles.resolve(base_dir)?)?;
+    let mission = manifest.mission;
+
+    let origin_index = resolve_reference(
+        "from",
+        "planet",
+        planets.iter().map(|p| p.name.as_str()),
+        &mission.from,
+    )?;
+    let destination_index = resolve_reference(
+        "to",
+        "planet",
+        planets.iter().map(|p| p.name.as_str()),
+        &mission.to,
+    )?;
+    let vehicle_index = mission
+        .vehicle
+        .as_deref()
+        .map(|name| {
+            resolve_reference(
+                "vehicle",
+                "vehicle",
+                vehicles.iter().map(|v| v.name.as_str()),
+                name,
+            )
+        })
+        .transpose()?;
+
+    Ok(Scenario {
+        mission,
+        planets,
+        vehicles,
+        constants: manifest.constants,
+        origin_index,
+        destination_index,
+        vehicle_index,
+    })
+}
+
+/// Index of the record named `name` (case-insensitive), or an error naming the mission field.
+fn resolve_reference<'a>(
+    field: &'static str,
+    catalog: &'static str,
+    mut names: impl Iterator<Item = &'a str>,
+    name: &str,
+) -> Result<usize, ConfigError> {
+    names
+        .position(|candidate| candidate.eq_ignore_ascii_case(name))
+        .ok_or_else(|| ConfigError::UnresolvedReference {
+            field,
+            catalog,
+            name: name.to_string(),
+        })
+}
+
+/// Reject catalogs where two records share a name (case-insensitive), since lookups by name
+/// would silently pick the first one.
+fn ensure_unique_names<'a>(
+    kind: &'static str,
+    names: impl Iterator<Item = &'a str>,
+) -> Result<(), ConfigError> {
+    let mut seen = std::collections::BTreeSet::new();
+    for name in names {
+        if !seen.insert(name.to_ascii_uppercase()) {
+            return Err(ConfigError::DuplicateName {
+                kind,
+                name: name.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// File extensions picked up when loading a catalog directory.
+const RECORD_EXTENSIONS: [&str; 4] = ["toml", "json", "yaml", "yml"];
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .map(|ext| ext == extension)
+        .unwrap_or(false)
+}
+
+/// Parse a single-document file by extension: TOML, JSON, otherwise YAML.
+fn read_document<T>(path: &Path) -> Result<T, ConfigError>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let contents = std::fs::read_to_string(path)?;
+    if has_extension(path, "toml") {
+        Ok(toml::from_str(&contents)?)
+    } else if has_extension(path, "json") {
+        Ok(serde_json::from_str(&contents)?)
+    } else {
+        Ok(serde_yaml::from_str(&contents)?)
+    }
 }
 
 fn load_records<T, P>(path: P) -> Result<Vec<T>, ConfigError>
@@ -128,16 +604,57 @@ where
     let path = path.as_ref();
     if path.is_dir() {
         read_dir_records(path)
-    } else if path.extension().map(|ext| ext == "toml").unwrap_or(false) {
-        let contents = std::fs::read_to_string(path)?;
+    } else {
+        read_file_records(path)
+    }
+}
+
+/// Records from one file: a single TOML record, or JSON/YAML holding a record or a list of them.
+fn read_file_records<T>(path: &Path) -> Result<Vec<T>, ConfigError>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let contents = std::fs::read_to_string(path)?;
+    if has_extension(path, "toml") {
         let record: T = toml::from_str(&contents)?;
         Ok(vec![record])
+    } else if has_extension(path, "json") {
+        read_json_records(&contents)
     } else {
-        let reader = File::open(path)?;
-        Ok(serde_yaml::from_reader(reader)?)
+        read_yaml_records(&contents)
+    }
+}
+
+/// Parse a JSON document that is either a list of records or a single record.
+fn read_json_records<T>(contents: &str) -> Result<Vec<T>, ConfigError>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    match serde_json::from_str(contents)? {
+        value @ serde_json::Value::Array(_) => Ok(serde_json::from_value(value)?),
+        value => Ok(vec![serde_json::from_value(value)?]),
     }
 }
 
+/// Parse a YAML stream where each document is either a list of records or a single record.
+fn read_yaml_records<T>(contents: &str) -> Result<Vec<T>, ConfigError>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let mut records = Vec::new();
+    for document in serde_yaml::Deserializer::from_str(contents) {
+        match serde_yaml::Value::deserialize(document)? {
+            serde_yaml::Value::Null => {}
+            value @ serde_yaml::Value::Sequence(_) => {
+                records.extend(serde_yaml::from_value::<Vec<T>>(value)?);
+            }
+            value => records.push(serde_yaml::from_value(value)?),
+        }
+    }
+    Ok(records)
+}
+
+/// Records from every TOML/JSON/YAML file in `dir`, in file-name order.
 fn read_dir_records<T>(dir: &Path) -> Result<Vec<T>, ConfigError>
 where
     T: for<'de> Deserialize<'de>,
@@ -145,13 +662,15 @@ where
     let mut records = Vec::new();
     let mut entries: Vec<PathBuf> = std::fs::read_dir(dir)?
         .filter_map(|entry| entry.ok().map(|e| e.path()))
-        .filter(|path| path.extension().map(|ext| ext == "toml").unwrap_or(false))
+        .filter(|path| {
+            RECORD_EXTENSIONS
+                .iter()
+                .any(|extension| has_extension(path, extension))
+        })
         .collect();
     entries.sort();
     for path in entries {
-        let contents = std::fs::read_to_string(&path)?;
-        let record: T = toml::from_str(&contents)?;
-        records.push(record);
+        records.extend(read_file_records(&path)?);
     }
     Ok(records)
 }
@@ -220,5 +739,35 @@ fn infer_kernel_dependencies(spice_name: &str) -> Vec<String> {
         add("tnosat_v001_20000617_jpl082_20230601.bsp");
     }
 
+    // Numeric SPICE names never match the arms above; resolve them by NAIF ID range instead.
+    if let Ok(id) = spice_name.trim().parse::<i64>() {
+        for kernel in naif_id_kernels(id) {
+            add(kernel);
+        }
+    }
+
     deps.into_iter().map(|s| s.to_string()).collect()
 }
+
+/// Satellite and small-body kernels needed for a NAIF integer ID.
+///
+/// Follows the NAIF conventions: `N` is a planetary system barycentre, `N01`–`N98` its moons and
+/// `N99` the planet itself; numbered asteroids are `2_000_000 + n`. Uranus has no satellite
+/// kernel in the catalog, so its IDs fall through to the defaults.
+fn naif_id_kernels(id: i64) -> &'static [&'static str] {
+    const TNO_KERNEL: &str = "tnosat_v001_20000617_jpl082_20230601.bsp";
+
+    match id {
+        4 | 400..=499 => &["mar099.bsp"],
+        5 | 500..=599 => &["jup365.bsp"],
+        6 | 600..=699 => &["sat455.bsp"],
+        8 | 800..=899 => &["nep095.bsp"],
+        9 | 900..=999 => &["plu060.bsp"],
+        2_000_001..=2_000_300 => &["codes_300ast_20100725.bsp", "codes_300ast_20100725.tf"],
+        // Eris, Haumea and Makemake primaries and system barycentres.
+        20_136_199 | 20_136_108 | 20_136_472 | 920_136_199 | 920_136_108 | 920_136_472 => {
+            &[TNO_KERNEL]
+        }
+        _ => &[],
+    }
+}
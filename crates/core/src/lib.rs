@@ -6,11 +6,17 @@ pub mod constants {
     pub const G0: f64 = 9.80665;
     /// Kilometres per astronomical unit.
     pub const AU_KM: f64 = 149_597_870.7;
+    /// Heliocentric GM matching the DE440 ephemeris bundled as `de440s.bsp` (km³/s²).
+    pub const MU_SUN_KM3_S2: f64 = 1.327_124_400_18e11;
     /// Seconds per Julian day.
     pub const SECONDS_PER_DAY: f64 = 86_400.0;
 }
 
-/// Basic unit conversion helpers.
+/// Basic unit conversion helpers and unit-tagged newtypes.
+///
+/// `Km`, `Meters`, `KmPerSec` and `Vector3Km` wrap the bare `f64`/`[f64; 3]` values used
+/// elsewhere so new APIs can reject mixed-unit arithmetic at compile time; the free
+/// functions remain for existing call sites.
 pub mod units {
     /// Convert kilometres to metres.
     #[inline]
@@ -35,6 +41,261 @@ pub mod units {
     pub fn kms_to_ms(v: f64) -> f64 {
         v * 1_000.0
     }
+
+    use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+    use super::vector::{self, Vector3};
+
+    /// Arithmetic shared by the scalar quantities: same-unit addition and subtraction,
+    /// scaling by a bare `f64`, and same-unit division yielding a dimensionless ratio.
+    macro_rules! scalar_quantity {
+        ($name:ident) => {
+            impl Add for $name {
+                type Output = $name;
+                #[inline]
+                fn add(self, rhs: $name) -> $name {
+                    $name(self.0 + rhs.0)
+                }
+            }
+
+            impl Sub for $name {
+                type Output = $name;
+                #[inline]
+                fn sub(self, rhs: $name) -> $name {
+                    $name(self.0 - rhs.0)
+                }
+            }
+
+            impl AddAssign for $name {
+                #[inline]
+                fn add_assign(&mut self, rhs: $name) {
+                    self.0 += rhs.0;
+                }
+            }
+
+            impl SubAssign for $name {
+                #[inline]
+                fn sub_assign(&mut self, rhs: $name) {
+                    self.0 -= rhs.0;
+                }
+            }
+
+            impl Neg for $name {
+                type Output = $name;
+                #[inline]
+                fn neg(self) -> $name {
+                    $name(-self.0)
+                }
+            }
+
+            impl Mul<f64> for $name {
+                type Output = $name;
+                #[inline]
+                fn mul(self, rhs: f64) -> $name {
+                    $name(self.0 * rhs)
+                }
+            }
+
+            impl Mul<$name> for f64 {
+                type Output = $name;
+                #[inline]
+                fn mul(self, rhs: $name) -> $name {
+                    $name(self * rhs.0)
+                }
+            }
+
+            impl Div<f64> for $name {
+                type Output = $name;
+                #[inline]
+                fn div(self, rhs: f64) -> $name {
+                    $name(self.0 / rhs)
+                }
+            }
+
+            impl Div for $name {
+                type Output = f64;
+                #[inline]
+                fn div(self, rhs: $name) -> f64 {
+                    self.0 / rhs.0
+                }
+            }
+        };
+    }
+
+    /// A distance in kilometres.
+    #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+    pub struct Km(pub f64);
+
+    /// A distance in metres.
+    #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+    pub struct Meters(pub f64);
+
+    /// A speed in kilometres per second.
+    #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+    pub struct KmPerSec(pub f64);
+
+    scalar_quantity!(Km);
+    scalar_quantity!(Meters);
+    scalar_quantity!(KmPerSec);
+
+    impl Km {
+        /// The same distance in metres.
+        #[inline]
+        pub fn to_meters(self) -> Meters {
+            Meters(km_to_m(self.0))
+        }
+    }
+
+    impl Meters {
+        /// The same distance in kilometres.
+        #[inline]
+        pub fn to_km(self) -> Km {
+            Km(m_to_km(self.0))
+        }
+    }
+
+    impl KmPerSec {
+        /// Wrap a speed given in metres per second.
+        #[inline]
+        pub fn from_m_per_s(v: f64) -> Self {
+            KmPerSec(ms_to_kms(v))
+        }
+
+        /// The same speed in metres per second.
+        #[inline]
+        pub fn to_m_per_s(self) -> f64 {
+            kms_to_ms(self.0)
+        }
+    }
+
+    impl From<Km> for Meters {
+        #[inline]
+        fn from(v: Km) -> Self {
+            v.to_meters()
+        }
+    }
+
+    impl From<Meters> for Km {
+        #[inline]
+        fn from(v: Meters) -> Self {
+            v.to_km()
+        }
+    }
+
+    impl Div<KmPerSec> for Km {
+        /// Travel time in seconds.
+        type Output = f64;
+        #[inline]
+        fn div(self, rhs: KmPerSec) -> f64 {
+            self.0 / rhs.0
+        }
+    }
+
+    /// A position (or displacement) vector in kilometres.
+    #[derive(Debug, Clone, Copy, PartialEq, Default)]
+    pub struct Vector3Km(pub Vector3);
+
+    impl Vector3Km {
+        /// Euclidean length of the vector.
+        #[inline]
+        pub fn norm(&self) -> Km {
+            Km(vector::norm(&self.0))
+        }
+
+        /// Dot product with `other` (km²).
+        #[inline]
+        pub fn dot(&self, other: &Vector3Km) -> f64 {
+            vector::dot(&self.0, &other.0)
+        }
+
+        /// Cross product `self × other` (km²).
+        #[inline]
+        pub fn cross(&self, other: &Vector3Km) -> Vector3 {
+            vector::cross(&self.0, &other.0)
+        }
+
+        /// Dimensionless unit vector, or `None` for the zero vector.
+        #[inline]
+        pub fn unit(&self) -> Option<Vector3> {
+            let length = vector::norm(&self.0);
+            (length > 0.0).then(|| vector::scale(&self.0, 1.0 / length))
+        }
+
+        /// Components converted to metres.
+        #[inline]
+        pub fn to_meters(self) -> [Meters; 3] {
+            self.0.map(|c| Km(c).to_meters())
+        }
+    }
+
+    impl From<Vector3> for Vector3Km {
+        #[inline]
+        fn from(v: Vector3) -> Self {
+            Vector3Km(v)
+        }
+    }
+
+    impl From<Vector3Km> for Vector3 {
+        #[inline]
+        fn from(v: Vector3Km) -> Self {
+            v.0
+        }
+    }
+
+    impl From<[Km; 3]> for Vector3Km {
+        #[inline]
+        fn from(v: [Km; 3]) -> Self {
+            Vector3Km(v.map(|c| c.0))
+        }
+    }
+
+    impl Add for Vector3Km {
+        type Output = Vector3Km;
+        #[inline]
+        fn add(self, rhs: Vector3Km) -> Vector3Km {
+            Vector3Km(vector::add(&self.0, &rhs.0))
+        }
+    }
+
+    impl Sub for Vector3Km {
+        type Output = Vector3Km;
+        #[inline]
+        fn sub(self, rhs: Vector3Km) -> Vector3Km {
+            Vector3Km(vector::sub(&self.0, &rhs.0))
+        }
+    }
+
+    impl Neg for Vector3Km {
+        type Output = Vector3Km;
+        #[inline]
+        fn neg(self) -> Vector3Km {
+            Vector3Km(vector::scale(&self.0, -1.0))
+        }
+    }
+
+    impl Mul<f64> for Vector3Km {
+        type Output = Vector3Km;
+        #[inline]
+        fn mul(self, rhs: f64) -> Vector3Km {
+            Vector3Km(vector::scale(&self.0, rhs))
+        }
+    }
+
+    impl Mul<Vector3Km> for f64 {
+        type Output = Vector3Km;
+        #[inline]
+        fn mul(self, rhs: Vector3Km) -> Vector3Km {
+            Vector3Km(vector::scale(&rhs.0, self))
+        }
+    }
+
+    impl Div<f64> for Vector3Km {
+        type Output = Vector3Km;
+        #[inline]
+        fn div(self, rhs: f64) -> Vector3Km {
+            Vector3Km(vector::scale(&self.0, 1.0 / rhs))
+        }
+    }
 }
 
 /// Lightweight time utilities shared across crates.
@@ -54,6 +315,45 @@ pub mod time {
     }
 }
 
+/// Angle conversions and normalisation shared across crates.
+pub mod angles {
+    use std::f64::consts::{PI, TAU};
+
+    /// Convert degrees to radians.
+    #[inline]
+    pub fn deg_to_rad(deg: f64) -> f64 {
+        deg.to_radians()
+    }
+
+    /// Convert radians to degrees.
+    #[inline]
+    pub fn rad_to_deg(rad: f64) -> f64 {
+        rad.to_degrees()
+    }
+
+    /// Wrap an angle (rad) into `[0, 2π)`.
+    #[inline]
+    pub fn wrap_to_2pi(angle: f64) -> f64 {
+        let wrapped = angle.rem_euclid(TAU);
+        // `rem_euclid` rounds tiny negative inputs up to exactly 2π.
+        if wrapped >= TAU { 0.0 } else { wrapped }
+    }
+
+    /// Wrap an angle (rad) into `(-π, π]`.
+    #[inline]
+    pub fn wrap_to_pi(angle: f64) -> f64 {
+        let wrapped = wrap_to_2pi(angle);
+        if wrapped > PI { wrapped - TAU } else { wrapped }
+    }
+
+    /// `acos` of a cosine clamped to `[-1, 1]`, so round-off just past ±1 yields 0 or π
+    /// instead of NaN. NaN inputs still return NaN.
+    #[inline]
+    pub fn acos_clamped(x: f64) -> f64 {
+        x.clamp(-1.0, 1.0).acos()
+    }
+}
+
 /// Minimal vector helpers to avoid ad-hoc `[f64; 3]` math everywhere.
 pub mod vector {
     /// Alias for a 3D vector in kilometres or km/s depending on context.
@@ -88,4 +388,29 @@ pub mod vector {
     pub fn scale(v: &Vector3, s: f64) -> Vector3 {
         [v[0] * s, v[1] * s, v[2] * s]
     }
+
+    /// Cross product `a × b`.
+    #[inline]
+    pub fn cross(a: &Vector3, b: &Vector3) -> Vector3 {
+        [
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ]
+    }
+}
+
+/// Classical orbital elements shared by the ephemeris and orbit crates.
+pub mod elements {
+    /// Osculating conic elements of a state relative to a central body, in km and radians.
+    #[derive(Debug, Clone, Copy)]
+    pub struct OrbitalElements {
+        /// Negative for hyperbolic orbits and infinite for parabolic ones.
+        pub semi_major_axis_km: f64,
+        pub eccentricity: f64,
+        pub inclination_rad: f64,
+        pub raan_rad: f64,
+        pub argument_of_periapsis_rad: f64,
+        pub mean_anomaly_rad: f64,
+    }
 }
@@ -0,0 +1,85 @@
+//! Closed-form fallback ephemerides for use when SPICE kernels are unavailable.
+//!
+//! These models are deliberately low accuracy. They exist for sanity checks and graceful
+//! degradation (e.g. heliocentric-vs-barycentric comparisons with a reduced kernel set) and must
+//! not be used where the SPK-derived states are available.
+
+use crate::StateVector;
+
+/// Kilometres per astronomical unit.
+const AU_KM: f64 = 149_597_870.7;
+/// Seconds per Julian day.
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Circular-orbit approximation of a giant planet used to derive the Sun's reflex motion.
+struct GiantPlanet {
+    /// Planet-to-Sun mass ratio.
+    mass_ratio: f64,
+    /// Semi-major axis (AU).
+    semi_major_axis_au: f64,
+    /// Mean longitude at J2000 (degrees, ecliptic J2000).
+    mean_longitude_j2000_deg: f64,
+    /// Sidereal orbital period (days).
+    period_days: f64,
+}
+
+/// Jupiter, Saturn, Uranus, and Neptune dominate the Sun's barycentric wobble (> 99%).
+const GIANT_PLANETS: [GiantPlanet; 4] = [
+    GiantPlanet {
+        mass_ratio: 1.0 / 1_047.348_6,
+        semi_major_axis_au: 5.202_887,
+        mean_longitude_j2000_deg: 34.396_44,
+        period_days: 4_332.589,
+    },
+    GiantPlanet {
+        mass_ratio: 1.0 / 3_497.898,
+        semi_major_axis_au: 9.536_676,
+        mean_longitude_j2000_deg: 49.954_24,
+        period_days: 10_759.22,
+    },
+    GiantPlanet {
+        mass_ratio: 1.0 / 22_902.98,
+        semi_major_axis_au: 19.189_165,
+        mean_longitude_j2000_deg: 313.238_10,
+        period_days: 30_685.4,
+    },
+    GiantPlanet {
+        mass_ratio: 1.0 / 19_412.24,
+        semi_major_axis_au: 30.069_923,
+        mean_longitude_j2000_deg: 304.879_97,
+        period_days: 60_189.0,
+    },
+];
+
+/// Approximate state of the Sun relative to the Solar System barycenter (ECLIPJ2000, km, km/s).
+///
+/// The model places the four giant planets on circular, coplanar orbits and returns the Sun's
+/// reflex displacement about their common barycenter. Expect errors of order 10⁵ km in position
+/// and a few m/s in velocity; the out-of-plane component is always zero and `light_time_seconds`
+/// is reported as zero. Use only for sanity checks or when the SPK is unavailable.
+pub fn sun_ssb_approx(et: f64) -> StateVector {
+    let days = et / SECONDS_PER_DAY;
+    let mut position_km = [0.0; 3];
+    let mut velocity_km_s = [0.0; 3];
+
+    for planet in &GIANT_PLANETS {
+        let mean_motion_rad_s = std::f64::consts::TAU / (planet.period_days * SECONDS_PER_DAY);
+        let longitude = planet.mean_longitude_j2000_deg.to_radians()
+            + std::f64::consts::TAU * days / planet.period_days;
+        let radius_km = planet.semi_major_axis_au * AU_KM;
+        // The Sun sits opposite each planet, scaled by the reduced mass fraction.
+        let reflex = planet.mass_ratio / (1.0 + planet.mass_ratio);
+        let (sin_l, cos_l) = longitude.sin_cos();
+
+        position_km[0] -= reflex * radius_km * cos_l;
+        position_km[1] -= reflex * radius_km * sin_l;
+        velocity_km_s[0] += reflex * radius_km * mean_motion_rad_s * sin_l;
+        velocity_km_s[1] -= reflex * radius_km * mean_motion_rad_s * cos_l;
+    }
+
+    StateVector {
+        position_km,
+        velocity_km_s,
+        light_time_seconds: 0.0,
+    }
+}
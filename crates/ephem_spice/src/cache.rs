@@ -0,0 +1,322 @@
+//! Optional caches of sampled states: [`EphemerisCache`] memoizes within a process, and
+//! [`CachedStates`] persists samples on disk so repeated studies skip SPICE entirely.
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::{EphemerisError, StateVector, state_vector_et, state_vectors_et};
+
+/// Default directory for [`CachedStates`] files.
+pub const DEFAULT_STATE_CACHE_DIR: &str = "data/ephem_cache";
+
+const MAGIC: &[u8; 8] = b"STVCACH1";
+/// `et`, position, velocity, and light time as little-endian `f64`s.
+const RECORD_BYTES: usize = 8 * 8;
+
+/// Source of geometric (uncorrected) state vectors.
+pub trait StateProvider {
+    fn state(
+        &mut self,
+        target: &str,
+        observer: &str,
+        frame: &str,
+        et: f64,
+    ) -> Result<StateVector, EphemerisError>;
+
+    /// States at every epoch, failing on the first epoch that cannot be resolved.
+    fn states(
+        &mut self,
+        target: &str,
+        observer: &str,
+        frame: &str,
+        epochs: &[f64],
+    ) -> Result<Vec<StateVector>, EphemerisError> {
+        epochs
+            .iter()
+            .map(|&et| self.state(target, observer, frame, et))
+            .collect()
+    }
+
+    /// Persist anything the provider has buffered.
+    fn flush(&mut self) -> Result<(), EphemerisError> {
+        Ok(())
+    }
+}
+
+/// Queries SPICE on every call.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SpiceStates;
+
+impl StateProvider for SpiceStates {
+    fn state(
+        &mut self,
+        target: &str,
+        observer: &str,
+        frame: &str,
+        et: f64,
+    ) -> Result<StateVector, EphemerisError> {
+        state_vector_et(target, observer, frame, "NONE", et)
+    }
+
+    fn states(
+        &mut self,
+        target: &str,
+        observer: &str,
+        frame: &str,
+        epochs: &[f64],
+    ) -> Result<Vec<StateVector>, EphemerisError> {
+        state_vectors_et(target, observer, frame, "NONE", epochs)
+    }
+}
+
+type MemoKey = (String, String, String, u64);
+
+/// Thread-safe in-process memo of geometric states keyed by `(target, observer, frame, et)`.
+///
+/// By default only bit-identical ETs share an entry. [`EphemerisCache::with_quantum`] opts into
+/// bucketing epochs to the nearest multiple of a tolerance, so nearby grid points reuse the first
+/// state sampled in their bucket.
+#[derive(Debug, Default)]
+pub struct EphemerisCache {
+    quantum_seconds: f64,
+    entries: Mutex<HashMap<MemoKey, StateVector>>,
+}
+
+impl EphemerisCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cache sharing one entry per `quantum_seconds` bucket of ephemeris time.
+    pub fn with_quantum(quantum_seconds: f64) -> Self {
+        Self {
+            quantum_seconds: quantum_seconds.max(0.0),
+            ..Self::default()
+        }
+    }
+
+    /// Geometric state of `target` relative to `observer`, queried from SPICE only on a miss.
+    pub fn cached_state_vector_et(
+        &self,
+        target: &str,
+        observer: &str,
+        frame: &str,
+        et: f64,
+    ) -> Result<StateVector, EphemerisError> {
+        let key = (
+            target.to_string(),
+            observer.to_string(),
+            frame.to_string(),
+            self.quantize(et),
+        );
+        if let Some(state) = self.entries.lock().expect("ephemeris cache lock").get(&key) {
+            return Ok(*state);
+        }
+        // Query outside the lock; SPICE calls are the slow part and a duplicate fill is harmless.
+        let state = state_vector_et(target, observer, frame, "NONE", et)?;
+        self.entries
+            .lock()
+            .expect("ephemeris cache lock")
+            .insert(key, state);
+        Ok(state)
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().expect("ephemeris cache lock").clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().expect("ephemeris cache lock").len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn quantize(&self, et: f64) -> u64 {
+        if self.quantum_seconds > 0.0 {
+            ((et / self.quantum_seconds).round() as i64) as u64
+        } else {
+            et.to_bits()
+        }
+    }
+}
+
+/// Wraps another provider with one `{target}_{observer}_{frame}.bin` file of `(et, state)`
+/// samples per query key.
+///
+/// Hits require the exact same ET, which grid epochs computed as `start + k·step` reproduce
+/// across runs. Misses go to the inner provider and are appended to disk on
+/// [`StateProvider::flush`] or drop. Failed lookups are never cached.
+#[derive(Debug)]
+pub struct CachedStates<P = SpiceStates> {
+    dir: PathBuf,
+    inner: P,
+    tables: HashMap<PathBuf, Table>,
+}
+
+#[derive(Debug, Default)]
+struct Table {
+    samples: HashMap<u64, StateVector>,
+    pending: Vec<(f64, StateVector)>,
+    /// The file is missing or malformed and must be rewritten rather than appended to.
+    rewrite: bool,
+}
+
+impl<P> CachedStates<P> {
+    pub fn new(dir: impl Into<PathBuf>, inner: P) -> Self {
+        Self {
+            dir: dir.into(),
+            inner,
+            tables: HashMap::new(),
+        }
+    }
+
+    /// Cache file backing lookups of `target` relative to `observer` in `frame`.
+    pub fn path_for(&self, target: &str, observer: &str, frame: &str) -> PathBuf {
+        self.dir.join(format!(
+            "{}_{}_{}.bin",
+            sanitize(target),
+            sanitize(observer),
+            sanitize(frame)
+        ))
+    }
+
+    fn write_pending(&mut self) -> Result<(), EphemerisError> {
+        for (path, table) in &mut self.tables {
+            if table.pending.is_empty() {
+                continue;
+            }
+            write_table(path, table).map_err(|source| EphemerisError::StateCache {
+                path: path.clone(),
+                source,
+            })?;
+            table.pending.clear();
+            table.rewrite = false;
+        }
+        Ok(())
+    }
+}
+
+impl<P: StateProvider> StateProvider for CachedStates<P> {
+    fn state(
+        &mut self,
+        target: &str,
+        observer: &str,
+        frame: &str,
+        et: f64,
+    ) -> Result<StateVector, EphemerisError> {
+        let path = self.path_for(target, observer, frame);
+        let table = self
+            .tables
+            .entry(path)
+            .or_insert_with_key(|path| read_table(path));
+        if let Some(state) = table.samples.get(&et.to_bits()) {
+            return Ok(*state);
+        }
+        let state = self.inner.state(target, observer, frame, et)?;
+        table.samples.insert(et.to_bits(), state);
+        table.pending.push((et, state));
+        Ok(state)
+    }
+
+    fn flush(&mut self) -> Result<(), EphemerisError> {
+        self.write_pending()?;
+        self.inner.flush()
+    }
+}
+
+impl<P> Drop for CachedStates<P> {
+    fn drop(&mut self) {
+        let _ = self.write_pending();
+    }
+}
+
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn read_table(path: &Path) -> Table {
+    let Ok(bytes) = fs::read(path) else {
+        return Table {
+            rewrite: true,
+            ..Table::default()
+        };
+    };
+    let Some(body) = bytes.strip_prefix(MAGIC.as_slice()) else {
+        return Table {
+            rewrite: true,
+            ..Table::default()
+        };
+    };
+
+    let records = body.chunks_exact(RECORD_BYTES);
+    let rewrite = !records.remainder().is_empty();
+    let samples = records
+        .map(|record| {
+            let mut values = [0.0; 8];
+            for (value, bytes) in values.iter_mut().zip(record.chunks_exact(8)) {
+                *value = f64::from_le_bytes(bytes.try_into().expect("8-byte chunk"));
+            }
+            let state = StateVector {
+                position_km: [values[1], values[2], values[3]],
+                velocity_km_s: [values[4], values[5], values[6]],
+                light_time_seconds: values[7],
+            };
+            (values[0].to_bits(), state)
+        })
+        .collect();
+    Table {
+        samples,
+        pending: Vec::new(),
+        rewrite,
+    }
+}
+
+fn write_table(path: &Path, table: &Table) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if table.rewrite {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(MAGIC)?;
+        for (bits, state) in &table.samples {
+            write_record(&mut writer, f64::from_bits(*bits), state)?;
+        }
+        writer.flush()
+    } else {
+        let mut writer = BufWriter::new(OpenOptions::new().append(true).open(path)?);
+        for (et, state) in &table.pending {
+            write_record(&mut writer, *et, state)?;
+        }
+        writer.flush()
+    }
+}
+
+fn write_record(writer: &mut impl Write, et: f64, state: &StateVector) -> io::Result<()> {
+    let values = [
+        et,
+        state.position_km[0],
+        state.position_km[1],
+        state.position_km[2],
+        state.velocity_km_s[0],
+        state.velocity_km_s[1],
+        state.velocity_km_s[2],
+        state.light_time_seconds,
+    ];
+    for value in values {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    Ok(())
+}
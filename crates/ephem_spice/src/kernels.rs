@@ -45,6 +45,8 @@ pub struct KernelDescriptor {
     pub url: &'static str,
     pub kind: KernelKind,
     pub description: &'static str,
+    /// Expected lowercase hex SHA-256 of the file; downloads are rejected on mismatch.
+    pub sha256: Option<&'static str>,
 }
 
 impl KernelDescriptor {
@@ -52,6 +54,30 @@ impl KernelDescriptor {
     pub fn local_path(self) -> PathBuf {
         Path::new(LOCAL_SPICE_DIR).join(self.filename)
     }
+
+    /// Returns the static description, extended with the actual ET coverage of a local SPK.
+    ///
+    /// Coverage is only appended when the kernel is an SPK present on disk; epochs are rendered
+    /// via `format_epoch`, falling back to raw ET seconds when the leap-second kernel is unavailable.
+    pub fn describe_with_coverage(&self) -> String {
+        let path = self.local_path();
+        if self.kind != KernelKind::Spk || !path.exists() {
+            return self.description.to_string();
+        }
+        match crate::spk_coverage_span(&path) {
+            Ok(Some((start_et, end_et))) => format!(
+                "{} [coverage: {} to {}]",
+                self.description,
+                format_coverage_epoch(start_et),
+                format_coverage_epoch(end_et)
+            ),
+            _ => self.description.to_string(),
+        }
+    }
+}
+
+fn format_coverage_epoch(et: f64) -> String {
+    crate::format_epoch(et).unwrap_or_else(|_| format!("ET {et:.0}"))
 }
 
 /// Canonical kernel set used to bootstrap the calculator.
@@ -61,65 +87,76 @@ pub const KERNEL_CATALOG: &[KernelDescriptor] = &[
         url: "https://naif.jpl.nasa.gov/pub/naif/generic_kernels/spk/planets/de440s.bsp",
         kind: KernelKind::Spk,
         description: "JPL DE440 short ephemeris: barycentric positions/velocities for Sun, planets, and Pluto (1550–2650).",
+        sha256: None,
     },
     KernelDescriptor {
         filename: "jup365.bsp",
         url: "https://naif.jpl.nasa.gov/pub/naif/generic_kernels/spk/satellites/jup365.bsp",
         kind: KernelKind::Spk,
         description: "Jupiter system satellites (Galilean moons and select inner moons) ephemeris (1965–2055).",
+        sha256: None,
     },
     KernelDescriptor {
         filename: "sat455.bsp",
         url: "https://naif.jpl.nasa.gov/pub/naif/generic_kernels/spk/satellites/sat455.bsp",
         kind: KernelKind::Spk,
         description: "Saturn system satellites ephemeris (major moons over modern epochs).",
+        sha256: None,
     },
     KernelDescriptor {
         filename: "mar099.bsp",
         url: "https://naif.jpl.nasa.gov/pub/naif/generic_kernels/spk/satellites/mar099.bsp",
         kind: KernelKind::Spk,
         description: "Mars satellites ephemeris (Phobos and Deimos).",
+        sha256: None,
     },
     KernelDescriptor {
         filename: "plu060.bsp",
         url: "https://naif.jpl.nasa.gov/pub/naif/generic_kernels/spk/satellites/plu060.bsp",
         kind: KernelKind::Spk,
         description: "Pluto system ephemeris (Pluto and Charon barycentric states).",
+        sha256: None,
     },
     KernelDescriptor {
         filename: "nep095.bsp",
         url: "https://naif.jpl.nasa.gov/pub/naif/generic_kernels/spk/satellites/nep095.bsp",
         kind: KernelKind::Spk,
         description: "Neptune system satellites ephemeris (includes Triton).",
+        sha256: None,
     },
     KernelDescriptor {
         filename: "codes_300ast_20100725.bsp",
         url: "https://naif.jpl.nasa.gov/pub/naif/generic_kernels/spk/asteroids/codes_300ast_20100725.bsp",
         kind: KernelKind::Spk,
         description: "Asteroid ephemeris covering the 300 largest main-belt bodies (Ceres, Vesta, Pallas, etc.).",
+        sha256: None,
     },
     KernelDescriptor {
         filename: "codes_300ast_20100725.tf",
         url: "https://naif.jpl.nasa.gov/pub/naif/generic_kernels/spk/asteroids/codes_300ast_20100725.tf",
         kind: KernelKind::Fk,
         description: "Reference frame definitions for the 300 largest asteroids (orientation metadata referenced by codes_300ast_20100725.bsp).",
+        sha256: None,
     },
     KernelDescriptor {
         filename: "tnosat_v001_20000617_jpl082_20230601.bsp",
         url: "https://naif.jpl.nasa.gov/pub/naif/generic_kernels/spk/tno/tnosat_v001_20000617_jpl082_20230601.bsp",
         kind: KernelKind::Spk,
         description: "Trans-Neptunian object ephemeris (TNO centroids and satellites for Eris, Haumea, Makemake, etc.).",
+        sha256: None,
     },
     KernelDescriptor {
         filename: "naif0012.tls",
         url: "https://naif.jpl.nasa.gov/pub/naif/generic_kernels/lsk/naif0012.tls",
         kind: KernelKind::Lsk,
         description: "NAIF leap seconds kernel: UTC↔TDB conversion table with historical and predicted leap seconds.",
+        sha256: None,
     },
     KernelDescriptor {
         filename: "pck00011.tpc",
         url: "https://naif.jpl.nasa.gov/pub/naif/generic_kernels/pck/pck00011.tpc",
         kind: KernelKind::Pck,
         description: "Planetary constants kernel: body orientation models, radii, and physical constants for the Sun, planets, and select moons.",
+        sha256: None,
     },
 ];
@@ -2,18 +2,26 @@
 
 use std::ffi::{CStr, CString};
 use std::fs;
-use std::path::PathBuf;
-use std::sync::{Mutex, OnceLock};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use cspice_sys::{
-    SpiceBoolean, SpiceDouble, SpiceInt, erract_c, et2utc_c, failed_c, furnsh_c, getmsg_c,
-    kclear_c, reset_c, spkezr_c, str2et_c,
+    _SpiceDataType_SPICE_DP, _SpiceDataType_SPICE_INT, SpiceBoolean, SpiceCell, SpiceDataType,
+    SpiceDouble, SpiceInt, bodc2n_c, bodfnd_c, bodn2c_c, bodvcd_c, erract_c, et2utc_c, failed_c,
+    furnsh_c, getmsg_c, j2000_c, kclear_c, kdata_c, ktotal_c, namfrm_c, oscelt_c, recrad_c,
+    reset_c, scard_c, spd_c, spkcov_c, spkezr_c, spkobj_c, str2et_c, sxform_c, wncard_c, wnfetd_c,
 };
 use thiserror::Error;
 
+pub use solar_core::elements::OrbitalElements;
+
+pub mod analytic;
+pub mod cache;
 pub mod kernels;
+pub mod source;
 
-use kernels::{KERNEL_CATALOG, KernelDescriptor};
+use kernels::{KERNEL_CATALOG, KernelDescriptor, KernelKind};
 
 /// Basic metadata describing a local SPICE kernel.
 #[derive(Debug)]
@@ -31,6 +39,80 @@ pub struct StateVector {
     pub light_time_seconds: f64,
 }
 
+impl StateVector {
+    /// Range (km) and line-of-sight range rate (km/s, positive when receding).
+    ///
+    /// The range rate is the relative velocity projected onto the position unit vector; it is
+    /// zero at a zero range.
+    pub fn range_and_rate(&self) -> (f64, f64) {
+        let [x, y, z] = self.position_km;
+        let [vx, vy, vz] = self.velocity_km_s;
+        let range = (x * x + y * y + z * z).sqrt();
+        if range == 0.0 {
+            return (0.0, 0.0);
+        }
+        (range, (x * vx + y * vy + z * vz) / range)
+    }
+}
+
+/// Time span (ET seconds past J2000) over which an SPK provides states for a body.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoverageWindow {
+    pub start_et: f64,
+    pub end_et: f64,
+}
+
+impl CoverageWindow {
+    /// Whether `et` falls inside the window (inclusive).
+    pub fn contains(&self, et: f64) -> bool {
+        (self.start_et..=self.end_et).contains(&et)
+    }
+}
+
+/// Aberration corrections accepted by `spkezr_c`.
+///
+/// Prefer this over the raw strings taken by [`state_vector`] and [`state_vector_et`]: a typo
+/// becomes a compile error instead of a SPICE failure at run time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AberrationCorrection {
+    /// Geometric state.
+    #[default]
+    None,
+    /// One-way light time (reception).
+    LightTime,
+    /// Light time and stellar aberration: the apparent state.
+    LightTimeStellar,
+    /// Converged Newtonian light time.
+    ConvergedNewtonian,
+    /// Converged Newtonian light time and stellar aberration.
+    ConvergedNewtonianStellar,
+    /// Light time for a signal transmitted from the observer.
+    TransmissionLightTime,
+    /// Transmission light time and stellar aberration.
+    TransmissionLightTimeStellar,
+}
+
+impl AberrationCorrection {
+    /// Canonical SPICE token, e.g. `"LT+S"`.
+    pub fn spice_token(self) -> &'static str {
+        match self {
+            Self::None => "NONE",
+            Self::LightTime => "LT",
+            Self::LightTimeStellar => "LT+S",
+            Self::ConvergedNewtonian => "CN",
+            Self::ConvergedNewtonianStellar => "CN+S",
+            Self::TransmissionLightTime => "XLT",
+            Self::TransmissionLightTimeStellar => "XLT+S",
+        }
+    }
+}
+
+impl std::fmt::Display for AberrationCorrection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.spice_token())
+    }
+}
+
 /// Errors surfaced while validating or querying the SPICE toolkit.
 #[derive(Debug, Error)]
 pub enum EphemerisError {
@@ -48,24 +130,123 @@ pub enum EphemerisError {
     InvalidEpoch { epoch: String },
     #[error("SPICE kernel call failed: {message}")]
     Spice { message: String },
+    #[error(
+        "no ephemeris for `{target}` relative to `{observer}`; check that {} are present in {}",
+        .kernels.join(", "),
+        kernels::LOCAL_SPICE_DIR
+    )]
+    UnavailableBody {
+        target: String,
+        observer: String,
+        kernels: Vec<String>,
+    },
+    #[error("no tabulated state for `{target}` relative to `{observer}` in {frame} at ET {et}")]
+    NotTabulated {
+        target: String,
+        observer: String,
+        frame: String,
+        et: f64,
+    },
+    #[error("state query failed at epoch #{index} (ET {et}): {source}")]
+    BatchEpoch {
+        index: usize,
+        et: f64,
+        #[source]
+        source: Box<EphemerisError>,
+    },
+    #[error(
+        "body `{name}` is not known to SPICE{}",
+        .suggestion.as_ref().map_or(String::new(), |s| format!("; did you mean `{s}`?"))
+    )]
+    UnknownBody {
+        name: String,
+        suggestion: Option<String>,
+    },
+    #[error("no BODY{id}_GM in the loaded kernels; load a PCK that defines it (e.g. gm_de440.tpc)")]
+    MissingGm { id: i32 },
+    #[error("reference frame `{frame}` is not known to SPICE; load a frame kernel that defines it")]
+    UnknownFrame { frame: String },
+    #[error(
+        "kernel `{name}` at {path} is empty, truncated or of the wrong type; delete it and re-run fetch_spice"
+    )]
+    CorruptKernel { name: &'static str, path: PathBuf },
+    #[error("cannot furnish kernel {path}: {reason}")]
+    UnusableKernel { path: PathBuf, reason: &'static str },
+    #[error("failed to write state cache {path}: {source}")]
+    StateCache {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
 }
 
-/// Ensure the CSPICE runtime has all required kernels loaded.
-static INITIALIZED: OnceLock<()> = OnceLock::new();
+/// Set once a kernel set is fully furnished; cleared while [`reinitialize`] swaps the pool.
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+/// Serializes every change to the kernel pool.
 static INITIALIZE_LOCK: Mutex<()> = Mutex::new(());
 
+/// Ensure the CSPICE runtime has all required kernels loaded.
 pub fn load_default_kernels() -> Result<(), EphemerisError> {
-    if INITIALIZED.get().is_some() {
+    if INITIALIZED.load(Ordering::Acquire) {
         return Ok(());
     }
     let _lock = INITIALIZE_LOCK.lock().unwrap();
-    if INITIALIZED.get().is_some() {
+    ensure_initialized_locked()
+}
+
+/// Furnish one more kernel (e.g. a spacecraft SPK) on top of the loaded set.
+///
+/// The default catalog is loaded first if nothing has been yet. The extra kernel stays in the
+/// pool until the next [`reinitialize`], which drops it.
+///
+/// Re-entrancy: loaders are serialized by an internal lock, but CSPICE itself is not
+/// thread-safe, so no other thread may be querying SPICE while this runs. Call it before
+/// spawning parallel searches (e.g. porkchop grids), never from inside one.
+pub fn furnish_additional(path: &Path) -> Result<(), EphemerisError> {
+    if !path.is_file() {
+        return Err(EphemerisError::UnusableKernel {
+            path: path.to_path_buf(),
+            reason: "no such file",
+        });
+    }
+    let c_path = path
+        .to_str()
+        .and_then(|path| CString::new(path).ok())
+        .ok_or_else(|| EphemerisError::UnusableKernel {
+            path: path.to_path_buf(),
+            reason: "path is not valid UTF-8 or contains a NUL byte",
+        })?;
+    let _lock = INITIALIZE_LOCK.lock().unwrap();
+    ensure_initialized_locked()?;
+    unsafe {
+        furnsh_c(c_path.as_ptr() as *mut i8);
+    }
+    check_for_spice_error()
+}
+
+/// Clear the kernel pool and furnish `descriptors` instead of the current set.
+///
+/// Kernels added through [`furnish_additional`] are dropped. If loading fails the pool is left
+/// marked uninitialized, so the next query reloads the default catalog. States already memoized
+/// in a [`cache::EphemerisCache`] or [`cache::CachedStates`] are not invalidated.
+///
+/// The same re-entrancy constraints as [`furnish_additional`] apply: no SPICE query may run on
+/// another thread while the pool is being swapped.
+pub fn reinitialize(descriptors: &[KernelDescriptor]) -> Result<(), EphemerisError> {
+    let _lock = INITIALIZE_LOCK.lock().unwrap();
+    INITIALIZED.store(false, Ordering::Release);
+    initialize_spice(descriptors)?;
+    INITIALIZED.store(true, Ordering::Release);
+    Ok(())
+}
+
+/// Load the default catalog unless already done; the caller holds `INITIALIZE_LOCK`.
+fn ensure_initialized_locked() -> Result<(), EphemerisError> {
+    if INITIALIZED.load(Ordering::Acquire) {
         return Ok(());
     }
-    initialize_spice()?;
-    INITIALIZED
-        .set(())
-        .expect("INITIALIZED OnceLock set exactly once");
+    initialize_spice(KERNEL_CATALOG)?;
+    INITIALIZED.store(true, Ordering::Release);
     Ok(())
 }
 
@@ -94,10 +275,11 @@ pub fn normalize_heliocentric_target_name(name: &str) -> String {
 
 /// Summarize the local kernel set with file sizes and descriptions.
 pub fn kernel_summaries() -> Result<Vec<KernelSummary>, EphemerisError> {
-    validate_kernel_paths()?;
+    validate_kernel_paths(KERNEL_CATALOG)?;
     KERNEL_CATALOG
         .iter()
         .map(|descriptor| {
+            verify_kernel(descriptor)?;
             let path = descriptor.local_path();
             let metadata = fs::metadata(&path).map_err(|source| EphemerisError::Io {
                 name: descriptor.filename,
@@ -113,6 +295,9 @@ pub fn kernel_summaries() -> Result<Vec<KernelSummary>, EphemerisError> {
 }
 
 /// Query the state vector of a target relative to an observer.
+///
+/// `aberration_correction` is passed to SPICE verbatim; [`AberrationCorrection`] covers the
+/// standard modes.
 pub fn state_vector(
     target: &str,
     observer: &str,
@@ -144,6 +329,8 @@ pub fn state_vector(
 }
 
 /// Query the state vector by supplying ephemeris seconds past J2000 directly.
+///
+/// See [`state_vector_et_corrected`] for the typed correction variant.
 pub fn state_vector_et(
     target: &str,
     observer: &str,
@@ -167,6 +354,438 @@ pub fn state_vector_et(
     )
 }
 
+/// [`state_vector_et`] with a typed aberration correction; the preferred entry point.
+pub fn state_vector_et_corrected(
+    target: &str,
+    observer: &str,
+    reference_frame: &str,
+    correction: AberrationCorrection,
+    ephemeris_time: f64,
+) -> Result<StateVector, EphemerisError> {
+    state_vector_et(
+        target,
+        observer,
+        reference_frame,
+        correction.spice_token(),
+        ephemeris_time,
+    )
+}
+
+/// Query the state vector at each of `epochs`, reusing the converted names across the batch.
+///
+/// Stops at the first failing epoch and reports its index as [`EphemerisError::BatchEpoch`].
+pub fn state_vectors_et(
+    target: &str,
+    observer: &str,
+    reference_frame: &str,
+    aberration_correction: &str,
+    epochs: &[f64],
+) -> Result<Vec<StateVector>, EphemerisError> {
+    load_default_kernels()?;
+
+    let target_c = CString::new(target).unwrap();
+    let observer_c = CString::new(observer).unwrap();
+    let reference_frame_c = CString::new(reference_frame).unwrap();
+    let aberration_c = CString::new(aberration_correction).unwrap();
+
+    epochs
+        .iter()
+        .enumerate()
+        .map(|(index, &et)| {
+            state_vector_et_internal(
+                &target_c,
+                &observer_c,
+                &reference_frame_c,
+                &aberration_c,
+                et,
+            )
+            .map_err(|source| EphemerisError::BatchEpoch {
+                index,
+                et,
+                source: Box::new(source),
+            })
+        })
+        .collect()
+}
+
+/// Query the state of `target` relative to `observer`, expressed in `observer_frame`.
+///
+/// The state is computed in `reference_frame` and then rotated into `observer_frame` at
+/// `epoch`, e.g. `IAU_MARS` for Mars-fixed coordinates of an approaching spacecraft. The
+/// rotation is evaluated without light-time delay, which matches SPICE when `observer_frame` is
+/// centred on `observer`. Both frames are checked up front so a missing frame kernel reports
+/// [`EphemerisError::UnknownFrame`] instead of a raw SPICE message.
+pub fn state_vector_from_frame(
+    target: &str,
+    observer: &str,
+    reference_frame: &str,
+    aberration_correction: &str,
+    epoch: &str,
+    observer_frame: &str,
+) -> Result<StateVector, EphemerisError> {
+    load_default_kernels()?;
+    let reference_frame_c = known_frame(reference_frame)?;
+    let observer_frame_c = known_frame(observer_frame)?;
+    let ephemeris_time = epoch_seconds(epoch)?;
+
+    let state = state_vector_et_internal(
+        &CString::new(target).unwrap(),
+        &CString::new(observer).unwrap(),
+        &reference_frame_c,
+        &CString::new(aberration_correction).unwrap(),
+        ephemeris_time,
+    )?;
+
+    let mut xform: [[SpiceDouble; 6]; 6] = [[0.0; 6]; 6];
+    unsafe {
+        sxform_c(
+            reference_frame_c.as_ptr() as *mut i8,
+            observer_frame_c.as_ptr() as *mut i8,
+            ephemeris_time,
+            xform.as_mut_ptr(),
+        );
+    }
+    check_for_spice_error()?;
+
+    let input = [
+        state.position_km[0],
+        state.position_km[1],
+        state.position_km[2],
+        state.velocity_km_s[0],
+        state.velocity_km_s[1],
+        state.velocity_km_s[2],
+    ];
+    let mut rotated = [0.0; 6];
+    for (out, row) in rotated.iter_mut().zip(&xform) {
+        *out = row.iter().zip(&input).map(|(m, x)| m * x).sum();
+    }
+    Ok(StateVector {
+        position_km: [rotated[0], rotated[1], rotated[2]],
+        velocity_km_s: [rotated[3], rotated[4], rotated[5]],
+        light_time_seconds: state.light_time_seconds,
+    })
+}
+
+/// Osculating elements of `target` about `observer` at `epoch`, from its geometric state.
+///
+/// `mu_km3_s2` is the observer's GM; CSPICE does not look it up.
+pub fn osculating_elements(
+    target: &str,
+    observer: &str,
+    reference_frame: &str,
+    epoch: &str,
+    mu_km3_s2: f64,
+) -> Result<OrbitalElements, EphemerisError> {
+    let ephemeris_time = epoch_seconds(epoch)?;
+    osculating_elements_et(target, observer, reference_frame, ephemeris_time, mu_km3_s2)
+}
+
+/// [`osculating_elements`] at an ephemeris time in seconds past J2000.
+pub fn osculating_elements_et(
+    target: &str,
+    observer: &str,
+    reference_frame: &str,
+    ephemeris_time: f64,
+    mu_km3_s2: f64,
+) -> Result<OrbitalElements, EphemerisError> {
+    let state = state_vector_et(target, observer, reference_frame, "NONE", ephemeris_time)?;
+    let mut state6 = [
+        state.position_km[0],
+        state.position_km[1],
+        state.position_km[2],
+        state.velocity_km_s[0],
+        state.velocity_km_s[1],
+        state.velocity_km_s[2],
+    ];
+    // rp, ecc, inc, lnode, argp, m0, t0, mu
+    let mut elts: [SpiceDouble; 8] = [0.0; 8];
+    unsafe {
+        oscelt_c(
+            state6.as_mut_ptr(),
+            ephemeris_time,
+            mu_km3_s2,
+            elts.as_mut_ptr(),
+        );
+    }
+    check_for_spice_error()?;
+
+    let eccentricity = elts[1];
+    Ok(OrbitalElements {
+        semi_major_axis_km: elts[0] / (1.0 - eccentricity),
+        eccentricity,
+        inclination_rad: elts[2],
+        raan_rad: elts[3],
+        argument_of_periapsis_rad: elts[4],
+        mean_anomaly_rad: elts[5],
+    })
+}
+
+/// Gravitational parameter (km³/s²) of the NAIF body `naif_id` from the loaded kernel pool.
+///
+/// Reads `BODY<id>_GM`, so the value stays consistent with whichever PCK accompanies the loaded
+/// SPKs. Kernels without GM keywords yield [`EphemerisError::MissingGm`], letting callers fall
+/// back to catalog constants.
+pub fn body_gm(naif_id: i32) -> Result<f64, EphemerisError> {
+    const GM: &[u8] = b"GM\0";
+    load_default_kernels()?;
+    let found = unsafe { bodfnd_c(naif_id, GM.as_ptr() as *mut i8) };
+    check_for_spice_error()?;
+    if found == 0 {
+        return Err(EphemerisError::MissingGm { id: naif_id });
+    }
+    let mut dim: SpiceInt = 0;
+    let mut value: SpiceDouble = 0.0;
+    unsafe {
+        bodvcd_c(naif_id, GM.as_ptr() as *mut i8, 1, &mut dim, &mut value);
+    }
+    check_for_spice_error()?;
+    if dim < 1 {
+        return Err(EphemerisError::MissingGm { id: naif_id });
+    }
+    Ok(value)
+}
+
+/// Right ascension and declination (radians) of `target` as seen from `observer` in J2000.
+///
+/// RA is in `[0, 2π)` and declination in `[-π/2, π/2]`. Pass `"LT+S"` as the correction for
+/// apparent (light-time and stellar-aberration corrected) coordinates.
+pub fn radec(
+    target: &str,
+    observer: &str,
+    epoch: &str,
+    aberration_correction: &str,
+) -> Result<(f64, f64), EphemerisError> {
+    let state = state_vector(target, observer, "J2000", aberration_correction, epoch)?;
+    let mut position = state.position_km;
+    let mut range: SpiceDouble = 0.0;
+    let mut ra: SpiceDouble = 0.0;
+    let mut dec: SpiceDouble = 0.0;
+    unsafe {
+        recrad_c(position.as_mut_ptr(), &mut range, &mut ra, &mut dec);
+    }
+    check_for_spice_error()?;
+    Ok((ra, dec))
+}
+
+/// Range (km) and range rate (km/s) of `target` relative to `observer` at `epoch`.
+///
+/// Uses the same `spkezr_c` query as [`state_vector`]; pass `"LT"` or `"CN"` to get the
+/// light-time-corrected geometry that a Doppler measurement would see. A sign change of the range
+/// rate at maximum range marks a conjunction.
+pub fn range_rate(
+    target: &str,
+    observer: &str,
+    reference_frame: &str,
+    aberration_correction: &str,
+    epoch: &str,
+) -> Result<(f64, f64), EphemerisError> {
+    let state = state_vector(
+        target,
+        observer,
+        reference_frame,
+        aberration_correction,
+        epoch,
+    )?;
+    Ok(state.range_and_rate())
+}
+
+/// Resolve a body name to its NAIF integer ID, returning `None` when SPICE does not know it.
+pub fn body_id(name: &str) -> Result<Option<i32>, EphemerisError> {
+    load_default_kernels()?;
+    let name_c = CString::new(name).map_err(|_| EphemerisError::Spice {
+        message: format!("body name `{name}` contains an interior NUL byte"),
+    })?;
+    let mut code: SpiceInt = 0;
+    let mut found: SpiceBoolean = 0;
+    unsafe {
+        bodn2c_c(name_c.as_ptr() as *mut i8, &mut code, &mut found);
+    }
+    check_for_spice_error()?;
+    Ok((found != 0).then_some(code))
+}
+
+/// Resolve a body name (or integer ID string) to its NAIF ID.
+///
+/// Unlike [`body_id`], an unknown name is an [`EphemerisError::UnknownBody`] error carrying the
+/// closest well-known body name when one is within a couple of typos.
+pub fn resolve_body(name: &str) -> Result<i32, EphemerisError> {
+    if let Ok(id) = name.trim().parse::<i32>() {
+        return Ok(id);
+    }
+    body_id(name)?.ok_or_else(|| EphemerisError::UnknownBody {
+        name: name.to_string(),
+        suggestion: suggest_body_name(name).map(str::to_string),
+    })
+}
+
+/// Name SPICE associates with the NAIF ID `id`.
+pub fn body_name(id: i32) -> Result<String, EphemerisError> {
+    load_default_kernels()?;
+    let mut buffer = vec![0i8; 64];
+    let mut found: SpiceBoolean = 0;
+    unsafe {
+        bodc2n_c(
+            id,
+            buffer.len() as SpiceInt,
+            buffer.as_mut_ptr(),
+            &mut found,
+        );
+    }
+    check_for_spice_error()?;
+    if found == 0 {
+        return Err(EphemerisError::UnknownBody {
+            name: id.to_string(),
+            suggestion: None,
+        });
+    }
+    let c_str = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+    Ok(c_str.to_string_lossy().trim().to_string())
+}
+
+/// Bodies covered by the kernel catalog, used to suggest corrections for misspelt names.
+const KNOWN_BODY_NAMES: &[&str] = &[
+    "SUN",
+    "SOLAR SYSTEM BARYCENTER",
+    "MERCURY",
+    "MERCURY BARYCENTER",
+    "VENUS",
+    "VENUS BARYCENTER",
+    "EARTH",
+    "EARTH BARYCENTER",
+    "MOON",
+    "MARS",
+    "MARS BARYCENTER",
+    "PHOBOS",
+    "DEIMOS",
+    "JUPITER",
+    "JUPITER BARYCENTER",
+    "IO",
+    "EUROPA",
+    "GANYMEDE",
+    "CALLISTO",
+    "SATURN",
+    "SATURN BARYCENTER",
+    "MIMAS",
+    "ENCELADUS",
+    "TETHYS",
+    "DIONE",
+    "RHEA",
+    "TITAN",
+    "IAPETUS",
+    "URANUS",
+    "URANUS BARYCENTER",
+    "NEPTUNE",
+    "NEPTUNE BARYCENTER",
+    "TRITON",
+    "PLUTO",
+    "PLUTO BARYCENTER",
+    "CHARON",
+    "CERES",
+    "PALLAS",
+    "VESTA",
+    "ERIS",
+    "HAUMEA",
+    "MAKEMAKE",
+];
+
+/// Closest well-known body name to `name` (case-insensitive), if within two edits.
+///
+/// Names shorter than four characters only match with a single edit, so short inputs such as
+/// `"IX"` are not stretched into unrelated bodies.
+pub fn suggest_body_name(name: &str) -> Option<&'static str> {
+    let upper = name.trim().to_ascii_uppercase();
+    let max_distance = if upper.chars().count() < 4 { 1 } else { 2 };
+    KNOWN_BODY_NAMES
+        .iter()
+        .map(|candidate| (edit_distance(&upper, candidate), *candidate))
+        .filter(|(distance, _)| (1..=max_distance).contains(distance))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between two strings, by character.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Coverage intervals for `target_id` across every loaded SPK, merged and sorted by start.
+///
+/// The SPKs are read from the kernel pool rather than the catalog, so kernels added with
+/// [`furnish_additional`] or [`reinitialize`] count too. Only segments whose target is
+/// `target_id` count: a state relative to another body also needs the segments linking the two
+/// (e.g. 399 → 3 → 0 for Earth relative to the SSB). An empty result means no loaded SPK has
+/// data for the body.
+pub fn kernel_coverage(target_id: i32) -> Result<Vec<CoverageWindow>, EphemerisError> {
+    load_default_kernels()?;
+    let mut cover = CellBuffer::<SpiceDouble>::new(_SpiceDataType_SPICE_DP, MAX_COVERAGE_INTERVALS);
+    for path_c in loaded_spk_paths()? {
+        // spkcov_c unions each file's coverage into the window.
+        unsafe {
+            spkcov_c(path_c.as_ptr() as *mut i8, target_id, &mut cover.cell);
+        }
+        check_for_spice_error()?;
+    }
+    coverage_intervals(&mut cover)
+}
+
+/// Longest kernel path `kdata_c` reports.
+const KERNEL_PATH_LEN: usize = 1_024;
+
+/// Paths of the SPKs currently furnished, in load order, as `ktotal_c`/`kdata_c` report them.
+fn loaded_spk_paths() -> Result<Vec<CString>, EphemerisError> {
+    let kind = CString::new("SPK").unwrap();
+    let mut count: SpiceInt = 0;
+    unsafe {
+        ktotal_c(kind.as_ptr() as *mut i8, &mut count);
+    }
+    check_for_spice_error()?;
+
+    let mut paths = Vec::with_capacity(count.max(0) as usize);
+    for which in 0..count {
+        let mut file = vec![0i8; KERNEL_PATH_LEN];
+        let mut file_type = vec![0i8; 32];
+        let mut source = vec![0i8; KERNEL_PATH_LEN];
+        let mut handle: SpiceInt = 0;
+        let mut found: SpiceBoolean = 0;
+        unsafe {
+            kdata_c(
+                which,
+                kind.as_ptr() as *mut i8,
+                file.len() as SpiceInt,
+                file_type.len() as SpiceInt,
+                source.len() as SpiceInt,
+                file.as_mut_ptr(),
+                file_type.as_mut_ptr(),
+                source.as_mut_ptr(),
+                &mut handle,
+                &mut found,
+            );
+        }
+        check_for_spice_error()?;
+        if found != 0 {
+            paths.push(unsafe { CStr::from_ptr(file.as_ptr()) }.to_owned());
+        }
+    }
+    Ok(paths)
+}
+
+/// Whether any loaded SPK has a segment for `target_id` at `et`.
+pub fn is_covered(target_id: i32, et: f64) -> Result<bool, EphemerisError> {
+    Ok(kernel_coverage(target_id)?
+        .iter()
+        .any(|window| window.contains(et)))
+}
+
 /// Convert a time string understood by SPICE into ephemeris seconds past J2000.
 pub fn epoch_seconds(epoch: &str) -> Result<f64, EphemerisError> {
     load_default_kernels()?;
@@ -181,11 +800,52 @@ pub fn epoch_seconds(epoch: &str) -> Result<f64, EphemerisError> {
     Ok(et)
 }
 
+/// Julian date of the Modified Julian Date epoch (1858-11-17 00:00).
+const MJD_EPOCH_JD: f64 = 2_400_000.5;
+
+/// Convert a TDB Julian date into ephemeris seconds past J2000.
+///
+/// Pure arithmetic on SPICE's `j2000_c`/`spd_c` constants, so no kernels are needed. The input
+/// must already be on the TDB scale (as Horizons vector tables are); UTC Julian dates should go
+/// through [`epoch_seconds`] with a `"JD ... UTC"` string instead.
+pub fn et_from_jd_tdb(jd: f64) -> f64 {
+    let (j2000, spd) = unsafe { (j2000_c(), spd_c()) };
+    (jd - j2000) * spd
+}
+
+/// Convert ephemeris seconds past J2000 into a TDB Julian date.
+pub fn jd_tdb_from_et(et: f64) -> f64 {
+    let (j2000, spd) = unsafe { (j2000_c(), spd_c()) };
+    j2000 + et / spd
+}
+
+/// Convert a TDB Modified Julian Date into ephemeris seconds past J2000.
+pub fn et_from_mjd_tdb(mjd: f64) -> f64 {
+    et_from_jd_tdb(mjd + MJD_EPOCH_JD)
+}
+
+/// Convert ephemeris seconds past J2000 into a TDB Modified Julian Date.
+pub fn mjd_tdb_from_et(et: f64) -> f64 {
+    jd_tdb_from_et(et) - MJD_EPOCH_JD
+}
+
 /// Format an ephemeris time (seconds past J2000) into a UTC calendar string.
+///
+/// Produces SPICE's `"C"` format, e.g. `"2026 JAN 01 00:00:00.000"`.
 pub fn format_epoch(et: f64) -> Result<String, EphemerisError> {
+    utc_string(et, "C")
+}
+
+/// Format an ephemeris time as an ISO-8601 UTC calendar string, e.g. `"2026-01-01T00:00:00.000"`.
+pub fn format_epoch_iso(et: f64) -> Result<String, EphemerisError> {
+    utc_string(et, "ISOC")
+}
+
+/// Convert `et` to UTC with the `et2utc_c` output `format`, at millisecond precision.
+fn utc_string(et: f64, format: &str) -> Result<String, EphemerisError> {
     load_default_kernels()?;
     let mut buffer = vec![0i8; 64];
-    let fmt = CString::new("C").unwrap();
+    let fmt = CString::new(format).unwrap();
     unsafe {
         et2utc_c(
             et,
@@ -200,13 +860,77 @@ pub fn format_epoch(et: f64) -> Result<String, EphemerisError> {
     Ok(c_str.to_string_lossy().trim().to_string())
 }
 
-fn initialize_spice() -> Result<(), EphemerisError> {
-    validate_kernel_paths()?;
+/// Resolve `frame` to a C string, failing when SPICE has no frame of that name.
+fn known_frame(frame: &str) -> Result<CString, EphemerisError> {
+    let unknown = || EphemerisError::UnknownFrame {
+        frame: frame.to_string(),
+    };
+    let frame_c = CString::new(frame).map_err(|_| unknown())?;
+    let mut code: SpiceInt = 0;
+    unsafe {
+        namfrm_c(frame_c.as_ptr() as *mut i8, &mut code);
+    }
+    check_for_spice_error()?;
+    if code == 0 {
+        Err(unknown())
+    } else {
+        Ok(frame_c)
+    }
+}
+
+/// Check that the local copy of `descriptor` starts with the SPICE header for its kind.
+///
+/// See [`verify_kernel_at`].
+pub fn verify_kernel(descriptor: &KernelDescriptor) -> Result<(), EphemerisError> {
+    verify_kernel_at(descriptor, &descriptor.local_path())
+}
+
+/// Check that the file at `path` starts with the SPICE header expected for `descriptor.kind`.
+///
+/// Binary kernels open with a DAF ID word (`DAF/SPK`, or the legacy `NAIF/DAF` for SPKs) and
+/// text kernels with a `KPL/` marker (`KPL/LSK`, `KPL/PCK`, `KPL/FK`). Empty, truncated or
+/// mislabelled files yield [`EphemerisError::CorruptKernel`] instead of failing inside
+/// `furnsh_c`.
+pub fn verify_kernel_at(descriptor: &KernelDescriptor, path: &Path) -> Result<(), EphemerisError> {
+    use std::io::Read;
+
+    let io_error = |source| EphemerisError::Io {
+        name: descriptor.filename,
+        source,
+    };
+    let mut header = Vec::with_capacity(8);
+    fs::File::open(path)
+        .map_err(io_error)?
+        .take(8)
+        .read_to_end(&mut header)
+        .map_err(io_error)?;
+
+    let accepted: &[&[u8]] = match descriptor.kind {
+        KernelKind::Spk => &[b"DAF/SPK", b"NAIF/DAF"],
+        KernelKind::Lsk => &[b"KPL/LSK"],
+        KernelKind::Pck => &[b"KPL/PCK", b"DAF/PCK"],
+        KernelKind::Fk => &[b"KPL/FK"],
+    };
+    if accepted.iter().any(|magic| header.starts_with(magic)) {
+        Ok(())
+    } else {
+        Err(EphemerisError::CorruptKernel {
+            name: descriptor.filename,
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+fn initialize_spice(descriptors: &[KernelDescriptor]) -> Result<(), EphemerisError> {
+    validate_kernel_paths(descriptors)?;
+    for descriptor in descriptors {
+        verify_kernel(descriptor)?;
+    }
     unsafe {
         kclear_c();
     }
     configure_error_handling();
-    for descriptor in KERNEL_CATALOG {
+    for descriptor in descriptors {
         let c_path = path_to_cstring(descriptor)?;
         unsafe {
             furnsh_c(c_path.as_ptr() as *mut i8);
@@ -216,8 +940,8 @@ fn initialize_spice() -> Result<(), EphemerisError> {
     Ok(())
 }
 
-fn validate_kernel_paths() -> Result<(), EphemerisError> {
-    for descriptor in KERNEL_CATALOG {
+fn validate_kernel_paths(descriptors: &[KernelDescriptor]) -> Result<(), EphemerisError> {
+    for descriptor in descriptors {
         let path = descriptor.local_path();
         if !path.exists() {
             return Err(EphemerisError::MissingKernel {
@@ -290,6 +1014,107 @@ fn state_vector_et_internal(
     })
 }
 
+/// Size of the control area SPICE reserves at the front of every cell.
+const SPICE_CELL_CTRLSZ: usize = 6;
+const MAX_SPK_OBJECTS: usize = 1_000;
+const MAX_COVERAGE_INTERVALS: usize = 2_000;
+
+/// Heap-backed SPICE cell; `_storage` owns the buffer the cell pointers refer to.
+struct CellBuffer<T> {
+    _storage: Vec<T>,
+    cell: SpiceCell,
+}
+
+impl<T: Copy + Default> CellBuffer<T> {
+    fn new(dtype: SpiceDataType, size: usize) -> Self {
+        let mut storage = vec![T::default(); SPICE_CELL_CTRLSZ + size];
+        let base = storage.as_mut_ptr();
+        let cell = SpiceCell {
+            dtype,
+            length: 0,
+            size: size as SpiceInt,
+            card: 0,
+            isSet: 1,
+            adjust: 0,
+            init: 0,
+            base: base.cast(),
+            data: unsafe { base.add(SPICE_CELL_CTRLSZ) }.cast(),
+        };
+        Self {
+            _storage: storage,
+            cell,
+        }
+    }
+}
+
+/// Combined ET coverage span `(start, end)` of every object in an SPK file.
+///
+/// Returns `Ok(None)` when the file contains no segments. The file does not need to be furnished.
+fn spk_coverage_span(path: &Path) -> Result<Option<(f64, f64)>, EphemerisError> {
+    let path_c = spk_path_cstring(path)?;
+
+    configure_error_handling();
+
+    let mut ids = CellBuffer::<SpiceInt>::new(_SpiceDataType_SPICE_INT, MAX_SPK_OBJECTS);
+    unsafe {
+        spkobj_c(path_c.as_ptr() as *mut i8, &mut ids.cell);
+    }
+    check_for_spice_error()?;
+
+    let object_ids: Vec<SpiceInt> = (0..ids.cell.card.max(0) as usize)
+        .map(|idx| unsafe { *ids.cell.data.cast::<SpiceInt>().add(idx) })
+        .collect();
+
+    let mut cover = CellBuffer::<SpiceDouble>::new(_SpiceDataType_SPICE_DP, MAX_COVERAGE_INTERVALS);
+    let mut span: Option<(f64, f64)> = None;
+    for id in object_ids {
+        unsafe {
+            scard_c(0, &mut cover.cell);
+            spkcov_c(path_c.as_ptr() as *mut i8, id, &mut cover.cell);
+        }
+        check_for_spice_error()?;
+
+        for window in coverage_intervals(&mut cover)? {
+            span = Some(match span {
+                Some((lo, hi)) => (lo.min(window.start_et), hi.max(window.end_et)),
+                None => (window.start_et, window.end_et),
+            });
+        }
+    }
+
+    Ok(span)
+}
+
+fn spk_path_cstring(path: &Path) -> Result<CString, EphemerisError> {
+    let path_str = path.to_str().ok_or_else(|| EphemerisError::Spice {
+        message: format!("kernel path {} contains invalid UTF-8", path.display()),
+    })?;
+    CString::new(path_str).map_err(|_| EphemerisError::Spice {
+        message: format!("kernel path {} contains a NUL byte", path.display()),
+    })
+}
+
+/// Read the intervals of a SPICE double-precision window cell.
+fn coverage_intervals(
+    cover: &mut CellBuffer<SpiceDouble>,
+) -> Result<Vec<CoverageWindow>, EphemerisError> {
+    let intervals = unsafe { wncard_c(&mut cover.cell) };
+    let mut windows = Vec::with_capacity(intervals.max(0) as usize);
+    for idx in 0..intervals {
+        let mut start: SpiceDouble = 0.0;
+        let mut end: SpiceDouble = 0.0;
+        unsafe {
+            wnfetd_c(&mut cover.cell, idx, &mut start, &mut end);
+        }
+        check_for_spice_error()?;
+        windows.push(CoverageWindow {
+            start_et: start,
+            end_et: end,
+        });
+    }
+    Ok(windows)
+}
+
 fn check_for_spice_error() -> Result<(), EphemerisError> {
     unsafe {
         if failed_c() != 0 as SpiceBoolean {
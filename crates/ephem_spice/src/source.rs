@@ -0,0 +1,199 @@
+//! Pluggable ephemeris backends: [`SpiceSource`] queries CSPICE, while [`FixedEphemerisSource`]
+//! interpolates a table of precomputed states so planning can run without any kernels.
+
+use std::collections::HashMap;
+
+use crate::{EphemerisError, StateVector};
+
+/// Epoch conversion and state lookup, the SPICE calls the mission planner depends on.
+pub trait EphemerisSource {
+    /// State of `target` relative to `observer` in `frame` at ephemeris time `et`.
+    fn state_vector_et(
+        &self,
+        target: &str,
+        observer: &str,
+        frame: &str,
+        aberration_correction: &str,
+        et: f64,
+    ) -> Result<StateVector, EphemerisError>;
+
+    /// Ephemeris seconds past J2000 for an epoch string.
+    fn epoch_seconds(&self, epoch: &str) -> Result<f64, EphemerisError>;
+
+    /// Human-readable label for `et`.
+    fn format_epoch(&self, et: f64) -> Result<String, EphemerisError>;
+}
+
+/// Delegates to the loaded SPICE kernels.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SpiceSource;
+
+impl EphemerisSource for SpiceSource {
+    fn state_vector_et(
+        &self,
+        target: &str,
+        observer: &str,
+        frame: &str,
+        aberration_correction: &str,
+        et: f64,
+    ) -> Result<StateVector, EphemerisError> {
+        crate::state_vector_et(target, observer, frame, aberration_correction, et)
+    }
+
+    fn epoch_seconds(&self, epoch: &str) -> Result<f64, EphemerisError> {
+        crate::epoch_seconds(epoch)
+    }
+
+    fn format_epoch(&self, et: f64) -> Result<String, EphemerisError> {
+        crate::format_epoch(et)
+    }
+}
+
+type TrackKey = (String, String, String);
+
+/// Deterministic, kernel-free source backed by tabulated states and named epochs.
+///
+/// States between two samples of the same `(target, observer, frame)` track are cubic-Hermite
+/// interpolated from the bracketing positions and velocities, so a table sampled every few days
+/// reproduces a planetary orbit closely. Queries outside a track's span fail with
+/// [`EphemerisError::NotTabulated`]. Aberration corrections are ignored: the table is taken to
+/// hold whatever states the caller wants returned.
+///
+/// Epoch strings resolve through the labels registered with [`insert_epoch`](Self::insert_epoch),
+/// and `"ET <seconds>"` strings (as produced by [`EphemerisSource::format_epoch`] for unlabelled
+/// times) parse directly.
+#[derive(Debug, Clone, Default)]
+pub struct FixedEphemerisSource {
+    tracks: HashMap<TrackKey, Vec<(f64, StateVector)>>,
+    epochs: Vec<(String, f64)>,
+}
+
+impl FixedEphemerisSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tabulate `state` for `target` relative to `observer` in `frame` at `et`, replacing any
+    /// sample already stored at that epoch. Names are matched case-insensitively.
+    pub fn insert_state(
+        &mut self,
+        target: &str,
+        observer: &str,
+        frame: &str,
+        et: f64,
+        state: StateVector,
+    ) {
+        let track = self
+            .tracks
+            .entry(track_key(target, observer, frame))
+            .or_default();
+        match track.binary_search_by(|(sample_et, _)| sample_et.total_cmp(&et)) {
+            Ok(index) => track[index].1 = state,
+            Err(index) => track.insert(index, (et, state)),
+        }
+    }
+
+    /// Register `label` as a name for ephemeris time `et`.
+    pub fn insert_epoch(&mut self, label: &str, et: f64) {
+        let label = label.trim();
+        match self.epochs.iter_mut().find(|(name, _)| name == label) {
+            Some(entry) => entry.1 = et,
+            None => self.epochs.push((label.to_string(), et)),
+        }
+    }
+}
+
+impl EphemerisSource for FixedEphemerisSource {
+    fn state_vector_et(
+        &self,
+        target: &str,
+        observer: &str,
+        frame: &str,
+        _aberration_correction: &str,
+        et: f64,
+    ) -> Result<StateVector, EphemerisError> {
+        let not_tabulated = || EphemerisError::NotTabulated {
+            target: target.to_string(),
+            observer: observer.to_string(),
+            frame: frame.to_string(),
+            et,
+        };
+        let track = self
+            .tracks
+            .get(&track_key(target, observer, frame))
+            .ok_or_else(not_tabulated)?;
+        let after = track.partition_point(|(sample_et, _)| *sample_et < et);
+        match (after.checked_sub(1).map(|i| &track[i]), track.get(after)) {
+            (_, Some((sample_et, state))) if *sample_et == et => Ok(*state),
+            (Some(before), Some(after)) => Ok(hermite(before, after, et)),
+            _ => Err(not_tabulated()),
+        }
+    }
+
+    fn epoch_seconds(&self, epoch: &str) -> Result<f64, EphemerisError> {
+        let epoch = epoch.trim();
+        self.epochs
+            .iter()
+            .find(|(label, _)| label == epoch)
+            .map(|(_, et)| *et)
+            .or_else(|| epoch.strip_prefix("ET ")?.trim().parse().ok())
+            .ok_or_else(|| EphemerisError::InvalidEpoch {
+                epoch: epoch.to_string(),
+            })
+    }
+
+    fn format_epoch(&self, et: f64) -> Result<String, EphemerisError> {
+        Ok(self
+            .epochs
+            .iter()
+            .find(|(_, labelled)| *labelled == et)
+            .map_or_else(|| format!("ET {et:.3}"), |(label, _)| label.clone()))
+    }
+}
+
+fn track_key(target: &str, observer: &str, frame: &str) -> TrackKey {
+    (
+        target.trim().to_ascii_uppercase(),
+        observer.trim().to_ascii_uppercase(),
+        frame.trim().to_ascii_uppercase(),
+    )
+}
+
+/// Cubic-Hermite state at `et` between two samples, with light time interpolated linearly.
+fn hermite(
+    (et0, state0): &(f64, StateVector),
+    (et1, state1): &(f64, StateVector),
+    et: f64,
+) -> StateVector {
+    let h = et1 - et0;
+    let s = (et - et0) / h;
+    let (s2, s3) = (s * s, s * s * s);
+    let (h00, h10, h01, h11) = (
+        2.0 * s3 - 3.0 * s2 + 1.0,
+        s3 - 2.0 * s2 + s,
+        -2.0 * s3 + 3.0 * s2,
+        s3 - s2,
+    );
+    let (d00, d10, d01, d11) = (
+        (6.0 * s2 - 6.0 * s) / h,
+        3.0 * s2 - 4.0 * s + 1.0,
+        (6.0 * s - 6.0 * s2) / h,
+        3.0 * s2 - 2.0 * s,
+    );
+    let (p0, v0, p1, v1) = (
+        state0.position_km,
+        state0.velocity_km_s,
+        state1.position_km,
+        state1.velocity_km_s,
+    );
+    StateVector {
+        position_km: std::array::from_fn(|i| {
+            h00 * p0[i] + h10 * h * v0[i] + h01 * p1[i] + h11 * h * v1[i]
+        }),
+        velocity_km_s: std::array::from_fn(|i| {
+            d00 * p0[i] + d10 * v0[i] + d01 * p1[i] + d11 * v1[i]
+        }),
+        light_time_seconds: state0.light_time_seconds
+            + s * (state1.light_time_seconds - state0.light_time_seconds),
+    }
+}
@@ -2,10 +2,10 @@
 
 pub mod porkchop {
     use std::fs::{self, File};
-    use std::io::{self, BufWriter, Write};
+    use std::io::{self, BufReader, BufWriter, Read, Write};
     use std::path::Path;
 
-    const HEADER: &str = "depart_et,arrive_et,depart_utc,arrive_utc,tof_days,c3_km2_s2,vinf_dep_km_s,vinf_arr_km_s,dv_dep_km_s,dv_arr_km_s,dv_total_km_s,propellant_used_kg,burn_time_s,final_mass_kg,lambert_path,feasible,origin_body,dest_body,rpark_dep_km,rpark_arr_km";
+    const HEADER: &str = "depart_et,arrive_et,depart_utc,arrive_utc,tof_days,c3_km2_s2,vinf_dep_km_s,vinf_arr_km_s,dv_dep_km_s,dv_arr_km_s,dv_total_km_s,propellant_used_kg,burn_time_s,final_mass_kg,lambert_path,feasible,origin_body,dest_body,rpark_dep_km,rpark_arr_km,depart_index,arrive_index,revolutions";
 
     /// Create a writer for the target path, handling stdout (`-`) by convention.
     pub fn writer_for_path(path: &Path) -> io::Result<Box<dyn Write>> {
@@ -21,6 +21,43 @@ pub mod porkchop {
         Ok(Box::new(BufWriter::new(file)))
     }
 
+    /// Default resolution, in seconds, applied to emitted `depart_et`/`arrive_et` values.
+    pub const DEFAULT_ET_RESOLUTION_S: f64 = 1.0;
+
+    /// Round an ephemeris time to the nearest multiple of `resolution_s`.
+    ///
+    /// Non-positive or non-finite resolutions leave the value untouched.
+    pub fn quantize_et(et: f64, resolution_s: f64) -> f64 {
+        if !(resolution_s.is_finite() && resolution_s > 0.0) {
+            return et;
+        }
+        (et / resolution_s).round() * resolution_s
+    }
+
+    /// Zero-based position of `et` on a grid starting at `start_et` with spacing `step_s`.
+    ///
+    /// Epochs before the start, or a non-positive step, map to index 0.
+    pub fn grid_index(et: f64, start_et: f64, step_s: f64) -> usize {
+        if !(step_s.is_finite() && step_s > 0.0) {
+            return 0;
+        }
+        let index = ((et - start_et) / step_s).round();
+        if index.is_finite() && index > 0.0 {
+            index as usize
+        } else {
+            0
+        }
+    }
+
+    /// Format a quantized ET with just enough decimals for `resolution_s`.
+    fn format_et(et: f64, resolution_s: f64) -> String {
+        if !(resolution_s.is_finite() && resolution_s > 0.0) {
+            return et.to_string();
+        }
+        let decimals = (-resolution_s.log10()).ceil().clamp(0.0, 9.0) as usize;
+        format!("{:.*}", decimals, quantize_et(et, resolution_s))
+    }
+
     /// Write the standard porkchop CSV header.
     pub fn write_header(writer: &mut dyn Write) -> io::Result<()> {
         writeln!(writer, "{}", HEADER)
@@ -49,16 +86,27 @@ pub mod porkchop {
         pub dest_body: &'a str,
         pub rpark_dep_km: f64,
         pub rpark_arr_km: f64,
+        /// Resolution in seconds that `depart_et`/`arrive_et` are quantized to on output.
+        pub et_resolution_s: f64,
+        /// Column of this cell in the departure window grid (see [`grid_index`]).
+        pub depart_index: usize,
+        /// Row of this cell in the arrival window grid (see [`grid_index`]).
+        pub arrive_index: usize,
+        /// Complete heliocentric revolutions flown by the chosen Lambert branch.
+        pub revolutions: u32,
     }
 
     impl<'a> Record<'a> {
         /// Serialize the record to CSV, matching the standard header ordering.
+        ///
+        /// Epochs are quantized to `et_resolution_s` so rows from the same grid line
+        /// compare exactly when re-read by downstream tools.
         pub fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
             writeln!(
                 writer,
-                "{},{},{},{},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.3},{:.3},{:.3},{},{},{},{},{:.3},{:.3}",
-                self.depart_et,
-                self.arrive_et,
+                "{},{},{},{},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.3},{:.3},{:.3},{},{},{},{},{:.3},{:.3},{},{},{}",
+                format_et(self.depart_et, self.et_resolution_s),
+                format_et(self.arrive_et, self.et_resolution_s),
                 self.depart_utc,
                 self.arrive_utc,
                 self.tof_days,
@@ -77,9 +125,221 @@ pub mod porkchop {
                 self.dest_body,
                 self.rpark_dep_km,
                 self.rpark_arr_km,
+                self.depart_index,
+                self.arrive_index,
+                self.revolutions,
             )
         }
     }
+
+    /// Leading bytes identifying a binary porkchop grid.
+    pub const BINARY_GRID_MAGIC: &[u8; 8] = b"STCPKGRD";
+
+    /// Schema version written by [`write_binary`].
+    ///
+    /// Later versions may only append header fields; readers skip header bytes they do not
+    /// understand using the stored header length.
+    pub const BINARY_GRID_VERSION: u32 = 1;
+
+    /// Descriptive fields stored in a binary grid header.
+    #[derive(Debug, Clone, PartialEq, Default)]
+    pub struct GridMetadata {
+        /// Column name of the stored metric, e.g. `dv_total_km_s`.
+        pub metric: String,
+        pub origin_body: String,
+        pub dest_body: String,
+    }
+
+    /// Departure×arrival matrix of a single porkchop metric.
+    ///
+    /// `values` and `feasible` are row-major with one row per departure epoch, so the cell for
+    /// `depart_et[i]`/`arrive_et[j]` sits at `i * arrive_et.len() + j`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Grid {
+        pub depart_et: Vec<f64>,
+        pub arrive_et: Vec<f64>,
+        pub values: Vec<f64>,
+        pub feasible: Vec<bool>,
+    }
+
+    impl Grid {
+        /// Grid over the given axes with every cell `NaN` and infeasible.
+        pub fn empty(depart_et: Vec<f64>, arrive_et: Vec<f64>) -> Self {
+            let cells = depart_et.len() * arrive_et.len();
+            Self {
+                depart_et,
+                arrive_et,
+                values: vec![f64::NAN; cells],
+                feasible: vec![false; cells],
+            }
+        }
+
+        /// Flat index of the cell at departure row `depart_idx` and arrival column `arrive_idx`.
+        pub fn index(&self, depart_idx: usize, arrive_idx: usize) -> usize {
+            depart_idx * self.arrive_et.len() + arrive_idx
+        }
+    }
+
+    /// Write `grid` to `path` in the compact binary layout (stdout for `-`).
+    ///
+    /// Layout, little-endian: magic, version (`u32`), header length (`u32`), header (departure
+    /// and arrival counts as `u32`, then metric, origin and destination as length-prefixed
+    /// UTF-8), departure axis, arrival axis and values as packed `f64`, and finally the
+    /// feasibility bitmask, eight cells per byte starting at the least significant bit.
+    pub fn write_binary(path: &Path, grid: &Grid, metadata: &GridMetadata) -> io::Result<()> {
+        let mut writer = writer_for_path(path)?;
+        write_binary_to(writer.as_mut(), grid, metadata)?;
+        writer.flush()
+    }
+
+    /// [`write_binary`] into an arbitrary writer.
+    pub fn write_binary_to(
+        writer: &mut dyn Write,
+        grid: &Grid,
+        metadata: &GridMetadata,
+    ) -> io::Result<()> {
+        let cells = grid.depart_et.len() * grid.arrive_et.len();
+        if grid.values.len() != cells || grid.feasible.len() != cells {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "grid has {} values and {} feasibility flags for {cells} cells",
+                    grid.values.len(),
+                    grid.feasible.len()
+                ),
+            ));
+        }
+
+        let mut header = Vec::new();
+        header.extend_from_slice(&dimension(grid.depart_et.len())?.to_le_bytes());
+        header.extend_from_slice(&dimension(grid.arrive_et.len())?.to_le_bytes());
+        for field in [&metadata.metric, &metadata.origin_body, &metadata.dest_body] {
+            header.extend_from_slice(&dimension(field.len())?.to_le_bytes());
+            header.extend_from_slice(field.as_bytes());
+        }
+
+        writer.write_all(BINARY_GRID_MAGIC)?;
+        writer.write_all(&BINARY_GRID_VERSION.to_le_bytes())?;
+        writer.write_all(&dimension(header.len())?.to_le_bytes())?;
+        writer.write_all(&header)?;
+        for value in grid
+            .depart_et
+            .iter()
+            .chain(&grid.arrive_et)
+            .chain(&grid.values)
+        {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        let mut mask = vec![0u8; cells.div_ceil(8)];
+        for (idx, _) in grid.feasible.iter().enumerate().filter(|(_, f)| **f) {
+            mask[idx / 8] |= 1 << (idx % 8);
+        }
+        writer.write_all(&mask)
+    }
+
+    /// Read a grid written by [`write_binary`].
+    pub fn read_binary(path: &Path) -> io::Result<(Grid, GridMetadata)> {
+        let mut reader = BufReader::new(File::open(path)?);
+        read_binary_from(&mut reader)
+    }
+
+    /// [`read_binary`] from an arbitrary reader.
+    pub fn read_binary_from(reader: &mut dyn Read) -> io::Result<(Grid, GridMetadata)> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != BINARY_GRID_MAGIC {
+            return Err(invalid_data("not a binary porkchop grid".to_string()));
+        }
+        let version = read_u32(reader)?;
+        if version == 0 {
+            return Err(invalid_data(format!(
+                "unsupported binary grid version {version}"
+            )));
+        }
+        let header_len = read_u32(reader)? as usize;
+        let mut header = vec![0u8; header_len];
+        reader.read_exact(&mut header)?;
+
+        let mut cursor = header.as_slice();
+        let depart_len = read_u32(&mut cursor)? as usize;
+        let arrive_len = read_u32(&mut cursor)? as usize;
+        let metadata = GridMetadata {
+            metric: read_string(&mut cursor)?,
+            origin_body: read_string(&mut cursor)?,
+            dest_body: read_string(&mut cursor)?,
+        };
+        // Any remaining header bytes belong to newer versions and are ignored.
+
+        let cells = depart_len
+            .checked_mul(arrive_len)
+            .ok_or_else(|| invalid_data("grid dimensions overflow".to_string()))?;
+        let depart_et = read_f64s(reader, depart_len)?;
+        let arrive_et = read_f64s(reader, arrive_len)?;
+        let values = read_f64s(reader, cells)?;
+        let mut mask = vec![0u8; cells.div_ceil(8)];
+        reader.read_exact(&mut mask)?;
+        let feasible = (0..cells)
+            .map(|idx| mask[idx / 8] & (1 << (idx % 8)) != 0)
+            .collect();
+
+        Ok((
+            Grid {
+                depart_et,
+                arrive_et,
+                values,
+                feasible,
+            },
+            metadata,
+        ))
+    }
+
+    /// Whether the file at `path` starts with [`BINARY_GRID_MAGIC`].
+    pub fn is_binary_grid(path: &Path) -> io::Result<bool> {
+        let mut magic = [0u8; 8];
+        match File::open(path)?.read_exact(&mut magic) {
+            Ok(()) => Ok(&magic == BINARY_GRID_MAGIC),
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn dimension(len: usize) -> io::Result<u32> {
+        u32::try_from(len).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{len} does not fit the binary grid header"),
+            )
+        })
+    }
+
+    fn invalid_data(message: String) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, message)
+    }
+
+    fn read_u32(reader: &mut dyn Read) -> io::Result<u32> {
+        let mut bytes = [0u8; 4];
+        reader.read_exact(&mut bytes)?;
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn read_string(reader: &mut dyn Read) -> io::Result<String> {
+        let len = read_u32(reader)? as usize;
+        let mut bytes = vec![0u8; len];
+        reader.read_exact(&mut bytes)?;
+        String::from_utf8(bytes).map_err(|err| invalid_data(err.to_string()))
+    }
+
+    fn read_f64s(reader: &mut dyn Read, count: usize) -> io::Result<Vec<f64>> {
+        let len = count
+            .checked_mul(8)
+            .ok_or_else(|| invalid_data("grid dimensions overflow".to_string()))?;
+        let mut bytes = vec![0u8; len];
+        reader.read_exact(&mut bytes)?;
+        Ok(bytes
+            .chunks_exact(8)
+            .map(|chunk| f64::from_le_bytes(chunk.try_into().expect("8-byte chunk")))
+            .collect())
+    }
 }
 
 pub mod continuous {
@@ -105,6 +365,8 @@ pub mod continuous {
         pub burn_time_total_s: f64,
         pub propellant_used_kg: f64,
         pub final_mass_kg: f64,
+        pub mass_ratio: f64,
+        pub payload_fraction: f64,
         pub max_velocity_m_s: f64,
         pub max_velocity_fraction_c: f64,
         pub total_distance_m: f64,
@@ -122,6 +384,9 @@ pub mod continuous {
         pub depart_utc: &'a str,
         pub arrive_et: f64,
         pub arrive_utc: &'a str,
+        /// Upper bound on hourly samples written to the sidecar (see [`decimate_samples`]);
+        /// `None` writes every sample.
+        pub max_samples: Option<usize>,
     }
 
     #[derive(Serialize)]
@@ -137,6 +402,8 @@ pub mod continuous {
         burn_time_total_s: f64,
         propellant_used_kg: f64,
         final_mass_kg: f64,
+        mass_ratio: f64,
+        payload_fraction: f64,
         max_velocity_m_s: f64,
         max_velocity_fraction_c: f64,
         total_distance_m: f64,
@@ -182,6 +449,15 @@ pub mod continuous {
         let hourly_path = parent.join(format!("{}_hourly.json", stem));
         let daily_path = parent.join(format!("{}_daily.json", stem));
 
+        let decimated;
+        let samples = match meta.max_samples {
+            Some(max) => {
+                decimated = decimate_samples(&summary.samples, max);
+                &decimated
+            }
+            None => &summary.samples,
+        };
+
         let hourly = TelemetrySidecar {
             vehicle: meta.vehicle,
             origin: meta.origin,
@@ -194,11 +470,13 @@ pub mod continuous {
             burn_time_total_s: summary.burn_time_total_s,
             propellant_used_kg: summary.propellant_used_kg,
             final_mass_kg: summary.final_mass_kg,
+            mass_ratio: summary.mass_ratio,
+            payload_fraction: summary.payload_fraction,
             max_velocity_m_s: summary.max_velocity_m_s,
             max_velocity_fraction_c: summary.max_velocity_fraction_c,
             total_distance_m: summary.total_distance_m,
             kinetic_energy_joules: summary.kinetic_energy_joules,
-            samples: &summary.samples,
+            samples,
         };
 
         to_writer_pretty(File::create(&hourly_path)?, &hourly)?;
@@ -221,6 +499,35 @@ pub mod continuous {
         Ok(())
     }
 
+    /// Thin `samples` to at most `max_samples` evenly spaced entries.
+    ///
+    /// The first, last and peak-velocity samples are always kept verbatim, so fewer than three
+    /// requested samples still yields those anchors.
+    pub fn decimate_samples(samples: &[Sample], max_samples: usize) -> Vec<Sample> {
+        let n = samples.len();
+        if n <= max_samples.max(1) {
+            return samples.to_vec();
+        }
+        let peak = samples
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.velocity_m_s.total_cmp(&b.velocity_m_s))
+            .map_or(0, |(idx, _)| idx);
+
+        // Spread the budget left after the peak over the whole run, endpoints included.
+        let spread = max_samples.saturating_sub(1).max(2);
+        let mut indices: Vec<usize> = (0..spread)
+            .map(|k| ((k * (n - 1)) as f64 / (spread - 1) as f64).round() as usize)
+            .collect();
+        indices.push(peak);
+        indices.sort_unstable();
+        indices.dedup();
+        indices
+            .into_iter()
+            .map(|idx| samples[idx].clone())
+            .collect()
+    }
+
     fn aggregate_daily(samples: &[Sample]) -> Vec<DailyAggregate> {
         let mut daily: Vec<DailyAggregate> = Vec::new();
         let seconds_per_day = 86_400.0;
@@ -254,3 +561,105 @@ pub mod continuous {
         daily
     }
 }
+
+pub mod trajectory {
+    //! Trajectory tracks for GIS and 3D viewers.
+    //!
+    //! Samples are `(et_seconds, [x, y, z])` with positions in km in a caller-chosen inertial
+    //! frame ([`DEFAULT_FRAME`] unless stated otherwise). The frame name is recorded in the
+    //! output but no transformation is applied.
+
+    use serde_json::{Value, json, to_writer_pretty};
+    use std::io::{self, Write};
+    use std::path::Path;
+
+    use crate::porkchop::writer_for_path;
+
+    /// Frame assumed when the caller does not name one.
+    pub const DEFAULT_FRAME: &str = "ECLIPJ2000";
+
+    /// Write `samples` as a GeoJSON `Feature` holding a single `LineString`.
+    ///
+    /// Coordinates are raw Cartesian `[x_km, y_km, z_km]` in `frame`, not longitude/latitude;
+    /// the feature's properties carry `frame`, `units` and the per-vertex `et_seconds` so
+    /// tools that expect WGS84 can be pointed at a custom CRS instead.
+    pub fn write_geojson(
+        path: &Path,
+        samples: &[(f64, [f64; 3])],
+        frame: Option<&str>,
+    ) -> io::Result<()> {
+        let mut writer = writer_for_path(path)?;
+        to_writer_pretty(
+            &mut writer,
+            &geojson(samples, frame.unwrap_or(DEFAULT_FRAME)),
+        )?;
+        writer.flush()
+    }
+
+    /// Write `samples` as a CZML document for Cesium with time-tagged positions.
+    ///
+    /// `epoch_utc` is the ISO-8601 UTC time of the first sample; later samples are tagged with
+    /// their offset from it in seconds. Positions are converted to metres as CZML requires and
+    /// tagged `INERTIAL`, with the source `frame` kept under `properties`.
+    pub fn write_czml(
+        path: &Path,
+        samples: &[(f64, [f64; 3])],
+        epoch_utc: &str,
+        frame: Option<&str>,
+    ) -> io::Result<()> {
+        let mut writer = writer_for_path(path)?;
+        to_writer_pretty(
+            &mut writer,
+            &czml(samples, epoch_utc, frame.unwrap_or(DEFAULT_FRAME)),
+        )?;
+        writer.flush()
+    }
+
+    fn geojson(samples: &[(f64, [f64; 3])], frame: &str) -> Value {
+        let coordinates: Vec<[f64; 3]> = samples.iter().map(|(_, position)| *position).collect();
+        let et_seconds: Vec<f64> = samples.iter().map(|(et, _)| *et).collect();
+        json!({
+            "type": "Feature",
+            "geometry": {
+                "type": "LineString",
+                "coordinates": coordinates,
+            },
+            "properties": {
+                "frame": frame,
+                "units": "km",
+                "et_seconds": et_seconds,
+            },
+        })
+    }
+
+    fn czml(samples: &[(f64, [f64; 3])], epoch_utc: &str, frame: &str) -> Value {
+        let t0 = samples.first().map_or(0.0, |(et, _)| *et);
+        let span_s = samples.last().map_or(0.0, |(et, _)| *et - t0);
+        let cartesian: Vec<f64> = samples
+            .iter()
+            .flat_map(|(et, [x, y, z])| [et - t0, x * 1_000.0, y * 1_000.0, z * 1_000.0])
+            .collect();
+        json!([
+            {
+                "id": "document",
+                "name": "trajectory",
+                "version": "1.0",
+            },
+            {
+                "id": "trajectory",
+                "position": {
+                    "epoch": epoch_utc,
+                    "referenceFrame": "INERTIAL",
+                    "cartesian": cartesian,
+                },
+                "path": {
+                    "leadTime": 0,
+                    "trailTime": span_s,
+                },
+                "properties": {
+                    "frame": frame,
+                },
+            },
+        ])
+    }
+}
@@ -1,58 +1,531 @@
 //! SPICE kernel import utilities.
 
-use reqwest::blocking::Client;
-use solar_ephem_spice::kernels::{KernelDescriptor, LOCAL_SPICE_DIR};
-use std::fs::{self, File};
-use std::io::copy;
+use reqwest::StatusCode;
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{ETAG, IF_RANGE, LAST_MODIFIED, RANGE};
+use ring::digest::{Context, SHA256};
+use solar_config::PlanetConfig;
+use solar_ephem_spice::kernels::{KERNEL_CATALOG, KernelDescriptor, LOCAL_SPICE_DIR};
+use std::collections::BTreeSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write, copy};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
 use thiserror::Error;
 
+/// Bytes between progress reports.
+const PROGRESS_INTERVAL_BYTES: u64 = 256 * 1024;
+
 #[derive(Debug, Error)]
 pub enum ImportError {
     #[error("network error: {0}")]
     Network(#[from] reqwest::Error),
     #[error("filesystem error: {0}")]
     Io(#[from] std::io::Error),
+    #[error("checksum mismatch for {path}: expected sha256 {expected}, got {actual}")]
+    Checksum {
+        path: PathBuf,
+        expected: String,
+        actual: String,
+    },
+}
+
+/// How often a single kernel fetch is attempted before giving up.
+///
+/// Only transient failures are retried: connection errors and 5xx responses. The delay before
+/// retry `n` (1-based) is `base_delay · 2^(n-1)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Single attempt, no retries.
+    pub const fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            base_delay: Duration::ZERO,
+        }
+    }
+
+    fn delay_before(&self, retry: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Catalog descriptors for the union of `planets`' kernel dependencies, in catalog order.
+///
+/// Dependencies with no catalog entry are left out; list them with
+/// [`unknown_kernel_dependencies`] so callers can warn about them.
+pub fn required_kernels_for(planets: &[PlanetConfig]) -> Vec<KernelDescriptor> {
+    let required = kernel_dependencies(planets);
+    KERNEL_CATALOG
+        .iter()
+        .filter(|descriptor| required.contains(descriptor.filename))
+        .copied()
+        .collect()
+}
+
+/// Kernel dependencies of `planets` that have no entry in the kernel catalog, sorted.
+pub fn unknown_kernel_dependencies(planets: &[PlanetConfig]) -> Vec<String> {
+    kernel_dependencies(planets)
+        .into_iter()
+        .filter(|filename| {
+            !KERNEL_CATALOG
+                .iter()
+                .any(|descriptor| descriptor.filename == *filename)
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+fn kernel_dependencies(planets: &[PlanetConfig]) -> BTreeSet<&str> {
+    planets
+        .iter()
+        .flat_map(|planet| planet.kernel_dependencies.iter().map(String::as_str))
+        .collect()
 }
 
 /// Download all kernels listed in the provided descriptor set.
+///
+/// A checksum mismatch is reported per kernel and does not stop the remaining downloads.
 pub fn download_kernels(
     descriptors: &[KernelDescriptor],
 ) -> Result<Vec<KernelStatus>, ImportError> {
-    fs::create_dir_all(LOCAL_SPICE_DIR)?;
+    download_kernels_with_policy(descriptors, &RetryPolicy::none())
+}
+
+/// [`download_kernels`] with transient failures retried according to `policy`.
+pub fn download_kernels_with_policy(
+    descriptors: &[KernelDescriptor],
+    policy: &RetryPolicy,
+) -> Result<Vec<KernelStatus>, ImportError> {
+    download_kernels_with_policy_to(Path::new(LOCAL_SPICE_DIR), descriptors, policy)
+}
+
+/// Sequentially download missing kernels into `dir`, retrying according to `policy`.
+pub fn download_kernels_with_policy_to(
+    dir: &Path,
+    descriptors: &[KernelDescriptor],
+    policy: &RetryPolicy,
+) -> Result<Vec<KernelStatus>, ImportError> {
+    download_kernels_with_progress_to(dir, descriptors, policy, |_, _, _| {})
+}
+
+/// [`download_kernels`] reporting `(descriptor, bytes so far, total bytes)` while each kernel
+/// streams in. The total comes from `Content-Length` and is `None` when the server omits it.
+/// Reports arrive every 256 KiB and once more when a kernel finishes.
+pub fn download_kernels_with_progress(
+    descriptors: &[KernelDescriptor],
+    on_progress: impl FnMut(&KernelDescriptor, u64, Option<u64>),
+) -> Result<Vec<KernelStatus>, ImportError> {
+    download_kernels_with_progress_to(
+        Path::new(LOCAL_SPICE_DIR),
+        descriptors,
+        &RetryPolicy::none(),
+        on_progress,
+    )
+}
+
+/// Sequentially download missing kernels into `dir` with retries and progress reporting.
+pub fn download_kernels_with_progress_to(
+    dir: &Path,
+    descriptors: &[KernelDescriptor],
+    policy: &RetryPolicy,
+    mut on_progress: impl FnMut(&KernelDescriptor, u64, Option<u64>),
+) -> Result<Vec<KernelStatus>, ImportError> {
+    fs::create_dir_all(dir)?;
     let client = Client::builder().build()?;
     let mut statuses = Vec::new();
 
     for descriptor in descriptors {
-        let dest = descriptor.local_path();
+        let dest = dir.join(descriptor.filename);
         if dest.exists() {
             statuses.push(KernelStatus::AlreadyPresent(dest));
             continue;
         }
-        download_kernel(&client, descriptor, &dest)?;
-        statuses.push(KernelStatus::Downloaded(dest));
+        let mut report = |done, total| on_progress(descriptor, done, total);
+        statuses.push(fetch_status(
+            &client,
+            descriptor,
+            dest,
+            policy,
+            &mut report,
+        )?);
     }
 
     Ok(statuses)
 }
 
+/// Concurrent variant of [`download_kernels`] using up to `max_concurrency` worker threads.
+pub fn download_kernels_parallel(
+    descriptors: &[KernelDescriptor],
+    max_concurrency: usize,
+) -> Result<Vec<KernelStatus>, ImportError> {
+    download_kernels_parallel_to(Path::new(LOCAL_SPICE_DIR), descriptors, max_concurrency)
+}
+
+/// Download missing kernels into `dir` concurrently, returning statuses in descriptor order.
+///
+/// Kernels already on disk are skipped up front. After the first network or filesystem error no
+/// new downloads start; in-flight ones finish and the earliest error (by descriptor order) is
+/// returned.
+pub fn download_kernels_parallel_to(
+    dir: &Path,
+    descriptors: &[KernelDescriptor],
+    max_concurrency: usize,
+) -> Result<Vec<KernelStatus>, ImportError> {
+    fs::create_dir_all(dir)?;
+    let client = Client::builder().build()?;
+
+    let mut slots: Vec<Option<Result<KernelStatus, ImportError>>> = Vec::new();
+    let mut pending = Vec::new();
+    for (idx, descriptor) in descriptors.iter().enumerate() {
+        let dest = dir.join(descriptor.filename);
+        if dest.exists() {
+            slots.push(Some(Ok(KernelStatus::AlreadyPresent(dest))));
+        } else {
+            slots.push(None);
+            pending.push((idx, dest));
+        }
+    }
+
+    let slots = Mutex::new(slots);
+    let next = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+    let workers = max_concurrency.clamp(1, pending.len().max(1));
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                while !failed.load(Ordering::Relaxed) {
+                    let Some((idx, dest)) = pending.get(next.fetch_add(1, Ordering::Relaxed))
+                    else {
+                        break;
+                    };
+                    let result = fetch_status(
+                        &client,
+                        &descriptors[*idx],
+                        dest.clone(),
+                        &RetryPolicy::none(),
+                        &mut |_, _| {},
+                    );
+                    if result.is_err() {
+                        failed.store(true, Ordering::Relaxed);
+                    }
+                    slots.lock().expect("status lock")[*idx] = Some(result);
+                }
+            });
+        }
+    });
+
+    // Slots are only left empty after a failure, so collecting surfaces that error first.
+    slots
+        .into_inner()
+        .expect("status lock")
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Download a single kernel to `dest`, verifying its checksum when the descriptor carries one.
+pub fn download_kernel_to(descriptor: &KernelDescriptor, dest: &Path) -> Result<(), ImportError> {
+    download_kernel_resumable(descriptor, dest, false)
+}
+
+/// Download a single kernel to `dest`, optionally resuming an interrupted earlier attempt.
+///
+/// With `resume`, an existing `.partial` file is kept when a transfer fails and continued with
+/// a `Range` request next time, but only when the appended bytes can be trusted to belong to the
+/// same file: either the descriptor carries a SHA-256 that verifies the finished kernel, or the
+/// interrupted response's `ETag`/`Last-Modified` was recorded at [`validator_path`] and is sent
+/// back as `If-Range`. Without either, the `.partial` is deleted and the download starts over.
+/// Servers that answer `200` instead of `206` (including on a changed validator) get a clean
+/// restart.
+pub fn download_kernel_resumable(
+    descriptor: &KernelDescriptor,
+    dest: &Path,
+    resume: bool,
+) -> Result<(), ImportError> {
+    let client = Client::builder().build()?;
+    download_kernel(&client, descriptor, dest, resume, &mut |_, _| {})
+}
+
+/// Temporary download target for `dest`. The suffix is appended to the full file name so
+/// kernels sharing a stem (e.g. `codes_300ast_20100725.bsp` and `.tf`) never collide.
+pub fn partial_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".partial");
+    dest.with_file_name(name)
+}
+
+/// Side file holding the `ETag` (or `Last-Modified`) of the response that produced the
+/// `.partial` for `dest`, replayed as `If-Range` when the download resumes.
+pub fn validator_path(dest: &Path) -> PathBuf {
+    let mut name = partial_path(dest).into_os_string();
+    name.push(".validator");
+    PathBuf::from(name)
+}
+
+/// Download one kernel, turning a checksum mismatch into a status rather than an error.
+fn fetch_status(
+    client: &Client,
+    descriptor: &KernelDescriptor,
+    dest: PathBuf,
+    policy: &RetryPolicy,
+    on_progress: &mut dyn FnMut(u64, Option<u64>),
+) -> Result<KernelStatus, ImportError> {
+    match download_with_retry(client, descriptor, &dest, policy, on_progress) {
+        Ok(attempts) => Ok(KernelStatus::Downloaded {
+            path: dest,
+            attempts,
+        }),
+        Err(ImportError::Checksum {
+            path,
+            expected,
+            actual,
+        }) => Ok(KernelStatus::ChecksumMismatch {
+            path,
+            expected,
+            actual,
+        }),
+        Err(err) => Err(err),
+    }
+}
+
+/// Returns the number of attempts the successful download took.
+fn download_with_retry(
+    client: &Client,
+    descriptor: &KernelDescriptor,
+    dest: &Path,
+    policy: &RetryPolicy,
+    on_progress: &mut dyn FnMut(u64, Option<u64>),
+) -> Result<u32, ImportError> {
+    let mut attempt = 1;
+    loop {
+        match download_kernel(client, descriptor, dest, true, on_progress) {
+            Ok(()) => return Ok(attempt),
+            Err(err) if attempt < policy.max_attempts && is_transient(&err) => {
+                thread::sleep(policy.delay_before(attempt));
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Connection-level failures and server errors may clear up; client errors such as 404 won't.
+fn is_transient(err: &ImportError) -> bool {
+    match err {
+        ImportError::Network(err) => err.status().is_none_or(|status| status.is_server_error()),
+        _ => false,
+    }
+}
+
 fn download_kernel(
     client: &Client,
     descriptor: &KernelDescriptor,
     dest: &Path,
+    resume: bool,
+    on_progress: &mut dyn FnMut(u64, Option<u64>),
 ) -> Result<(), ImportError> {
     if let Some(parent) = dest.parent() {
         fs::create_dir_all(parent)?;
     }
-    let mut response = client.get(descriptor.url).send()?.error_for_status()?;
-    let mut file = File::create(dest)?;
-    copy(&mut response, &mut file)?;
+    // Stream into a side file so an interrupted or rejected download never looks complete.
+    let partial = partial_path(dest);
+    let validator = validator_path(dest);
+    let result = fetch_verified(
+        client,
+        descriptor,
+        dest,
+        &partial,
+        &validator,
+        resume,
+        on_progress,
+    );
+    // Bytes that failed verification are useless; an interrupted transfer is worth keeping.
+    if matches!(result, Err(ImportError::Checksum { .. })) || (result.is_err() && !resume) {
+        let _ = fs::remove_file(&partial);
+    }
+    // A validator only means something next to the partial file it describes.
+    if !partial.exists() {
+        let _ = fs::remove_file(&validator);
+    }
+    result
+}
+
+fn fetch_verified(
+    client: &Client,
+    descriptor: &KernelDescriptor,
+    dest: &Path,
+    partial: &Path,
+    validator_file: &Path,
+    resume: bool,
+    on_progress: &mut dyn FnMut(u64, Option<u64>),
+) -> Result<(), ImportError> {
+    let validator = resume
+        .then(|| fs::read_to_string(validator_file).ok())
+        .flatten()
+        .filter(|value| !value.trim().is_empty());
+    // Appending to bytes nothing can vouch for risks splicing two different files together.
+    let resumable = descriptor.sha256.is_some() || validator.is_some();
+    let offset = if resume && resumable {
+        fs::metadata(partial).map_or(0, |meta| meta.len())
+    } else {
+        if partial.exists() {
+            fs::remove_file(partial)?;
+        }
+        0
+    };
+    let mut request = client.get(descriptor.url);
+    if offset > 0 {
+        request = request.header(RANGE, format!("bytes={offset}-"));
+        if let Some(validator) = &validator {
+            request = request.header(IF_RANGE, validator.trim());
+        }
+    }
+    let response = request.send()?;
+    if offset > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        // The partial file is already as long as (or longer than) the kernel; start over.
+        fs::remove_file(partial)?;
+        return fetch_verified(
+            client,
+            descriptor,
+            dest,
+            partial,
+            validator_file,
+            false,
+            on_progress,
+        );
+    }
+    let mut response = response.error_for_status()?;
+
+    let mut digest = Context::new(&SHA256);
+    let resumed = offset > 0 && response.status() == StatusCode::PARTIAL_CONTENT;
+    let (file, written) = if resumed {
+        // The checksum covers the whole kernel, so fold in the bytes already on disk.
+        hash_file(partial, &mut digest)?;
+        (OpenOptions::new().append(true).open(partial)?, offset)
+    } else {
+        // Fresh download, or the server ignored the range and is sending everything.
+        record_validator(&response, validator_file)?;
+        (File::create(partial)?, 0)
+    };
+    let mut writer = HashingWriter {
+        file,
+        digest,
+        written,
+        reported: written,
+        total: response.content_length().map(|len| written + len),
+        on_progress,
+    };
+    copy(&mut response, &mut writer)?;
+    (writer.on_progress)(writer.written, writer.total);
+    writer.file.sync_all()?;
+
+    if let Some(expected) = descriptor.sha256 {
+        let actual: String = writer
+            .digest
+            .finish()
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(ImportError::Checksum {
+                path: dest.to_path_buf(),
+                expected: expected.to_string(),
+                actual,
+            });
+        }
+    }
+    fs::rename(partial, dest)?;
     Ok(())
 }
 
+/// Remember the response's strong `ETag`, else its `Last-Modified`, so an interrupted transfer
+/// can resume with `If-Range`; without either, any stale validator is dropped.
+fn record_validator(response: &Response, validator_file: &Path) -> io::Result<()> {
+    let headers = response.headers();
+    let value = headers
+        .get(ETAG)
+        .filter(|etag| !etag.as_bytes().starts_with(b"W/"))
+        .or_else(|| headers.get(LAST_MODIFIED))
+        .and_then(|value| value.to_str().ok());
+    match value {
+        Some(value) => fs::write(validator_file, value),
+        None => match fs::remove_file(validator_file) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        },
+    }
+}
+
+fn hash_file(path: &Path, digest: &mut Context) -> io::Result<()> {
+    let mut file = File::open(path)?;
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buf)?;
+        if read == 0 {
+            return Ok(());
+        }
+        digest.update(&buf[..read]);
+    }
+}
+
+struct HashingWriter<'a> {
+    file: File,
+    digest: Context,
+    written: u64,
+    reported: u64,
+    total: Option<u64>,
+    on_progress: &'a mut dyn FnMut(u64, Option<u64>),
+}
+
+impl Write for HashingWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.file.write(buf)?;
+        self.digest.update(&buf[..written]);
+        self.written += written as u64;
+        if self.written - self.reported >= PROGRESS_INTERVAL_BYTES {
+            self.reported = self.written;
+            (self.on_progress)(self.written, self.total);
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
 /// Outcome of attempting to download a kernel.
 #[derive(Debug)]
 pub enum KernelStatus {
-    Downloaded(PathBuf),
+    /// Fetched successfully; `attempts` is 1 unless transient failures were retried.
+    Downloaded {
+        path: PathBuf,
+        attempts: u32,
+    },
     AlreadyPresent(PathBuf),
+    /// The download completed but did not match the catalog digest; nothing was kept on disk.
+    ChecksumMismatch {
+        path: PathBuf,
+        expected: String,
+        actual: String,
+    },
 }
@@ -1,12 +1,63 @@
-use lambert_bate::get_velocities;
+use std::f64::consts::{PI, TAU};
+
+use solar_orbits::stumpff;
 use thiserror::Error;
 
+/// Relative time-of-flight residual `t(ψ)/tof − 1` accepted by [`solve`].
+pub const DEFAULT_TOLERANCE: f64 = 1e-8;
+/// Iteration cap used by [`solve`] and [`solve_multirev`].
+pub const DEFAULT_MAX_ITERATIONS: u32 = 500;
+
+/// Golden-section steps when locating a revolution count's minimum-time point.
+const MINIMUM_SEARCH_ITERATIONS: usize = 200;
+/// How many times the hyperbolic end of the zero-revolution bracket may be doubled.
+const MAX_BRACKET_EXPANSIONS: u32 = 12;
+
 #[derive(Debug, Error)]
 pub enum LambertSolverError {
-    #[error("lambert solver failed: {0}")]
-    Failure(String),
+    /// The inputs do not define a transfer (zero radius, collinear endpoints, non-positive time).
+    #[error("degenerate Lambert geometry: {0}")]
+    Degenerate(String),
+    /// The iteration cap was reached before the time of flight matched to tolerance.
+    #[error("lambert solver hit the iteration cap (relative residual {achieved_residual:.3e})")]
+    MaxIterations { achieved_residual: f64 },
+    /// The iteration produced NaN or could not bracket the solution.
+    #[error("lambert solver did not converge: {0}")]
+    NonConvergent(String),
+    /// The time of flight is too short for the requested number of revolutions.
+    #[error("time of flight is below the {revolutions}-revolution minimum of {minimum_tof_s:.0} s")]
+    BelowMinimumTime {
+        revolutions: u32,
+        minimum_tof_s: f64,
+    },
+}
+
+/// Which solution of Lambert's problem a velocity pair belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Branch {
+    /// Zero-revolution transfer through less than 180°.
+    Short,
+    /// Zero-revolution transfer through more than 180°.
+    Long,
+    /// Multi-revolution solution with ψ below the minimum-time point.
+    Left,
+    /// Multi-revolution solution with ψ above the minimum-time point.
+    Right,
+}
+
+/// A converged Lambert solution and the iterations it took.
+#[derive(Debug, Clone, Copy)]
+pub struct LambertSolution {
+    pub revolutions: u32,
+    pub branch: Branch,
+    pub v1_km_s: [f64; 3],
+    pub v2_km_s: [f64; 3],
+    pub iterations: u32,
 }
 
+/// Zero-revolution Lambert velocities with the default tolerance.
+///
+/// Thin wrapper over [`solve_with_diagnostics`] for callers that only need the velocities.
 pub fn solve(
     r1_km: [f64; 3],
     r2_km: [f64; 3],
@@ -14,6 +65,358 @@ pub fn solve(
     mu_km3_s2: f64,
     short: bool,
 ) -> Result<([f64; 3], [f64; 3]), LambertSolverError> {
-    get_velocities(r1_km, r2_km, time_of_flight_s, mu_km3_s2, short, 1e-8, 500)
-        .map_err(|e| LambertSolverError::Failure(format!("{e:?}")))
+    solve_with_diagnostics(
+        r1_km,
+        r2_km,
+        time_of_flight_s,
+        mu_km3_s2,
+        short,
+        DEFAULT_TOLERANCE,
+        DEFAULT_MAX_ITERATIONS,
+    )
+    .map(|solution| (solution.v1_km_s, solution.v2_km_s))
+}
+
+/// Zero-revolution Lambert solve reporting the iteration count, with a caller-chosen relative
+/// time-of-flight tolerance and iteration cap.
+pub fn solve_with_diagnostics(
+    r1_km: [f64; 3],
+    r2_km: [f64; 3],
+    time_of_flight_s: f64,
+    mu_km3_s2: f64,
+    short: bool,
+    tolerance: f64,
+    max_iterations: u32,
+) -> Result<LambertSolution, LambertSolverError> {
+    check_time_of_flight(time_of_flight_s)?;
+    let geometry = Geometry::with_direction(r1_km, r2_km, mu_km3_s2, short)?;
+
+    // t(ψ) rises monotonically up to the one-revolution singularity at ψ = (2π)²; widen the
+    // hyperbolic side until it is fast enough.
+    let upper = TAU * TAU;
+    let mut lower = -upper;
+    let mut expansions = 0;
+    while geometry.time_of_flight(lower) > time_of_flight_s {
+        expansions += 1;
+        if expansions > MAX_BRACKET_EXPANSIONS {
+            return Err(LambertSolverError::NonConvergent(format!(
+                "no hyperbolic bracket for a {time_of_flight_s:.0} s transfer"
+            )));
+        }
+        lower *= 2.0;
+    }
+
+    let (psi, iterations) = bisect(
+        &geometry,
+        time_of_flight_s,
+        (lower, upper),
+        true,
+        tolerance,
+        max_iterations,
+    )?;
+    let (v1_km_s, v2_km_s) = geometry.velocities(psi);
+    Ok(LambertSolution {
+        revolutions: 0,
+        branch: if short { Branch::Short } else { Branch::Long },
+        v1_km_s,
+        v2_km_s,
+        iterations,
+    })
+}
+
+/// Solve Lambert's problem for a prograde transfer completing `revolutions` full orbits.
+///
+/// Motion is assumed prograde about +z, which fixes the transfer angle. For `revolutions >= 1`
+/// there are two solutions when the time of flight exceeds that revolution count's minimum;
+/// `left_branch` picks between them. With zero revolutions this is [`solve`] along the prograde
+/// direction and `left_branch` is ignored.
+pub fn solve_multirev(
+    r1_km: [f64; 3],
+    r2_km: [f64; 3],
+    time_of_flight_s: f64,
+    mu_km3_s2: f64,
+    revolutions: u32,
+    left_branch: bool,
+) -> Result<([f64; 3], [f64; 3]), LambertSolverError> {
+    multirev_solution(
+        r1_km,
+        r2_km,
+        time_of_flight_s,
+        mu_km3_s2,
+        revolutions,
+        left_branch,
+    )
+    .map(|solution| (solution.v1_km_s, solution.v2_km_s))
+}
+
+/// Every solution up to `max_revolutions`: the zero-revolution short and long paths, then the
+/// left and right branches of each revolution count the time of flight allows.
+pub fn solve_all_branches(
+    r1_km: [f64; 3],
+    r2_km: [f64; 3],
+    time_of_flight_s: f64,
+    mu_km3_s2: f64,
+    max_revolutions: u32,
+) -> Vec<LambertSolution> {
+    let zero_rev = [true, false].into_iter().filter_map(|short| {
+        solve_with_diagnostics(
+            r1_km,
+            r2_km,
+            time_of_flight_s,
+            mu_km3_s2,
+            short,
+            DEFAULT_TOLERANCE,
+            DEFAULT_MAX_ITERATIONS,
+        )
+        .ok()
+    });
+    let multi_rev = (1..=max_revolutions).flat_map(|revolutions| {
+        [true, false].into_iter().filter_map(move |left| {
+            multirev_solution(r1_km, r2_km, time_of_flight_s, mu_km3_s2, revolutions, left).ok()
+        })
+    });
+    zero_rev.chain(multi_rev).collect()
+}
+
+fn multirev_solution(
+    r1_km: [f64; 3],
+    r2_km: [f64; 3],
+    time_of_flight_s: f64,
+    mu_km3_s2: f64,
+    revolutions: u32,
+    left_branch: bool,
+) -> Result<LambertSolution, LambertSolverError> {
+    let geometry = Geometry::prograde(r1_km, r2_km, mu_km3_s2)?;
+    if revolutions == 0 {
+        return solve_with_diagnostics(
+            r1_km,
+            r2_km,
+            time_of_flight_s,
+            mu_km3_s2,
+            geometry.short,
+            DEFAULT_TOLERANCE,
+            DEFAULT_MAX_ITERATIONS,
+        );
+    }
+    check_time_of_flight(time_of_flight_s)?;
+
+    // For N revolutions ψ lies in ((2πN)², (2π(N+1))²); the time of flight grows without bound
+    // at both ends and has a single minimum in between.
+    let n = f64::from(revolutions);
+    let (lower, upper) = ((TAU * n).powi(2), (TAU * (n + 1.0)).powi(2));
+    let psi_min = minimum_time_psi(&geometry, lower, upper);
+    let minimum_tof_s = geometry.time_of_flight(psi_min);
+    if time_of_flight_s < minimum_tof_s {
+        return Err(LambertSolverError::BelowMinimumTime {
+            revolutions,
+            minimum_tof_s,
+        });
+    }
+
+    // Time falls towards the minimum on the left branch and rises after it on the right.
+    let bracket = if left_branch {
+        (lower, psi_min)
+    } else {
+        (psi_min, upper)
+    };
+    let (psi, iterations) = bisect(
+        &geometry,
+        time_of_flight_s,
+        bracket,
+        !left_branch,
+        DEFAULT_TOLERANCE,
+        DEFAULT_MAX_ITERATIONS,
+    )?;
+    let (v1_km_s, v2_km_s) = geometry.velocities(psi);
+    Ok(LambertSolution {
+        revolutions,
+        branch: if left_branch {
+            Branch::Left
+        } else {
+            Branch::Right
+        },
+        v1_km_s,
+        v2_km_s,
+        iterations,
+    })
+}
+
+fn check_time_of_flight(time_of_flight_s: f64) -> Result<(), LambertSolverError> {
+    if time_of_flight_s > 0.0 {
+        Ok(())
+    } else {
+        Err(LambertSolverError::Degenerate(format!(
+            "time of flight must be positive (got {time_of_flight_s} s)"
+        )))
+    }
+}
+
+/// Bisect ψ within `bracket` until `t(ψ)` matches `time_of_flight_s` to `tolerance`.
+///
+/// `rising` says whether the time of flight increases with ψ across the bracket. Returns the
+/// converged ψ and the number of iterations used.
+fn bisect(
+    geometry: &Geometry,
+    time_of_flight_s: f64,
+    (mut lo, mut hi): (f64, f64),
+    rising: bool,
+    tolerance: f64,
+    max_iterations: u32,
+) -> Result<(f64, u32), LambertSolverError> {
+    let mut residual = f64::INFINITY;
+    for iteration in 1..=max_iterations {
+        let psi = 0.5 * (lo + hi);
+        let tof = geometry.time_of_flight(psi);
+        if tof.is_nan() {
+            return Err(LambertSolverError::NonConvergent(format!(
+                "time of flight evaluated to NaN at ψ = {psi}"
+            )));
+        }
+        residual = tof / time_of_flight_s - 1.0;
+        if residual.abs() <= tolerance {
+            return Ok((psi, iteration));
+        }
+        if (residual > 0.0) == rising {
+            hi = psi;
+        } else {
+            lo = psi;
+        }
+    }
+    Err(LambertSolverError::MaxIterations {
+        achieved_residual: residual,
+    })
+}
+
+/// Universal-variable transfer geometry (Bate, Mueller & White §5.3).
+struct Geometry {
+    r1: [f64; 3],
+    r2: [f64; 3],
+    r1_mag: f64,
+    r2_mag: f64,
+    a: f64,
+    mu: f64,
+    short: bool,
+}
+
+impl Geometry {
+    /// Geometry for an explicitly chosen short (< 180°) or long way round.
+    fn with_direction(
+        r1: [f64; 3],
+        r2: [f64; 3],
+        mu: f64,
+        short: bool,
+    ) -> Result<Self, LambertSolverError> {
+        let (r1_mag, r2_mag) = Self::check_inputs(&r1, &r2, mu)?;
+        let a_mag = (r1_mag * r2_mag + dot(&r1, &r2)).max(0.0).sqrt();
+        if a_mag <= 1e-10 * (r1_mag * r2_mag).sqrt() {
+            return Err(LambertSolverError::Degenerate(
+                "endpoints are 180° apart, so the transfer plane is undefined".to_string(),
+            ));
+        }
+        Ok(Self {
+            r1,
+            r2,
+            r1_mag,
+            r2_mag,
+            a: if short { a_mag } else { -a_mag },
+            mu,
+            short,
+        })
+    }
+
+    /// Geometry for prograde motion about +z, which decides short versus long.
+    fn prograde(r1: [f64; 3], r2: [f64; 3], mu: f64) -> Result<Self, LambertSolverError> {
+        let (r1_mag, r2_mag) = Self::check_inputs(&r1, &r2, mu)?;
+        let cos_dnu = (dot(&r1, &r2) / (r1_mag * r2_mag)).clamp(-1.0, 1.0);
+        let cross_z = r1[0] * r2[1] - r1[1] * r2[0];
+        let dnu = if cross_z >= 0.0 {
+            cos_dnu.acos()
+        } else {
+            TAU - cos_dnu.acos()
+        };
+        if dnu.sin().abs() < 1e-10 {
+            return Err(LambertSolverError::Degenerate(
+                "transfer plane is undefined for collinear positions".to_string(),
+            ));
+        }
+        Self::with_direction(r1, r2, mu, dnu < PI)
+    }
+
+    fn check_inputs(
+        r1: &[f64; 3],
+        r2: &[f64; 3],
+        mu: f64,
+    ) -> Result<(f64, f64), LambertSolverError> {
+        let (r1_mag, r2_mag) = (norm(r1), norm(r2));
+        if !(r1_mag > 0.0 && r2_mag > 0.0 && mu > 0.0) {
+            return Err(LambertSolverError::Degenerate(format!(
+                "radii and μ must be positive (|r1| = {r1_mag}, |r2| = {r2_mag}, μ = {mu})"
+            )));
+        }
+        Ok((r1_mag, r2_mag))
+    }
+
+    fn y(&self, psi: f64) -> f64 {
+        let (c2, c3) = stumpff(psi);
+        self.r1_mag + self.r2_mag + self.a * (psi * c3 - 1.0) / c2.sqrt()
+    }
+
+    /// Time of flight at ψ. Where `y(ψ)` is negative no conic connects the endpoints; that only
+    /// happens on the fast side of a zero-revolution short-way transfer, so it reads as zero.
+    fn time_of_flight(&self, psi: f64) -> f64 {
+        let (c2, c3) = stumpff(psi);
+        if c2 <= 0.0 {
+            return f64::INFINITY;
+        }
+        let y = self.y(psi);
+        if y < 0.0 {
+            return 0.0;
+        }
+        let chi = (y / c2).sqrt();
+        (chi.powi(3) * c3 + self.a * y.sqrt()) / self.mu.sqrt()
+    }
+
+    fn velocities(&self, psi: f64) -> ([f64; 3], [f64; 3]) {
+        let y = self.y(psi).max(0.0);
+        let f = 1.0 - y / self.r1_mag;
+        let g = self.a * (y / self.mu).sqrt();
+        let g_dot = 1.0 - y / self.r2_mag;
+        let v1 = std::array::from_fn(|i| (self.r2[i] - f * self.r1[i]) / g);
+        let v2 = std::array::from_fn(|i| (g_dot * self.r2[i] - self.r1[i]) / g);
+        (v1, v2)
+    }
+}
+
+/// Golden-section search for the ψ of least time of flight within one revolution interval.
+fn minimum_time_psi(geometry: &Geometry, lower: f64, upper: f64) -> f64 {
+    let ratio = 0.5 * (5f64.sqrt() - 1.0);
+    let (mut lo, mut hi) = (lower, upper);
+    let mut x1 = hi - ratio * (hi - lo);
+    let mut x2 = lo + ratio * (hi - lo);
+    let mut t1 = geometry.time_of_flight(x1);
+    let mut t2 = geometry.time_of_flight(x2);
+    for _ in 0..MINIMUM_SEARCH_ITERATIONS {
+        if t1 < t2 {
+            hi = x2;
+            x2 = x1;
+            t2 = t1;
+            x1 = hi - ratio * (hi - lo);
+            t1 = geometry.time_of_flight(x1);
+        } else {
+            lo = x1;
+            x1 = x2;
+            t1 = t2;
+            x2 = lo + ratio * (hi - lo);
+            t2 = geometry.time_of_flight(x2);
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+fn dot(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn norm(v: &[f64; 3]) -> f64 {
+    dot(v, v).sqrt()
 }
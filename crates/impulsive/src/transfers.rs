@@ -3,6 +3,32 @@
 //! Provides Hohmann and bi-elliptic transfer calculators that return delta-v components
 //! and time of flight for two-body Keplerian motion with a specified central GM.
 
+use solar_core::angles::wrap_to_pi;
+use thiserror::Error;
+
+/// Input validation failures for the checked transfer calculators.
+#[derive(Debug, Clone, Copy, PartialEq, Error)]
+pub enum TransferError {
+    #[error("transfer inputs must be positive and finite (got {name} = {value})")]
+    NonPositiveInput { name: &'static str, value: f64 },
+    #[error(
+        "bi-elliptic apoapsis bound {max_rb_km} km lies inside the larger orbit ({min_rb_km} km)"
+    )]
+    ApoapsisBoundTooLow { max_rb_km: f64, min_rb_km: f64 },
+    #[error("plane-change split fraction must lie in [0, 1] (got {0})")]
+    SplitOutOfRange(f64),
+    #[error("inclination change must be finite (got {0})")]
+    NonFiniteInclination(f64),
+}
+
+fn require_positive(name: &'static str, value: f64) -> Result<(), TransferError> {
+    if value.is_finite() && value > 0.0 {
+        Ok(())
+    } else {
+        Err(TransferError::NonPositiveInput { name, value })
+    }
+}
+
 /// Result for a Hohmann transfer between circular, coplanar orbits of radii r1 and r2.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct HohmannResult {
@@ -18,9 +44,26 @@ pub struct HohmannResult {
 /// - `r1_km`: initial circular orbit radius (km)
 /// - `r2_km`: target circular orbit radius (km)
 /// - `mu_km3_s2`: gravitational parameter of central body (km^3/s^2)
+///
+/// Panics on non-positive inputs; use [`hohmann_checked`] for user-supplied values.
 pub fn hohmann(r1_km: f64, r2_km: f64, mu_km3_s2: f64) -> HohmannResult {
     assert!(r1_km > 0.0 && r2_km > 0.0 && mu_km3_s2 > 0.0);
+    hohmann_unchecked(r1_km, r2_km, mu_km3_s2)
+}
 
+/// Non-panicking [`hohmann`] that rejects non-positive or non-finite inputs.
+pub fn hohmann_checked(
+    r1_km: f64,
+    r2_km: f64,
+    mu_km3_s2: f64,
+) -> Result<HohmannResult, TransferError> {
+    require_positive("r1_km", r1_km)?;
+    require_positive("r2_km", r2_km)?;
+    require_positive("mu_km3_s2", mu_km3_s2)?;
+    Ok(hohmann_unchecked(r1_km, r2_km, mu_km3_s2))
+}
+
+fn hohmann_unchecked(r1_km: f64, r2_km: f64, mu_km3_s2: f64) -> HohmannResult {
     let v1 = (mu_km3_s2 / r1_km).sqrt();
     let v2 = (mu_km3_s2 / r2_km).sqrt();
     let a_t = 0.5 * (r1_km + r2_km);
@@ -56,12 +99,31 @@ pub struct BiEllipticResult {
 
 /// Compute a bi-elliptic transfer using a specified intermediate apoapsis radius `rb_km`.
 ///
-/// This function does not optimize `rb_km`; it evaluates the three impulsive burns and TOF
+/// This function does not optimize `rb_km` (see [`bi_elliptic_optimal`]); it evaluates the three impulsive burns and TOF
 /// for the two transfer ellipses: (r1 -> rb) and (rb -> r2). Users may sweep `rb_km` to study
 /// trade-offs; for very large r2/r1 ratios, bi-elliptic can beat Hohmann beyond ~11.94.
+///
+/// Panics on non-positive inputs; use [`bi_elliptic_checked`] for user-supplied values.
 pub fn bi_elliptic(r1_km: f64, r2_km: f64, rb_km: f64, mu_km3_s2: f64) -> BiEllipticResult {
     assert!(r1_km > 0.0 && r2_km > 0.0 && rb_km > 0.0 && mu_km3_s2 > 0.0);
+    bi_elliptic_unchecked(r1_km, r2_km, rb_km, mu_km3_s2)
+}
 
+/// Non-panicking [`bi_elliptic`] that rejects non-positive or non-finite inputs.
+pub fn bi_elliptic_checked(
+    r1_km: f64,
+    r2_km: f64,
+    rb_km: f64,
+    mu_km3_s2: f64,
+) -> Result<BiEllipticResult, TransferError> {
+    require_positive("r1_km", r1_km)?;
+    require_positive("r2_km", r2_km)?;
+    require_positive("rb_km", rb_km)?;
+    require_positive("mu_km3_s2", mu_km3_s2)?;
+    Ok(bi_elliptic_unchecked(r1_km, r2_km, rb_km, mu_km3_s2))
+}
+
+fn bi_elliptic_unchecked(r1_km: f64, r2_km: f64, rb_km: f64, mu_km3_s2: f64) -> BiEllipticResult {
     let v1 = (mu_km3_s2 / r1_km).sqrt();
     let v2 = (mu_km3_s2 / r2_km).sqrt();
 
@@ -93,3 +155,236 @@ pub fn bi_elliptic(r1_km: f64, r2_km: f64, rb_km: f64, mu_km3_s2: f64) -> BiElli
         tof_seconds: tof,
     }
 }
+
+/// Delta-v magnitude (km/s) of a single burn that takes the speed from `v_before_km_s` to
+/// `v_after_km_s` while rotating the orbit plane by `inclination_change_rad`.
+///
+/// Law of cosines on the two velocity vectors; reduces to `|v_after - v_before|` for a
+/// coplanar burn and to `2 v sin(Δi/2)` for a pure plane change.
+pub fn combined_plane_change(
+    v_before_km_s: f64,
+    v_after_km_s: f64,
+    inclination_change_rad: f64,
+) -> f64 {
+    let squared = v_before_km_s * v_before_km_s + v_after_km_s * v_after_km_s
+        - 2.0 * v_before_km_s * v_after_km_s * inclination_change_rad.cos();
+    squared.max(0.0).sqrt()
+}
+
+/// Golden-section steps used to locate the cheapest plane-change split.
+const PLANE_CHANGE_SPLIT_STEPS: usize = 80;
+
+/// Hohmann transfer with an inclination change shared between its two burns.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HohmannPlaneChangeResult {
+    /// Departure burn magnitude, including `split_fraction` of the plane change.
+    pub dv1_km_s: f64,
+    /// Arrival burn magnitude, including the remaining plane change.
+    pub dv2_km_s: f64,
+    pub dv_total_km_s: f64,
+    pub tof_seconds: f64,
+    /// Fraction of the inclination change applied at the departure burn.
+    pub split_fraction: f64,
+    /// Split fraction that minimises `dv_total_km_s` for the same orbits.
+    pub optimal_split_fraction: f64,
+    pub optimal_dv_total_km_s: f64,
+}
+
+/// Hohmann transfer between circular orbits whose planes differ by `delta_i_rad`, applying
+/// `split_fraction` of the plane change at departure and the rest at arrival.
+///
+/// The plane change is cheapest where the spacecraft is slowest, so the optimal split usually
+/// puts most of it at the outer burn; it is reported alongside the requested split.
+pub fn hohmann_with_plane_change(
+    r1_km: f64,
+    r2_km: f64,
+    mu_km3_s2: f64,
+    delta_i_rad: f64,
+    split_fraction: f64,
+) -> Result<HohmannPlaneChangeResult, TransferError> {
+    require_positive("r1_km", r1_km)?;
+    require_positive("r2_km", r2_km)?;
+    require_positive("mu_km3_s2", mu_km3_s2)?;
+    if !delta_i_rad.is_finite() {
+        return Err(TransferError::NonFiniteInclination(delta_i_rad));
+    }
+    if !(0.0..=1.0).contains(&split_fraction) {
+        return Err(TransferError::SplitOutOfRange(split_fraction));
+    }
+
+    let v1 = (mu_km3_s2 / r1_km).sqrt();
+    let v2 = (mu_km3_s2 / r2_km).sqrt();
+    let a_t = 0.5 * (r1_km + r2_km);
+    let v_t1 = (mu_km3_s2 * (2.0 / r1_km - 1.0 / a_t)).sqrt();
+    let v_t2 = (mu_km3_s2 * (2.0 / r2_km - 1.0 / a_t)).sqrt();
+    let burns = |fraction: f64| {
+        (
+            combined_plane_change(v1, v_t1, fraction * delta_i_rad),
+            combined_plane_change(v_t2, v2, (1.0 - fraction) * delta_i_rad),
+        )
+    };
+    let total = |fraction: f64| {
+        let (dv1, dv2) = burns(fraction);
+        dv1 + dv2
+    };
+
+    // Each burn is convex in its share of the plane change, so the sum is unimodal in the split.
+    let inv_phi = 0.5 * (5f64.sqrt() - 1.0);
+    let (mut a, mut b) = (0.0, 1.0);
+    let mut c = b - inv_phi * (b - a);
+    let mut d = a + inv_phi * (b - a);
+    let (mut fc, mut fd) = (total(c), total(d));
+    for _ in 0..PLANE_CHANGE_SPLIT_STEPS {
+        if fc < fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = b - inv_phi * (b - a);
+            fc = total(c);
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = a + inv_phi * (b - a);
+            fd = total(d);
+        }
+    }
+    let optimal_split_fraction = [0.0, 0.5 * (a + b), 1.0]
+        .into_iter()
+        .min_by(|x, y| total(*x).total_cmp(&total(*y)))
+        .unwrap_or(0.5 * (a + b));
+
+    let (dv1, dv2) = burns(split_fraction);
+    Ok(HohmannPlaneChangeResult {
+        dv1_km_s: dv1,
+        dv2_km_s: dv2,
+        dv_total_km_s: dv1 + dv2,
+        tof_seconds: std::f64::consts::PI * (a_t.powi(3) / mu_km3_s2).sqrt(),
+        split_fraction,
+        optimal_split_fraction,
+        optimal_dv_total_km_s: total(optimal_split_fraction),
+    })
+}
+
+/// Log-spaced samples of the intermediate apoapsis before golden-section refinement.
+const BI_ELLIPTIC_SWEEP_SAMPLES: usize = 64;
+/// Golden-section steps refining the best sampled bracket.
+const BI_ELLIPTIC_REFINE_STEPS: usize = 80;
+
+/// Best bi-elliptic transfer found by [`bi_elliptic_optimal`], alongside the Hohmann baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BiEllipticOptimum {
+    pub transfer: BiEllipticResult,
+    pub hohmann: HohmannResult,
+    /// Whether the bi-elliptic total strictly undercuts Hohmann. Expect `false` for radius
+    /// ratios below ~11.94, where the optimum collapses onto the Hohmann ellipse (`rb = r2`),
+    /// and `true` above ~15.58; in between it depends on how large `max_rb_km` is allowed.
+    pub beats_hohmann: bool,
+}
+
+/// Choose the intermediate apoapsis in `[max(r1, r2), max_rb_km]` that minimises total
+/// delta-v, and compare the result with the Hohmann transfer between the same orbits.
+///
+/// The delta-v curve is not unimodal in `rb` for ratios between ~11.94 and ~15.58 (it rises
+/// above Hohmann before falling below it), so the interval is swept on a log-spaced grid and
+/// only the best bracket is refined by golden section.
+pub fn bi_elliptic_optimal(
+    r1_km: f64,
+    r2_km: f64,
+    mu_km3_s2: f64,
+    max_rb_km: f64,
+) -> Result<BiEllipticOptimum, TransferError> {
+    require_positive("r1_km", r1_km)?;
+    require_positive("r2_km", r2_km)?;
+    require_positive("mu_km3_s2", mu_km3_s2)?;
+    require_positive("max_rb_km", max_rb_km)?;
+    let min_rb_km = r1_km.max(r2_km);
+    if max_rb_km < min_rb_km {
+        return Err(TransferError::ApoapsisBoundTooLow {
+            max_rb_km,
+            min_rb_km,
+        });
+    }
+
+    let cost =
+        |ln_rb: f64| bi_elliptic_unchecked(r1_km, r2_km, ln_rb.exp(), mu_km3_s2).dv_total_km_s;
+    let (lo, hi) = (min_rb_km.ln(), max_rb_km.ln());
+    let step = (hi - lo) / (BI_ELLIPTIC_SWEEP_SAMPLES - 1) as f64;
+    let best = (0..BI_ELLIPTIC_SWEEP_SAMPLES)
+        .map(|i| (i, cost(lo + i as f64 * step)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+
+    let mut a = lo + best.saturating_sub(1) as f64 * step;
+    let mut b = (lo + (best + 1) as f64 * step).min(hi);
+    let inv_phi = 0.5 * (5f64.sqrt() - 1.0);
+    let mut c = b - inv_phi * (b - a);
+    let mut d = a + inv_phi * (b - a);
+    let (mut fc, mut fd) = (cost(c), cost(d));
+    for _ in 0..BI_ELLIPTIC_REFINE_STEPS {
+        if fc < fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = b - inv_phi * (b - a);
+            fc = cost(c);
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = a + inv_phi * (b - a);
+            fd = cost(d);
+        }
+    }
+
+    // Keep the sampled point if refinement drifted onto a worse value (e.g. at the bounds).
+    let sampled = lo + best as f64 * step;
+    let refined = 0.5 * (a + b);
+    let ln_rb = if cost(refined) < cost(sampled) {
+        refined
+    } else {
+        sampled
+    };
+    let transfer = bi_elliptic_unchecked(r1_km, r2_km, ln_rb.exp(), mu_km3_s2);
+    let hohmann = hohmann_unchecked(r1_km, r2_km, mu_km3_s2);
+    // Ignore round-off when the optimum collapses onto the Hohmann ellipse.
+    let beats_hohmann = transfer.dv_total_km_s < hohmann.dv_total_km_s * (1.0 - 1e-12);
+
+    Ok(BiEllipticOptimum {
+        transfer,
+        hohmann,
+        beats_hohmann,
+    })
+}
+
+/// Phase angle (rad) the target must lead the departure body by for a Hohmann transfer.
+///
+/// Measured in the direction of motion and wrapped to `(-π, π]`; negative values mean the
+/// target must trail (inward transfers).
+pub fn hohmann_phase_angle(r1_km: f64, r2_km: f64, mu_km3_s2: f64) -> f64 {
+    let tof = hohmann(r1_km, r2_km, mu_km3_s2).tof_seconds;
+    let n2 = (mu_km3_s2 / r2_km.powi(3)).sqrt();
+    wrap_to_pi(std::f64::consts::PI - n2 * tof)
+}
+
+/// Time (s) until the phase angle between two circular orbits next reaches `required_rad`.
+///
+/// `current_rad` is the present target-minus-departure angle. Returns `None` for co-orbital
+/// radii, where the phase never changes.
+pub fn time_to_phase_alignment(
+    current_rad: f64,
+    required_rad: f64,
+    r1_km: f64,
+    r2_km: f64,
+    mu_km3_s2: f64,
+) -> Option<f64> {
+    let n1 = (mu_km3_s2 / r1_km.powi(3)).sqrt();
+    let n2 = (mu_km3_s2 / r2_km.powi(3)).sqrt();
+    let rate = n2 - n1;
+    if rate.abs() < 1e-15 {
+        return None;
+    }
+    let synodic_period = 2.0 * std::f64::consts::PI / rate.abs();
+    Some(((required_rad - current_rad) / rate).rem_euclid(synodic_period))
+}
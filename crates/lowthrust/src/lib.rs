@@ -14,6 +14,32 @@ pub struct ConstantAccelInputs {
     pub dry_mass_kg: f64,
 }
 
+impl ConstantAccelInputs {
+    /// Effective exhaust velocity `isp · g0` (m/s).
+    pub fn exhaust_velocity_m_s(&self) -> f64 {
+        self.isp_seconds * G0
+    }
+}
+
+/// Inputs describing a throttled engine following a piecewise-constant thrust schedule.
+#[derive(Debug, Clone)]
+pub struct ThrottledInputs {
+    pub max_thrust_newtons: f64,
+    pub isp_seconds: f64,
+    pub initial_mass_kg: f64,
+    pub dry_mass_kg: f64,
+    /// `(time_fraction, throttle)` breakpoints sorted by fraction of the total time; each
+    /// throttle in `[-1, 1]` holds until the next breakpoint and the engine is off before the first.
+    pub throttle_profile: Vec<(f64, f64)>,
+}
+
+impl ThrottledInputs {
+    /// Effective exhaust velocity `isp · g0` (m/s).
+    pub fn exhaust_velocity_m_s(&self) -> f64 {
+        self.isp_seconds * G0
+    }
+}
+
 /// Per-sample telemetry record for the continuous profile.
 #[derive(Debug, Clone, Serialize)]
 pub struct ProfileSample {
@@ -29,6 +55,10 @@ pub struct ContinuousTransferSummary {
     pub burn_time_total_s: f64,
     pub propellant_used_kg: f64,
     pub final_mass_kg: f64,
+    /// Initial mass divided by final mass (the rocket-equation mass ratio).
+    pub mass_ratio: f64,
+    /// Dry mass divided by initial mass.
+    pub payload_fraction: f64,
     pub dv_each_km_s: f64,
     pub dv_total_km_s: f64,
     pub max_velocity_m_s: f64,
@@ -44,6 +74,174 @@ pub struct ContinuousTransferSummary {
 pub fn constant_accel_profile(
     inputs: &ConstantAccelInputs,
     total_time_s: f64,
+) -> Option<ContinuousTransferSummary> {
+    profile_with_samples(inputs, total_time_s, || build_samples(inputs, total_time_s))
+}
+
+/// Same profile as [`constant_accel_profile`], sampled with steps sized so that linear
+/// interpolation between consecutive samples stays within `rel_tol` of the exact motion.
+///
+/// Errors are measured against each quantity's scale over the whole profile (total distance,
+/// peak velocity, initial mass). The flip midpoint and the endpoint are always sampled exactly.
+/// Returns `None` for a non-positive or non-finite tolerance, or when the fixed-step variant would.
+pub fn constant_accel_profile_adaptive(
+    inputs: &ConstantAccelInputs,
+    total_time_s: f64,
+    rel_tol: f64,
+) -> Option<ContinuousTransferSummary> {
+    if !(rel_tol.is_finite() && rel_tol > 0.0) {
+        return None;
+    }
+    profile_with_samples(inputs, total_time_s, || {
+        build_samples_adaptive(inputs, total_time_s, rel_tol)
+    })
+}
+
+/// Integrates 1D motion under the throttle schedule of `inputs` over `total_time_s`.
+///
+/// Thrust is `throttle · max_thrust` along the direction of travel, so negative throttles brake
+/// and `[(0.0, 1.0), (0.25, 0.0), (0.75, -1.0)]` flies a bang-coast-bang transfer. Mass, velocity
+/// and distance are integrated exactly within each constant-throttle step, and every breakpoint
+/// is sampled. `dv_total_km_s` is the delta-v delivered across all burns and `dv_each_km_s` the
+/// peak speed. Returns `None` for an invalid schedule (unsorted fractions, fractions outside
+/// `[0, 1)`, throttles outside `[-1, 1]`) or when the burns need more propellant than carried.
+pub fn throttled_profile(
+    inputs: &ThrottledInputs,
+    total_time_s: f64,
+) -> Option<ContinuousTransferSummary> {
+    if !(total_time_s.is_finite() && total_time_s > 0.0) {
+        return None;
+    }
+    let ve = inputs.exhaust_velocity_m_s();
+    if !(ve > 0.0 && inputs.max_thrust_newtons > 0.0 && inputs.initial_mass_kg > 0.0) {
+        return None;
+    }
+    let segments = throttle_segments(&inputs.throttle_profile, total_time_s)?;
+
+    let dt_base = if total_time_s > 18_000.0 {
+        3_600.0
+    } else {
+        (total_time_s / 200.0).clamp(30.0, 3_600.0)
+    };
+
+    let mut sample = ProfileSample {
+        time_s: 0.0,
+        distance_m: 0.0,
+        velocity_m_s: 0.0,
+        mass_kg: inputs.initial_mass_kg,
+    };
+    let mut samples = vec![sample.clone()];
+    let mut burn_time_total = 0.0;
+    let mut max_velocity = 0.0_f64;
+    let mut peak_mass = inputs.initial_mass_kg;
+
+    for (start, end, throttle) in segments {
+        let mass_flow = inputs.max_thrust_newtons * throttle.abs() / ve;
+        if throttle != 0.0 {
+            burn_time_total += end - start;
+        }
+        let mut time = start;
+        while time + 1e-9 < end {
+            let dt = (end - time).min(dt_base);
+            time = if end - time <= dt_base {
+                end
+            } else {
+                time + dt
+            };
+            sample = throttled_step(&sample, dt, throttle.signum(), mass_flow, ve);
+            sample.time_s = time;
+            if sample.mass_kg < inputs.dry_mass_kg - 1e-6 {
+                return None;
+            }
+            if sample.velocity_m_s.abs() > max_velocity {
+                max_velocity = sample.velocity_m_s.abs();
+                peak_mass = sample.mass_kg;
+            }
+            samples.push(sample.clone());
+        }
+    }
+
+    let final_mass = sample.mass_kg;
+    Some(ContinuousTransferSummary {
+        burn_time_total_s: burn_time_total,
+        propellant_used_kg: inputs.initial_mass_kg - final_mass,
+        final_mass_kg: final_mass,
+        mass_ratio: inputs.initial_mass_kg / final_mass,
+        payload_fraction: inputs.dry_mass_kg / inputs.initial_mass_kg,
+        dv_each_km_s: max_velocity / 1_000.0,
+        dv_total_km_s: ve * (inputs.initial_mass_kg / final_mass).ln() / 1_000.0,
+        max_velocity_m_s: max_velocity,
+        max_velocity_fraction_c: max_velocity / SPEED_OF_LIGHT_M_S,
+        total_distance_m: sample.distance_m,
+        kinetic_energy_joules: 0.5 * peak_mass * max_velocity * max_velocity,
+        time_of_flight_s: total_time_s,
+        samples,
+    })
+}
+
+/// Constant-throttle `(start_s, end_s, throttle)` spans covering `[0, total_time_s]`.
+fn throttle_segments(profile: &[(f64, f64)], total_time_s: f64) -> Option<Vec<(f64, f64, f64)>> {
+    let valid = profile.iter().all(|&(fraction, throttle)| {
+        (0.0..1.0).contains(&fraction) && throttle.is_finite() && throttle.abs() <= 1.0
+    }) && profile.windows(2).all(|w| w[1].0 > w[0].0);
+    if !valid {
+        return None;
+    }
+
+    let mut segments = Vec::with_capacity(profile.len() + 1);
+    if let Some(&(first, _)) = profile.first().filter(|(fraction, _)| *fraction > 0.0) {
+        segments.push((0.0, first * total_time_s, 0.0));
+    }
+    for (i, &(fraction, throttle)) in profile.iter().enumerate() {
+        let end = profile.get(i + 1).map_or(1.0, |next| next.0);
+        segments.push((fraction * total_time_s, end * total_time_s, throttle));
+    }
+    if segments.is_empty() {
+        segments.push((0.0, total_time_s, 0.0));
+    }
+    Some(segments)
+}
+
+/// Advance `state` by `dt` under constant thrust in direction `sign` (zero to coast).
+///
+/// Uses the closed-form rocket-equation solution for a constant mass flow `mass_flow` (kg/s).
+fn throttled_step(
+    state: &ProfileSample,
+    dt: f64,
+    sign: f64,
+    mass_flow: f64,
+    ve: f64,
+) -> ProfileSample {
+    let coast_distance = state.distance_m + state.velocity_m_s * dt;
+    if sign == 0.0 || mass_flow == 0.0 {
+        return ProfileSample {
+            time_s: state.time_s + dt,
+            distance_m: coast_distance,
+            ..state.clone()
+        };
+    }
+    let mass = state.mass_kg - mass_flow * dt;
+    if mass <= 0.0 {
+        return ProfileSample {
+            time_s: state.time_s + dt,
+            distance_m: coast_distance,
+            velocity_m_s: state.velocity_m_s,
+            mass_kg: mass,
+        };
+    }
+    let log_ratio = (state.mass_kg / mass).ln();
+    ProfileSample {
+        time_s: state.time_s + dt,
+        distance_m: coast_distance + sign * ve * (dt - mass / mass_flow * log_ratio),
+        velocity_m_s: state.velocity_m_s + sign * ve * log_ratio,
+        mass_kg: mass,
+    }
+}
+
+fn profile_with_samples(
+    inputs: &ConstantAccelInputs,
+    total_time_s: f64,
+    build: impl FnOnce() -> Vec<ProfileSample>,
 ) -> Option<ContinuousTransferSummary> {
     if total_time_s <= 0.0 {
         return None;
@@ -53,13 +251,13 @@ pub fn constant_accel_profile(
         return None;
     }
     let t_half = total_time_s * 0.5;
-    let isp = inputs.isp_seconds;
-    if isp <= 0.0 {
+    let ve = inputs.exhaust_velocity_m_s();
+    if ve <= 0.0 {
         return None;
     }
 
     // mass evolution with throttle adjusted to maintain constant acceleration a
-    let exponent = -a * total_time_s / (isp * G0);
+    let exponent = -a * total_time_s / ve;
     let final_mass = inputs.initial_mass_kg * exponent.exp();
     if final_mass < inputs.dry_mass_kg - 1e-6 {
         return None;
@@ -69,10 +267,10 @@ pub fn constant_accel_profile(
     let burn_time_total = total_time_s;
     let max_velocity = a * t_half;
     let total_distance = a * t_half * t_half;
-    let peak_mass = inputs.initial_mass_kg * (-a * t_half / (isp * G0)).exp();
+    let peak_mass = inputs.initial_mass_kg * (-a * t_half / ve).exp();
     let kinetic_energy = 0.5 * peak_mass * max_velocity * max_velocity;
 
-    let mut samples = build_samples(inputs, total_time_s);
+    let mut samples = build();
     if let Some(last) = samples.last_mut() {
         last.time_s = total_time_s;
         last.distance_m = total_distance;
@@ -84,6 +282,8 @@ pub fn constant_accel_profile(
         burn_time_total_s: burn_time_total,
         propellant_used_kg: propellant_used,
         final_mass_kg: final_mass,
+        mass_ratio: inputs.initial_mass_kg / final_mass,
+        payload_fraction: inputs.dry_mass_kg / inputs.initial_mass_kg,
         dv_each_km_s: max_velocity / 1_000.0,
         dv_total_km_s: 2.0 * max_velocity / 1_000.0,
         max_velocity_m_s: max_velocity,
@@ -117,8 +317,7 @@ fn build_samples(inputs: &ConstantAccelInputs, total_time_s: f64) -> Vec<Profile
         (total_time_s / 200.0).clamp(30.0, 3_600.0)
     };
 
-    let isp = inputs.isp_seconds;
-    let mass_factor = -a / (isp * G0);
+    let mass_factor = -a / inputs.exhaust_velocity_m_s();
 
     while time + 1e-9 < t_half {
         let dt = (t_half - time).min(dt_base);
@@ -157,3 +356,105 @@ fn build_samples(inputs: &ConstantAccelInputs, total_time_s: f64) -> Vec<Profile
 
     samples
 }
+
+/// Exact state of the symmetric profile at `time_s`.
+fn exact_sample(inputs: &ConstantAccelInputs, total_time_s: f64, time_s: f64) -> ProfileSample {
+    let a = inputs.acceleration_m_s2;
+    let t_half = total_time_s * 0.5;
+    let mass_factor = -a / inputs.exhaust_velocity_m_s();
+    let mass_kg = inputs.initial_mass_kg * (mass_factor * time_s).exp();
+    if time_s <= t_half {
+        return ProfileSample {
+            time_s,
+            distance_m: 0.5 * a * time_s * time_s,
+            velocity_m_s: a * time_s,
+            mass_kg,
+        };
+    }
+    let peak_velocity = a * t_half;
+    let tau = time_s - t_half;
+    ProfileSample {
+        time_s,
+        distance_m: 0.5 * a * t_half * t_half + peak_velocity * tau - 0.5 * a * tau * tau,
+        velocity_m_s: (peak_velocity - a * tau).max(0.0),
+        mass_kg,
+    }
+}
+
+fn build_samples_adaptive(
+    inputs: &ConstantAccelInputs,
+    total_time_s: f64,
+    rel_tol: f64,
+) -> Vec<ProfileSample> {
+    // Keeps pathological tolerances from stalling the step controller.
+    const MIN_STEP_FRACTION: f64 = 1e-9;
+
+    let t_half = total_time_s * 0.5;
+    let peak = exact_sample(inputs, total_time_s, t_half);
+    let distance_scale = (2.0 * peak.distance_m).max(f64::MIN_POSITIVE);
+    let velocity_scale = peak.velocity_m_s.max(f64::MIN_POSITIVE);
+    let mass_scale = inputs.initial_mass_kg;
+    let min_step = total_time_s * MIN_STEP_FRACTION;
+
+    // Largest normalised error of linear interpolation across [start, start + dt], probed at the
+    // step midpoint.
+    let step_error = |start: &ProfileSample, dt: f64| {
+        let end = exact_sample(inputs, total_time_s, start.time_s + dt);
+        let mid = exact_sample(inputs, total_time_s, start.time_s + 0.5 * dt);
+        let miss = |a: f64, b: f64, exact: f64, scale: f64| {
+            (0.5 * (a + b) - exact).abs() / exact.abs().max(scale)
+        };
+        let error = miss(
+            start.distance_m,
+            end.distance_m,
+            mid.distance_m,
+            distance_scale,
+        )
+        .max(miss(
+            start.velocity_m_s,
+            end.velocity_m_s,
+            mid.velocity_m_s,
+            velocity_scale,
+        ))
+        .max(miss(start.mass_kg, end.mass_kg, mid.mass_kg, mass_scale));
+        (error, end)
+    };
+
+    let mut samples = vec![exact_sample(inputs, total_time_s, 0.0)];
+    let mut dt = t_half;
+    for boundary in [t_half, total_time_s] {
+        loop {
+            let start = samples.last().expect("samples start at t = 0").clone();
+            let remaining = boundary - start.time_s;
+            if remaining <= min_step {
+                break;
+            }
+            // Never step across the flip, and never leave a sliver before it, so both halves
+            // end on an exact sample.
+            let trial = if remaining - dt <= min_step {
+                remaining
+            } else {
+                dt
+            };
+            let (error, end) = step_error(&start, trial);
+            if error > rel_tol && trial > min_step {
+                dt = (0.5 * trial).max(min_step);
+                continue;
+            }
+            let end = if trial == remaining {
+                exact_sample(inputs, total_time_s, boundary)
+            } else {
+                end
+            };
+            samples.push(end);
+            // Interpolation error scales with dt², so grow towards the tolerance boundary.
+            let growth = if error > 0.0 {
+                (0.9 * (rel_tol / error).sqrt()).clamp(1.0, 2.0)
+            } else {
+                2.0
+            };
+            dt = (trial * growth).max(min_step);
+        }
+    }
+    samples
+}
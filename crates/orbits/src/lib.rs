@@ -1,4 +1,9 @@
-//! Orbit utility helpers (patched-conic escape/capture estimates).
+//! Orbit utility helpers (patched-conic escape/capture estimates, two-body propagation).
+use std::f64::consts::TAU;
+
+use solar_core::angles::acos_clamped;
+use solar_core::constants::G0;
+pub use solar_core::elements::OrbitalElements;
 use solar_core::vector::{self, Vector3};
 
 /// Euclidean norm helper maintained for backwards compatibility.
@@ -26,6 +31,33 @@ pub fn scale(v: &Vector3, s: f64) -> Vector3 {
     vector::scale(v, s)
 }
 
+/// Eccentricities this close to 1 are treated as parabolic, which the elements cannot describe.
+const PARABOLIC_TOLERANCE: f64 = 1e-9;
+/// Below this eccentricity the periapsis direction is undefined and the node line is used.
+const CIRCULAR_TOLERANCE: f64 = 1e-11;
+
+/// Newton iterations allowed when solving the universal Kepler equation.
+const KEPLER_MAX_ITERATIONS: usize = 100;
+/// Convergence tolerance on the universal anomaly (km^½), relative to its magnitude.
+const KEPLER_TOLERANCE: f64 = 1e-12;
+
+/// Characteristic energy C3 (km²/s²) for a hyperbolic excess speed (km/s).
+pub fn c3_from_vinf(vinf_km_s: f64) -> f64 {
+    vinf_km_s * vinf_km_s
+}
+
+/// Hyperbolic excess speed (km/s) for a characteristic energy C3 (km²/s²).
+///
+/// Negative C3 describes a bound orbit and yields zero excess speed.
+pub fn vinf_from_c3(c3_km2_s2: f64) -> f64 {
+    c3_km2_s2.max(0.0).sqrt()
+}
+
+/// Specific orbital energy (km²/s²) of a hyperbola with the given excess speed, i.e. C3 / 2.
+pub fn specific_energy_from_vinf(vinf_km_s: f64) -> f64 {
+    0.5 * c3_from_vinf(vinf_km_s)
+}
+
 /// Patched-conic escape delta-v from a circular parking orbit.
 pub fn escape_delta_v(mu_km3_s2: f64, parking_radius_km: f64, vinf_km_s: f64) -> f64 {
     let circular_speed = (mu_km3_s2 / parking_radius_km).sqrt();
@@ -39,3 +71,437 @@ pub fn capture_delta_v(mu_km3_s2: f64, parking_radius_km: f64, vinf_km_s: f64) -
     let hyperbolic_speed = (vinf_km_s * vinf_km_s + 2.0 * mu_km3_s2 / parking_radius_km).sqrt();
     (hyperbolic_speed - circular_speed).max(0.0)
 }
+
+/// [`escape_delta_v`] from a circular parking orbit around an oblate body.
+///
+/// First-order J2 correction, averaged over the parking orbit at `inclination_rad` to the
+/// body's equator: with `k = J2 (R/r)² (1 − 1.5 sin² i)` the circular speed becomes
+/// `√(μ/r (1 + 1.5 k))` and the periapsis speed of the departure hyperbola
+/// `√(v∞² + 2μ/r (1 + 0.5 k))`. Higher-order zonal terms and the precession of the parking
+/// orbit are ignored; with `j2 = 0` this reduces to the spherical result.
+pub fn escape_delta_v_j2(
+    mu_km3_s2: f64,
+    parking_radius_km: f64,
+    vinf_km_s: f64,
+    j2: f64,
+    body_radius_km: f64,
+    inclination_rad: f64,
+) -> f64 {
+    let (circular_speed, hyperbolic_speed) = j2_parking_speeds(
+        mu_km3_s2,
+        parking_radius_km,
+        vinf_km_s,
+        j2,
+        body_radius_km,
+        inclination_rad,
+    );
+    (hyperbolic_speed - circular_speed).max(0.0)
+}
+
+/// [`capture_delta_v`] into a circular parking orbit around an oblate body, with the same
+/// first-order J2 correction as [`escape_delta_v_j2`].
+pub fn capture_delta_v_j2(
+    mu_km3_s2: f64,
+    parking_radius_km: f64,
+    vinf_km_s: f64,
+    j2: f64,
+    body_radius_km: f64,
+    inclination_rad: f64,
+) -> f64 {
+    let (circular_speed, hyperbolic_speed) = j2_parking_speeds(
+        mu_km3_s2,
+        parking_radius_km,
+        vinf_km_s,
+        j2,
+        body_radius_km,
+        inclination_rad,
+    );
+    (hyperbolic_speed - circular_speed).max(0.0)
+}
+
+/// Orbit-averaged circular and hyperbolic periapsis speeds (km/s) under a J2 potential.
+fn j2_parking_speeds(
+    mu_km3_s2: f64,
+    parking_radius_km: f64,
+    vinf_km_s: f64,
+    j2: f64,
+    body_radius_km: f64,
+    inclination_rad: f64,
+) -> (f64, f64) {
+    let ratio = body_radius_km / parking_radius_km;
+    let sin_i = inclination_rad.sin();
+    let k = j2 * ratio * ratio * (1.0 - 1.5 * sin_i * sin_i);
+    let mu_over_r = mu_km3_s2 / parking_radius_km;
+    let circular_speed = (mu_over_r * (1.0 + 1.5 * k)).sqrt();
+    let hyperbolic_speed = (vinf_km_s * vinf_km_s + 2.0 * mu_over_r * (1.0 + 0.5 * k)).sqrt();
+    (circular_speed, hyperbolic_speed)
+}
+
+/// Advance a two-body state by `dt_seconds` (negative runs backwards) under gravitational
+/// parameter `mu_km3_s2`.
+///
+/// Solves the universal Kepler equation for the universal anomaly χ with Newton's method and
+/// maps the state through the Lagrange `f`/`g` coefficients, so elliptic, parabolic and
+/// hyperbolic orbits share one code path. Elliptic propagations are first reduced modulo the
+/// orbital period. Returns `(position_km, velocity_km_s)`.
+pub fn propagate_kepler(
+    position_km: Vector3,
+    velocity_km_s: Vector3,
+    mu_km3_s2: f64,
+    dt_seconds: f64,
+) -> (Vector3, Vector3) {
+    let r0 = norm3(&position_km);
+    let v0 = norm3(&velocity_km_s);
+    if dt_seconds == 0.0 || r0 == 0.0 {
+        return (position_km, velocity_km_s);
+    }
+    let sqrt_mu = mu_km3_s2.sqrt();
+    let radial_speed = dot(&position_km, &velocity_km_s) / r0;
+    // Reciprocal semi-major axis: positive for ellipses, zero for parabolas.
+    let alpha = 2.0 / r0 - v0 * v0 / mu_km3_s2;
+
+    let dt = if alpha > 1e-12 {
+        let period = TAU / (sqrt_mu * alpha.powf(1.5));
+        dt_seconds.rem_euclid(period)
+    } else {
+        dt_seconds
+    };
+
+    let sigma = r0 * radial_speed / sqrt_mu;
+    let mut chi = if alpha > 1e-12 {
+        sqrt_mu * alpha * dt
+    } else if alpha < -1e-12 {
+        // Logarithmic start keeps the hyperbolic Stumpff terms from overflowing on long arcs.
+        let a = 1.0 / alpha;
+        let sign = dt.signum();
+        let guess = sign
+            * (-a).sqrt()
+            * ((-2.0 * mu_km3_s2 * alpha * dt)
+                / (r0 * radial_speed + sign * (-mu_km3_s2 * a).sqrt() * (1.0 - r0 * alpha)))
+                .ln();
+        if guess.is_finite() {
+            guess
+        } else {
+            sqrt_mu * dt / r0
+        }
+    } else {
+        sqrt_mu * dt / r0
+    };
+    for _ in 0..KEPLER_MAX_ITERATIONS {
+        let z = alpha * chi * chi;
+        let (c2, c3) = stumpff(z);
+        let time = sigma * chi * chi * c2 + (1.0 - alpha * r0) * chi.powi(3) * c3 + r0 * chi;
+        // dt/dχ is the current radius, always positive.
+        let radius = sigma * chi * (1.0 - z * c3) + (1.0 - alpha * r0) * chi * chi * c2 + r0;
+        let step = (time - sqrt_mu * dt) / radius;
+        chi -= step;
+        if step.abs() <= KEPLER_TOLERANCE * chi.abs().max(1.0) {
+            break;
+        }
+    }
+
+    let z = alpha * chi * chi;
+    let (c2, c3) = stumpff(z);
+    let f = 1.0 - chi * chi / r0 * c2;
+    let g = dt - chi.powi(3) / sqrt_mu * c3;
+    let position = add(&scale(&position_km, f), &scale(&velocity_km_s, g));
+    let r = norm3(&position);
+    let f_dot = sqrt_mu / (r * r0) * chi * (z * c3 - 1.0);
+    let g_dot = 1.0 - chi * chi / r * c2;
+    let velocity = add(&scale(&position_km, f_dot), &scale(&velocity_km_s, g_dot));
+    (position, velocity)
+}
+
+/// Cartesian state `(position_km, velocity_km_s)` of the conic described by `elements` about a
+/// body with gravitational parameter `mu_km3_s2`.
+///
+/// Solves Kepler's equation for the eccentric (or hyperbolic) anomaly, places the state in the
+/// perifocal frame and rotates it by the argument of periapsis, inclination and RAAN into the
+/// elements' reference frame. Returns `None` for parabolic or otherwise invalid elements (their
+/// infinite semi-major axis does not fix the conic's size).
+pub fn state_from_elements(
+    elements: &OrbitalElements,
+    mu_km3_s2: f64,
+) -> Option<(Vector3, Vector3)> {
+    let e = elements.eccentricity;
+    let a = elements.semi_major_axis_km;
+    let m = elements.mean_anomaly_rad;
+    let elliptic = (0.0..1.0 - PARABOLIC_TOLERANCE).contains(&e) && a > 0.0;
+    let hyperbolic = e > 1.0 + PARABOLIC_TOLERANCE && a < 0.0;
+    if !(mu_km3_s2 > 0.0 && a.is_finite() && m.is_finite() && (elliptic || hyperbolic)) {
+        return None;
+    }
+
+    let true_anomaly = if elliptic {
+        let m = m.rem_euclid(TAU);
+        let mut anomaly = if e < 0.8 { m } else { std::f64::consts::PI };
+        for _ in 0..KEPLER_MAX_ITERATIONS {
+            let step = (anomaly - e * anomaly.sin() - m) / (1.0 - e * anomaly.cos());
+            anomaly -= step;
+            if step.abs() <= KEPLER_TOLERANCE {
+                break;
+            }
+        }
+        2.0 * ((1.0 + e).sqrt() * (0.5 * anomaly).sin())
+            .atan2((1.0 - e).sqrt() * (0.5 * anomaly).cos())
+    } else {
+        let mut anomaly = (2.0 * m.abs() / e + 1.8).ln().copysign(m);
+        for _ in 0..KEPLER_MAX_ITERATIONS {
+            let step = (e * anomaly.sinh() - anomaly - m) / (e * anomaly.cosh() - 1.0);
+            anomaly -= step;
+            if step.abs() <= KEPLER_TOLERANCE * anomaly.abs().max(1.0) {
+                break;
+            }
+        }
+        2.0 * ((e + 1.0).sqrt() * (0.5 * anomaly).sinh())
+            .atan2((e - 1.0).sqrt() * (0.5 * anomaly).cosh())
+    };
+
+    let p = a * (1.0 - e * e);
+    let (sin_nu, cos_nu) = true_anomaly.sin_cos();
+    let radius = p / (1.0 + e * cos_nu);
+    let speed_scale = (mu_km3_s2 / p).sqrt();
+    let perifocal_position = [radius * cos_nu, radius * sin_nu];
+    let perifocal_velocity = [-speed_scale * sin_nu, speed_scale * (e + cos_nu)];
+
+    let (sin_raan, cos_raan) = elements.raan_rad.sin_cos();
+    let (sin_i, cos_i) = elements.inclination_rad.sin_cos();
+    let (sin_w, cos_w) = elements.argument_of_periapsis_rad.sin_cos();
+    let p_hat = [
+        cos_raan * cos_w - sin_raan * sin_w * cos_i,
+        sin_raan * cos_w + cos_raan * sin_w * cos_i,
+        sin_w * sin_i,
+    ];
+    let q_hat = [
+        -cos_raan * sin_w - sin_raan * cos_w * cos_i,
+        -sin_raan * sin_w + cos_raan * cos_w * cos_i,
+        cos_w * sin_i,
+    ];
+    let to_inertial = |[x, y]: [f64; 2]| add(&scale(&p_hat, x), &scale(&q_hat, y));
+    Some((
+        to_inertial(perifocal_position),
+        to_inertial(perifocal_velocity),
+    ))
+}
+
+/// Classical elements of a Cartesian state about a body with gravitational parameter
+/// `mu_km3_s2`; the inverse of [`state_from_elements`].
+///
+/// Angles are in `[0, 2π)` except the hyperbolic mean anomaly, which is signed. Circular orbits
+/// measure the periapsis from the ascending node, and equatorial orbits take the node on the
+/// reference x axis.
+pub fn elements_from_state(
+    position_km: Vector3,
+    velocity_km_s: Vector3,
+    mu_km3_s2: f64,
+) -> OrbitalElements {
+    let r = norm3(&position_km);
+    let speed = norm3(&velocity_km_s);
+    let h = vector::cross(&position_km, &velocity_km_s);
+    let h_norm = norm3(&h);
+    let h_hat = scale(&h, 1.0 / h_norm);
+
+    let radial = dot(&position_km, &velocity_km_s);
+    let eccentricity_vector = scale(
+        &sub(
+            &scale(&position_km, speed * speed - mu_km3_s2 / r),
+            &scale(&velocity_km_s, radial),
+        ),
+        1.0 / mu_km3_s2,
+    );
+    let e = norm3(&eccentricity_vector);
+    let energy = 0.5 * speed * speed - mu_km3_s2 / r;
+    let semi_major_axis_km = -mu_km3_s2 / (2.0 * energy);
+
+    let node = [-h[1], h[0], 0.0];
+    let node_norm = norm3(&node);
+    let node_hat = if node_norm > CIRCULAR_TOLERANCE * h_norm {
+        scale(&node, 1.0 / node_norm)
+    } else {
+        [1.0, 0.0, 0.0]
+    };
+    let periapsis_hat = if e > CIRCULAR_TOLERANCE {
+        scale(&eccentricity_vector, 1.0 / e)
+    } else {
+        node_hat
+    };
+    let angle_in_plane = |from: &Vector3, to: &Vector3| {
+        dot(&vector::cross(from, to), &h_hat)
+            .atan2(dot(from, to))
+            .rem_euclid(TAU)
+    };
+    let true_anomaly = angle_in_plane(&periapsis_hat, &position_km);
+
+    let mean_anomaly_rad = if e < 1.0 {
+        let eccentric = 2.0
+            * ((1.0 - e).sqrt() * (0.5 * true_anomaly).sin())
+                .atan2((1.0 + e).sqrt() * (0.5 * true_anomaly).cos());
+        (eccentric - e * eccentric.sin()).rem_euclid(TAU)
+    } else {
+        let nu = if true_anomaly > std::f64::consts::PI {
+            true_anomaly - TAU
+        } else {
+            true_anomaly
+        };
+        let hyperbolic = 2.0 * (((e - 1.0) / (e + 1.0)).sqrt() * (0.5 * nu).tan()).atanh();
+        e * hyperbolic.sinh() - hyperbolic
+    };
+
+    OrbitalElements {
+        semi_major_axis_km,
+        eccentricity: e,
+        inclination_rad: acos_clamped(h_hat[2]),
+        raan_rad: node_hat[1].atan2(node_hat[0]).rem_euclid(TAU),
+        argument_of_periapsis_rad: angle_in_plane(&node_hat, &periapsis_hat),
+        mean_anomaly_rad,
+    }
+}
+
+/// Stumpff functions `c2(ψ)` and `c3(ψ)`, using their series near ψ = 0.
+pub fn stumpff(psi: f64) -> (f64, f64) {
+    if psi.abs() < 1e-3 {
+        (
+            0.5 - psi / 24.0 + psi * psi / 720.0,
+            1.0 / 6.0 - psi / 120.0 + psi * psi / 5040.0,
+        )
+    } else if psi > 0.0 {
+        let sqrt_psi = psi.sqrt();
+        (
+            (1.0 - sqrt_psi.cos()) / psi,
+            (sqrt_psi - sqrt_psi.sin()) / (psi * sqrt_psi),
+        )
+    } else {
+        let sqrt_neg = (-psi).sqrt();
+        (
+            (1.0 - sqrt_neg.cosh()) / psi,
+            (sqrt_neg.sinh() - sqrt_neg) / (-psi * sqrt_neg),
+        )
+    }
+}
+
+/// Maximum heliocentric turn (rad) an unpowered flyby can impart on the v-infinity vector
+/// without dipping below `periapsis_radius_km`.
+pub fn flyby_max_turn_angle(vinf_km_s: f64, mu_km3_s2: f64, periapsis_radius_km: f64) -> f64 {
+    let eccentricity = 1.0 + periapsis_radius_km * vinf_km_s * vinf_km_s / mu_km3_s2;
+    2.0 * (1.0 / eccentricity).asin()
+}
+
+/// Low-thrust spiral escape delta-v (km/s) from a circular orbit at `r_start_km` out to
+/// `r_escape_km`, using Edelbaum's tangential-thrust approximation.
+///
+/// Pass `f64::INFINITY` for `r_escape_km` to escape to infinity, where the cost equals the
+/// starting circular speed.
+pub fn spiral_escape_delta_v(mu_km3_s2: f64, r_start_km: f64, r_escape_km: f64) -> f64 {
+    spiral_delta_v(mu_km3_s2, r_start_km, r_escape_km)
+}
+
+/// Low-thrust spiral capture delta-v (km/s) from `r_capture_km` (infinity for an arrival at zero
+/// v-infinity) down to a circular parking orbit at `r_parking_km`.
+pub fn spiral_capture_delta_v(mu_km3_s2: f64, r_capture_km: f64, r_parking_km: f64) -> f64 {
+    spiral_delta_v(mu_km3_s2, r_capture_km, r_parking_km)
+}
+
+/// Duration (s) of a constant-thrust spiral between circular radii `r_start_km` and `r_end_km`,
+/// accounting for propellant depletion through the rocket equation.
+///
+/// Returns `None` when thrust, mass or specific impulse is not positive.
+pub fn spiral_time(
+    mu_km3_s2: f64,
+    thrust_n: f64,
+    mass_kg: f64,
+    isp_s: f64,
+    r_start_km: f64,
+    r_end_km: f64,
+) -> Option<f64> {
+    if !(thrust_n > 0.0 && mass_kg > 0.0 && isp_s > 0.0) {
+        return None;
+    }
+    let exhaust_velocity_m_s = isp_s * G0;
+    let delta_v_m_s = spiral_delta_v(mu_km3_s2, r_start_km, r_end_km) * 1_000.0;
+    let propellant_kg = mass_kg * (1.0 - (-delta_v_m_s / exhaust_velocity_m_s).exp());
+    Some(propellant_kg * exhaust_velocity_m_s / thrust_n)
+}
+
+/// Edelbaum circular-to-circular delta-v: the difference in circular speeds.
+fn spiral_delta_v(mu_km3_s2: f64, r_a_km: f64, r_b_km: f64) -> f64 {
+    let circular_speed = |r: f64| {
+        if r.is_infinite() {
+            0.0
+        } else {
+            (mu_km3_s2 / r).sqrt()
+        }
+    };
+    (circular_speed(r_a_km) - circular_speed(r_b_km)).abs()
+}
+
+/// Finite-burn correction of an impulsive manoeuvre performed from a circular orbit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FiniteBurn {
+    /// Delta-v (km/s) the engine must deliver, including gravity losses.
+    pub delta_v_km_s: f64,
+    /// Burn duration (s) at full thrust.
+    pub burn_duration_s: f64,
+    /// True-anomaly arc (rad) swept while the engine fires.
+    pub burn_arc_rad: f64,
+}
+
+impl FiniteBurn {
+    /// Extra delta-v (km/s) over the impulsive value.
+    pub fn gravity_loss_km_s(&self, ideal_delta_v_km_s: f64) -> f64 {
+        (self.delta_v_km_s - ideal_delta_v_km_s).max(0.0)
+    }
+}
+
+/// Estimate the gravity loss of delivering `ideal_delta_v_km_s` with a thrust-limited engine from
+/// a circular orbit of radius `radius_km`.
+///
+/// The burn is centred on the ideal impulse point with an inertially fixed thrust direction, so
+/// only the cosine of the misalignment across the swept arc `Δf` does useful work and the
+/// delivered delta-v shrinks by `sin(Δf/2) / (Δf/2)`. The arc follows from the mean speed over
+/// the burn; it is capped at half an orbit, beyond which a single burn is no longer meaningful.
+/// Returns `None` when thrust, mass or specific impulse is not positive.
+pub fn finite_burn_correction(
+    ideal_delta_v_km_s: f64,
+    mu_km3_s2: f64,
+    radius_km: f64,
+    thrust_n: f64,
+    mass_kg: f64,
+    isp_s: f64,
+) -> Option<FiniteBurn> {
+    const ITERATIONS: usize = 50;
+
+    if !(thrust_n > 0.0 && mass_kg > 0.0 && isp_s > 0.0) {
+        return None;
+    }
+    let exhaust_velocity_m_s = isp_s * G0;
+    let burn_time = |delta_v_km_s: f64| {
+        let propellant_kg =
+            mass_kg * (1.0 - (-delta_v_km_s * 1_000.0 / exhaust_velocity_m_s).exp());
+        propellant_kg * exhaust_velocity_m_s / thrust_n
+    };
+    let mean_speed = (mu_km3_s2 / radius_km).sqrt() + 0.5 * ideal_delta_v_km_s;
+    let arc = |duration_s: f64| (mean_speed * duration_s / radius_km).min(std::f64::consts::PI);
+
+    let mut delta_v = ideal_delta_v_km_s.max(0.0);
+    for _ in 0..ITERATIONS {
+        let half_arc = 0.5 * arc(burn_time(delta_v));
+        let efficiency = if half_arc > 1e-12 {
+            half_arc.sin() / half_arc
+        } else {
+            1.0
+        };
+        let next = ideal_delta_v_km_s.max(0.0) / efficiency;
+        let converged = (next - delta_v).abs() <= 1e-12 * next.max(1.0);
+        delta_v = next;
+        if converged {
+            break;
+        }
+    }
+
+    let burn_duration_s = burn_time(delta_v);
+    Some(FiniteBurn {
+        delta_v_km_s: delta_v,
+        burn_duration_s,
+        burn_arc_rad: arc(burn_duration_s),
+    })
+}
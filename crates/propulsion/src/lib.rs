@@ -1,5 +1,7 @@
 //! Propulsion mode descriptors and vehicle mass properties.
 
+use solar_core::constants::G0;
+
 /// Simple propulsion mode enumeration. Additional parameters can be layered on per mode.
 #[derive(Debug, Clone)]
 pub enum PropulsionMode {
@@ -14,12 +16,76 @@ pub enum PropulsionMode {
         max_thrust_newtons: f64,
         isp_seconds: f64,
         max_acceleration_m_s2: Option<f64>,
+        /// Scale thrust with available solar power, `(r_ref / r)²` beyond the reference distance.
+        power_limited: bool,
+        /// Heliocentric distance (AU) at which the full `max_thrust_newtons` is available.
+        reference_distance_au: f64,
+    },
+    /// Throttleable engine following a piecewise-constant thrust schedule over the burn.
+    Throttled {
+        max_thrust_newtons: f64,
+        isp_seconds: f64,
+        /// `(time_fraction, throttle)` breakpoints sorted by fraction of the total transfer time;
+        /// each throttle in `[-1, 1]` holds until the next breakpoint, negative values thrusting
+        /// retrograde and zero coasting.
+        throttle_profile: Vec<(f64, f64)>,
     },
     /// Hybrid strategies (placeholder for future modelling).
     Hybrid,
 }
 
+impl PropulsionMode {
+    /// Effective exhaust velocity `isp · g0` in m/s; `None` for modes without a specific impulse.
+    pub fn exhaust_velocity_m_s(&self) -> Option<f64> {
+        match self {
+            Self::Impulsive { isp_seconds, .. }
+            | Self::Continuous { isp_seconds, .. }
+            | Self::Throttled { isp_seconds, .. } => Some(isp_seconds * G0),
+            Self::Hybrid => None,
+        }
+    }
+
+    /// Thrust available at `distance_au` from the Sun (N).
+    ///
+    /// Power-limited continuous drives deliver `max_thrust · min(1, (r_ref/r)²)`; all
+    /// other modes are distance independent. Throttled engines report their full-throttle thrust.
+    pub fn available_thrust_newtons(&self, distance_au: f64) -> Option<f64> {
+        match self {
+            Self::Impulsive {
+                max_thrust_newtons, ..
+            } => *max_thrust_newtons,
+            Self::Continuous {
+                max_thrust_newtons,
+                power_limited,
+                reference_distance_au,
+                ..
+            } => {
+                if *power_limited && distance_au > 0.0 {
+                    let ratio = reference_distance_au / distance_au;
+                    Some(max_thrust_newtons * (ratio * ratio).min(1.0))
+                } else {
+                    Some(*max_thrust_newtons)
+                }
+            }
+            Self::Throttled {
+                max_thrust_newtons, ..
+            } => Some(*max_thrust_newtons),
+            Self::Hybrid => None,
+        }
+    }
+}
+
+/// Reference distance (AU) assumed for power-limited drives that do not specify one.
+pub const DEFAULT_POWER_REFERENCE_AU: f64 = 1.0;
+
+/// Bisection steps for [`Vehicle::deliverable_payload_kg`]; resolves the payload to well under
+/// a gram for any realistic vehicle.
+const PAYLOAD_BISECTION_STEPS: usize = 60;
+
 /// Basic vehicle definition used to check feasibility across mission legs.
+///
+/// `dry_mass_kg`, `propellant_mass_kg` and `propulsion` describe the final (core) stage, which
+/// is never jettisoned. Continuous-thrust solvers carry any lower `stages` as inert mass.
 #[derive(Debug, Clone)]
 pub struct Vehicle {
     pub name: String,
@@ -27,20 +93,259 @@ pub struct Vehicle {
     pub propellant_mass_kg: f64,
     pub propulsion: PropulsionMode,
     pub aero: Option<VehicleAero>,
+    /// Lower stages in firing order, each dropped once its propellant is spent; empty for
+    /// single-stage vehicles.
+    pub stages: Vec<Stage>,
+}
+
+/// A jettisonable stage burned before the vehicle's core propulsion.
+#[derive(Debug, Clone)]
+pub struct Stage {
+    pub dry_mass_kg: f64,
+    pub propellant_mass_kg: f64,
+    pub propulsion: PropulsionMode,
 }
 
 impl Vehicle {
-    /// Convenience accessor for total initial mass.
+    /// Convenience accessor for total initial mass, including any lower stages.
     pub fn initial_mass_kg(&self) -> f64 {
+        self.core_mass_kg()
+            + self
+                .stages
+                .iter()
+                .map(|stage| stage.dry_mass_kg + stage.propellant_mass_kg)
+                .sum::<f64>()
+    }
+
+    /// Fuelled mass of the core stage alone, i.e. after every lower stage has been dropped.
+    pub fn core_mass_kg(&self) -> f64 {
         self.dry_mass_kg + self.propellant_mass_kg
     }
 
+    /// Propellant carried across all stages.
+    pub fn total_propellant_kg(&self) -> f64 {
+        self.propellant_mass_kg
+            + self
+                .stages
+                .iter()
+                .map(|stage| stage.propellant_mass_kg)
+                .sum::<f64>()
+    }
+
+    /// Ideal delta-v (km/s) of burning every stage dry in order, from the rocket equation with
+    /// each stage's Isp; `None` when a stage's propulsion mode has no specific impulse.
+    pub fn total_delta_v_km_s(&self) -> Option<f64> {
+        let core = (&self.propulsion, self.dry_mass_kg, self.propellant_mass_kg);
+        let mut mass = self.initial_mass_kg();
+        let mut total_m_s = 0.0;
+        for (propulsion, dry_kg, propellant_kg) in self
+            .stages
+            .iter()
+            .map(|stage| {
+                (
+                    &stage.propulsion,
+                    stage.dry_mass_kg,
+                    stage.propellant_mass_kg,
+                )
+            })
+            .chain(std::iter::once(core))
+        {
+            let ve = propulsion.exhaust_velocity_m_s().filter(|ve| *ve > 0.0)?;
+            total_m_s += ve * (mass / (mass - propellant_kg)).ln();
+            mass -= dry_kg + propellant_kg;
+        }
+        Some(total_m_s / 1_000.0)
+    }
+
+    /// Initial over final mass after delivering `dv_km_s` through the stages; `None` when the
+    /// vehicle runs dry first or a stage has no specific impulse.
+    pub fn mass_ratio_for_dv(&self, dv_km_s: f64) -> Option<f64> {
+        let mut staging = self.staging();
+        let burn = staging.burn(dv_km_s)?;
+        (burn.shortfall_km_s == 0.0).then(|| self.initial_mass_kg() / staging.mass_kg())
+    }
+
+    /// Largest payload (kg) riding on the core stage with which the vehicle still delivers
+    /// `dv_km_s`, found by bisection on [`Vehicle::mass_ratio_for_dv`].
+    ///
+    /// Returns `None` when even the unloaded vehicle falls short, and infinity when the burn is
+    /// too small for any finite payload to matter.
+    pub fn deliverable_payload_kg(&self, dv_km_s: f64) -> Option<f64> {
+        let delivers = |payload_kg: f64| {
+            let mut loaded = self.clone();
+            loaded.dry_mass_kg += payload_kg;
+            loaded.mass_ratio_for_dv(dv_km_s).is_some()
+        };
+        if !delivers(0.0) {
+            return None;
+        }
+        let mut low = 0.0;
+        let mut high = self.initial_mass_kg().max(1.0);
+        let mut doublings = 0;
+        while delivers(high) {
+            low = high;
+            high *= 2.0;
+            doublings += 1;
+            if doublings > 64 {
+                return Some(f64::INFINITY);
+            }
+        }
+        for _ in 0..PAYLOAD_BISECTION_STEPS {
+            let mid = 0.5 * (low + high);
+            if delivers(mid) {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        Some(low)
+    }
+
+    /// Fully fuelled staging state, ready to size burns in order with [`Staging::burn`].
+    pub fn staging(&self) -> Staging<'_> {
+        let remaining_kg = self
+            .stages
+            .first()
+            .map_or(self.propellant_mass_kg, |stage| stage.propellant_mass_kg);
+        Staging {
+            vehicle: self,
+            stage: 0,
+            remaining_kg,
+            mass_kg: self.initial_mass_kg(),
+        }
+    }
+
     /// Reference entry mass to use for aerobraking when available.
     pub fn reference_entry_mass_kg(&self) -> f64 {
         self.aero
             .as_ref()
             .and_then(|a| a.entry_mass_ref_kg)
-            .unwrap_or_else(|| self.initial_mass_kg())
+            .unwrap_or_else(|| self.core_mass_kg())
+    }
+}
+
+/// Propellant, burn time and staging events of one manoeuvre sized by [`Staging::burn`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StagedBurn {
+    pub propellant_kg: f64,
+    pub burn_time_s: f64,
+    /// Stages dropped during (or immediately before) the burn.
+    pub stages_jettisoned: usize,
+    /// Delta-v (km/s) left undelivered once the core stage ran dry; zero for a complete burn.
+    pub shortfall_km_s: f64,
+}
+
+/// Mass bookkeeping for a vehicle burning through its stages in order.
+///
+/// Each burn draws on the active stage with that stage's Isp and thrust; when its propellant
+/// runs out mid-burn the stage's dry mass is dropped and the next stage finishes the burn.
+#[derive(Debug, Clone)]
+pub struct Staging<'a> {
+    vehicle: &'a Vehicle,
+    /// Index into `vehicle.stages`; equal to its length once only the core stage remains.
+    stage: usize,
+    remaining_kg: f64,
+    mass_kg: f64,
+}
+
+impl<'a> Staging<'a> {
+    /// Current vehicle mass (kg).
+    pub fn mass_kg(&self) -> f64 {
+        self.mass_kg
+    }
+
+    /// Deliver `delta_v_km_s` with the rocket equation, staging as needed.
+    ///
+    /// Returns `None` when an active stage has no specific impulse (hybrid propulsion). Running
+    /// out of propellant is not an error: the undelivered part is reported as the shortfall.
+    pub fn burn(&mut self, delta_v_km_s: f64) -> Option<StagedBurn> {
+        let mut burn = StagedBurn {
+            propellant_kg: 0.0,
+            burn_time_s: 0.0,
+            stages_jettisoned: 0,
+            shortfall_km_s: 0.0,
+        };
+        let mut needed_m_s = delta_v_km_s.max(0.0) * 1_000.0;
+        while needed_m_s > 1e-6 {
+            let propulsion = self.propulsion();
+            let ve = propulsion.exhaust_velocity_m_s().filter(|ve| *ve > 0.0)?;
+            let mass_before = self.mass_kg;
+            let capacity_m_s = if self.remaining_kg > 0.0 {
+                ve * (mass_before / (mass_before - self.remaining_kg)).ln()
+            } else {
+                0.0
+            };
+            let propellant = if needed_m_s <= capacity_m_s {
+                mass_before * (1.0 - (-needed_m_s / ve).exp())
+            } else {
+                self.remaining_kg
+            };
+            self.remaining_kg = (self.remaining_kg - propellant).max(0.0);
+            self.mass_kg -= propellant;
+            burn.propellant_kg += propellant;
+            burn.burn_time_s += burn_time_s(propulsion, mass_before, self.mass_kg, ve);
+            needed_m_s -= capacity_m_s.min(needed_m_s);
+
+            if needed_m_s > 1e-6 {
+                if self.stage >= self.vehicle.stages.len() {
+                    burn.shortfall_km_s = needed_m_s / 1_000.0;
+                    break;
+                }
+                self.jettison();
+                burn.stages_jettisoned += 1;
+            }
+        }
+        Some(burn)
+    }
+
+    fn propulsion(&self) -> &'a PropulsionMode {
+        self.vehicle
+            .stages
+            .get(self.stage)
+            .map_or(&self.vehicle.propulsion, |stage| &stage.propulsion)
+    }
+
+    /// Drop the spent active stage and load the next one's propellant.
+    fn jettison(&mut self) {
+        self.mass_kg -= self.vehicle.stages[self.stage].dry_mass_kg;
+        self.stage += 1;
+        self.remaining_kg = self
+            .vehicle
+            .stages
+            .get(self.stage)
+            .map_or(self.vehicle.propellant_mass_kg, |stage| {
+                stage.propellant_mass_kg
+            });
+    }
+}
+
+/// Time (s) to burn from `mass_before` to `mass_after` at full thrust, respecting a continuous
+/// drive's acceleration limit at the mean mass; zero when the mode has no thrust limit.
+fn burn_time_s(propulsion: &PropulsionMode, mass_before: f64, mass_after: f64, ve: f64) -> f64 {
+    let propellant = mass_before - mass_after;
+    let thrust = match propulsion {
+        PropulsionMode::Impulsive {
+            max_thrust_newtons, ..
+        } => *max_thrust_newtons,
+        PropulsionMode::Continuous {
+            max_thrust_newtons,
+            max_acceleration_m_s2,
+            ..
+        } => {
+            let mean_mass = 0.5 * (mass_before + mass_after);
+            Some(match max_acceleration_m_s2 {
+                Some(limit) => max_thrust_newtons.min(limit * mean_mass),
+                None => *max_thrust_newtons,
+            })
+        }
+        PropulsionMode::Throttled {
+            max_thrust_newtons, ..
+        } => Some(*max_thrust_newtons),
+        PropulsionMode::Hybrid => None,
+    };
+    match thrust {
+        Some(thrust) if thrust > 0.0 && propellant > 0.0 => propellant * ve / thrust,
+        _ => 0.0,
     }
 }
 
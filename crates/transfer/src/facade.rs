@@ -1,18 +1,30 @@
 //! Re-exported APIs for consumers of the transfer crate.
 
 pub use crate::mission::arrival::{
-    AerobrakeReport, AerobrakingOption, ArrivalConfig, ArrivalError, ArrivalPlan,
+    AerobrakeReport, AerobrakingOption, ArrivalConfig, ArrivalError, ArrivalPlan, CampaignReport,
+};
+pub use crate::mission::departure::{
+    DepartureConfig, DepartureError, DeparturePhasing, DeparturePlan, VInfinityTarget,
+    departure_phasing,
 };
-pub use crate::mission::departure::{DepartureConfig, DepartureError, DeparturePlan};
 pub use crate::mission::interplanetary::{
-    InterplanetaryConfig, InterplanetaryError, InterplanetaryPlan,
+    InterplanetaryConfig, InterplanetaryError, InterplanetaryPlan, TrajectorySample,
+    propagate_tangential,
+};
+pub use crate::mission::{
+    GM_MISMATCH_TOLERANCE, GmMismatch, MissionConfig, MissionError, MissionProfile, PhaseTimings,
+    gm_mismatches, kernel_constants, plan_mission, plan_mission_timed, plan_mission_with_cruise,
+    plan_mission_with_source,
+};
+pub use solar_propulsion::{
+    DEFAULT_POWER_REFERENCE_AU, PropulsionMode, Stage, StagedBurn, Staging, Vehicle, VehicleAero,
 };
-pub use crate::mission::{MissionConfig, MissionError, MissionProfile, plan_mission};
-pub use solar_propulsion::{PropulsionMode, Vehicle, VehicleAero};
 
 pub mod vehicle {
     use solar_config::{VehicleAeroConfig, VehicleConfig, VehiclePropulsionConfig};
-    use solar_propulsion::{PropulsionMode, Vehicle, VehicleAero};
+    use solar_propulsion::{
+        DEFAULT_POWER_REFERENCE_AU, PropulsionMode, Stage, Vehicle, VehicleAero,
+    };
     use thiserror::Error;
 
     /// Errors surfaced when selecting or converting vehicles.
@@ -28,15 +40,47 @@ pub mod vehicle {
 
     /// Convert a `VehicleConfig` into runtime `Vehicle` representation.
     pub fn from_config(config: &VehicleConfig) -> Result<Vehicle, VehicleError> {
-        let propulsion = match &config.propulsion {
+        let propulsion = to_propulsion_mode(&config.propulsion)?;
+        let stages = config
+            .stages
+            .iter()
+            .map(|stage| {
+                Ok(Stage {
+                    dry_mass_kg: stage.dry_mass_kg,
+                    propellant_mass_kg: stage.propellant_mass_kg,
+                    propulsion: to_propulsion_mode(&stage.propulsion)?,
+                })
+            })
+            .collect::<Result<Vec<_>, VehicleError>>()?;
+
+        let aero = config.aero.as_ref().map(to_vehicle_aero);
+
+        Ok(Vehicle {
+            name: config.name.clone(),
+            dry_mass_kg: config.dry_mass_kg,
+            propellant_mass_kg: config.propellant_mass_kg,
+            propulsion,
+            aero,
+            stages,
+        })
+    }
+
+    fn to_propulsion_mode(
+        config: &VehiclePropulsionConfig,
+    ) -> Result<PropulsionMode, VehicleError> {
+        let propulsion = match config {
             VehiclePropulsionConfig::Continuous {
                 max_thrust_newtons,
                 isp_seconds,
                 max_acceleration_m_s2,
+                power_limited,
+                reference_distance_au,
             } => PropulsionMode::Continuous {
                 max_thrust_newtons: *max_thrust_newtons,
                 isp_seconds: *isp_seconds,
                 max_acceleration_m_s2: *max_acceleration_m_s2,
+                power_limited: *power_limited,
+                reference_distance_au: reference_distance_au.unwrap_or(DEFAULT_POWER_REFERENCE_AU),
             },
             VehiclePropulsionConfig::Impulsive {
                 max_delta_v_km_s,
@@ -47,20 +91,20 @@ pub mod vehicle {
                 isp_seconds: *isp_seconds,
                 max_thrust_newtons: *max_thrust_newtons,
             },
+            VehiclePropulsionConfig::Throttled {
+                max_thrust_newtons,
+                isp_seconds,
+                throttle_profile,
+            } => PropulsionMode::Throttled {
+                max_thrust_newtons: *max_thrust_newtons,
+                isp_seconds: *isp_seconds,
+                throttle_profile: throttle_profile.clone(),
+            },
             VehiclePropulsionConfig::Unsupported => {
                 return Err(VehicleError::UnsupportedPropulsion);
             }
         };
-
-        let aero = config.aero.as_ref().map(to_vehicle_aero);
-
-        Ok(Vehicle {
-            name: config.name.clone(),
-            dry_mass_kg: config.dry_mass_kg,
-            propellant_mass_kg: config.propellant_mass_kg,
-            propulsion,
-            aero,
-        })
+        Ok(propulsion)
     }
 
     /// Select a vehicle from the catalog by optional name, defaulting to continuous propulsion entries.
@@ -88,6 +132,34 @@ pub mod vehicle {
         from_config(chosen)
     }
 
+    /// A catalog vehicle scored by [`rank`].
+    #[derive(Debug, Clone)]
+    pub struct RankedVehicle {
+        pub vehicle: Vehicle,
+        /// Mass ratio of the unloaded vehicle over the burn.
+        pub mass_ratio: f64,
+        /// Payload (kg) the vehicle can still carry through the burn.
+        pub payload_kg: f64,
+    }
+
+    /// Rank catalog vehicles by the payload they can carry through a `dv_km_s` burn, largest
+    /// first. Vehicles with unsupported propulsion or too little delta-v are left out.
+    pub fn rank(configs: &[VehicleConfig], dv_km_s: f64) -> Vec<RankedVehicle> {
+        let mut ranked: Vec<RankedVehicle> = configs
+            .iter()
+            .filter_map(|cfg| from_config(cfg).ok())
+            .filter_map(|vehicle| {
+                Some(RankedVehicle {
+                    mass_ratio: vehicle.mass_ratio_for_dv(dv_km_s)?,
+                    payload_kg: vehicle.deliverable_payload_kg(dv_km_s)?,
+                    vehicle,
+                })
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.payload_kg.total_cmp(&a.payload_kg));
+        ranked
+    }
+
     fn to_vehicle_aero(config: &VehicleAeroConfig) -> VehicleAero {
         VehicleAero {
             attitude: config.attitude.clone(),
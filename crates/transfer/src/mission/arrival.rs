@@ -1,20 +1,27 @@
 //! Arrival phase: capture into destination parking orbit, optionally with aerobraking support.
 
-use solar_config::PlanetConfig;
-use solar_ephem_spice::{self as ephemeris, EphemerisError};
+use solar_config::{ConstantsSet, PlanetConfig};
+use solar_ephem_spice::EphemerisError;
+use solar_ephem_spice::source::{EphemerisSource, SpiceSource};
 
 use super::interplanetary::{InterplanetaryConfig, InterplanetaryPlan};
 use solar_aerobrake::{
-    AerobrakeRequest as AeroRequest, PlanetEntryContext as AeroPlanet,
+    AerobrakeRequest as AeroRequest, PlanetEntryContext as AeroPlanet, SUTTON_GRAVES_K,
     VehicleEntryContext as AeroVehicle, simulate_ballistic_pass,
 };
 use solar_impulsive::lambert;
-use solar_orbits::{capture_delta_v, norm3};
-use solar_propulsion::{PropulsionMode, Vehicle};
+use solar_orbits::{capture_delta_v, capture_delta_v_j2, norm3, spiral_time};
+use solar_propulsion::{PropulsionMode, Vehicle, VehicleAero};
+use std::f64::consts::PI;
 
-const MU_SUN: f64 = 1.327_124_400_18e11;
 const MAX_AEROBRAKE_DYNAMIC_PRESSURE_PA: f64 = 80_000.0;
 const MAX_AEROBRAKE_ACCEL_M_S2: f64 = 39.24; // ≈ 4 g
+/// Periapsis raise applied by each trim burn when a pass would breach the heating limits.
+const CAMPAIGN_TRIM_STEP_M: f64 = 1_000.0;
+/// Safety cap on drag passes plus trims in a single campaign.
+const MAX_CAMPAIGN_STEPS: usize = 5_000;
+/// Near-parabolic v∞ used to drive the hyperbolic pass integrator for bound orbits.
+const CAMPAIGN_PASS_VINF_M_S: f64 = 1.0;
 
 /// Aerobraking option describing whether atmospheric drag can reduce capture delta-v.
 #[derive(Debug, Clone, Copy)]
@@ -35,6 +42,9 @@ pub struct ArrivalConfig {
     pub encounter_epoch: String,
     pub propulsion_mode: PropulsionMode,
     pub aerobraking: Option<AerobrakingOption>,
+    /// Reject aerobrake passes whose peak stagnation heat flux exceeds this value (W/m²);
+    /// `None` leaves heating unconstrained.
+    pub max_heat_flux_w_m2: Option<f64>,
 }
 
 /// Result of the arrival planning phase.
@@ -55,6 +65,30 @@ pub struct AerobrakeReport {
     pub peak_deceleration_m_s2: f64,
     pub periapsis_altitude_m: f64,
     pub integration_steps: usize,
+    pub pass_duration_s: Option<f64>,
+    pub atmospheric_time_s: f64,
+    pub peak_heat_flux_w_m2: f64,
+    pub integrated_heat_load_j_m2: f64,
+}
+
+/// Effective nose radius for stagnation heating: the vehicle's reference radius, falling back
+/// to the radius of a disc of the reference area and then to 1 m.
+fn nose_radius_m(aero: &VehicleAero) -> f64 {
+    aero.ref_diameter_m
+        .map(|d| 0.5 * d)
+        .filter(|r| *r > 0.0)
+        .or_else(|| (aero.ref_area_m2 > 0.0).then(|| (aero.ref_area_m2 / PI).sqrt()))
+        .unwrap_or(1.0)
+}
+
+/// Whether a pass stays within the structural and (optional) heating limits.
+fn within_limits(
+    result: &solar_aerobrake::AerobrakeResult,
+    max_heat_flux_w_m2: Option<f64>,
+) -> bool {
+    result.peak_dynamic_pressure_pa <= MAX_AEROBRAKE_DYNAMIC_PRESSURE_PA
+        && result.peak_deceleration_m_s2 <= MAX_AEROBRAKE_ACCEL_M_S2
+        && max_heat_flux_w_m2.is_none_or(|limit| result.peak_heat_flux_w_m2 <= limit)
 }
 
 fn optimize_periapsis_altitude(
@@ -63,6 +97,7 @@ fn optimize_periapsis_altitude(
     vinf_m_s: f64,
     lower_alt_m: f64,
     upper_alt_m: f64,
+    max_heat_flux_w_m2: Option<f64>,
 ) -> Option<solar_aerobrake::AerobrakeResult> {
     if upper_alt_m <= lower_alt_m {
         return None;
@@ -89,9 +124,7 @@ fn optimize_periapsis_altitude(
                 initial_vinf_m_s: vinf_m_s,
             };
             if let Ok(result) = simulate_ballistic_pass(&request) {
-                if result.peak_dynamic_pressure_pa > MAX_AEROBRAKE_DYNAMIC_PRESSURE_PA
-                    || result.peak_deceleration_m_s2 > MAX_AEROBRAKE_ACCEL_M_S2
-                {
+                if !within_limits(&result, max_heat_flux_w_m2) {
                     continue;
                 }
                 if result.final_vinf_m_s < best_score {
@@ -128,6 +161,8 @@ pub enum ArrivalError {
     UnsupportedPropulsion,
     #[error("lambert solver failed: {0}")]
     Lambert(String),
+    #[error("aerobraking campaign not possible: {0}")]
+    Campaign(String),
 }
 
 /// Compute the capture manoeuvre required at the destination body, optionally modelling aerobraking.
@@ -142,11 +177,37 @@ pub fn plan_arrival(
     cruise_config: &InterplanetaryConfig,
     aerobraking: Option<AerobrakingOption>,
     cruise: &InterplanetaryPlan,
+    constants: &ConstantsSet,
+) -> Result<ArrivalPlan, ArrivalError> {
+    plan_arrival_with_source(
+        &SpiceSource,
+        vehicle,
+        config,
+        destination,
+        cruise_config,
+        aerobraking,
+        cruise,
+        constants,
+    )
+}
+
+/// Same as [`plan_arrival`], resolving a fixed arrival epoch through `source` instead of SPICE.
+#[allow(clippy::too_many_arguments)]
+pub fn plan_arrival_with_source(
+    source: &dyn EphemerisSource,
+    vehicle: &Vehicle,
+    config: &ArrivalConfig,
+    destination: &PlanetConfig,
+    cruise_config: &InterplanetaryConfig,
+    aerobraking: Option<AerobrakingOption>,
+    cruise: &InterplanetaryPlan,
+    constants: &ConstantsSet,
 ) -> Result<ArrivalPlan, ArrivalError> {
+    let destination_mu = constants.body_mu(destination);
     let parking_radius = destination.radius_km + config.target_parking_altitude_km;
     let tof_seconds = if let Some(arrival_epoch) = &cruise_config.arrival_epoch {
-        let departure_et = ephemeris::epoch_seconds(&cruise_config.departure_epoch)?;
-        let arrival_et = ephemeris::epoch_seconds(arrival_epoch)?;
+        let departure_et = source.epoch_seconds(&cruise_config.departure_epoch)?;
+        let arrival_et = source.epoch_seconds(arrival_epoch)?;
         (arrival_et - departure_et).abs().max(1.0)
     } else {
         cruise.time_of_flight_days * 86_400.0
@@ -170,7 +231,7 @@ pub fn plan_arrival(
                 cruise.departure_state.position_km,
                 *arrival_position,
                 tof_seconds,
-                MU_SUN,
+                constants.mu_sun_km3_s2,
                 short,
             ) {
                 let v_infinity_vec = [
@@ -223,17 +284,22 @@ pub fn plan_arrival(
                         .unwrap_or(target_periapsis_m + atmosphere.scale_height_km * 1_000.0 * 6.0);
 
                     let base_planet = AeroPlanet {
-                        mu_m3_s2: destination.mu_km3_s2 * 1.0e9,
-                        radius_m: destination.radius_km * 1_000.0,
-                        surface_density_kg_m3: atmosphere.surface_density_kg_m3,
-                        scale_height_m: atmosphere.scale_height_km * 1_000.0,
-                        target_periapsis_altitude_m: target_periapsis_m,
-                        exit_altitude_m,
+                        sutton_graves_k: atmosphere.sutton_graves_k.unwrap_or(SUTTON_GRAVES_K),
+                        ..AeroPlanet::exponential(
+                            destination_mu * 1.0e9,
+                            destination.radius_km * 1_000.0,
+                            atmosphere.surface_density_kg_m3,
+                            atmosphere.scale_height_km * 1_000.0,
+                            target_periapsis_m,
+                            exit_altitude_m,
+                        )
                     };
 
                     let vehicle_ctx = AeroVehicle {
                         ballistic_coefficient_kg_m2: beta,
                         lift_to_drag: vehicle_aero.lift_to_drag,
+                        nose_radius_m: nose_radius_m(vehicle_aero),
+                        bank_angle_schedule: None,
                     };
 
                     let vinf_m_s = v_infinity * 1_000.0;
@@ -257,16 +323,15 @@ pub fn plan_arrival(
                                 vinf_m_s,
                                 lower,
                                 upper,
+                                config.max_heat_flux_w_m2,
                             )
                         }
                         AerobrakingOption::Disabled => None,
                     };
 
                     if let Some(result) = result_opt {
-                        if result.peak_dynamic_pressure_pa > MAX_AEROBRAKE_DYNAMIC_PRESSURE_PA
-                            || result.peak_deceleration_m_s2 > MAX_AEROBRAKE_ACCEL_M_S2
-                        {
-                            // Aerobrake would exceed structural limits; ignore.
+                        if !within_limits(&result, config.max_heat_flux_w_m2) {
+                            // Aerobrake would exceed structural or heating limits; ignore.
                         } else {
                             let delta_v_drag_km_s = result.delta_v_drag_m_s / 1_000.0;
                             let final_vinf_km_s = (result.final_vinf_m_s / 1_000.0).max(0.0);
@@ -278,6 +343,10 @@ pub fn plan_arrival(
                                 peak_deceleration_m_s2: result.peak_deceleration_m_s2,
                                 periapsis_altitude_m: result.periapsis_altitude_m,
                                 integration_steps: result.integration_steps,
+                                pass_duration_s: result.pass_duration_s,
+                                atmospheric_time_s: result.atmospheric_time_s,
+                                peak_heat_flux_w_m2: result.peak_heat_flux_w_m2,
+                                integrated_heat_load_j_m2: result.integrated_heat_load_j_m2,
                             });
                         }
                     }
@@ -286,14 +355,38 @@ pub fn plan_arrival(
         }
     }
 
-    let mut capture_delta_v =
-        capture_delta_v(destination.mu_km3_s2, parking_radius, effective_v_infinity);
+    // Same equatorial parking-orbit assumption and J2 correction as the departure burn.
+    let mut capture_delta_v = if destination.j2 != 0.0 {
+        capture_delta_v_j2(
+            destination_mu,
+            parking_radius,
+            effective_v_infinity,
+            destination.j2,
+            destination.radius_km,
+            0.0,
+        )
+    } else {
+        capture_delta_v(destination_mu, parking_radius, effective_v_infinity)
+    };
     capture_delta_v = capture_delta_v.max(0.0);
 
+    // Continuous-thrust vehicles spiral down to the parking orbit; initial mass keeps the estimate
+    // conservative because cruise propellant use is not tracked here.
     let burn_duration = match vehicle.propulsion {
-        PropulsionMode::Continuous { .. } => None,
+        PropulsionMode::Continuous {
+            max_thrust_newtons,
+            isp_seconds,
+            ..
+        } => spiral_time(
+            destination_mu,
+            max_thrust_newtons,
+            vehicle.initial_mass_kg(),
+            isp_seconds,
+            f64::INFINITY,
+            parking_radius,
+        ),
         PropulsionMode::Impulsive { .. } => None,
-        PropulsionMode::Hybrid => None,
+        PropulsionMode::Throttled { .. } | PropulsionMode::Hybrid => None,
     };
 
     Ok(ArrivalPlan {
@@ -303,3 +396,158 @@ pub fn plan_arrival(
         aerobrake_report,
     })
 }
+
+/// Outcome of a multi-pass aerobraking campaign from [`plan_aerobraking_campaign`].
+#[derive(Debug, Clone)]
+pub struct CampaignReport {
+    /// One report per drag pass; passes are bound, so `final_vinf_km_s` is always zero.
+    pub passes: Vec<AerobrakeReport>,
+    /// Elapsed time across all post-pass orbits.
+    pub total_days: f64,
+    /// Apoapsis burns spent raising periapsis whenever a pass would exceed the heating limits.
+    pub trim_burns_dv_km_s: f64,
+}
+
+/// Walk an elliptical capture orbit down from `initial_apoapsis_altitude_km` to
+/// `target_apoapsis_altitude_km` with repeated drag passes at `periapsis_altitude_km`.
+///
+/// Each pass runs [`simulate_ballistic_pass`] on a near-parabolic approach and rescales drag
+/// by the orbit's actual periapsis speed (Δv ∝ v, dynamic pressure and deceleration ∝ v²,
+/// heat flux ∝ v³).
+/// The drag impulse is applied at periapsis to lower the apoapsis. Whenever a pass would exceed
+/// the dynamic-pressure or deceleration limit, or the optional `max_heat_flux_w_m2`, a trim burn
+/// at apoapsis raises periapsis by 1 km and the pass is retried.
+pub fn plan_aerobraking_campaign(
+    vehicle: &Vehicle,
+    destination: &PlanetConfig,
+    constants: &ConstantsSet,
+    initial_apoapsis_altitude_km: f64,
+    target_apoapsis_altitude_km: f64,
+    periapsis_altitude_km: f64,
+    max_heat_flux_w_m2: Option<f64>,
+) -> Result<CampaignReport, ArrivalError> {
+    let atmosphere = destination
+        .atmosphere
+        .as_ref()
+        .filter(|atmosphere| atmosphere.exists)
+        .ok_or_else(|| ArrivalError::Campaign(format!("{} has no atmosphere", destination.name)))?;
+    let aero = vehicle.aero.as_ref().ok_or_else(|| {
+        ArrivalError::Campaign(format!("{} has no aerodynamic data", vehicle.name))
+    })?;
+    let beta = aero
+        .ballistic_coefficient(vehicle.reference_entry_mass_kg())
+        .ok_or_else(|| {
+            ArrivalError::Campaign(format!("{} has no ballistic coefficient", vehicle.name))
+        })?;
+    if target_apoapsis_altitude_km < periapsis_altitude_km
+        || initial_apoapsis_altitude_km < target_apoapsis_altitude_km
+    {
+        return Err(ArrivalError::Campaign(
+            "apoapsis altitudes must satisfy periapsis <= target <= initial".to_string(),
+        ));
+    }
+
+    let mu = constants.body_mu(destination) * 1.0e9;
+    let radius_m = destination.radius_km * 1_000.0;
+    let exit_altitude_m = destination
+        .entry_target
+        .as_ref()
+        .map(|t| t.atm_exit_altitude_m)
+        .unwrap_or(periapsis_altitude_km * 1_000.0 + atmosphere.scale_height_km * 6_000.0);
+    let vehicle_ctx = AeroVehicle {
+        ballistic_coefficient_kg_m2: beta,
+        lift_to_drag: aero.lift_to_drag,
+        nose_radius_m: nose_radius_m(aero),
+        bank_angle_schedule: None,
+    };
+
+    let target_ra = radius_m + target_apoapsis_altitude_km * 1_000.0;
+    let mut ra = radius_m + initial_apoapsis_altitude_km * 1_000.0;
+    let mut periapsis_alt_m = periapsis_altitude_km * 1_000.0;
+    let mut passes = Vec::new();
+    let mut total_seconds = 0.0;
+    let mut trim_dv_m_s = 0.0;
+
+    for _ in 0..MAX_CAMPAIGN_STEPS {
+        if ra <= target_ra {
+            return Ok(CampaignReport {
+                passes,
+                total_days: total_seconds / 86_400.0,
+                trim_burns_dv_km_s: trim_dv_m_s / 1_000.0,
+            });
+        }
+        if periapsis_alt_m >= exit_altitude_m {
+            return Err(ArrivalError::Campaign(format!(
+                "periapsis raised above the sensible atmosphere after {} passes",
+                passes.len()
+            )));
+        }
+
+        let rp = radius_m + periapsis_alt_m;
+        let v_periapsis = (mu * (2.0 / rp - 2.0 / (ra + rp))).sqrt();
+        let v_escape = (2.0 * mu / rp + CAMPAIGN_PASS_VINF_M_S.powi(2)).sqrt();
+        let speed_ratio = v_periapsis / v_escape;
+        let request = AeroRequest {
+            planet: AeroPlanet {
+                sutton_graves_k: atmosphere.sutton_graves_k.unwrap_or(SUTTON_GRAVES_K),
+                ..AeroPlanet::exponential(
+                    mu,
+                    radius_m,
+                    atmosphere.surface_density_kg_m3,
+                    atmosphere.scale_height_km * 1_000.0,
+                    periapsis_alt_m,
+                    exit_altitude_m,
+                )
+            },
+            vehicle: vehicle_ctx.clone(),
+            initial_vinf_m_s: CAMPAIGN_PASS_VINF_M_S,
+        };
+        let result = simulate_ballistic_pass(&request)
+            .map_err(|err| ArrivalError::Campaign(err.to_string()))?;
+        let pass = solar_aerobrake::AerobrakeResult {
+            peak_dynamic_pressure_pa: result.peak_dynamic_pressure_pa * speed_ratio * speed_ratio,
+            peak_deceleration_m_s2: result.peak_deceleration_m_s2 * speed_ratio * speed_ratio,
+            peak_heat_flux_w_m2: result.peak_heat_flux_w_m2 * speed_ratio.powi(3),
+            ..result
+        };
+
+        if !within_limits(&pass, max_heat_flux_w_m2) {
+            let raised_rp = rp + CAMPAIGN_TRIM_STEP_M;
+            let v_apoapsis = (mu * (2.0 / ra - 2.0 / (ra + rp))).sqrt();
+            let v_raised = (mu * (2.0 / ra - 2.0 / (ra + raised_rp))).sqrt();
+            trim_dv_m_s += (v_raised - v_apoapsis).abs();
+            periapsis_alt_m += CAMPAIGN_TRIM_STEP_M;
+            continue;
+        }
+
+        let delta_v_drag = pass.delta_v_drag_m_s * speed_ratio;
+        if delta_v_drag <= 0.0 {
+            return Err(ArrivalError::Campaign(format!(
+                "no drag at {:.1} km periapsis",
+                periapsis_alt_m / 1_000.0
+            )));
+        }
+        let v_after = (v_periapsis - delta_v_drag).max(0.0);
+        let sma = 1.0 / (2.0 / rp - v_after * v_after / mu);
+        ra = (2.0 * sma - rp).max(rp);
+        let period = 2.0 * std::f64::consts::PI * (sma.powi(3) / mu).sqrt();
+        total_seconds += period;
+
+        passes.push(AerobrakeReport {
+            delta_v_drag_km_s: delta_v_drag / 1_000.0,
+            final_vinf_km_s: 0.0,
+            peak_dynamic_pressure_pa: pass.peak_dynamic_pressure_pa,
+            peak_deceleration_m_s2: pass.peak_deceleration_m_s2,
+            periapsis_altitude_m: periapsis_alt_m,
+            integration_steps: pass.integration_steps,
+            pass_duration_s: Some(period),
+            atmospheric_time_s: pass.atmospheric_time_s / speed_ratio,
+            peak_heat_flux_w_m2: pass.peak_heat_flux_w_m2,
+            integrated_heat_load_j_m2: pass.integrated_heat_load_j_m2 * speed_ratio * speed_ratio,
+        });
+    }
+
+    Err(ArrivalError::Campaign(format!(
+        "target apoapsis not reached within {MAX_CAMPAIGN_STEPS} passes and trims"
+    )))
+}
@@ -1,15 +1,14 @@
 //! Departure phase: depart a parking orbit around the origin body and inject onto an interplanetary trajectory.
 
-use solar_config::PlanetConfig;
-use solar_ephem_spice::{self as ephemeris, EphemerisError};
+use solar_config::{ConstantsSet, PlanetConfig};
+use solar_ephem_spice::EphemerisError;
+use solar_ephem_spice::source::{EphemerisSource, SpiceSource};
 use solar_impulsive::lambert;
-use solar_orbits::{escape_delta_v, norm3};
+use solar_orbits::{escape_delta_v, escape_delta_v_j2, finite_burn_correction, norm3, spiral_time};
 use solar_propulsion::{PropulsionMode, Vehicle};
 
 use super::interplanetary::{InterplanetaryConfig, InterplanetaryPlan};
 
-const MU_SUN: f64 = 1.327_124_400_18e11;
-
 /// Configuration for the departure burn from a parking orbit.
 #[derive(Debug, Clone)]
 pub struct DepartureConfig {
@@ -19,19 +18,129 @@ pub struct DepartureConfig {
     pub parking_altitude_km: f64,
     /// Desired epoch for departure (UTC/TDB string understood by SPICE).
     pub departure_epoch: String,
-    /// Target hyperbolic excess vector magnitude (km/s) seeded by the interplanetary solver.
-    pub required_v_infinity: Option<f64>,
+    /// Target hyperbolic excess (km/s) seeded by the interplanetary solver or a launch constraint.
+    pub required_v_infinity: Option<VInfinityTarget>,
     /// Propulsion strategy to use for the departure phase.
     pub propulsion_mode: PropulsionMode,
+    /// Parking-orbit inclination to the ECLIPJ2000 plane (degrees); `None` or zero leaves the
+    /// orbit plane free and skips the phasing check.
+    pub parking_inclination_deg: Option<f64>,
+}
+
+/// Required departure hyperbolic excess, either as a magnitude or a full asymptote vector.
+#[derive(Debug, Clone, Copy)]
+pub enum VInfinityTarget {
+    /// Magnitude only (km/s); used as a fallback when no Lambert branch converges.
+    Magnitude(f64),
+    /// Heliocentric ECLIPJ2000 v-infinity vector (km/s); Lambert branches are scored by their
+    /// distance to it, which encodes a departure-asymptote (launch azimuth) constraint.
+    Vector([f64; 3]),
+}
+
+impl VInfinityTarget {
+    /// Magnitude of the target hyperbolic excess (km/s).
+    pub fn magnitude(&self) -> f64 {
+        match self {
+            Self::Magnitude(value) => *value,
+            Self::Vector(vector) => norm3(vector),
+        }
+    }
 }
 
 /// Result of the departure planning phase.
 #[derive(Debug, Clone)]
 pub struct DeparturePlan {
+    /// Delta-v to budget (km/s), including finite-burn gravity losses for thrust-limited stages.
     pub delta_v_required: f64,
+    /// Patched-conic escape delta-v (km/s) assuming an instantaneous burn.
+    pub ideal_delta_v_km_s: f64,
     pub burn_duration_s: Option<f64>,
     pub hyperbolic_excess_km_s: f64,
     pub parking_orbit_velocity_km_s: f64,
+    /// Extra delta-v (km/s) to fold the plane change the departure asymptote needs into the
+    /// escape burn; not included in `delta_v_required`. Zero when the parking orbit can reach
+    /// the asymptote or its inclination is unspecified.
+    pub plane_change_penalty_km_s: f64,
+    /// Parking-orbit RAAN (rad, ECLIPJ2000) that best aligns the orbit plane with the departure
+    /// asymptote; `None` without an inclination or asymptote direction.
+    pub optimal_parking_raan_rad: Option<f64>,
+}
+
+/// Reachability of a departure asymptote from an inclined circular parking orbit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeparturePhasing {
+    /// Declination of the asymptote above the reference plane (rad).
+    pub asymptote_declination_rad: f64,
+    /// Plane change (rad) still needed with the best-aligned parking orbit.
+    pub plane_change_rad: f64,
+    /// Extra delta-v (km/s) of combining that plane change with the escape burn.
+    pub plane_change_penalty_km_s: f64,
+    /// Ascending-node longitude (rad, `[0, 2π)`) of the best-aligned parking orbit.
+    pub optimal_raan_rad: f64,
+    /// True anomaly (rad) of the outgoing asymptote on the escape hyperbola, i.e. how far past
+    /// the burn point the spacecraft turns before heading along `v_inf`.
+    pub asymptote_true_anomaly_rad: f64,
+}
+
+/// Check whether a circular parking orbit of `inclination_rad` (to the frame of
+/// `v_infinity_km_s`) can contain the departure asymptote.
+///
+/// An orbit plane only reaches declinations up to its inclination (or `π − i` when
+/// retrograde); beyond that the escape burn must also turn the plane by the difference, which
+/// costs `√(v_c² + v_p² − 2 v_c v_p cos Δi) − (v_p − v_c)` when combined with the burn at
+/// periapsis speed `v_p`. The optimal RAAN places the asymptote on the ascending half of the
+/// orbit (or at its highest latitude when out of reach). Returns `None` for a zero v-infinity.
+pub fn departure_phasing(
+    v_infinity_km_s: [f64; 3],
+    inclination_rad: f64,
+    mu_km3_s2: f64,
+    parking_radius_km: f64,
+) -> Option<DeparturePhasing> {
+    let vinf = norm3(&v_infinity_km_s);
+    if vinf <= 0.0 {
+        return None;
+    }
+    let [x, y, z] = v_infinity_km_s.map(|component| component / vinf);
+    let declination = z.clamp(-1.0, 1.0).asin();
+    let right_ascension = y.atan2(x);
+
+    let sin_i = inclination_rad.sin();
+    let max_latitude = sin_i.abs().clamp(0.0, 1.0).asin();
+    let plane_change = (declination.abs() - max_latitude).max(0.0);
+    let argument_of_latitude = if sin_i.abs() > 1e-12 {
+        (declination.sin() / sin_i).clamp(-1.0, 1.0).asin()
+    } else {
+        0.0
+    };
+    let raan = right_ascension
+        - (inclination_rad.cos() * argument_of_latitude.sin()).atan2(argument_of_latitude.cos());
+
+    let circular_speed = (mu_km3_s2 / parking_radius_km).sqrt();
+    let periapsis_speed = (vinf * vinf + 2.0 * mu_km3_s2 / parking_radius_km).sqrt();
+    let penalty = if plane_change > 0.0 {
+        let combined = (circular_speed * circular_speed + periapsis_speed * periapsis_speed
+            - 2.0 * circular_speed * periapsis_speed * plane_change.cos())
+        .sqrt();
+        (combined - (periapsis_speed - circular_speed)).max(0.0)
+    } else {
+        0.0
+    };
+    let eccentricity = 1.0 + parking_radius_km * vinf * vinf / mu_km3_s2;
+
+    Some(DeparturePhasing {
+        asymptote_declination_rad: declination,
+        plane_change_rad: plane_change,
+        plane_change_penalty_km_s: penalty,
+        optimal_raan_rad: raan.rem_euclid(std::f64::consts::TAU),
+        asymptote_true_anomaly_rad: (-1.0 / eccentricity).acos(),
+    })
+}
+
+impl DeparturePlan {
+    /// Finite-burn penalty (km/s) over the impulsive escape burn.
+    pub fn gravity_loss_km_s(&self) -> f64 {
+        (self.delta_v_required - self.ideal_delta_v_km_s).max(0.0)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -47,28 +156,59 @@ pub enum DepartureError {
 /// Compute the departure manoeuvre required to transition from a parking orbit to the heliocentric leg.
 ///
 /// The solver uses a Lambert solution to determine the required hyperbolic excess vector relative to the
-/// origin body and converts this into a parking-orbit burn (patched-conic escape). Continuous-thrust and
-/// finite-burn corrections remain future enhancements, but delta-v and v-infinity are now physically grounded.
+/// origin body and converts this into a parking-orbit burn (patched-conic escape). Continuous-thrust
+/// vehicles additionally report an Edelbaum spiral duration, and impulsive stages with a thrust
+/// limit have their delta-v raised by the finite-burn gravity loss (the ideal value is kept on the
+/// plan).
 pub fn plan_departure(
     vehicle: &Vehicle,
     config: &DepartureConfig,
     origin: &PlanetConfig,
     cruise_config: &InterplanetaryConfig,
     cruise: &InterplanetaryPlan,
+    constants: &ConstantsSet,
+) -> Result<DeparturePlan, DepartureError> {
+    plan_departure_with_source(
+        &SpiceSource,
+        vehicle,
+        config,
+        origin,
+        cruise_config,
+        cruise,
+        constants,
+    )
+}
+
+/// Same as [`plan_departure`], resolving a fixed arrival epoch through `source` instead of SPICE.
+pub fn plan_departure_with_source(
+    source: &dyn EphemerisSource,
+    vehicle: &Vehicle,
+    config: &DepartureConfig,
+    origin: &PlanetConfig,
+    cruise_config: &InterplanetaryConfig,
+    cruise: &InterplanetaryPlan,
+    constants: &ConstantsSet,
 ) -> Result<DeparturePlan, DepartureError> {
+    let origin_mu = constants.body_mu(origin);
     let parking_radius = origin.radius_km + config.parking_altitude_km;
-    let circular_speed = (origin.mu_km3_s2 / parking_radius).sqrt();
+    let circular_speed = (origin_mu / parking_radius).sqrt();
 
-    let departure_et = ephemeris::epoch_seconds(&config.departure_epoch)?;
-    let arrival_et = if let Some(epoch) = &cruise_config.arrival_epoch {
-        ephemeris::epoch_seconds(epoch)?
+    // Without a fixed arrival epoch the cruise TOF is authoritative, so no SPICE lookup is needed.
+    let tof_seconds = if let Some(epoch) = &cruise_config.arrival_epoch {
+        let departure_et = source.epoch_seconds(&config.departure_epoch)?;
+        let arrival_et = source.epoch_seconds(epoch)?;
+        (arrival_et - departure_et).abs().max(1.0)
     } else {
-        departure_et + cruise.time_of_flight_days * 86_400.0
+        (cruise.time_of_flight_days * 86_400.0).abs().max(1.0)
     };
-    let tof_seconds = (arrival_et - departure_et).abs().max(1.0);
 
     let planet_velocity = cruise.departure_state.velocity_km_s;
-    let mut best_v_infinity: Option<f64> = None;
+    let target_vector = match config.required_v_infinity {
+        Some(VInfinityTarget::Vector(vector)) => Some(vector),
+        _ => None,
+    };
+    // (score, vector): score is the magnitude itself, or the miss distance from the target vector.
+    let mut best_v_infinity: Option<(f64, [f64; 3])> = None;
 
     let arrival_positions = [
         cruise.arrival_state.position_km,
@@ -85,7 +225,7 @@ pub fn plan_departure(
                 cruise.departure_state.position_km,
                 *arrival_position,
                 tof_seconds,
-                MU_SUN,
+                constants.mu_sun_km3_s2,
                 short,
             ) {
                 let v_infinity_vec = [
@@ -94,27 +234,102 @@ pub fn plan_departure(
                     lambert_v1[2] - planet_velocity[2],
                 ];
                 let vinf_mag = norm3(&v_infinity_vec);
-                if best_v_infinity.map_or(true, |current| vinf_mag < current) {
-                    best_v_infinity = Some(vinf_mag);
+                let score = match target_vector {
+                    Some(target) => norm3(&[
+                        v_infinity_vec[0] - target[0],
+                        v_infinity_vec[1] - target[1],
+                        v_infinity_vec[2] - target[2],
+                    ]),
+                    None => vinf_mag,
+                };
+                if best_v_infinity.map_or(true, |(current, _)| score < current) {
+                    best_v_infinity = Some((score, v_infinity_vec));
                 }
             }
         }
     }
 
-    let v_infinity = best_v_infinity.unwrap_or_else(|| config.required_v_infinity.unwrap_or(0.0));
+    let asymptote = best_v_infinity.map(|(_, vector)| vector).or(target_vector);
+    let v_infinity = best_v_infinity
+        .map(|(_, vector)| norm3(&vector))
+        .unwrap_or_else(|| {
+            config
+                .required_v_infinity
+                .map_or(0.0, |target| target.magnitude())
+        });
 
-    let delta_v = escape_delta_v(origin.mu_km3_s2, parking_radius, v_infinity);
+    // Parking orbits are taken as equatorial; oblate bodies get the first-order J2 correction.
+    let ideal_delta_v = if origin.j2 != 0.0 {
+        escape_delta_v_j2(
+            origin_mu,
+            parking_radius,
+            v_infinity,
+            origin.j2,
+            origin.radius_km,
+            0.0,
+        )
+    } else {
+        escape_delta_v(origin_mu, parking_radius, v_infinity)
+    };
 
-    let burn_duration = match vehicle.propulsion {
-        PropulsionMode::Continuous { .. } => None,
-        PropulsionMode::Impulsive { .. } => None,
-        PropulsionMode::Hybrid => None,
+    // Continuous-thrust vehicles spiral out from the parking orbit instead of burning impulsively;
+    // thrust-limited impulsive stages pay a gravity loss for the arc swept during the burn.
+    let (delta_v, burn_duration) = match vehicle.propulsion {
+        PropulsionMode::Continuous {
+            max_thrust_newtons,
+            isp_seconds,
+            ..
+        } => (
+            ideal_delta_v,
+            spiral_time(
+                origin_mu,
+                max_thrust_newtons,
+                vehicle.initial_mass_kg(),
+                isp_seconds,
+                parking_radius,
+                f64::INFINITY,
+            ),
+        ),
+        PropulsionMode::Impulsive {
+            isp_seconds,
+            max_thrust_newtons: Some(max_thrust_newtons),
+            ..
+        } => finite_burn_correction(
+            ideal_delta_v,
+            origin_mu,
+            parking_radius,
+            max_thrust_newtons,
+            vehicle.initial_mass_kg(),
+            isp_seconds,
+        )
+        .map_or((ideal_delta_v, None), |burn| {
+            (burn.delta_v_km_s, Some(burn.burn_duration_s))
+        }),
+        PropulsionMode::Impulsive { .. }
+        | PropulsionMode::Throttled { .. }
+        | PropulsionMode::Hybrid => (ideal_delta_v, None),
     };
 
+    let phasing = config
+        .parking_inclination_deg
+        .filter(|inclination| *inclination != 0.0)
+        .zip(asymptote)
+        .and_then(|(inclination, asymptote)| {
+            departure_phasing(
+                asymptote,
+                inclination.to_radians(),
+                origin_mu,
+                parking_radius,
+            )
+        });
+
     Ok(DeparturePlan {
         delta_v_required: delta_v,
+        ideal_delta_v_km_s: ideal_delta_v,
         burn_duration_s: burn_duration,
         hyperbolic_excess_km_s: v_infinity,
         parking_orbit_velocity_km_s: circular_speed,
+        plane_change_penalty_km_s: phasing.map_or(0.0, |p| p.plane_change_penalty_km_s),
+        optimal_parking_raan_rad: phasing.map(|p| p.optimal_raan_rad),
     })
 }
@@ -0,0 +1,304 @@
+//! First-order single gravity-assist screening: answers "is a flyby of this body worth a closer
+//! look?" before committing to a full multi-leg plan.
+
+use solar_config::{ConstantsSet, PlanetConfig};
+use solar_core::angles::acos_clamped;
+use solar_core::vector::cross;
+use solar_ephem_spice::{self as ephemeris, StateVector};
+use solar_impulsive::lambert;
+use solar_orbits::{add, dot, flyby_max_turn_angle, norm3, scale, sub};
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+/// Shortest leg considered on either side of the flyby.
+const MIN_LEG_DAYS: f64 = 20.0;
+/// Grid resolution per leg; each leg spans up to twice the direct time of flight.
+const LEG_STEPS: usize = 60;
+/// Allowed mismatch between inbound and outbound |v∞| for a flyby to count as unpowered.
+const VINF_MATCH_TOLERANCE_KM_S: f64 = 0.2;
+
+/// Inputs for screening a single unpowered flyby against the direct transfer.
+#[derive(Debug, Clone)]
+pub struct FlybyScreenRequest<'a> {
+    pub origin: &'a PlanetConfig,
+    pub flyby: &'a PlanetConfig,
+    pub destination: &'a PlanetConfig,
+    pub departure_et: f64,
+    /// Time of flight of the direct transfer being compared against.
+    pub direct_tof_days: f64,
+    /// Lowest periapsis altitude allowed at the flyby body.
+    pub min_flyby_altitude_km: f64,
+    pub constants: &'a ConstantsSet,
+}
+
+/// Best flyby-via-body trajectory found by [`screen_single_flyby`].
+#[derive(Debug, Clone)]
+pub struct FlybyCandidate {
+    pub flyby_et: f64,
+    pub arrival_et: f64,
+    pub vinf_depart_km_s: f64,
+    /// Inbound v∞ at the flyby body.
+    pub vinf_flyby_km_s: f64,
+    pub turn_angle_deg: f64,
+    pub max_turn_angle_deg: f64,
+    pub vinf_arrive_km_s: f64,
+}
+
+/// Direct transfer v∞ alongside the best single-flyby alternative, if any.
+#[derive(Debug, Clone)]
+pub struct FlybyScreening {
+    pub direct_vinf_depart_km_s: f64,
+    pub direct_vinf_arrive_km_s: f64,
+    pub assist: Option<FlybyCandidate>,
+}
+
+impl FlybyScreening {
+    /// Arrival v∞ saved by the flyby (positive when the assist helps).
+    pub fn vinf_reduction_km_s(&self) -> Option<f64> {
+        self.assist
+            .as_ref()
+            .map(|assist| self.direct_vinf_arrive_km_s - assist.vinf_arrive_km_s)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FlybyError {
+    #[error("ephemeris lookup failed: {0}")]
+    Ephemeris(#[from] ephemeris::EphemerisError),
+    #[error("no Lambert solution for the direct transfer")]
+    NoDirectTransfer,
+    #[error("degenerate flyby geometry: {0}")]
+    Degenerate(String),
+}
+
+/// Hyperbolic geometry needed to bend `vinf_in` onto a target outbound v∞, from [`solve_flyby`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlybyResult {
+    /// Periapsis radius flown; infinite when no turn is required.
+    pub periapsis_radius_km: f64,
+    /// Turn actually imparted on the v∞ vector.
+    pub turn_angle_rad: f64,
+    /// Outbound correction left over after the flyby: the gap between the target v∞ and the
+    /// v∞ the unpowered hyperbola delivers (turn shortfall and magnitude mismatch).
+    pub delta_v_deficit_km_s: f64,
+    /// Periapsis stays above the minimum and the residual is within the unpowered tolerance.
+    pub feasible: bool,
+}
+
+/// Solve the unpowered flyby that rotates `vinf_in` towards `vinf_out_target`.
+///
+/// The required turn fixes the hyperbola's eccentricity and hence its periapsis. When that
+/// periapsis would dip below `min_periapsis_radius_km` the flyby is flown at the minimum radius
+/// instead, and the turn it falls short by shows up in `delta_v_deficit_km_s` alongside any
+/// mismatch between the inbound and target v∞ magnitudes.
+pub fn solve_flyby(
+    vinf_in: [f64; 3],
+    vinf_out_target: [f64; 3],
+    mu_body: f64,
+    min_periapsis_radius_km: f64,
+) -> Result<FlybyResult, FlybyError> {
+    if !(mu_body.is_finite() && mu_body > 0.0) {
+        return Err(FlybyError::Degenerate(format!(
+            "gravitational parameter must be positive, got {mu_body}"
+        )));
+    }
+    if !(min_periapsis_radius_km.is_finite() && min_periapsis_radius_km > 0.0) {
+        return Err(FlybyError::Degenerate(format!(
+            "minimum periapsis radius must be positive, got {min_periapsis_radius_km}"
+        )));
+    }
+    let speed_in = norm3(&vinf_in);
+    let speed_out = norm3(&vinf_out_target);
+    let usable = |speed: f64| speed.is_finite() && speed > 0.0;
+    if !(usable(speed_in) && usable(speed_out)) {
+        return Err(FlybyError::Degenerate(
+            "inbound and target v-infinity must be non-zero".to_string(),
+        ));
+    }
+
+    let unit_in = scale(&vinf_in, 1.0 / speed_in);
+    let unit_out = scale(&vinf_out_target, 1.0 / speed_out);
+    // atan2 of the raw cross and dot products keeps a straight-through target at exactly zero.
+    let required_turn =
+        norm3(&cross(&vinf_in, &vinf_out_target)).atan2(dot(&vinf_in, &vinf_out_target));
+    let max_turn = flyby_max_turn_angle(speed_in, mu_body, min_periapsis_radius_km);
+
+    let (turn_angle_rad, periapsis_radius_km) = if required_turn <= max_turn {
+        // e = 1 / sin(δ/2) and rp = μ (e − 1) / v∞².
+        let half = 0.5 * required_turn;
+        let periapsis = if half.sin() > 0.0 {
+            mu_body * (1.0 / half.sin() - 1.0) / (speed_in * speed_in)
+        } else {
+            f64::INFINITY
+        };
+        (required_turn, periapsis)
+    } else {
+        (max_turn, min_periapsis_radius_km)
+    };
+
+    // Rotate the inbound asymptote towards the target within their common plane.
+    let in_plane = sub(&unit_out, &scale(&unit_in, dot(&unit_in, &unit_out)));
+    let in_plane_norm = norm3(&in_plane);
+    let normal = if in_plane_norm > 1e-12 {
+        scale(&in_plane, 1.0 / in_plane_norm)
+    } else {
+        any_perpendicular(&unit_in)
+    };
+    let delivered = scale(
+        &add(
+            &scale(&unit_in, turn_angle_rad.cos()),
+            &scale(&normal, turn_angle_rad.sin()),
+        ),
+        speed_in,
+    );
+    let delta_v_deficit_km_s = norm3(&sub(&vinf_out_target, &delivered));
+
+    Ok(FlybyResult {
+        periapsis_radius_km,
+        turn_angle_rad,
+        delta_v_deficit_km_s,
+        feasible: periapsis_radius_km >= min_periapsis_radius_km
+            && delta_v_deficit_km_s <= VINF_MATCH_TOLERANCE_KM_S,
+    })
+}
+
+/// Unit vector perpendicular to `unit`, used when the required turn is a full reversal.
+fn any_perpendicular(unit: &[f64; 3]) -> [f64; 3] {
+    let axis = if unit[0].abs() < 0.9 {
+        [1.0, 0.0, 0.0]
+    } else {
+        [0.0, 1.0, 0.0]
+    };
+    let perpendicular = sub(&axis, &scale(unit, dot(unit, &axis)));
+    scale(&perpendicular, 1.0 / norm3(&perpendicular))
+}
+
+/// Compare the direct transfer with the best origin → flyby → destination path departing at the
+/// same epoch.
+///
+/// Both legs are swept on a coarse grid. A candidate counts when the inbound and outbound v∞
+/// magnitudes agree (unpowered flyby), the required turn stays within the periapsis-limited
+/// turn angle, and the departure v∞ is no worse than the direct transfer's; among those the
+/// lowest arrival v∞ wins. This is a screening tool, not a trajectory optimiser.
+pub fn screen_single_flyby(request: &FlybyScreenRequest<'_>) -> Result<FlybyScreening, FlybyError> {
+    let origin_target = ephemeris::normalize_heliocentric_target_name(&request.origin.spice_name);
+    let flyby_target = ephemeris::normalize_heliocentric_target_name(&request.flyby.spice_name);
+    let destination_target =
+        ephemeris::normalize_heliocentric_target_name(&request.destination.spice_name);
+    let mu_sun = request.constants.mu_sun_km3_s2;
+
+    let departure_state = heliocentric_state(&origin_target, request.departure_et)?;
+    let direct_tof = request.direct_tof_days * SECONDS_PER_DAY;
+    let direct_arrival =
+        heliocentric_state(&destination_target, request.departure_et + direct_tof)?;
+    let (direct_vinf_depart_km_s, direct_vinf_arrive_km_s) = [true, false]
+        .into_iter()
+        .filter_map(|short| vinf_pair(&departure_state, &direct_arrival, direct_tof, mu_sun, short))
+        .map(|(dep, arr)| (norm3(&dep), norm3(&arr)))
+        .min_by(|a, b| (a.0 + a.1).total_cmp(&(b.0 + b.1)))
+        .ok_or(FlybyError::NoDirectTransfer)?;
+
+    let min_leg = MIN_LEG_DAYS * SECONDS_PER_DAY;
+    let step = ((2.0 * direct_tof - min_leg) / LEG_STEPS as f64).max(SECONDS_PER_DAY);
+    let leg_tof = |k: usize| min_leg + k as f64 * step;
+
+    // Both legs share the grid, so arrival epochs depend only on i + j.
+    let flyby_states: Vec<Option<StateVector>> = (0..=LEG_STEPS)
+        .map(|i| heliocentric_state(&flyby_target, request.departure_et + leg_tof(i)).ok())
+        .collect();
+    let arrival_states: Vec<Option<StateVector>> = (0..=2 * LEG_STEPS)
+        .map(|k| {
+            let et = request.departure_et + 2.0 * min_leg + k as f64 * step;
+            heliocentric_state(&destination_target, et).ok()
+        })
+        .collect();
+
+    let flyby_mu = request.constants.body_mu(request.flyby);
+    let periapsis_radius_km = request.flyby.radius_km + request.min_flyby_altitude_km;
+    let mut best: Option<FlybyCandidate> = None;
+
+    for (i, flyby_state) in flyby_states.iter().enumerate() {
+        let Some(flyby_state) = flyby_state else {
+            continue;
+        };
+        let inbound: Vec<([f64; 3], [f64; 3])> = [true, false]
+            .into_iter()
+            .filter_map(|short| vinf_pair(&departure_state, flyby_state, leg_tof(i), mu_sun, short))
+            .filter(|(dep, _)| norm3(dep) <= direct_vinf_depart_km_s)
+            .collect();
+        if inbound.is_empty() {
+            continue;
+        }
+
+        for j in 0..=LEG_STEPS {
+            let Some(arrival_state) = &arrival_states[i + j] else {
+                continue;
+            };
+            for short in [true, false] {
+                let Some((vinf_out, vinf_arr)) =
+                    vinf_pair(flyby_state, arrival_state, leg_tof(j), mu_sun, short)
+                else {
+                    continue;
+                };
+                let vinf_arrive_km_s = norm3(&vinf_arr);
+                if best
+                    .as_ref()
+                    .is_some_and(|b| vinf_arrive_km_s >= b.vinf_arrive_km_s)
+                {
+                    continue;
+                }
+                for (vinf_dep, vinf_in) in &inbound {
+                    let speed_in = norm3(vinf_in);
+                    let speed_out = norm3(&vinf_out);
+                    if (speed_in - speed_out).abs() > VINF_MATCH_TOLERANCE_KM_S {
+                        continue;
+                    }
+                    let turn = acos_clamped(dot(vinf_in, &vinf_out) / (speed_in * speed_out));
+                    let max_turn = flyby_max_turn_angle(
+                        0.5 * (speed_in + speed_out),
+                        flyby_mu,
+                        periapsis_radius_km,
+                    );
+                    if turn.is_nan() || turn > max_turn {
+                        continue;
+                    }
+                    best = Some(FlybyCandidate {
+                        flyby_et: request.departure_et + leg_tof(i),
+                        arrival_et: request.departure_et + leg_tof(i) + leg_tof(j),
+                        vinf_depart_km_s: norm3(vinf_dep),
+                        vinf_flyby_km_s: speed_in,
+                        turn_angle_deg: turn.to_degrees(),
+                        max_turn_angle_deg: max_turn.to_degrees(),
+                        vinf_arrive_km_s,
+                    });
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(FlybyScreening {
+        direct_vinf_depart_km_s,
+        direct_vinf_arrive_km_s,
+        assist: best,
+    })
+}
+
+pub(super) fn heliocentric_state(
+    target: &str,
+    et: f64,
+) -> Result<StateVector, ephemeris::EphemerisError> {
+    ephemeris::state_vector_et(target, "SUN", "ECLIPJ2000", "NONE", et)
+}
+
+/// Departure and arrival v∞ vectors of a Lambert arc between two body states.
+pub(super) fn vinf_pair(
+    from: &StateVector,
+    to: &StateVector,
+    tof_seconds: f64,
+    mu_sun: f64,
+    short: bool,
+) -> Option<([f64; 3], [f64; 3])> {
+    let (v1, v2) =
+        lambert::solve(from.position_km, to.position_km, tof_seconds, mu_sun, short).ok()?;
+    Some((sub(&v1, &from.velocity_km_s), sub(&v2, &to.velocity_km_s)))
+}
@@ -1,15 +1,23 @@
 //! Interplanetary cruise phase: integrates the heliocentric transfer leg using the selected propulsion model.
 
-use solar_config::PlanetConfig;
+use std::time::{Duration, Instant};
+
+use solar_config::{ConstantsSet, PlanetConfig};
+use solar_core::angles::wrap_to_2pi;
+use solar_core::vector::{Vector3, cross, dot, scale, sub};
+use solar_ephem_spice::source::{EphemerisSource, SpiceSource};
 use solar_ephem_spice::{self as ephemeris, StateVector};
-use solar_impulsive::{lambert, transfers::hohmann};
+use solar_impulsive::{lambert, transfers::hohmann_checked};
 use solar_orbits::norm3;
 use solar_propulsion::{PropulsionMode, Vehicle};
 
-const MU_SUN: f64 = 1.327_124_400_18e11; // km^3 / s^2
 const SECONDS_PER_DAY: f64 = 86_400.0;
 
 mod continuous;
+mod impulsive;
+
+pub use continuous::propagate_tangential;
+pub use impulsive::ImpulsiveBurns;
 
 /// Configuration for the interplanetary leg.
 #[derive(Debug, Clone)]
@@ -19,6 +27,12 @@ pub struct InterplanetaryConfig {
     pub departure_epoch: String,
     pub arrival_epoch: Option<String>,
     pub propulsion_mode: PropulsionMode,
+    /// Optional time of flight (days) used to seed the impulsive arrival search, typically taken
+    /// from a cached window dataset's baseline sample.
+    pub warm_start_tof_days: Option<f64>,
+    /// Parking-orbit altitudes (km) at the origin and destination used to size impulsive burns;
+    /// `None` falls back to each body's default parking altitude.
+    pub parking_altitudes_km: Option<(f64, f64)>,
 }
 
 /// Result from planning the cruise leg.
@@ -29,6 +43,60 @@ pub struct InterplanetaryPlan {
     pub departure_state: StateVector,
     pub arrival_state: StateVector,
     pub peak_speed_km_s: Option<f64>,
+    /// Distance flown along the heliocentric path, as opposed to the endpoint chord.
+    pub arc_length_km: Option<f64>,
+    /// Heliocentric angle swept between departure and arrival.
+    pub sweep_angle_deg: Option<f64>,
+    /// Integrated heliocentric samples for continuous-thrust legs; empty for ballistic arcs.
+    pub trajectory: Vec<TrajectorySample>,
+    /// Departure/capture burns sized from the Lambert arc, for impulsive vehicles.
+    pub impulsive_burns: Option<ImpulsiveBurns>,
+    /// Distance between the vehicle and the destination body at arrival, for integrated
+    /// continuous-thrust legs; `None` for Lambert arcs, which meet the body by construction.
+    pub miss_distance_km: Option<f64>,
+}
+
+/// Heliocentric state of the vehicle at `time_s` seconds after departure.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrajectorySample {
+    pub time_s: f64,
+    pub position_km: [f64; 3],
+    pub velocity_km_s: [f64; 3],
+    pub mass_kg: f64,
+}
+
+impl InterplanetaryPlan {
+    /// Build a ballistic cruise plan from injected heliocentric states, bypassing SPICE.
+    ///
+    /// Useful for offline tests and for callers that source states elsewhere; pair it with an
+    /// `InterplanetaryConfig` whose `arrival_epoch` is `None` so later phases take the TOF from here.
+    /// The arc metrics assume the default (DE440) solar GM.
+    pub fn from_states(
+        departure_state: StateVector,
+        arrival_state: StateVector,
+        time_of_flight_days: f64,
+    ) -> Self {
+        let peak_speed =
+            norm3(&departure_state.velocity_km_s).max(norm3(&arrival_state.velocity_km_s));
+        let arc = ballistic_arc(
+            &departure_state,
+            &arrival_state,
+            time_of_flight_days * SECONDS_PER_DAY,
+            ConstantsSet::default().mu_sun_km3_s2,
+        );
+        Self {
+            time_of_flight_days,
+            propellant_used_kg: None,
+            departure_state,
+            arrival_state,
+            peak_speed_km_s: Some(peak_speed),
+            arc_length_km: arc.map(|(length, _)| length),
+            sweep_angle_deg: arc.map(|(_, sweep)| sweep.to_degrees()),
+            trajectory: Vec::new(),
+            impulsive_burns: None,
+            miss_distance_km: None,
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -41,121 +109,320 @@ pub enum InterplanetaryError {
     InvalidAcceleration,
     #[error("continuous-thrust solver requires positive specific impulse")]
     InvalidSpecificImpulse,
+    #[error("continuous-thrust cruise did not reach the target orbit within {days:.0} days")]
+    TargetNotReached { days: f64 },
+    #[error("arrival epoch must follow the departure epoch (time of flight {days:.1} days)")]
+    ArrivalBeforeDeparture { days: f64 },
+    #[error(
+        "throttled engines fly their schedule over a fixed time of flight; set an arrival epoch"
+    )]
+    ArrivalEpochRequired,
+    #[error(
+        "throttle profile needs strictly increasing fractions in [0, 1) and throttles in [-1, 1]"
+    )]
+    InvalidThrottleProfile,
 }
 
 /// Propagates the interplanetary leg between the origin and destination bodies.
 ///
-/// Continuous-thrust missions are integrated with a steering-aware solver that considers
-/// solar gravity, thrust limits, and propellant consumption. Impulsive missions currently
-/// return analytic placeholders until the impulsive solver is implemented.
+/// Continuous-thrust missions are integrated with an RK4 propagator under solar gravity and a
+/// tangential steering law, respecting thrust limits and propellant consumption; throttled
+/// missions fly their throttle schedule through the same integrator. Impulsive missions
+/// fly the Lambert arc with the best arrival epoch and report the escape/capture burns and the
+/// rocket-equation propellant needed for them (see [`InterplanetaryPlan::size_impulsive_burns`]).
 pub fn plan_interplanetary(
     vehicle: &Vehicle,
     config: &InterplanetaryConfig,
     origin: &PlanetConfig,
     destination: &PlanetConfig,
+    constants: &ConstantsSet,
+) -> Result<InterplanetaryPlan, InterplanetaryError> {
+    plan_interplanetary_with_source(
+        &SpiceSource,
+        vehicle,
+        config,
+        origin,
+        destination,
+        constants,
+    )
+}
+
+/// Same as [`plan_interplanetary`], taking epochs and heliocentric states from `source` instead
+/// of the loaded SPICE kernels.
+pub fn plan_interplanetary_with_source(
+    source: &dyn EphemerisSource,
+    vehicle: &Vehicle,
+    config: &InterplanetaryConfig,
+    origin: &PlanetConfig,
+    destination: &PlanetConfig,
+    constants: &ConstantsSet,
+) -> Result<InterplanetaryPlan, InterplanetaryError> {
+    let mut spice_time = Duration::ZERO;
+    plan_interplanetary_instrumented(
+        source,
+        vehicle,
+        config,
+        origin,
+        destination,
+        constants,
+        &mut spice_time,
+    )
+}
+
+/// Same as [`plan_interplanetary_with_source`], accumulating wall-clock time spent in ephemeris
+/// calls into `spice_time` so callers can separate ephemeris cost from the transfer maths.
+pub(crate) fn plan_interplanetary_instrumented(
+    source: &dyn EphemerisSource,
+    vehicle: &Vehicle,
+    config: &InterplanetaryConfig,
+    origin: &PlanetConfig,
+    destination: &PlanetConfig,
+    constants: &ConstantsSet,
+    spice_time: &mut Duration,
 ) -> Result<InterplanetaryPlan, InterplanetaryError> {
+    let mu_sun = constants.mu_sun_km3_s2;
     let dep_target = ephemeris::normalize_heliocentric_target_name(&config.departure_body);
     let arr_target = ephemeris::normalize_heliocentric_target_name(&config.destination_body);
-    let departure_et = ephemeris::epoch_seconds(&config.departure_epoch)?;
+    let departure_et = epoch_et(source, &config.departure_epoch, spice_time)?;
 
-    let departure_state =
-        ephemeris::state_vector_et(&dep_target, "SUN", "ECLIPJ2000", "NONE", departure_et)?;
+    let departure_state = heliocentric_state(source, &dep_target, departure_et, spice_time)?;
     let destination_state_at_departure =
-        ephemeris::state_vector_et(&arr_target, "SUN", "ECLIPJ2000", "NONE", departure_et)?;
+        heliocentric_state(source, &arr_target, departure_et, spice_time)?;
 
     match &config.propulsion_mode {
-        PropulsionMode::Continuous { .. } => {
-            let arrival_et = if let Some(epoch) = &config.arrival_epoch {
-                ephemeris::epoch_seconds(epoch)?
-            } else {
-                departure_et
+        PropulsionMode::Continuous { .. } | PropulsionMode::Throttled { .. } => {
+            let arrival_tof_s = match &config.arrival_epoch {
+                Some(epoch) => {
+                    let tof_s = epoch_et(source, epoch, spice_time)? - departure_et;
+                    if tof_s <= 0.0 {
+                        return Err(InterplanetaryError::ArrivalBeforeDeparture {
+                            days: tof_s / SECONDS_PER_DAY,
+                        });
+                    }
+                    Some(tof_s)
+                }
+                None => None,
+            };
+            let mut destination_track = |elapsed_s: f64| {
+                heliocentric_state(source, &arr_target, departure_et + elapsed_s, spice_time)
+                    .map_err(InterplanetaryError::from)
             };
-            let arrival_state =
-                ephemeris::state_vector_et(&arr_target, "SUN", "ECLIPJ2000", "NONE", arrival_et)?;
 
             continuous::solve(
                 vehicle,
                 config,
-                origin,
-                destination,
                 departure_state,
-                arrival_state,
+                arrival_tof_s,
+                &mut destination_track,
+                mu_sun,
             )
         }
         PropulsionMode::Impulsive { .. } | PropulsionMode::Hybrid => {
-            let (_arrival_et, tof_seconds, arrival_state) = if let Some(epoch) =
-                &config.arrival_epoch
-            {
-                let arrival_et = ephemeris::epoch_seconds(epoch)?;
-                let arrival_state = ephemeris::state_vector_et(
-                    &arr_target,
-                    "SUN",
-                    "ECLIPJ2000",
-                    "NONE",
-                    arrival_et,
-                )?;
-                (arrival_et, (arrival_et - departure_et).abs(), arrival_state)
-            } else {
-                let r1 = norm3(&departure_state.position_km);
-                let r2 = norm3(&destination_state_at_departure.position_km);
-                let hohmann = hohmann(r1, r2, MU_SUN);
-                let baseline_tof = if hohmann.tof_seconds.is_finite() && hohmann.tof_seconds > 0.0 {
-                    hohmann.tof_seconds
+            let (_arrival_et, tof_seconds, arrival_state) =
+                if let Some(epoch) = &config.arrival_epoch {
+                    let arrival_et = epoch_et(source, epoch, spice_time)?;
+                    let arrival_state =
+                        heliocentric_state(source, &arr_target, arrival_et, spice_time)?;
+                    (arrival_et, (arrival_et - departure_et).abs(), arrival_state)
                 } else {
-                    200.0 * 86_400.0
-                };
+                    let r1 = norm3(&departure_state.position_km);
+                    let r2 = norm3(&destination_state_at_departure.position_km);
+                    let baseline_tof = hohmann_checked(r1, r2, mu_sun)
+                        .ok()
+                        .map(|h| h.tof_seconds)
+                        .filter(|tof| tof.is_finite() && *tof > 0.0)
+                        .unwrap_or(200.0 * 86_400.0);
 
-                match optimize_impulsive_arrival(
-                    departure_et,
-                    &departure_state,
-                    &arr_target,
-                    baseline_tof,
-                )? {
-                    Some((best_arrival_et, best_arrival_state, best_tof)) => {
-                        (best_arrival_et, best_tof, best_arrival_state)
-                    }
-                    None => {
-                        let fallback_et = departure_et + baseline_tof;
-                        let fallback_state = ephemeris::state_vector_et(
-                            &arr_target,
-                            "SUN",
-                            "ECLIPJ2000",
-                            "NONE",
-                            fallback_et,
-                        )?;
-                        (fallback_et, baseline_tof, fallback_state)
+                    let warm_start_tof = config
+                        .warm_start_tof_days
+                        .map(|days| days * SECONDS_PER_DAY);
+
+                    match optimize_impulsive_arrival(
+                        source,
+                        departure_et,
+                        &departure_state,
+                        &arr_target,
+                        baseline_tof,
+                        warm_start_tof,
+                        mu_sun,
+                        spice_time,
+                    )? {
+                        Some((best_arrival_et, best_arrival_state, best_tof)) => {
+                            (best_arrival_et, best_tof, best_arrival_state)
+                        }
+                        None => {
+                            let fallback_et = departure_et + baseline_tof;
+                            let fallback_state =
+                                heliocentric_state(source, &arr_target, fallback_et, spice_time)?;
+                            (fallback_et, baseline_tof, fallback_state)
+                        }
                     }
-                }
-            };
+                };
 
             let tof_days = tof_seconds / 86_400.0;
 
             let depart_speed = norm3(&departure_state.velocity_km_s);
             let arrival_speed = norm3(&arrival_state.velocity_km_s);
             let peak_speed = depart_speed.max(arrival_speed);
+            let arc = ballistic_arc(&departure_state, &arrival_state, tof_seconds, mu_sun);
 
-            Ok(InterplanetaryPlan {
+            let mut plan = InterplanetaryPlan {
                 time_of_flight_days: tof_days,
                 propellant_used_kg: None,
                 departure_state,
                 arrival_state,
                 peak_speed_km_s: Some(peak_speed),
-            })
+                arc_length_km: arc.map(|(length, _)| length),
+                sweep_angle_deg: arc.map(|(_, sweep)| sweep.to_degrees()),
+                trajectory: Vec::new(),
+                impulsive_burns: None,
+                miss_distance_km: None,
+            };
+            let parking_altitudes_km = config.parking_altitudes_km.unwrap_or((
+                origin.default_parking_altitude_km,
+                destination.default_parking_altitude_km,
+            ));
+            plan.impulsive_burns = plan.size_impulsive_burns(
+                vehicle,
+                origin,
+                destination,
+                parking_altitudes_km,
+                constants,
+            );
+            plan.propellant_used_kg = plan
+                .impulsive_burns
+                .map(|burns| burns.propellant_total_kg());
+            Ok(plan)
         }
     }
 }
 
+fn heliocentric_state(
+    source: &dyn EphemerisSource,
+    target: &str,
+    et: f64,
+    spice_time: &mut Duration,
+) -> Result<StateVector, ephemeris::EphemerisError> {
+    let start = Instant::now();
+    let state = source.state_vector_et(target, "SUN", "ECLIPJ2000", "NONE", et);
+    *spice_time += start.elapsed();
+    state
+}
+
+fn epoch_et(
+    source: &dyn EphemerisSource,
+    epoch: &str,
+    spice_time: &mut Duration,
+) -> Result<f64, ephemeris::EphemerisError> {
+    let start = Instant::now();
+    let et = source.epoch_seconds(epoch);
+    *spice_time += start.elapsed();
+    et
+}
+
+/// Arc length (km) and sweep angle (rad) of the ballistic conic joining two heliocentric states.
+///
+/// Uses the Lambert branch with the lower combined v-infinity, matching the arrival search.
+fn ballistic_arc(
+    departure_state: &StateVector,
+    arrival_state: &StateVector,
+    tof_seconds: f64,
+    mu_sun: f64,
+) -> Option<(f64, f64)> {
+    let (v1, _) = best_lambert_branch(departure_state, arrival_state, tof_seconds, mu_sun)?;
+    conic_arc(
+        &departure_state.position_km,
+        &v1,
+        &arrival_state.position_km,
+        mu_sun,
+    )
+}
+
+/// Heliocentric departure/arrival velocities of the Lambert branch with the lower combined
+/// v-infinity between two states.
+fn best_lambert_branch(
+    departure_state: &StateVector,
+    arrival_state: &StateVector,
+    tof_seconds: f64,
+    mu_sun: f64,
+) -> Option<(Vector3, Vector3)> {
+    if !(tof_seconds.is_finite() && tof_seconds > 0.0) {
+        return None;
+    }
+    let (_, v1, v2) = [true, false]
+        .into_iter()
+        .filter_map(|short| {
+            let (v1, v2) = lambert::solve(
+                departure_state.position_km,
+                arrival_state.position_km,
+                tof_seconds,
+                mu_sun,
+                short,
+            )
+            .ok()?;
+            let score = norm3(&sub(&v1, &departure_state.velocity_km_s))
+                + norm3(&sub(&v2, &arrival_state.velocity_km_s));
+            score.is_finite().then_some((score, v1, v2))
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0))?;
+    Some((v1, v2))
+}
+
+/// Integrate `ds = sqrt(r² + (dr/df)²) df` along the conic through `r1` with velocity `v1`
+/// until it reaches the direction of `r2`.
+fn conic_arc(r1: &Vector3, v1: &Vector3, r2: &Vector3, mu: f64) -> Option<(f64, f64)> {
+    const ARC_INTERVALS: usize = 512; // even, for Simpson's rule
+
+    let h = cross(r1, v1);
+    let h_mag = norm3(&h);
+    let r1_mag = norm3(r1);
+    if h_mag <= 0.0 || r1_mag <= 0.0 {
+        return None;
+    }
+    let h_hat = scale(&h, 1.0 / h_mag);
+    let sweep = wrap_to_2pi(dot(&cross(r1, r2), &h_hat).atan2(dot(r1, r2)));
+
+    let p = h_mag * h_mag / mu;
+    let e_vec = sub(&scale(&cross(v1, &h), 1.0 / mu), &scale(r1, 1.0 / r1_mag));
+    let e = norm3(&e_vec);
+    let f1 = if e > 1e-12 {
+        dot(&cross(&e_vec, r1), &h_hat).atan2(dot(&e_vec, r1))
+    } else {
+        0.0
+    };
+
+    let integrand = |theta: f64| -> Option<f64> {
+        let (sin_f, cos_f) = (f1 + theta).sin_cos();
+        let denom = 1.0 + e * cos_f;
+        if denom <= 0.0 {
+            return None;
+        }
+        let r = p / denom;
+        let dr_df = p * e * sin_f / (denom * denom);
+        Some((r * r + dr_df * dr_df).sqrt())
+    };
+
+    let step = sweep / ARC_INTERVALS as f64;
+    let mut sum = integrand(0.0)? + integrand(sweep)?;
+    for i in 1..ARC_INTERVALS {
+        let weight = if i % 2 == 1 { 4.0 } else { 2.0 };
+        sum += weight * integrand(step * i as f64)?;
+    }
+    Some((sum * step / 3.0, sweep))
+}
+
 fn lambert_vinf_score(
     departure_state: &StateVector,
     arrival_state: &StateVector,
     tof_seconds: f64,
+    mu_sun: f64,
     short: bool,
 ) -> Option<f64> {
     let (v1, v2) = lambert::solve(
         departure_state.position_km,
         arrival_state.position_km,
         tof_seconds,
-        MU_SUN,
+        mu_sun,
         short,
     )
     .ok()?;
@@ -175,19 +442,41 @@ fn lambert_vinf_score(
     Some(score)
 }
 
+/// Search for the arrival epoch minimising the combined Lambert v-infinity.
+///
+/// When `warm_start_tof_seconds` is supplied (e.g. from a cached window dataset), the initial
+/// bracket is centred on it and narrowed, otherwise the Hohmann-based baseline seeds a broad scan.
+#[allow(clippy::too_many_arguments)]
 fn optimize_impulsive_arrival(
+    source: &dyn EphemerisSource,
     departure_et: f64,
     departure_state: &StateVector,
     arrival_target: &str,
     baseline_tof_seconds: f64,
+    warm_start_tof_seconds: Option<f64>,
+    mu_sun: f64,
+    spice_time: &mut Duration,
 ) -> Result<Option<(f64, StateVector, f64)>, ephemeris::EphemerisError> {
     let mut baseline_days = (baseline_tof_seconds / SECONDS_PER_DAY).abs();
     if !baseline_days.is_finite() || baseline_days < 1.0 {
         baseline_days = 200.0;
     }
 
-    let mut min_days = (baseline_days * 0.8).max(30.0);
-    let mut max_days = (baseline_days * 1.2).max(min_days + 20.0).min(1_500.0);
+    let warm_days = warm_start_tof_seconds
+        .map(|tof| (tof / SECONDS_PER_DAY).abs())
+        .filter(|days| days.is_finite() && *days >= 1.0);
+
+    let (mut min_days, mut max_days) = if let Some(warm) = warm_days {
+        baseline_days = warm;
+        let min_days = (warm * 0.9).max(30.0);
+        (min_days, (warm * 1.1).max(min_days + 10.0).min(1_500.0))
+    } else {
+        let min_days = (baseline_days * 0.8).max(30.0);
+        (
+            min_days,
+            (baseline_days * 1.2).max(min_days + 20.0).min(1_500.0),
+        )
+    };
     let mut step_days = ((max_days - min_days) / 120.0).max(1.0);
 
     let score_tolerance = 0.2;
@@ -199,28 +488,23 @@ fn optimize_impulsive_arrival(
         while tof_days <= max_days + 1e-6 {
             let tof_seconds = tof_days * SECONDS_PER_DAY;
             let arrival_et = departure_et + tof_seconds;
-            let arrival_state = match ephemeris::state_vector_et(
-                arrival_target,
-                "SUN",
-                "ECLIPJ2000",
-                "NONE",
-                arrival_et,
-            ) {
-                Ok(state) => state,
-                Err(_) => {
-                    tof_days += step_days;
-                    continue;
-                }
-            };
+            let arrival_state =
+                match heliocentric_state(source, arrival_target, arrival_et, spice_time) {
+                    Ok(state) => state,
+                    Err(_) => {
+                        tof_days += step_days;
+                        continue;
+                    }
+                };
 
             let mut best_score_for_candidate: Option<f64> = None;
             if let Some(score) =
-                lambert_vinf_score(departure_state, &arrival_state, tof_seconds, true)
+                lambert_vinf_score(departure_state, &arrival_state, tof_seconds, mu_sun, true)
             {
                 best_score_for_candidate = Some(score);
             }
             if let Some(score) =
-                lambert_vinf_score(departure_state, &arrival_state, tof_seconds, false)
+                lambert_vinf_score(departure_state, &arrival_state, tof_seconds, mu_sun, false)
             {
                 if best_score_for_candidate.map_or(true, |current| score < current) {
                     best_score_for_candidate = Some(score);
@@ -1,126 +1,561 @@
-use solar_core::constants::G0;
+use solar_core::angles::acos_clamped;
+use solar_core::constants::AU_KM;
+use solar_core::vector::cross;
 use solar_ephem_spice::StateVector;
 use solar_orbits::{add, dot, norm3, scale, sub};
 use solar_propulsion::{PropulsionMode, Vehicle};
 
-use super::{InterplanetaryConfig, InterplanetaryError, InterplanetaryPlan};
+use super::{
+    InterplanetaryConfig, InterplanetaryError, InterplanetaryPlan, SECONDS_PER_DAY,
+    TrajectorySample,
+};
 
-const MU_SUN: f64 = 1.327_124_400_18e11; // km^3 / s^2
+/// Longest RK4 step (s); coasting arcs are integrated at this resolution.
+const MAX_STEP_S: f64 = 3_600.0;
+/// Shortest RK4 step (s), bounding the cost of very high-acceleration burns.
+const MIN_STEP_S: f64 = 1.0;
+/// Fraction of the current speed a single thrusting step may add or remove.
+const MAX_STEP_DV_FRACTION: f64 = 1e-3;
+/// Spacing of the recorded trajectory samples (s).
+const SAMPLE_INTERVAL_S: f64 = SECONDS_PER_DAY;
+/// Give up on cruises that have not reached the target orbit after this long.
+const MAX_CRUISE_DAYS: f64 = 20.0 * 365.25;
+/// Bisection iterations used to locate a steering switch or the target-radius crossing inside a
+/// step.
+const CROSSING_ITERATIONS: usize = 40;
+/// Coast delays sampled across the cruise when aiming for a fixed arrival epoch.
+const DELAY_SAMPLES: usize = 16;
+/// Burn targets sampled between the destination's radius and the overshoot limit.
+const REACH_SAMPLES: usize = 8;
+/// Alternating golden-section passes refining the best delay and burn target.
+const AIM_ROUNDS: usize = 3;
+/// Golden-section iterations per refinement pass.
+const GOLDEN_ITERATIONS: usize = 20;
 
+/// Heliocentric state of the destination body `t` seconds after departure.
+pub(super) type DestinationTrack<'a> =
+    dyn FnMut(f64) -> Result<StateVector, InterplanetaryError> + 'a;
+
+/// Thrust and mass-flow limits of a continuous-thrust or throttled vehicle.
+struct Engine<'a> {
+    propulsion: &'a PropulsionMode,
+    max_thrust_n: f64,
+    max_accel_m_s2: Option<f64>,
+    exhaust_velocity_m_s: f64,
+    dry_mass_kg: f64,
+}
+
+impl Engine<'_> {
+    /// Delivered thrust acceleration (km/s²) and mass flow (kg/s) at heliocentric radius `r_km`.
+    fn output(&self, r_km: f64, mass_kg: f64) -> (f64, f64) {
+        if mass_kg <= self.dry_mass_kg {
+            return (0.0, 0.0);
+        }
+        let thrust = self
+            .propulsion
+            .available_thrust_newtons(r_km / AU_KM)
+            .unwrap_or(self.max_thrust_n);
+        let mut accel = thrust / mass_kg;
+        if let Some(limit) = self.max_accel_m_s2.filter(|limit| *limit > 0.0) {
+            accel = accel.min(limit);
+        }
+        // Mass flow follows the delivered thrust at constant Isp.
+        (accel / 1_000.0, accel * mass_kg / self.exhaust_velocity_m_s)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    r: [f64; 3],
+    v: [f64; 3],
+    m: f64,
+}
+
+/// Fly a continuous-thrust cruise towards the destination body.
+///
+/// Without `arrival_tof_s` the vehicle steers tangentially until its osculating energy matches a
+/// circular orbit at the destination's radius, then coasts; the leg ends when it first reaches
+/// that radius and the arrival state is the body's state at that moment. With `arrival_tof_s` the
+/// leg lasts exactly that long and aims at the destination's state at that time: after an initial
+/// coast the vehicle thrusts until its osculating apsis reaches a chosen radius, with the coast
+/// and the radius picked to minimise the distance to the body on arrival. Either way the
+/// remaining separation is reported as `miss_distance_km`.
+///
+/// Throttled vehicles instead fly their `throttle_profile` open-loop over the fixed time of
+/// flight, so they need `arrival_tof_s`.
 pub(super) fn solve(
     vehicle: &Vehicle,
     config: &InterplanetaryConfig,
-    _origin: &solar_config::PlanetConfig,
-    _destination: &solar_config::PlanetConfig,
     departure_state: StateVector,
-    arrival_state: StateVector,
+    arrival_tof_s: Option<f64>,
+    destination: &mut DestinationTrack<'_>,
+    mu_sun: f64,
 ) -> Result<InterplanetaryPlan, InterplanetaryError> {
-    let (max_thrust, isp, max_accel) = match (&vehicle.propulsion, &config.propulsion_mode) {
-        (
-            PropulsionMode::Continuous {
-                max_thrust_newtons,
-                isp_seconds,
-                max_acceleration_m_s2,
-            },
-            PropulsionMode::Continuous { .. },
-        ) => (*max_thrust_newtons, *isp_seconds, *max_acceleration_m_s2),
-        _ => return Err(InterplanetaryError::UnsupportedPropulsion),
-    };
-
-    if isp <= 0.0 {
-        return Err(InterplanetaryError::InvalidSpecificImpulse);
+    if !matches!(
+        config.propulsion_mode,
+        PropulsionMode::Continuous { .. } | PropulsionMode::Throttled { .. }
+    ) {
+        return Err(InterplanetaryError::UnsupportedPropulsion);
     }
-    if max_thrust <= 0.0 {
+    let engine = engine(vehicle)?;
+    if engine.max_thrust_n <= 0.0 {
         return Err(InterplanetaryError::InvalidAcceleration);
     }
 
-    let displacement = sub(&arrival_state.position_km, &departure_state.position_km);
-    let distance = norm3(&displacement);
-    if distance == 0.0 {
+    let initial_mass = vehicle.initial_mass_kg();
+    let aim_state = destination(arrival_tof_s.unwrap_or(0.0))?;
+    let chord = sub(&aim_state.position_km, &departure_state.position_km);
+    if norm3(&chord) == 0.0 {
         return Ok(InterplanetaryPlan {
             time_of_flight_days: 0.0,
             propellant_used_kg: Some(0.0),
             departure_state,
-            arrival_state,
+            arrival_state: aim_state,
             peak_speed_km_s: Some(0.0),
+            arc_length_km: Some(0.0),
+            sweep_angle_deg: Some(0.0),
+            trajectory: vec![sample(
+                0.0,
+                &State {
+                    r: departure_state.position_km,
+                    v: departure_state.velocity_km_s,
+                    m: initial_mass,
+                },
+            )],
+            impulsive_burns: None,
+            miss_distance_km: Some(0.0),
         });
     }
 
-    let direction = scale(&displacement, 1.0 / distance);
+    let target_radius = norm3(&aim_state.position_km);
+    let departure_radius = norm3(&departure_state.position_km);
+    let outbound = target_radius > departure_radius;
 
-    let initial_mass = vehicle.initial_mass_kg();
-    let mut mass = initial_mass;
-    let dry_mass = vehicle.dry_mass_kg;
-    let m_dot = max_thrust / (isp * G0);
+    let run = match (&vehicle.propulsion, arrival_tof_s) {
+        (
+            PropulsionMode::Throttled {
+                throttle_profile, ..
+            },
+            Some(tof_s),
+        ) => integrate(
+            &engine,
+            departure_state,
+            initial_mass,
+            mu_sun,
+            tof_s,
+            |time: f64, _: &State| scheduled_throttle(throttle_profile, time / tof_s),
+            None,
+        ),
+        (PropulsionMode::Throttled { .. }, None) => {
+            return Err(InterplanetaryError::ArrivalEpochRequired);
+        }
+        (_, Some(tof_s)) => {
+            let fly = |delay_s: f64, reach_km: f64| {
+                integrate(
+                    &engine,
+                    departure_state,
+                    initial_mass,
+                    mu_sun,
+                    tof_s,
+                    |time: f64, state: &State| {
+                        let apsis = osculating_apsis(state, mu_sun, outbound);
+                        match (time < delay_s, outbound, apsis < reach_km) {
+                            (true, _, _) => 0.0,
+                            (false, true, true) => 1.0,
+                            (false, false, false) => -1.0,
+                            _ => 0.0,
+                        }
+                    },
+                    None,
+                )
+            };
+            // Overshooting the destination's radius shortens the transfer; a factor of
+            // `target / departure` beyond it spans the useful range.
+            let reach =
+                |fraction: f64| target_radius * (target_radius / departure_radius).powf(fraction);
+            let (delay_s, fraction) = aim(tof_s, |delay_s, fraction| {
+                miss_distance(&fly(delay_s, reach(fraction)), &aim_state)
+            });
+            fly(delay_s, reach(fraction))
+        }
+        (_, None) => {
+            // An orbit with the target's circular-orbit energy always crosses the target radius.
+            let target_energy = -mu_sun / (2.0 * target_radius);
+            let steering = |_: f64, state: &State| {
+                let energy = 0.5 * dot(&state.v, &state.v) - mu_sun / norm3(&state.r);
+                match (outbound, energy < target_energy) {
+                    (true, true) => 1.0,
+                    (false, false) => -1.0,
+                    _ => 0.0,
+                }
+            };
+            let crossing = |state: &State| {
+                let distance = norm3(&state.r) - target_radius;
+                if outbound { distance } else { -distance }
+            };
+            let run = integrate(
+                &engine,
+                departure_state,
+                initial_mass,
+                mu_sun,
+                MAX_CRUISE_DAYS * SECONDS_PER_DAY,
+                steering,
+                Some(&crossing),
+            );
+            if !run.reached {
+                return Err(InterplanetaryError::TargetNotReached {
+                    days: MAX_CRUISE_DAYS,
+                });
+            }
+            run
+        }
+    };
 
-    let mut accel_limit = max_accel.unwrap_or(0.0);
-    if accel_limit <= 0.0 {
-        accel_limit = max_thrust / mass;
+    let final_state = run
+        .trajectory
+        .last()
+        .expect("trajectory holds the initial sample");
+    let arrival_state = match arrival_tof_s {
+        Some(_) => aim_state,
+        None => destination(final_state.time_s)?,
+    };
+    let propellant_used = (initial_mass - final_state.mass_kg)
+        .min(vehicle.propellant_mass_kg)
+        .max(0.0);
+
+    Ok(InterplanetaryPlan {
+        time_of_flight_days: final_state.time_s / SECONDS_PER_DAY,
+        propellant_used_kg: Some(propellant_used),
+        departure_state,
+        arrival_state,
+        peak_speed_km_s: Some(run.peak_speed),
+        arc_length_km: Some(run.arc_length),
+        sweep_angle_deg: Some(run.sweep_angle.to_degrees()),
+        miss_distance_km: Some(miss_distance(&run, &arrival_state)),
+        trajectory: run.trajectory,
+        impulsive_burns: None,
+    })
+}
+
+/// Throttle of the breakpoint in effect at `fraction` of the transfer; coasting before the first.
+fn scheduled_throttle(profile: &[(f64, f64)], fraction: f64) -> f64 {
+    profile
+        .iter()
+        .take_while(|(start, _)| *start <= fraction)
+        .last()
+        .map_or(0.0, |(_, throttle)| *throttle)
+}
+
+/// Coast delay in `[0, tof_s]` and burn-target fraction in `[0, 1]` minimising `miss`: a grid
+/// scan followed by alternating golden-section refinements around the best sample.
+fn aim(tof_s: f64, miss: impl Fn(f64, f64) -> f64) -> (f64, f64) {
+    let delay_step = tof_s / DELAY_SAMPLES as f64;
+    let fraction_step = 1.0 / (REACH_SAMPLES - 1) as f64;
+    let mut best = (0.0, 0.0, f64::INFINITY);
+    for i in 0..=DELAY_SAMPLES {
+        for j in 0..REACH_SAMPLES {
+            let (delay_s, fraction) = (i as f64 * delay_step, j as f64 * fraction_step);
+            let distance = miss(delay_s, fraction);
+            if distance < best.2 {
+                best = (delay_s, fraction, distance);
+            }
+        }
     }
-    if accel_limit <= 0.0 {
-        return Err(InterplanetaryError::InvalidAcceleration);
+
+    let (mut delay_s, mut fraction, mut distance) = best;
+    for _ in 0..AIM_ROUNDS {
+        let candidate = golden_section(
+            (delay_s - delay_step).max(0.0),
+            (delay_s + delay_step).min(tof_s),
+            |d| miss(d, fraction),
+        );
+        let candidate_miss = miss(candidate, fraction);
+        if candidate_miss < distance {
+            (delay_s, distance) = (candidate, candidate_miss);
+        }
+        let candidate = golden_section(
+            (fraction - fraction_step).max(0.0),
+            (fraction + fraction_step).min(1.0),
+            |f| miss(delay_s, f),
+        );
+        let candidate_miss = miss(delay_s, candidate);
+        if candidate_miss < distance {
+            (fraction, distance) = (candidate, candidate_miss);
+        }
     }
+    (delay_s, fraction)
+}
 
-    let accel_km_s2 = accel_limit / 1_000.0;
-    let half_time = f64::sqrt(distance / accel_km_s2);
-    let total_time = 2.0 * half_time;
-    let steps = 10_000.max((total_time / 10.0).ceil() as usize);
-    let dt = total_time / steps as f64;
+/// Minimiser of `f` on `[lo, hi]`, assuming it is unimodal there.
+fn golden_section(mut lo: f64, mut hi: f64, f: impl Fn(f64) -> f64) -> f64 {
+    let ratio = 0.5 * (5.0_f64.sqrt() - 1.0);
+    let (mut a, mut b) = (hi - ratio * (hi - lo), lo + ratio * (hi - lo));
+    let (mut fa, mut fb) = (f(a), f(b));
+    for _ in 0..GOLDEN_ITERATIONS {
+        if fa <= fb {
+            (hi, b, fb) = (b, a, fa);
+            a = hi - ratio * (hi - lo);
+            fa = f(a);
+        } else {
+            (lo, a, fa) = (a, b, fb);
+            b = lo + ratio * (hi - lo);
+            fb = f(b);
+        }
+    }
+    0.5 * (lo + hi)
+}
 
-    let mut x = 0.0;
-    let mut v = dot(&departure_state.velocity_km_s, &direction);
-    let mut peak_speed = v.abs();
-    let mut time = 0.0;
+/// Osculating heliocentric apoapsis (`outbound`) or periapsis radius; an unbound orbit has an
+/// infinite apoapsis.
+fn osculating_apsis(state: &State, mu_sun: f64, outbound: bool) -> f64 {
+    let energy = 0.5 * dot(&state.v, &state.v) - mu_sun / norm3(&state.r);
+    let h = norm3(&cross(&state.r, &state.v));
+    let p = h * h / mu_sun;
+    let e = (1.0 + 2.0 * energy * h * h / (mu_sun * mu_sun))
+        .max(0.0)
+        .sqrt();
+    match (outbound, e < 1.0) {
+        (true, true) => p / (1.0 - e),
+        (true, false) => f64::INFINITY,
+        (false, _) => p / (1.0 + e),
+    }
+}
 
-    for step in 0..steps {
-        let thrust_dir = if x < distance / 2.0 { 1.0 } else { -1.0 };
+/// Distance (km) between the end of `run` and `target`.
+fn miss_distance(run: &Run, target: &StateVector) -> f64 {
+    run.trajectory.last().map_or(f64::INFINITY, |last| {
+        norm3(&sub(&last.position_km, &target.position_km))
+    })
+}
 
-        let thrust_accel_mag = (max_thrust / mass) / 1_000.0;
-        let limited_accel = thrust_accel_mag.min(accel_limit / 1_000.0);
-        let a_thrust = thrust_dir * limited_accel;
+/// Propagate a continuous-thrust vehicle for `duration_s` under solar gravity with thrust held
+/// along (`prograde`) or against the velocity vector.
+///
+/// Samples are recorded daily plus at both endpoints. A vehicle with zero thrust follows the
+/// ballistic conic through `initial_state`.
+pub fn propagate_tangential(
+    vehicle: &Vehicle,
+    initial_state: StateVector,
+    mu_sun: f64,
+    duration_s: f64,
+    prograde: bool,
+) -> Result<Vec<TrajectorySample>, InterplanetaryError> {
+    let engine = engine(vehicle)?;
+    if engine.max_thrust_n < 0.0 {
+        return Err(InterplanetaryError::InvalidAcceleration);
+    }
+    let sign = if prograde { 1.0 } else { -1.0 };
+    let run = integrate(
+        &engine,
+        initial_state,
+        vehicle.initial_mass_kg(),
+        mu_sun,
+        duration_s.max(0.0),
+        |_: f64, _: &State| sign,
+        None,
+    );
+    Ok(run.trajectory)
+}
 
-        let position_vec = add(&departure_state.position_km, &scale(&direction, x));
-        let r_mag = norm3(&position_vec).max(1.0);
-        let grav_vec = scale(&position_vec, -MU_SUN / (r_mag.powi(3)));
-        let a_grav = dot(&grav_vec, &direction);
+fn engine(vehicle: &Vehicle) -> Result<Engine<'_>, InterplanetaryError> {
+    let (max_thrust_newtons, isp_seconds, max_acceleration_m_s2) = match &vehicle.propulsion {
+        PropulsionMode::Continuous {
+            max_thrust_newtons,
+            isp_seconds,
+            max_acceleration_m_s2,
+            ..
+        } => (max_thrust_newtons, isp_seconds, *max_acceleration_m_s2),
+        PropulsionMode::Throttled {
+            max_thrust_newtons,
+            isp_seconds,
+            throttle_profile,
+        } => {
+            let valid = throttle_profile.iter().all(|&(fraction, throttle)| {
+                (0.0..1.0).contains(&fraction) && throttle.is_finite() && throttle.abs() <= 1.0
+            }) && throttle_profile.windows(2).all(|w| w[1].0 > w[0].0);
+            if !valid {
+                return Err(InterplanetaryError::InvalidThrottleProfile);
+            }
+            (max_thrust_newtons, isp_seconds, None)
+        }
+        _ => return Err(InterplanetaryError::UnsupportedPropulsion),
+    };
+    if *isp_seconds <= 0.0 {
+        return Err(InterplanetaryError::InvalidSpecificImpulse);
+    }
+    let exhaust_velocity_m_s = vehicle
+        .propulsion
+        .exhaust_velocity_m_s()
+        .ok_or(InterplanetaryError::UnsupportedPropulsion)?;
+    Ok(Engine {
+        propulsion: &vehicle.propulsion,
+        max_thrust_n: *max_thrust_newtons,
+        max_accel_m_s2: max_acceleration_m_s2,
+        exhaust_velocity_m_s,
+        // Lower stages ride along as inert mass.
+        dry_mass_kg: vehicle.initial_mass_kg() - vehicle.propellant_mass_kg,
+    })
+}
 
-        let total_accel = a_thrust + a_grav;
+struct Run {
+    trajectory: Vec<TrajectorySample>,
+    reached: bool,
+    peak_speed: f64,
+    arc_length: f64,
+    sweep_angle: f64,
+}
+
+/// RK4 propagation under solar gravity plus tangential thrust scaled by `steering` (-1..=1 as a
+/// function of elapsed time and state, held fixed across each step). Steps that would carry the
+/// steering past a switch are cut at the switch. Stops early once `crossing` changes sign from
+/// negative.
+fn integrate(
+    engine: &Engine<'_>,
+    initial: StateVector,
+    initial_mass: f64,
+    mu_sun: f64,
+    max_time_s: f64,
+    steering: impl Fn(f64, &State) -> f64,
+    crossing: Option<&dyn Fn(&State) -> f64>,
+) -> Run {
+    let mut state = State {
+        r: initial.position_km,
+        v: initial.velocity_km_s,
+        m: initial_mass,
+    };
+    let mut time = 0.0;
+    let mut run = Run {
+        trajectory: vec![sample(time, &state)],
+        reached: false,
+        peak_speed: norm3(&state.v),
+        arc_length: 0.0,
+        sweep_angle: 0.0,
+    };
+    let mut next_sample = SAMPLE_INTERVAL_S;
 
-        v += total_accel * dt;
-        peak_speed = peak_speed.max(v.abs());
-        x += v * dt;
+    while time < max_time_s {
+        let throttle = steering(time, &state);
+        let mut dt = MAX_STEP_S.min(max_time_s - time);
+        if throttle != 0.0 {
+            let (accel, _) = engine.output(norm3(&state.r), state.m);
+            if accel > 0.0 {
+                let limit = MAX_STEP_DV_FRACTION * norm3(&state.v) / accel;
+                dt = dt.min(limit.max(MIN_STEP_S));
+            }
+        }
 
-        if x < 0.0 {
-            x = 0.0;
-            v = 0.0;
+        let mut next = rk4_step(engine, &state, dt, throttle, mu_sun);
+        if steering(time + dt, &next) != throttle {
+            // Bisect the step so the burn starts or stops on the switch.
+            let (mut lo, mut hi) = (0.0, dt);
+            for _ in 0..CROSSING_ITERATIONS {
+                let mid = 0.5 * (lo + hi);
+                let probe = rk4_step(engine, &state, mid, throttle, mu_sun);
+                if steering(time + mid, &probe) != throttle {
+                    hi = mid;
+                } else {
+                    lo = mid;
+                }
+            }
+            dt = hi;
+            next = rk4_step(engine, &state, dt, throttle, mu_sun);
         }
-        if x > distance {
-            x = distance;
+        if let Some(crossing) = crossing
+            && crossing(&next) >= 0.0
+        {
+            // Bisect the step so the run ends on the target radius.
+            let (mut lo, mut hi) = (0.0, dt);
+            for _ in 0..CROSSING_ITERATIONS {
+                let mid = 0.5 * (lo + hi);
+                if crossing(&rk4_step(engine, &state, mid, throttle, mu_sun)) >= 0.0 {
+                    hi = mid;
+                } else {
+                    lo = mid;
+                }
+            }
+            dt = hi;
+            next = rk4_step(engine, &state, dt, throttle, mu_sun);
+            run.reached = true;
         }
 
+        run.arc_length += norm3(&sub(&next.r, &state.r));
+        run.sweep_angle += angle_between(&state.r, &next.r);
+        run.peak_speed = run.peak_speed.max(norm3(&next.v));
+        state = next;
         time += dt;
 
-        if mass > dry_mass {
-            mass -= m_dot * dt;
-            if mass < dry_mass {
-                mass = dry_mass;
-            }
+        if run.reached || time >= max_time_s {
+            run.trajectory.push(sample(time, &state));
+            break;
         }
-
-        if step == steps - 1 {
-            x = distance;
+        if time >= next_sample {
+            run.trajectory.push(sample(time, &state));
+            next_sample += SAMPLE_INTERVAL_S;
         }
     }
+    run
+}
 
-    let propellant_used = (initial_mass - mass)
-        .min(vehicle.propellant_mass_kg)
-        .max(0.0);
+fn rk4_step(engine: &Engine<'_>, state: &State, dt: f64, throttle: f64, mu_sun: f64) -> State {
+    let k1 = derivative(engine, state, throttle, mu_sun);
+    let k2 = derivative(engine, &advance(state, &k1, 0.5 * dt), throttle, mu_sun);
+    let k3 = derivative(engine, &advance(state, &k2, 0.5 * dt), throttle, mu_sun);
+    let k4 = derivative(engine, &advance(state, &k3, dt), throttle, mu_sun);
+    let weighted = |a: [f64; 3], b: [f64; 3], c: [f64; 3], d: [f64; 3]| {
+        add(&add(&a, &scale(&b, 2.0)), &add(&scale(&c, 2.0), &d))
+    };
+    State {
+        r: add(
+            &state.r,
+            &scale(&weighted(k1.r, k2.r, k3.r, k4.r), dt / 6.0),
+        ),
+        v: add(
+            &state.v,
+            &scale(&weighted(k1.v, k2.v, k3.v, k4.v), dt / 6.0),
+        ),
+        m: (state.m + (k1.m + 2.0 * k2.m + 2.0 * k3.m + k4.m) * dt / 6.0).max(engine.dry_mass_kg),
+    }
+}
 
-    Ok(InterplanetaryPlan {
-        time_of_flight_days: time / 86_400.0,
-        propellant_used_kg: Some(propellant_used),
-        departure_state,
-        arrival_state,
-        peak_speed_km_s: Some(peak_speed.abs()),
-    })
+/// Time derivative of the state; the `m` slot carries the (negative) mass flow.
+fn derivative(engine: &Engine<'_>, state: &State, throttle: f64, mu_sun: f64) -> State {
+    let r_mag = norm3(&state.r).max(1.0);
+    let mut accel = scale(&state.r, -mu_sun / r_mag.powi(3));
+    let mut mass_rate = 0.0;
+    let speed = norm3(&state.v);
+    if throttle != 0.0 && speed > 0.0 {
+        let (thrust_accel, mass_flow) = engine.output(r_mag, state.m);
+        accel = add(&accel, &scale(&state.v, throttle * thrust_accel / speed));
+        mass_rate = -throttle.abs() * mass_flow;
+    }
+    State {
+        r: state.v,
+        v: accel,
+        m: mass_rate,
+    }
+}
+
+fn advance(state: &State, rate: &State, dt: f64) -> State {
+    State {
+        r: add(&state.r, &scale(&rate.r, dt)),
+        v: add(&state.v, &scale(&rate.v, dt)),
+        m: state.m + rate.m * dt,
+    }
+}
+
+fn sample(time_s: f64, state: &State) -> TrajectorySample {
+    TrajectorySample {
+        time_s,
+        position_km: state.r,
+        velocity_km_s: state.v,
+        mass_kg: state.m,
+    }
+}
+
+fn angle_between(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    let denom = norm3(a) * norm3(b);
+    if denom <= 0.0 {
+        return 0.0;
+    }
+    acos_clamped(dot(a, b) / denom)
 }
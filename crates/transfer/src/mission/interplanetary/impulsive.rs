@@ -0,0 +1,85 @@
+use solar_config::{ConstantsSet, PlanetConfig};
+use solar_orbits::{capture_delta_v, escape_delta_v, norm3, sub};
+use solar_propulsion::Vehicle;
+
+use super::{InterplanetaryPlan, SECONDS_PER_DAY, best_lambert_branch};
+
+/// Burns and propellant of an impulsive cruise, sized from the Lambert arc joining the plan's
+/// departure and arrival states.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImpulsiveBurns {
+    /// Heliocentric injection v-infinity relative to the origin body (km/s).
+    pub vinf_depart_km_s: f64,
+    /// Heliocentric arrival v-infinity relative to the destination body (km/s).
+    pub vinf_arrive_km_s: f64,
+    /// Patched-conic escape burn from the origin parking orbit (km/s).
+    pub dv_depart_km_s: f64,
+    /// Patched-conic capture burn into the destination parking orbit (km/s).
+    pub dv_arrive_km_s: f64,
+    pub propellant_depart_kg: f64,
+    pub propellant_arrive_kg: f64,
+    /// Delta-v the vehicle could not deliver once all its propellant was spent (km/s).
+    pub shortfall_km_s: f64,
+}
+
+impl ImpulsiveBurns {
+    /// Propellant burned across both manoeuvres (kg).
+    pub fn propellant_total_kg(&self) -> f64 {
+        self.propellant_depart_kg + self.propellant_arrive_kg
+    }
+}
+
+impl InterplanetaryPlan {
+    /// Size the departure and capture burns of this cruise for an impulsive `vehicle`.
+    ///
+    /// The v-infinities come from the Lambert branch with the lower combined excess speed over
+    /// the plan's TOF, and the burns are ideal patched-conic escape/capture manoeuvres between
+    /// circular parking orbits at `parking_altitudes_km` (origin, destination). Propellant follows
+    /// the rocket equation from the fully fuelled vehicle through its stages (see
+    /// [`Vehicle::staging`]), with capture assumed fully propulsive; when the vehicle runs dry
+    /// the undelivered delta-v is reported as `shortfall_km_s`. Returns `None` when no Lambert
+    /// branch converges or a stage's propulsion mode has no specific impulse.
+    pub fn size_impulsive_burns(
+        &self,
+        vehicle: &Vehicle,
+        origin: &PlanetConfig,
+        destination: &PlanetConfig,
+        parking_altitudes_km: (f64, f64),
+        constants: &ConstantsSet,
+    ) -> Option<ImpulsiveBurns> {
+        let (v1, v2) = best_lambert_branch(
+            &self.departure_state,
+            &self.arrival_state,
+            self.time_of_flight_days * SECONDS_PER_DAY,
+            constants.mu_sun_km3_s2,
+        )?;
+        let vinf_depart = norm3(&sub(&v1, &self.departure_state.velocity_km_s));
+        let vinf_arrive = norm3(&sub(&v2, &self.arrival_state.velocity_km_s));
+
+        let (depart_altitude, arrive_altitude) = parking_altitudes_km;
+        let dv_depart = escape_delta_v(
+            constants.body_mu(origin),
+            origin.radius_km + depart_altitude,
+            vinf_depart,
+        );
+        let dv_arrive = capture_delta_v(
+            constants.body_mu(destination),
+            destination.radius_km + arrive_altitude,
+            vinf_arrive,
+        );
+
+        let mut staging = vehicle.staging();
+        let depart = staging.burn(dv_depart)?;
+        let arrive = staging.burn(dv_arrive)?;
+
+        Some(ImpulsiveBurns {
+            vinf_depart_km_s: vinf_depart,
+            vinf_arrive_km_s: vinf_arrive,
+            dv_depart_km_s: dv_depart,
+            dv_arrive_km_s: dv_arrive,
+            propellant_depart_kg: depart.propellant_kg,
+            propellant_arrive_kg: arrive.propellant_kg,
+            shortfall_km_s: depart.shortfall_km_s + arrive.shortfall_km_s,
+        })
+    }
+}
@@ -0,0 +1,378 @@
+//! Multi-leg gravity-assist itineraries: Lambert arcs chained through unpowered flybys.
+
+use solar_config::{ConstantsSet, PlanetConfig};
+use solar_ephem_spice::{self as ephemeris, StateVector};
+use solar_orbits::{capture_delta_v, escape_delta_v, norm3};
+use solar_propulsion::Vehicle;
+
+use super::flyby::{self, FlybyError, FlybyResult, solve_flyby};
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// When a body in the sequence is visited.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LegEpoch {
+    /// Fixed encounter epoch (TDB seconds past J2000).
+    Fixed(f64),
+    /// Encounter epoch searched on an inclusive grid from `start_et` to `end_et`.
+    Window {
+        start_et: f64,
+        end_et: f64,
+        step_days: f64,
+    },
+}
+
+/// One body in an itinerary sequence: the departure body, a flyby body, or the destination.
+#[derive(Debug, Clone)]
+pub struct LegSpec<'a> {
+    pub body: &'a PlanetConfig,
+    pub epoch: LegEpoch,
+    /// Parking altitude at the departure/destination body, or the minimum periapsis altitude
+    /// allowed at a flyby body.
+    pub altitude_km: f64,
+}
+
+/// Heliocentric Lambert arc between two consecutive bodies of an [`Itinerary`].
+#[derive(Debug, Clone)]
+pub struct ItineraryLeg {
+    pub from_body: String,
+    pub to_body: String,
+    pub depart_et: f64,
+    pub arrive_et: f64,
+    pub tof_days: f64,
+    pub short_path: bool,
+    pub vinf_depart_km_s: [f64; 3],
+    pub vinf_arrive_km_s: [f64; 3],
+}
+
+/// Flyby at an intermediate body, matching the inbound and outbound arcs' v∞.
+#[derive(Debug, Clone)]
+pub struct FlybyEncounter {
+    pub body: String,
+    pub et: f64,
+    pub vinf_in_km_s: f64,
+    pub vinf_out_km_s: f64,
+    pub geometry: FlybyResult,
+}
+
+/// Propellant bookkeeping for one leg, with the vehicle's mass carried over from the leg before.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LegReport {
+    /// Δv burned on the leg: the escape burn on the first, the flyby correction at the leg's
+    /// starting body on later ones, plus the capture burn on the last.
+    pub delta_v_km_s: f64,
+    pub propellant_used_kg: f64,
+    pub mass_after_kg: f64,
+    /// Whether the vehicle still had the propellant to deliver the leg's Δv in full.
+    pub feasible: bool,
+}
+
+/// Lowest-Δv chain found by [`plan_itinerary`].
+#[derive(Debug, Clone)]
+pub struct Itinerary {
+    pub legs: Vec<ItineraryLeg>,
+    /// One entry per leg, burning through the vehicle's stages in order.
+    pub reports: Vec<LegReport>,
+    /// One entry per intermediate body; infeasible matches are kept with `geometry.feasible`
+    /// unset rather than failing the chain.
+    pub flybys: Vec<FlybyEncounter>,
+    /// Escape burn from the parking orbit at the first body.
+    pub departure_dv_km_s: f64,
+    /// Capture burn into the parking orbit at the last body.
+    pub arrival_dv_km_s: f64,
+}
+
+impl Itinerary {
+    /// Sum of the residual corrections the flybys could not supply.
+    pub fn flyby_dv_km_s(&self) -> f64 {
+        self.flybys
+            .iter()
+            .map(|encounter| encounter.geometry.delta_v_deficit_km_s)
+            .sum()
+    }
+
+    /// Departure, arrival and flyby correction Δv combined.
+    pub fn total_dv_km_s(&self) -> f64 {
+        self.departure_dv_km_s + self.arrival_dv_km_s + self.flyby_dv_km_s()
+    }
+
+    /// First leg on which the vehicle runs out of propellant, if any.
+    pub fn first_infeasible_leg(&self) -> Option<usize> {
+        self.reports.iter().position(|report| !report.feasible)
+    }
+
+    /// Whether every flyby in the chain is achievable unpowered.
+    pub fn is_feasible(&self) -> bool {
+        self.flybys
+            .iter()
+            .all(|encounter| encounter.geometry.feasible)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ItineraryError {
+    #[error("an itinerary needs at least two bodies, got {0}")]
+    TooFewBodies(usize),
+    #[error("epoch window for body {index} is empty or has a non-positive step")]
+    InvalidWindow { index: usize },
+    #[error("ephemeris lookup failed: {0}")]
+    Ephemeris(#[from] ephemeris::EphemerisError),
+    #[error("no Lambert arc found for leg {leg} ({from} -> {to})")]
+    NoArc {
+        leg: usize,
+        from: String,
+        to: String,
+    },
+    #[error(transparent)]
+    Flyby(#[from] FlybyError),
+}
+
+/// Candidate arc for one leg between grid epochs `from_epoch` and `to_epoch`, together with the
+/// cheapest chain reaching it.
+struct ArcLabel {
+    from_epoch: usize,
+    to_epoch: usize,
+    short: bool,
+    vinf_depart: [f64; 3],
+    vinf_arrive: [f64; 3],
+    cost: f64,
+    parent: Option<usize>,
+}
+
+/// Chain zero-revolution Lambert arcs through `sequence`, choosing encounter epochs and arc
+/// branches that minimise escape + flyby correction + capture Δv.
+///
+/// Each intermediate body is flown with [`solve_flyby`]; a turn beyond what the body's minimum
+/// periapsis allows is charged as residual Δv and reported on its [`FlybyEncounter`]. The
+/// chosen chain's Δv is then charged leg by leg against `vehicle` (see [`leg_reports`]).
+pub fn plan_itinerary(
+    sequence: &[LegSpec<'_>],
+    vehicle: &Vehicle,
+    constants: &ConstantsSet,
+) -> Result<Itinerary, ItineraryError> {
+    if sequence.len() < 2 {
+        return Err(ItineraryError::TooFewBodies(sequence.len()));
+    }
+    let mu_sun = constants.mu_sun_km3_s2;
+
+    let mut epochs = Vec::with_capacity(sequence.len());
+    let mut states = Vec::with_capacity(sequence.len());
+    for (index, spec) in sequence.iter().enumerate() {
+        let grid = epoch_grid(&spec.epoch).ok_or(ItineraryError::InvalidWindow { index })?;
+        let target = ephemeris::normalize_heliocentric_target_name(&spec.body.spice_name);
+        let body_states = grid
+            .iter()
+            .map(|&et| flyby::heliocentric_state(&target, et))
+            .collect::<Result<Vec<StateVector>, _>>()?;
+        epochs.push(grid);
+        states.push(body_states);
+    }
+
+    let last = sequence.len() - 1;
+    let mut layers: Vec<Vec<ArcLabel>> = Vec::with_capacity(last);
+    for leg in 0..last {
+        let (from, to) = (&sequence[leg], &sequence[leg + 1]);
+        let mut layer = Vec::new();
+        for (a, &depart_et) in epochs[leg].iter().enumerate() {
+            for (b, &arrive_et) in epochs[leg + 1].iter().enumerate() {
+                let tof = arrive_et - depart_et;
+                if tof <= 0.0 {
+                    continue;
+                }
+                for short in [true, false] {
+                    let Some((vinf_depart, vinf_arrive)) =
+                        flyby::vinf_pair(&states[leg][a], &states[leg + 1][b], tof, mu_sun, short)
+                    else {
+                        continue;
+                    };
+                    let (cost, parent) = if leg == 0 {
+                        let dv = escape_delta_v(
+                            constants.body_mu(from.body),
+                            from.body.radius_km + from.altitude_km,
+                            norm3(&vinf_depart),
+                        );
+                        (dv, None)
+                    } else {
+                        let flyby_mu = constants.body_mu(from.body);
+                        let min_periapsis = from.body.radius_km + from.altitude_km;
+                        let mut best: Option<(f64, usize)> = None;
+                        for (index, prev) in layers[leg - 1].iter().enumerate() {
+                            if prev.to_epoch != a {
+                                continue;
+                            }
+                            let geometry = solve_flyby(
+                                prev.vinf_arrive,
+                                vinf_depart,
+                                flyby_mu,
+                                min_periapsis,
+                            )?;
+                            let cost = prev.cost + geometry.delta_v_deficit_km_s;
+                            if best.is_none_or(|(c, _)| cost < c) {
+                                best = Some((cost, index));
+                            }
+                        }
+                        let Some((cost, index)) = best else {
+                            continue;
+                        };
+                        (cost, Some(index))
+                    };
+                    let cost = if leg + 1 == last {
+                        cost + capture_delta_v(
+                            constants.body_mu(to.body),
+                            to.body.radius_km + to.altitude_km,
+                            norm3(&vinf_arrive),
+                        )
+                    } else {
+                        cost
+                    };
+                    layer.push(ArcLabel {
+                        from_epoch: a,
+                        to_epoch: b,
+                        short,
+                        vinf_depart,
+                        vinf_arrive,
+                        cost,
+                        parent,
+                    });
+                }
+            }
+        }
+        if layer.is_empty() {
+            return Err(ItineraryError::NoArc {
+                leg,
+                from: from.body.name.clone(),
+                to: to.body.name.clone(),
+            });
+        }
+        layers.push(layer);
+    }
+
+    // Walk the back-pointers from the cheapest final arc.
+    let mut chosen = Vec::with_capacity(last);
+    let mut cursor = layers[last - 1]
+        .iter()
+        .enumerate()
+        .min_by(|x, y| x.1.cost.total_cmp(&y.1.cost))
+        .map(|(index, _)| index);
+    for layer in layers.iter().rev() {
+        let Some(index) = cursor else { break };
+        chosen.push(&layer[index]);
+        cursor = layer[index].parent;
+    }
+    chosen.reverse();
+
+    let legs: Vec<ItineraryLeg> = chosen
+        .iter()
+        .enumerate()
+        .map(|(leg, arc)| {
+            let depart_et = epochs[leg][arc.from_epoch];
+            let arrive_et = epochs[leg + 1][arc.to_epoch];
+            ItineraryLeg {
+                from_body: sequence[leg].body.name.clone(),
+                to_body: sequence[leg + 1].body.name.clone(),
+                depart_et,
+                arrive_et,
+                tof_days: (arrive_et - depart_et) / SECONDS_PER_DAY,
+                short_path: arc.short,
+                vinf_depart_km_s: arc.vinf_depart,
+                vinf_arrive_km_s: arc.vinf_arrive,
+            }
+        })
+        .collect();
+
+    let mut flybys = Vec::with_capacity(last - 1);
+    for node in 1..last {
+        let (inbound, outbound) = (&legs[node - 1], &legs[node]);
+        let spec = &sequence[node];
+        let geometry = solve_flyby(
+            inbound.vinf_arrive_km_s,
+            outbound.vinf_depart_km_s,
+            constants.body_mu(spec.body),
+            spec.body.radius_km + spec.altitude_km,
+        )?;
+        flybys.push(FlybyEncounter {
+            body: spec.body.name.clone(),
+            et: outbound.depart_et,
+            vinf_in_km_s: norm3(&inbound.vinf_arrive_km_s),
+            vinf_out_km_s: norm3(&outbound.vinf_depart_km_s),
+            geometry,
+        });
+    }
+
+    let (first, final_body) = (&sequence[0], &sequence[last]);
+    let departure_dv_km_s = escape_delta_v(
+        constants.body_mu(first.body),
+        first.body.radius_km + first.altitude_km,
+        norm3(&legs[0].vinf_depart_km_s),
+    );
+    let arrival_dv_km_s = capture_delta_v(
+        constants.body_mu(final_body.body),
+        final_body.body.radius_km + final_body.altitude_km,
+        norm3(&legs[last - 1].vinf_arrive_km_s),
+    );
+
+    let leg_delta_v = (0..last).map(|leg| {
+        let start = match leg {
+            0 => departure_dv_km_s,
+            _ => flybys[leg - 1].geometry.delta_v_deficit_km_s,
+        };
+        if leg + 1 == last {
+            start + arrival_dv_km_s
+        } else {
+            start
+        }
+    });
+    let reports = leg_reports(vehicle, leg_delta_v);
+
+    Ok(Itinerary {
+        legs,
+        reports,
+        flybys,
+        departure_dv_km_s,
+        arrival_dv_km_s,
+    })
+}
+
+/// Burn each leg's Δv in turn with the rocket equation, carrying the vehicle's mass and staging
+/// state from one leg to the next.
+///
+/// Once the vehicle runs dry, every later leg that needs Δv is reported infeasible with no
+/// propellant used. A stage without a specific impulse cannot be sized and marks the leg
+/// infeasible as well.
+pub fn leg_reports(
+    vehicle: &Vehicle,
+    delta_v_km_s: impl IntoIterator<Item = f64>,
+) -> Vec<LegReport> {
+    let mut staging = vehicle.staging();
+    delta_v_km_s
+        .into_iter()
+        .map(|delta_v_km_s| {
+            let burn = staging.burn(delta_v_km_s);
+            LegReport {
+                delta_v_km_s,
+                propellant_used_kg: burn.map_or(0.0, |burn| burn.propellant_kg),
+                mass_after_kg: staging.mass_kg(),
+                feasible: burn.is_some_and(|burn| burn.shortfall_km_s == 0.0),
+            }
+        })
+        .collect()
+}
+
+/// Expand a [`LegEpoch`] into its candidate epochs; `None` for an unusable window.
+fn epoch_grid(epoch: &LegEpoch) -> Option<Vec<f64>> {
+    match *epoch {
+        LegEpoch::Fixed(et) => Some(vec![et]),
+        LegEpoch::Window {
+            start_et,
+            end_et,
+            step_days,
+        } => {
+            let step = step_days * SECONDS_PER_DAY;
+            if !(step.is_finite() && step > 0.0) || end_et < start_et {
+                return None;
+            }
+            let count = ((end_et - start_et) / step).floor() as usize + 1;
+            Some((0..count).map(|i| start_et + i as f64 * step).collect())
+        }
+    }
+}
@@ -2,14 +2,24 @@
 
 pub mod arrival;
 pub mod departure;
+pub mod flyby;
 pub mod interplanetary;
+pub mod itinerary;
 pub mod porkchop;
+pub mod synodic;
+
+pub use self::porkchop::fixed_tof_windows;
+
+use std::time::{Duration, Instant};
 
 use self::arrival::{ArrivalConfig, ArrivalPlan};
 use self::departure::{DepartureConfig, DeparturePlan};
 use self::interplanetary::{InterplanetaryConfig, InterplanetaryPlan};
-use solar_config::PlanetConfig;
-use solar_propulsion::Vehicle;
+use solar_config::{ConstantsSet, PlanetConfig};
+use solar_ephem_spice::source::{EphemerisSource, SpiceSource};
+use solar_ephem_spice::{self as ephemeris, EphemerisError};
+use solar_orbits::{capture_delta_v, escape_delta_v};
+use solar_propulsion::{PropulsionMode, Vehicle};
 
 /// Aggregated mission profile describing the three sequential legs.
 #[derive(Debug)]
@@ -19,15 +29,40 @@ pub struct MissionProfile {
     pub arrival: ArrivalPlan,
 }
 
+/// Wall-clock time spent in each phase of [`plan_mission_timed`].
+///
+/// Phases that were not reached because an earlier phase failed report zero.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PhaseTimings {
+    pub departure: Duration,
+    pub cruise: Duration,
+    /// Portion of `cruise` spent in SPICE epoch conversions and state lookups.
+    pub cruise_spice: Duration,
+    /// Portion of `cruise` spent outside SPICE (Lambert solves, integration, bookkeeping).
+    pub cruise_math: Duration,
+    pub arrival: Duration,
+    pub total: Duration,
+}
+
 /// Top-level mission planning error.
 #[derive(Debug, thiserror::Error)]
 pub enum MissionError {
+    #[error("origin and destination are the same body ({0})")]
+    IdenticalBodies(String),
     #[error("departure planning failed: {0}")]
     Departure(#[from] departure::DepartureError),
     #[error("interplanetary planning failed: {0}")]
     Cruise(#[from] interplanetary::InterplanetaryError),
     #[error("arrival planning failed: {0}")]
     Arrival(#[from] arrival::ArrivalError),
+    #[error(
+        "{vehicle} carries {available_km_s:.3} km/s of delta-v but leaving and entering the parking orbits needs at least {required_km_s:.3} km/s"
+    )]
+    InsufficientDeltaV {
+        vehicle: String,
+        required_km_s: f64,
+        available_km_s: f64,
+    },
 }
 
 /// Inputs necessary to compute an end-to-end transfer between parking orbits.
@@ -39,22 +74,55 @@ pub struct MissionConfig {
     pub departure: DepartureConfig,
     pub cruise: InterplanetaryConfig,
     pub arrival: ArrivalConfig,
+    /// Gravitational parameters shared by every phase.
+    pub constants: ConstantsSet,
 }
 
 /// Run the three-phase mission planner, chaining departure, interplanetary, and arrival calculations.
 pub fn plan_mission(config: MissionConfig) -> Result<MissionProfile, MissionError> {
-    let cruise = interplanetary::plan_interplanetary(
-        &config.vehicle,
-        &config.cruise,
-        &config.origin,
-        &config.destination,
-    )?;
+    plan_mission_timed(config).0
+}
+
+/// Variant of [`plan_mission`] that also records per-phase wall-clock timings.
+pub fn plan_mission_timed(
+    config: MissionConfig,
+) -> (Result<MissionProfile, MissionError>, PhaseTimings) {
+    let mut timings = PhaseTimings::default();
+    let started = Instant::now();
+    let result = plan_phases(&SpiceSource, &config, &mut timings);
+    timings.total = started.elapsed();
+    (result, timings)
+}
+
+/// Variant of [`plan_mission`] that takes every epoch and body state from `source`.
+///
+/// With a [`FixedEphemerisSource`](solar_ephem_spice::source::FixedEphemerisSource) holding the
+/// origin and destination heliocentric tracks this runs the full orchestration deterministically
+/// and without kernels.
+pub fn plan_mission_with_source(
+    config: &MissionConfig,
+    source: &dyn EphemerisSource,
+) -> Result<MissionProfile, MissionError> {
+    plan_phases(source, config, &mut PhaseTimings::default())
+}
+
+/// Plan the departure and arrival phases around a pre-computed cruise leg.
+///
+/// Together with [`InterplanetaryPlan::from_states`] this runs the Lambert, escape/capture, and
+/// aerobraking logic without any SPICE kernels, provided `config.cruise.arrival_epoch` is `None`.
+pub fn plan_mission_with_cruise(
+    config: &MissionConfig,
+    cruise: InterplanetaryPlan,
+) -> Result<MissionProfile, MissionError> {
+    ensure_distinct_bodies(config)?;
+    ensure_delta_v_budget(config)?;
     let departure = departure::plan_departure(
         &config.vehicle,
         &config.departure,
         &config.origin,
         &config.cruise,
         &cruise,
+        &config.constants,
     )?;
     let arrival = arrival::plan_arrival(
         &config.vehicle,
@@ -63,6 +131,7 @@ pub fn plan_mission(config: MissionConfig) -> Result<MissionProfile, MissionErro
         &config.cruise,
         config.arrival.aerobraking,
         &cruise,
+        &config.constants,
     )?;
 
     Ok(MissionProfile {
@@ -71,3 +140,170 @@ pub fn plan_mission(config: MissionConfig) -> Result<MissionProfile, MissionErro
         arrival,
     })
 }
+
+/// NAIF ID of the Sun.
+const SUN_NAIF_ID: i32 = 10;
+/// Relative GM difference above which a catalog value is reported as disagreeing with the
+/// kernel pool.
+pub const GM_MISMATCH_TOLERANCE: f64 = 1e-6;
+
+/// A body whose catalog `mu_km3_s2` disagrees with the GM in use for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GmMismatch {
+    pub body: String,
+    pub catalog_mu_km3_s2: f64,
+    /// GM the planner will use instead, typically read by [`kernel_constants`].
+    pub kernel_mu_km3_s2: f64,
+}
+
+impl GmMismatch {
+    /// `|kernel − catalog| / kernel`.
+    pub fn relative_difference(&self) -> f64 {
+        ((self.kernel_mu_km3_s2 - self.catalog_mu_km3_s2) / self.kernel_mu_km3_s2).abs()
+    }
+}
+
+/// Gravitational constants read from the loaded kernel pool, so the Sun and body GMs match the
+/// ephemeris the states come from.
+///
+/// Each of `bodies` whose `BODY<id>_GM` is defined gets a `body_mu_km3_s2` override; bodies
+/// without one (and the Sun, when the pool carries no GMs at all) keep the DE440/catalog values
+/// of [`ConstantsSet::default`].
+pub fn kernel_constants(bodies: &[PlanetConfig]) -> Result<ConstantsSet, EphemerisError> {
+    let mut constants = ConstantsSet::default();
+    if let Some(mu_sun) = pooled_gm(SUN_NAIF_ID)? {
+        constants.mu_sun_km3_s2 = mu_sun;
+    }
+    for body in bodies {
+        let Some(id) = ephemeris::body_id(&body.spice_name)? else {
+            continue;
+        };
+        if let Some(mu) = pooled_gm(id)? {
+            constants.body_mu_km3_s2.insert(body.spice_name.clone(), mu);
+        }
+    }
+    Ok(constants)
+}
+
+/// Bodies whose catalog GM differs from the one `constants` resolves for them by more than
+/// `tolerance` (relative), so callers can warn before planning with [`kernel_constants`].
+pub fn gm_mismatches(
+    bodies: &[PlanetConfig],
+    constants: &ConstantsSet,
+    tolerance: f64,
+) -> Vec<GmMismatch> {
+    bodies
+        .iter()
+        .map(|body| GmMismatch {
+            body: body.name.clone(),
+            catalog_mu_km3_s2: body.mu_km3_s2,
+            kernel_mu_km3_s2: constants.body_mu(body),
+        })
+        .filter(|mismatch| mismatch.relative_difference() > tolerance)
+        .collect()
+}
+
+fn pooled_gm(naif_id: i32) -> Result<Option<f64>, EphemerisError> {
+    match ephemeris::body_gm(naif_id) {
+        Ok(mu) => Ok(Some(mu)),
+        Err(EphemerisError::MissingGm { .. }) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+fn ensure_distinct_bodies(config: &MissionConfig) -> Result<(), MissionError> {
+    let origin = &config.origin.spice_name;
+    if origin.eq_ignore_ascii_case(&config.destination.spice_name) {
+        return Err(MissionError::IdenticalBodies(origin.clone()));
+    }
+    Ok(())
+}
+
+/// Reject impulsive vehicles whose [`Vehicle::total_delta_v_km_s`] cannot even cover escape and
+/// capture at zero v-infinity, before any Lambert solve. Aerobraking arrivals skip the capture
+/// floor.
+fn ensure_delta_v_budget(config: &MissionConfig) -> Result<(), MissionError> {
+    if !matches!(config.vehicle.propulsion, PropulsionMode::Impulsive { .. }) {
+        return Ok(());
+    }
+    let Some(available) = config.vehicle.total_delta_v_km_s() else {
+        return Ok(());
+    };
+    let escape = escape_delta_v(
+        config.constants.body_mu(&config.origin),
+        config.origin.radius_km + config.departure.parking_altitude_km,
+        0.0,
+    );
+    let capture = if config.arrival.aerobraking.is_some() {
+        0.0
+    } else {
+        capture_delta_v(
+            config.constants.body_mu(&config.destination),
+            config.destination.radius_km + config.arrival.target_parking_altitude_km,
+            0.0,
+        )
+    };
+    if escape + capture > available {
+        return Err(MissionError::InsufficientDeltaV {
+            vehicle: config.vehicle.name.clone(),
+            required_km_s: escape + capture,
+            available_km_s: available,
+        });
+    }
+    Ok(())
+}
+
+fn plan_phases(
+    source: &dyn EphemerisSource,
+    config: &MissionConfig,
+    timings: &mut PhaseTimings,
+) -> Result<MissionProfile, MissionError> {
+    ensure_distinct_bodies(config)?;
+    ensure_delta_v_budget(config)?;
+    let phase_start = Instant::now();
+    let cruise = interplanetary::plan_interplanetary_instrumented(
+        source,
+        &config.vehicle,
+        &config.cruise,
+        &config.origin,
+        &config.destination,
+        &config.constants,
+        &mut timings.cruise_spice,
+    );
+    timings.cruise = phase_start.elapsed();
+    timings.cruise_math = timings.cruise.saturating_sub(timings.cruise_spice);
+    let cruise = cruise?;
+
+    let phase_start = Instant::now();
+    let departure = departure::plan_departure_with_source(
+        source,
+        &config.vehicle,
+        &config.departure,
+        &config.origin,
+        &config.cruise,
+        &cruise,
+        &config.constants,
+    );
+    timings.departure = phase_start.elapsed();
+    let departure = departure?;
+
+    let phase_start = Instant::now();
+    let arrival = arrival::plan_arrival_with_source(
+        source,
+        &config.vehicle,
+        &config.arrival,
+        &config.destination,
+        &config.cruise,
+        config.arrival.aerobraking,
+        &cruise,
+        &config.constants,
+    );
+    timings.arrival = phase_start.elapsed();
+    let arrival = arrival?;
+
+    Ok(MissionProfile {
+        departure,
+        cruise,
+        arrival,
+    })
+}
@@ -2,20 +2,30 @@
 
 use std::cmp::Ordering;
 use std::fs::{self, File};
-use std::io::{BufReader, BufWriter};
+use std::io::{self, BufReader, BufWriter, Write};
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::thread;
 
 use serde::{Deserialize, Serialize};
 use serde_json;
-use solar_config::PlanetConfig;
-use solar_core::constants::G0;
+use solar_config::{ConstantsSet, PlanetConfig};
+use solar_core::angles::acos_clamped;
+use solar_core::vector::{cross, dot};
+use solar_ephem_spice::cache::{CachedStates, SpiceStates, StateProvider};
 use solar_ephem_spice::{self as ephemeris, StateVector};
+use solar_export::porkchop::Grid;
 use solar_impulsive::lambert;
+use solar_impulsive::transfers::combined_plane_change;
+use solar_orbits::c3_from_vinf;
 use solar_propulsion::{PropulsionMode, Vehicle};
 
-const MU_SUN: f64 = 1.327_124_400_18e11; // km^3 / s^2
-pub const WINDOW_DATASET_VERSION: u32 = 1;
+pub const WINDOW_DATASET_VERSION: u32 = 4;
 const TIME_GROUP_TOLERANCE_S: f64 = 1.0;
+/// Parking radii closer than this are treated as the same window-dataset request.
+const PARKING_RADIUS_TOLERANCE_KM: f64 = 1e-3;
+/// Slack allowed when deciding whether the last grid step still lands on `end_et`.
+const WINDOW_END_TOLERANCE_S: f64 = 1.0;
 
 #[derive(Debug, Clone)]
 pub struct TimeWindow {
@@ -24,6 +34,44 @@ pub struct TimeWindow {
     pub step_seconds: f64,
 }
 
+impl TimeWindow {
+    /// Number of epochs yielded by [`TimeWindow::epochs`].
+    pub fn len(&self) -> usize {
+        if self.end_et + WINDOW_END_TOLERANCE_S < self.start_et {
+            return 0;
+        }
+        if !(self.step_seconds.is_finite() && self.step_seconds > 0.0) {
+            return 1;
+        }
+        ((self.end_et - self.start_et + WINDOW_END_TOLERANCE_S) / self.step_seconds).floor()
+            as usize
+            + 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Grid epochs `start + k·step`, including `end_et` when a step lands on it.
+    ///
+    /// Epochs are computed by multiplication rather than accumulation, so long windows do not
+    /// drift off the grid.
+    pub fn epochs(&self) -> impl Iterator<Item = f64> + use<> {
+        let start = self.start_et;
+        let step = self.step_seconds;
+        (0..self.len()).map(move |k| start + k as f64 * step)
+    }
+}
+
+/// One end of a transfer: the body, the planet it orbits when it is a moon, and the parking-orbit
+/// radius its escape or capture burn is sized at.
+#[derive(Debug, Clone, Copy)]
+pub struct TransferEndpoint<'a> {
+    pub body: &'a PlanetConfig,
+    pub parent: Option<&'a PlanetConfig>,
+    pub rpark_km: f64,
+}
+
 #[derive(Debug, Clone)]
 pub struct PorkchopRequest<'a> {
     pub origin_body: &'a PlanetConfig,
@@ -37,12 +85,46 @@ pub struct PorkchopRequest<'a> {
     pub arrival_window: TimeWindow,
     pub long_path_only: bool,
     pub ignore_vehicle_limits: bool,
+    /// Emit grid cells with no feasible transfer; plotters need them to draw the full grid.
+    pub include_infeasible: bool,
+    /// Gravitational parameters for the Lambert solves and escape/capture burns.
+    pub constants: &'a ConstantsSet,
+    /// Directory of on-disk state caches consulted before SPICE; `None` always queries SPICE.
+    pub state_cache_dir: Option<&'a Path>,
+    /// Also try multi-revolution Lambert arcs up to this many full orbits; 0 keeps to direct arcs.
+    pub max_revolutions: u32,
+    /// Fold the heliocentric plane change between the two bodies' orbits into the capture
+    /// burn; `false` keeps the coplanar burn sizing.
+    pub plane_change_penalty: bool,
+}
+
+impl<'a> PorkchopRequest<'a> {
+    pub fn origin(&self) -> TransferEndpoint<'a> {
+        TransferEndpoint {
+            body: self.origin_body,
+            parent: self.origin_parent,
+            rpark_km: self.rpark_depart_km,
+        }
+    }
+
+    pub fn destination(&self) -> TransferEndpoint<'a> {
+        TransferEndpoint {
+            body: self.destination_body,
+            parent: self.destination_parent,
+            rpark_km: self.rpark_arrive_km,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PorkchopPath {
     Short,
     Long,
+    /// Multi-revolution arc; `left` selects the branch below the minimum-time point.
+    MultiRev {
+        revolutions: u32,
+        left: bool,
+    },
     None,
 }
 
@@ -64,6 +146,9 @@ pub struct PorkchopPoint {
     pub final_mass_kg: f64,
     pub lambert_path: PorkchopPath,
     pub feasible: bool,
+    /// Angle between the departure body's orbit plane at departure and the arrival body's at
+    /// arrival (see [`relative_inclination_rad`]).
+    pub relative_inclination_deg: f64,
 }
 
 #[derive(Debug, Clone)]
@@ -126,6 +211,14 @@ pub struct WindowSample {
     pub dv_total_km_s: f64,
     pub dv_depart_km_s: f64,
     pub dv_arrive_km_s: f64,
+    pub c3_km2_s2: f64,
+}
+
+impl WindowSample {
+    /// Time of flight of this sample in days.
+    pub fn tof_days(&self) -> f64 {
+        (self.arrive_et - self.depart_et) / 86_400.0
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -138,12 +231,42 @@ pub struct WindowDataset {
     pub step_days: f64,
     pub min_tof_days: f64,
     pub max_tof_days: f64,
+    /// Vehicle the burns were sized for.
+    #[serde(default)]
+    pub vehicle: String,
+    #[serde(default)]
+    pub rpark_depart_km: f64,
+    #[serde(default)]
+    pub rpark_arrive_km: f64,
+    /// [`ConstantsSet::fingerprint`] of the GMs the sweep was sized with; empty in caches written
+    /// before it was recorded.
+    #[serde(default)]
+    pub constants_fingerprint: String,
     pub min_dv_total_km_s: Option<f64>,
     pub samples: Vec<WindowSample>,
+    /// Non-dominated (Δv, TOF) samples of every departure epoch, ordered by departure and then
+    /// by increasing TOF. Empty for datasets written before version 3.
+    #[serde(default)]
+    pub pareto: Vec<WindowSample>,
 }
 
 impl WindowDataset {
-    fn baseline_sample(&self) -> Option<&WindowSample> {
+    /// Whether the dataset was computed for `vehicle`, these parking radii, and `constants`.
+    pub fn matches_parameters(
+        &self,
+        vehicle: &str,
+        rpark_depart_km: f64,
+        rpark_arrive_km: f64,
+        constants: &ConstantsSet,
+    ) -> bool {
+        self.vehicle == vehicle
+            && self.constants_fingerprint == constants.fingerprint()
+            && (self.rpark_depart_km - rpark_depart_km).abs() <= PARKING_RADIUS_TOLERANCE_KM
+            && (self.rpark_arrive_km - rpark_arrive_km).abs() <= PARKING_RADIUS_TOLERANCE_KM
+    }
+
+    /// Lowest total delta-v sample across the whole dataset.
+    pub fn baseline_sample(&self) -> Option<&WindowSample> {
         self.samples.iter().min_by(|a, b| {
             a.dv_total_km_s
                 .partial_cmp(&b.dv_total_km_s)
@@ -152,16 +275,32 @@ impl WindowDataset {
     }
 }
 
+/// Distribution of departure C3 across the samples of a window.
+#[derive(Debug, Clone, Copy)]
+pub struct C3Stats {
+    pub sample_count: usize,
+    pub min_km2_s2: f64,
+    pub median_km2_s2: f64,
+    pub max_km2_s2: f64,
+}
+
 #[derive(Debug, Clone)]
 pub struct WindowSuggestion {
     pub baseline: WindowSample,
     pub recommended: WindowSample,
     pub user_total_dv_km_s: f64,
     pub threshold_dv_km_s: f64,
+    /// Quickest Pareto sample that beats `recommended` on TOF without costing more Δv than
+    /// the user's own plan.
+    pub faster: Option<WindowSample>,
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum WindowError {
+    #[error("origin and destination are the same body ({0})")]
+    IdenticalBodies(String),
+    #[error("time of flight must be a positive, finite number of days (got {0})")]
+    InvalidTimeOfFlight(f64),
     #[error("ephemeris error: {0}")]
     Ephemeris(#[from] ephemeris::EphemerisError),
     #[error("I/O error: {0}")]
@@ -170,163 +309,400 @@ pub enum WindowError {
     Json(#[from] serde_json::Error),
 }
 
-pub fn generate(
-    request: &PorkchopRequest<'_>,
-) -> Result<Vec<PorkchopPoint>, ephemeris::EphemerisError> {
-    let transfer_origin = request.origin_parent.unwrap_or(request.origin_body);
-    let transfer_destination = request
-        .destination_parent
-        .unwrap_or(request.destination_body);
+pub fn generate(request: &PorkchopRequest<'_>) -> Result<Vec<PorkchopPoint>, WindowError> {
+    let grid = sample_grid(request)?;
+    let paths = candidate_paths(request);
+    Ok((0..grid.departure.len())
+        .flat_map(|dep_idx| evaluate_row(request, &grid, &paths, dep_idx))
+        .collect())
+}
+
+/// [`generate`] with the Lambert evaluations spread over worker threads, one departure row at a
+/// time.
+///
+/// Ephemeris sampling stays serial because CSPICE is not re-entrant; only the pure Lambert and
+/// burn assembly runs concurrently. Rows are stitched back in departure order, so the output is
+/// identical to [`generate`].
+pub fn generate_parallel(request: &PorkchopRequest<'_>) -> Result<Vec<PorkchopPoint>, WindowError> {
+    let grid = sample_grid(request)?;
+    let paths = candidate_paths(request);
+    let rows = grid.departure.len();
+    let workers = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .clamp(1, rows.max(1));
+
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, Vec<PorkchopPoint>)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut finished = Vec::new();
+                    loop {
+                        let dep_idx = next.fetch_add(1, AtomicOrdering::Relaxed);
+                        if dep_idx >= rows {
+                            break finished;
+                        }
+                        finished.push((dep_idx, evaluate_row(request, &grid, &paths, dep_idx)));
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("porkchop worker panicked"))
+            .collect()
+    });
+    results.sort_unstable_by_key(|(dep_idx, _)| *dep_idx);
+    Ok(results.into_iter().flat_map(|(_, row)| row).collect())
+}
+
+/// Ephemeris samples backing a porkchop grid.
+struct GridSamples {
+    departure: Vec<EphemerisSample>,
+    arrival: Vec<EphemerisSample>,
+    origin_rel: Option<Vec<EphemerisSample>>,
+    destination_rel: Option<Vec<EphemerisSample>>,
+}
+
+/// Sample every body state the grid needs; the only part of a sweep that touches SPICE.
+fn sample_grid(request: &PorkchopRequest<'_>) -> Result<GridSamples, WindowError> {
+    let (origin, destination) = (request.origin(), request.destination());
+    ensure_distinct_bodies(origin.body, destination.body)?;
+    let mut states = state_provider(request);
+    ensure_bodies_available(
+        origin,
+        destination,
+        states.as_mut(),
+        request.departure_window.start_et,
+        request.arrival_window.start_et,
+    )?;
+    sample_windows(
+        origin,
+        destination,
+        &request.departure_window,
+        &request.arrival_window,
+        states.as_mut(),
+    )
+}
+
+/// Transfer-body states over `departure` and `arrival`, plus each moon's state relative to its
+/// parent over the same epochs.
+fn sample_windows(
+    origin: TransferEndpoint<'_>,
+    destination: TransferEndpoint<'_>,
+    departure: &TimeWindow,
+    arrival: &TimeWindow,
+    states: &mut dyn StateProvider,
+) -> Result<GridSamples, WindowError> {
+    let transfer_origin = origin.parent.unwrap_or(origin.body);
+    let transfer_destination = destination.parent.unwrap_or(destination.body);
 
     let dep_transfer_target =
         ephemeris::normalize_heliocentric_target_name(&transfer_origin.spice_name);
     let arr_transfer_target =
         ephemeris::normalize_heliocentric_target_name(&transfer_destination.spice_name);
 
-    let dep_samples = build_samples(&dep_transfer_target, "SUN", &request.departure_window)?;
-    let arr_samples = build_samples(&arr_transfer_target, "SUN", &request.arrival_window)?;
-
-    let origin_rel_samples = request.origin_parent.map(|parent| {
-        build_samples(
-            &request.origin_body.spice_name,
-            &parent.spice_name,
-            &request.departure_window,
-        )
-    });
-    let destination_rel_samples = request.destination_parent.map(|parent| {
-        build_samples(
-            &request.destination_body.spice_name,
-            &parent.spice_name,
-            &request.arrival_window,
-        )
-    });
+    let dep_samples = build_samples(states, &dep_transfer_target, "SUN", departure)?;
+    let arr_samples = build_samples(states, &arr_transfer_target, "SUN", arrival)?;
+
+    let origin_rel = origin
+        .parent
+        .map(|parent| {
+            build_samples(
+                states,
+                &origin.body.spice_name,
+                &parent.spice_name,
+                departure,
+            )
+        })
+        .transpose()?;
+    let destination_rel = destination
+        .parent
+        .map(|parent| {
+            build_samples(
+                states,
+                &destination.body.spice_name,
+                &parent.spice_name,
+                arrival,
+            )
+        })
+        .transpose()?;
+    states.flush()?;
+
+    Ok(GridSamples {
+        departure: dep_samples,
+        arrival: arr_samples,
+        origin_rel,
+        destination_rel,
+    })
+}
 
-    let origin_rel_samples = match origin_rel_samples.transpose() {
-        Ok(samples) => samples,
-        Err(err) => return Err(err),
-    };
-    let destination_rel_samples = match destination_rel_samples.transpose() {
-        Ok(samples) => samples,
-        Err(err) => return Err(err),
+/// Grid points for one departure epoch, in arrival order.
+fn evaluate_row(
+    request: &PorkchopRequest<'_>,
+    grid: &GridSamples,
+    paths: &[PorkchopPath],
+    dep_idx: usize,
+) -> Vec<PorkchopPoint> {
+    let dep_sample = &grid.departure[dep_idx];
+    let Some(dep_state) = dep_sample.state.as_ref() else {
+        return Vec::new();
     };
-
-    let mut points = Vec::new();
-
-    for (dep_idx, dep_sample) in dep_samples.iter().enumerate() {
-        let dep_state = match dep_sample.state.as_ref() {
+    let mu_sun = request.constants.mu_sun_km3_s2;
+    let mut row = Vec::new();
+    let origin_rel_state = grid
+        .origin_rel
+        .as_ref()
+        .and_then(|samples| samples.get(dep_idx))
+        .and_then(|sample| sample.state.as_ref());
+
+    for (arr_idx, arr_sample) in grid.arrival.iter().enumerate() {
+        if arr_sample.et <= dep_sample.et {
+            continue;
+        }
+        let arr_state = match arr_sample.state.as_ref() {
             Some(state) => state,
             None => continue,
         };
-        let origin_rel_state = origin_rel_samples
+        let destination_rel_state = grid
+            .destination_rel
             .as_ref()
-            .and_then(|samples| samples.get(dep_idx))
+            .and_then(|samples| samples.get(arr_idx))
             .and_then(|sample| sample.state.as_ref());
 
-        for (arr_idx, arr_sample) in arr_samples.iter().enumerate() {
-            if arr_sample.et <= dep_sample.et {
-                continue;
-            }
-            let arr_state = match arr_sample.state.as_ref() {
-                Some(state) => state,
-                None => continue,
-            };
-            let destination_rel_state = destination_rel_samples
-                .as_ref()
-                .and_then(|samples| samples.get(arr_idx))
-                .and_then(|sample| sample.state.as_ref());
-
-            let tof = arr_sample.et - dep_sample.et;
-            let mut branch_results = Vec::new();
-
-            if !request.long_path_only {
-                if let Some(branch) = evaluate_branch(dep_state, arr_state, tof, true) {
-                    if let Some(result) =
-                        assemble_result(&branch, request, origin_rel_state, destination_rel_state)
-                    {
-                        branch_results.push(result);
-                    }
-                }
-            }
+        let tof = arr_sample.et - dep_sample.et;
+        let relative_inclination = relative_inclination_rad(dep_state, arr_state);
+        let mut branch_results: Vec<BranchResult> = paths
+            .iter()
+            .filter_map(|&path| evaluate_branch(dep_state, arr_state, tof, mu_sun, path))
+            .filter_map(|branch| {
+                assemble_result(
+                    &branch,
+                    request,
+                    origin_rel_state,
+                    destination_rel_state,
+                    relative_inclination,
+                )
+            })
+            .collect();
 
-            if let Some(branch) = evaluate_branch(dep_state, arr_state, tof, false) {
-                if let Some(result) =
-                    assemble_result(&branch, request, origin_rel_state, destination_rel_state)
-                {
-                    branch_results.push(result);
-                }
-            }
+        branch_results.sort_by(|a, b| match a.dv_total.partial_cmp(&b.dv_total) {
+            Some(order) => order,
+            None => Ordering::Equal,
+        });
 
-            branch_results.sort_by(|a, b| match a.dv_total.partial_cmp(&b.dv_total) {
-                Some(order) => order,
-                None => Ordering::Equal,
-            });
+        let (best, feasible) = if let Some(best) = branch_results.first() {
+            (best.clone(), true)
+        } else {
+            (
+                BranchResult::empty(if request.long_path_only {
+                    PorkchopPath::Long
+                } else {
+                    PorkchopPath::None
+                }),
+                false,
+            )
+        };
+        if !feasible && !request.include_infeasible {
+            continue;
+        }
 
-            let (best, feasible) = if let Some(best) = branch_results.first() {
-                (best.clone(), true)
-            } else {
-                (
-                    BranchResult::empty(if request.long_path_only {
-                        PorkchopPath::Long
-                    } else {
-                        PorkchopPath::None
-                    }),
-                    false,
-                )
-            };
+        row.push(PorkchopPoint {
+            depart_et: dep_sample.et,
+            arrive_et: arr_sample.et,
+            depart_utc: dep_sample.utc.clone(),
+            arrive_utc: arr_sample.utc.clone(),
+            tof_days: tof / 86_400.0,
+            c3_km2_s2: best.c3,
+            vinf_depart_km_s: best.vinf_dep,
+            vinf_arrive_km_s: best.vinf_arr,
+            dv_depart_km_s: best.dv_dep,
+            dv_arrive_km_s: best.dv_arr,
+            dv_total_km_s: best.dv_total,
+            propellant_used_kg: best.propellant_used_kg,
+            burn_time_s: best.burn_time_s,
+            final_mass_kg: best.final_mass_kg,
+            lambert_path: best.path,
+            feasible,
+            relative_inclination_deg: relative_inclination.to_degrees(),
+        });
+    }
 
-            points.push(PorkchopPoint {
-                depart_et: dep_sample.et,
-                arrive_et: arr_sample.et,
-                depart_utc: dep_sample.utc.clone(),
-                arrive_utc: arr_sample.utc.clone(),
-                tof_days: tof / 86_400.0,
-                c3_km2_s2: best.c3,
-                vinf_depart_km_s: best.vinf_dep,
-                vinf_arrive_km_s: best.vinf_arr,
-                dv_depart_km_s: best.dv_dep,
-                dv_arrive_km_s: best.dv_arr,
-                dv_total_km_s: best.dv_total,
-                propellant_used_kg: best.propellant_used_kg,
-                burn_time_s: best.burn_time_s,
-                final_mass_kg: best.final_mass_kg,
-                lambert_path: best.path,
-                feasible,
-            });
+    row
+}
+
+/// Reject sweeps whose origin and destination are the same body; Lambert has no meaningful
+/// solution between a body and itself.
+fn ensure_distinct_bodies(
+    origin: &PlanetConfig,
+    destination: &PlanetConfig,
+) -> Result<(), WindowError> {
+    if origin
+        .spice_name
+        .eq_ignore_ascii_case(&destination.spice_name)
+    {
+        return Err(WindowError::IdenticalBodies(origin.spice_name.clone()));
+    }
+    Ok(())
+}
+
+/// Check up front that every body the sweep samples has ephemeris data.
+///
+/// Without this, a missing satellite SPK only surfaces as a raw SPICE message from the grid loop;
+/// here it becomes [`ephemeris::EphemerisError::UnavailableBody`] naming the kernels to fetch.
+/// The probes go through the sweep's own `states`, so a warm state cache answers them without
+/// SPICE and the probed epochs are the first samples the sweep reuses.
+fn ensure_bodies_available(
+    origin: TransferEndpoint<'_>,
+    destination: TransferEndpoint<'_>,
+    states: &mut dyn StateProvider,
+    departure_et: f64,
+    arrival_et: f64,
+) -> Result<(), ephemeris::EphemerisError> {
+    let transfer_origin = origin.parent.unwrap_or(origin.body);
+    let transfer_destination = destination.parent.unwrap_or(destination.body);
+
+    let mut checks = vec![
+        (
+            transfer_origin,
+            ephemeris::normalize_heliocentric_target_name(&transfer_origin.spice_name),
+            "SUN".to_string(),
+            departure_et,
+        ),
+        (
+            transfer_destination,
+            ephemeris::normalize_heliocentric_target_name(&transfer_destination.spice_name),
+            "SUN".to_string(),
+            arrival_et,
+        ),
+    ];
+    if let Some(parent) = origin.parent {
+        checks.push((
+            origin.body,
+            origin.body.spice_name.clone(),
+            parent.spice_name.clone(),
+            departure_et,
+        ));
+    }
+    if let Some(parent) = destination.parent {
+        checks.push((
+            destination.body,
+            destination.body.spice_name.clone(),
+            parent.spice_name.clone(),
+            arrival_et,
+        ));
+    }
+
+    for (body, target, observer, et) in checks {
+        let unavailable = || ephemeris::EphemerisError::UnavailableBody {
+            target: target.clone(),
+            observer: observer.clone(),
+            kernels: body.kernel_dependencies.clone(),
+        };
+        if states.state(&target, &observer, "ECLIPJ2000", et).is_err() {
+            return Err(unavailable());
         }
     }
+    Ok(())
+}
 
-    Ok(points)
+fn state_provider(request: &PorkchopRequest<'_>) -> Box<dyn StateProvider> {
+    match request.state_cache_dir {
+        Some(dir) => Box::new(CachedStates::new(dir, SpiceStates)),
+        None => Box::new(SpiceStates),
+    }
 }
 
 fn build_samples(
+    states: &mut dyn StateProvider,
     target: &str,
     observer: &str,
     window: &TimeWindow,
 ) -> Result<Vec<EphemerisSample>, ephemeris::EphemerisError> {
-    let mut samples = Vec::new();
-    let mut t = window.start_et;
-    while t <= window.end_et + 1.0 {
-        let state = ephemeris::state_vector_et(target, observer, "ECLIPJ2000", "NONE", t).ok();
-        let utc = ephemeris::format_epoch(t)?;
+    let epochs: Vec<f64> = window.epochs().collect();
+    // One batched query covers the usual case; if part of the window lies outside kernel
+    // coverage, fall back to per-epoch lookups so the covered samples survive.
+    let resolved: Vec<Option<StateVector>> =
+        match states.states(target, observer, "ECLIPJ2000", &epochs) {
+            Ok(all) => all.into_iter().map(Some).collect(),
+            Err(_) => epochs
+                .iter()
+                .map(|&t| states.state(target, observer, "ECLIPJ2000", t).ok())
+                .collect(),
+        };
+
+    let mut samples = Vec::with_capacity(epochs.len());
+    for (t, state) in epochs.into_iter().zip(resolved) {
+        let utc = ephemeris::format_epoch_iso(t)?;
         samples.push(EphemerisSample { et: t, utc, state });
-        t += window.step_seconds;
     }
     Ok(samples)
 }
 
+/// Lambert paths tried for every grid cell; none when the vehicle cannot afford any transfer.
+fn candidate_paths(request: &PorkchopRequest<'_>) -> Vec<PorkchopPath> {
+    let mut paths = Vec::new();
+    if !request.ignore_vehicle_limits && !within_delta_v_budget(request) {
+        return paths;
+    }
+    if !request.long_path_only {
+        paths.push(PorkchopPath::Short);
+    }
+    paths.push(PorkchopPath::Long);
+    for revolutions in 1..=request.max_revolutions {
+        paths.push(PorkchopPath::MultiRev {
+            revolutions,
+            left: true,
+        });
+        paths.push(PorkchopPath::MultiRev {
+            revolutions,
+            left: false,
+        });
+    }
+    paths
+}
+
+/// Whether an impulsive vehicle's delta-v covers escape and capture at zero v-infinity, the
+/// floor every grid cell's burns sit above.
+fn within_delta_v_budget(request: &PorkchopRequest<'_>) -> bool {
+    let PropulsionMode::Impulsive {
+        max_delta_v_km_s, ..
+    } = request.vehicle.propulsion
+    else {
+        return true;
+    };
+    let budget = request
+        .vehicle
+        .total_delta_v_km_s()
+        .map_or(max_delta_v_km_s, |total| total.min(max_delta_v_km_s));
+    let floor = burn_from_vinf(
+        request.constants.body_mu(request.origin_body),
+        request.rpark_depart_km,
+        0.0,
+    ) + burn_from_vinf(
+        request.constants.body_mu(request.destination_body),
+        request.rpark_arrive_km,
+        0.0,
+    );
+    floor <= budget
+}
+
 fn evaluate_branch(
     dep_state: &StateVector,
     arr_state: &StateVector,
     tof: f64,
-    short: bool,
+    mu_sun: f64,
+    path: PorkchopPath,
 ) -> Option<LambertBranch> {
-    let (v1_lam, v2_lam) = lambert::solve(
-        dep_state.position_km,
-        arr_state.position_km,
-        tof,
-        MU_SUN,
-        short,
-    )
+    let (r1, r2) = (dep_state.position_km, arr_state.position_km);
+    let (v1_lam, v2_lam) = match path {
+        PorkchopPath::Short => lambert::solve(r1, r2, tof, mu_sun, true),
+        PorkchopPath::Long => lambert::solve(r1, r2, tof, mu_sun, false),
+        PorkchopPath::MultiRev { revolutions, left } => {
+            lambert::solve_multirev(r1, r2, tof, mu_sun, revolutions, left)
+        }
+        PorkchopPath::None => return None,
+    }
     .ok()?;
 
     let vinf_dep_vec = [
@@ -343,11 +719,7 @@ fn evaluate_branch(
     Some(LambertBranch {
         vinf_dep_vec,
         vinf_arr_vec,
-        path: if short {
-            PorkchopPath::Short
-        } else {
-            PorkchopPath::Long
-        },
+        path,
     })
 }
 
@@ -356,6 +728,7 @@ fn assemble_result(
     request: &PorkchopRequest<'_>,
     origin_rel_state: Option<&StateVector>,
     destination_rel_state: Option<&StateVector>,
+    relative_inclination: f64,
 ) -> Option<BranchResult> {
     let vinf_dep_vec = vinf_vector_for_body(
         request.origin_parent,
@@ -370,22 +743,28 @@ fn assemble_result(
 
     let vinf_dep = norm3(&vinf_dep_vec);
     let vinf_arr = norm3(&vinf_arr_vec);
-    let c3 = vinf_dep * vinf_dep;
+    let c3 = c3_from_vinf(vinf_dep);
 
     let (dv_dep, dv_arr, propulsive) = match request.vehicle.propulsion {
         PropulsionMode::Impulsive {
             max_delta_v_km_s, ..
         } => {
             let dv_dep = burn_from_vinf(
-                request.origin_body.mu_km3_s2,
+                request.constants.body_mu(request.origin_body),
                 request.rpark_depart_km,
                 vinf_dep,
             );
-            let dv_arr = burn_from_vinf(
-                request.destination_body.mu_km3_s2,
-                request.rpark_arrive_km,
-                vinf_arr,
-            );
+            let arrive_mu = request.constants.body_mu(request.destination_body);
+            let dv_arr = if request.plane_change_penalty {
+                capture_with_plane_change(
+                    arrive_mu,
+                    request.rpark_arrive_km,
+                    vinf_arr,
+                    relative_inclination,
+                )
+            } else {
+                burn_from_vinf(arrive_mu, request.rpark_arrive_km, vinf_arr)
+            };
             let total = dv_dep + dv_arr;
             if !request.ignore_vehicle_limits && total > max_delta_v_km_s {
                 return None;
@@ -401,7 +780,8 @@ fn assemble_result(
             };
             (dv_dep, dv_arr, summary)
         }
-        PropulsionMode::Continuous { .. } => return None,
+        // Low-thrust vehicles fly integrated spirals (see `interplanetary`), not Lambert arcs.
+        PropulsionMode::Continuous { .. } | PropulsionMode::Throttled { .. } => return None,
         PropulsionMode::Hybrid => return None,
     };
 
@@ -442,96 +822,44 @@ fn burn_from_vinf(mu: f64, r: f64, vinf: f64) -> f64 {
     (v_req - v_circ).max(0.0)
 }
 
+/// Capture burn at periapsis that also rotates the orbit plane by `inclination_rad`.
+fn capture_with_plane_change(mu: f64, r: f64, vinf: f64, inclination_rad: f64) -> f64 {
+    let v_circ = (mu / r).sqrt();
+    let v_req = (vinf * vinf + 2.0 * mu / r).sqrt();
+    combined_plane_change(v_req, v_circ, inclination_rad)
+}
+
+/// Angle (rad) between the orbit planes of two heliocentric states, from their specific
+/// angular momentum vectors `r × v`. Degenerate (radial) states report zero.
+pub fn relative_inclination_rad(a: &StateVector, b: &StateVector) -> f64 {
+    let h_a = cross(&a.position_km, &a.velocity_km_s);
+    let h_b = cross(&b.position_km, &b.velocity_km_s);
+    let (n_a, n_b) = (norm3(&h_a), norm3(&h_b));
+    if n_a == 0.0 || n_b == 0.0 {
+        return 0.0;
+    }
+    acos_clamped(dot(&h_a, &h_b) / (n_a * n_b))
+}
+
 fn compute_propellant_and_burn(
     vehicle: &Vehicle,
     dv_dep: f64,
     dv_arr: f64,
 ) -> Option<PropulsiveSummary> {
-    let mut remaining_prop = vehicle.propellant_mass_kg;
-    let mut mass_before = vehicle.initial_mass_kg();
-
-    let (prop_dep, burn_dep, mass_after_dep) =
-        compute_single_burn(&vehicle.propulsion, mass_before, dv_dep, remaining_prop)?;
-    remaining_prop -= prop_dep;
-    mass_before = mass_after_dep;
-
-    let (prop_arr, burn_arr, mass_after_arr) =
-        compute_single_burn(&vehicle.propulsion, mass_before, dv_arr, remaining_prop)?;
-
-    if mass_after_arr < vehicle.dry_mass_kg - 1e-6 {
+    let mut staging = vehicle.staging();
+    let depart = staging.burn(dv_dep)?;
+    let arrive = staging.burn(dv_arr)?;
+    if depart.shortfall_km_s > 0.0 || arrive.shortfall_km_s > 0.0 {
         return None;
     }
 
     Some(PropulsiveSummary {
-        propellant_total: prop_dep + prop_arr,
-        burn_time_total: burn_dep + burn_arr,
-        final_mass: mass_after_arr,
+        propellant_total: depart.propellant_kg + arrive.propellant_kg,
+        burn_time_total: depart.burn_time_s + arrive.burn_time_s,
+        final_mass: staging.mass_kg(),
     })
 }
 
-fn compute_single_burn(
-    mode: &PropulsionMode,
-    mass_before: f64,
-    dv_km_s: f64,
-    propellant_available: f64,
-) -> Option<(f64, f64, f64)> {
-    if dv_km_s.abs() < 1e-9 {
-        return Some((0.0, 0.0, mass_before));
-    }
-
-    let (ve, thrust_opt, accel_limit) = match mode {
-        PropulsionMode::Impulsive {
-            isp_seconds,
-            max_thrust_newtons,
-            ..
-        } => (isp_seconds * G0, *max_thrust_newtons, None::<f64>),
-        PropulsionMode::Continuous {
-            max_thrust_newtons,
-            isp_seconds,
-            max_acceleration_m_s2,
-        } => (
-            isp_seconds * G0,
-            Some(*max_thrust_newtons),
-            *max_acceleration_m_s2,
-        ),
-        PropulsionMode::Hybrid => return None,
-    };
-
-    if ve <= 0.0 {
-        return None;
-    }
-
-    let dv_m_s = dv_km_s * 1000.0;
-    let exponent = -dv_m_s / ve;
-    let mass_after = mass_before * exponent.exp();
-    let prop_used = mass_before - mass_after;
-
-    if prop_used.is_nan() || prop_used < -1e-9 {
-        return None;
-    }
-    if prop_used > propellant_available + 1e-9 {
-        return None;
-    }
-
-    let burn_time = match thrust_opt {
-        Some(thrust) if thrust > 0.0 && prop_used > 0.0 => {
-            let avg_mass = 0.5 * (mass_before + mass_after);
-            let mut effective_thrust = thrust;
-            if let Some(limit) = accel_limit {
-                effective_thrust = effective_thrust.min(limit * avg_mass);
-            }
-            if effective_thrust > 0.0 {
-                prop_used * ve / effective_thrust
-            } else {
-                0.0
-            }
-        }
-        _ => 0.0,
-    };
-
-    Some((prop_used, burn_time, mass_after))
-}
-
 fn norm3(v: &[f64; 3]) -> f64 {
     (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
 }
@@ -545,23 +873,36 @@ fn window_sample_from_point(point: &PorkchopPoint) -> WindowSample {
         dv_total_km_s: point.dv_total_km_s,
         dv_depart_km_s: point.dv_depart_km_s,
         dv_arrive_km_s: point.dv_arrive_km_s,
+        c3_km2_s2: point.c3_km2_s2,
     }
 }
 
+/// Departure sweep behind a [`WindowDataset`]: departures every `step_days` for `span_days` from
+/// `depart_start_et`, each paired with arrivals `min_tof_days` to `max_tof_days` later.
+#[derive(Debug, Clone, Copy)]
+pub struct WindowSweep {
+    pub depart_start_et: f64,
+    pub span_days: f64,
+    pub step_days: f64,
+    pub min_tof_days: f64,
+    pub max_tof_days: f64,
+}
+
 pub fn compute_window_dataset(
-    origin_body: &PlanetConfig,
-    origin_parent: Option<&PlanetConfig>,
-    destination_body: &PlanetConfig,
-    destination_parent: Option<&PlanetConfig>,
+    origin: TransferEndpoint<'_>,
+    destination: TransferEndpoint<'_>,
     vehicle: &Vehicle,
-    rpark_depart_km: f64,
-    rpark_arrive_km: f64,
-    depart_start_et: f64,
-    span_days: f64,
-    step_days: f64,
-    min_tof_days: f64,
-    max_tof_days: f64,
+    sweep: &WindowSweep,
+    constants: &ConstantsSet,
+    state_cache_dir: Option<&Path>,
 ) -> Result<WindowDataset, WindowError> {
+    let WindowSweep {
+        depart_start_et,
+        span_days,
+        step_days,
+        min_tof_days,
+        max_tof_days,
+    } = *sweep;
     let step_seconds = step_days.max(0.1) * 86_400.0;
     let depart_end_et = depart_start_et + span_days.max(step_days) * 86_400.0;
     let arrival_start_et = depart_start_et + min_tof_days.max(1.0) * 86_400.0;
@@ -582,24 +923,31 @@ pub fn compute_window_dataset(
     };
 
     let request = PorkchopRequest {
-        origin_body,
-        origin_parent,
-        destination_body,
-        destination_parent,
+        origin_body: origin.body,
+        origin_parent: origin.parent,
+        destination_body: destination.body,
+        destination_parent: destination.parent,
         vehicle,
-        rpark_depart_km,
-        rpark_arrive_km,
+        rpark_depart_km: origin.rpark_km,
+        rpark_arrive_km: destination.rpark_km,
         departure_window,
         arrival_window,
         long_path_only: false,
         ignore_vehicle_limits: true,
+        include_infeasible: false,
+        constants,
+        state_cache_dir,
+        max_revolutions: 0,
+        plane_change_penalty: false,
     };
 
     let points = generate(&request)?;
 
     let mut samples = Vec::new();
+    let mut pareto = Vec::new();
     let mut current_depart: Option<f64> = None;
     let mut best_sample: Option<WindowSample> = None;
+    let mut group: Vec<WindowSample> = Vec::new();
 
     for point in points.iter() {
         if !point.feasible {
@@ -615,6 +963,8 @@ pub fn compute_window_dataset(
             if let Some(sample) = best_sample.take() {
                 samples.push(sample);
             }
+            pareto.extend(pareto_front(&group));
+            group.clear();
             current_depart = Some(point.depart_et);
             best_sample = Some(window_sample_from_point(point));
         } else if let Some(sample) = best_sample.as_mut() {
@@ -622,11 +972,13 @@ pub fn compute_window_dataset(
                 *sample = window_sample_from_point(point);
             }
         }
+        group.push(window_sample_from_point(point));
     }
 
     if let Some(sample) = best_sample {
         samples.push(sample);
     }
+    pareto.extend(pareto_front(&group));
 
     samples.sort_by(|a, b| {
         a.depart_et
@@ -644,18 +996,165 @@ pub fn compute_window_dataset(
 
     Ok(WindowDataset {
         version: WINDOW_DATASET_VERSION,
-        origin_spice: origin_body.spice_name.clone(),
-        destination_spice: destination_body.spice_name.clone(),
+        origin_spice: origin.body.spice_name.clone(),
+        destination_spice: destination.body.spice_name.clone(),
         depart_start_et,
         depart_end_et,
         step_days,
         min_tof_days,
         max_tof_days,
+        vehicle: vehicle.name.clone(),
+        rpark_depart_km: origin.rpark_km,
+        rpark_arrive_km: destination.rpark_km,
+        constants_fingerprint: constants.fingerprint(),
         min_dv_total_km_s: min_dv,
         samples,
+        pareto,
     })
 }
 
+/// Samples not dominated by another with both lower-or-equal Δv and shorter-or-equal TOF,
+/// ordered by increasing TOF (and therefore decreasing Δv).
+pub fn pareto_front(samples: &[WindowSample]) -> Vec<WindowSample> {
+    let mut by_tof: Vec<&WindowSample> = samples
+        .iter()
+        .filter(|s| s.dv_total_km_s.is_finite())
+        .collect();
+    by_tof.sort_by(|a, b| {
+        a.tof_days()
+            .total_cmp(&b.tof_days())
+            .then(a.dv_total_km_s.total_cmp(&b.dv_total_km_s))
+    });
+    let mut front: Vec<WindowSample> = Vec::new();
+    for sample in by_tof {
+        let improves = front
+            .last()
+            .is_none_or(|last| sample.dv_total_km_s < last.dv_total_km_s);
+        if improves {
+            front.push(sample.clone());
+        }
+    }
+    front
+}
+
+/// Per-cell quantity written by [`write_grid`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PorkchopMetric {
+    /// Total impulsive delta-v in km/s.
+    #[default]
+    DvTotal,
+    /// Departure characteristic energy in km²/s².
+    C3,
+    /// Time of flight in days.
+    Tof,
+}
+
+impl PorkchopMetric {
+    /// Column name used in exported headers, matching the CSV exporter.
+    pub fn label(self) -> &'static str {
+        match self {
+            PorkchopMetric::DvTotal => "dv_total_km_s",
+            PorkchopMetric::C3 => "c3_km2_s2",
+            PorkchopMetric::Tof => "tof_days",
+        }
+    }
+
+    fn value(self, point: &PorkchopPoint) -> f64 {
+        match self {
+            PorkchopMetric::DvTotal => point.dv_total_km_s,
+            PorkchopMetric::C3 => point.c3_km2_s2,
+            PorkchopMetric::Tof => point.tof_days,
+        }
+    }
+}
+
+/// Reshape `points` into a departure×arrival [`Grid`] of `metric`.
+///
+/// Where several Lambert branches share a cell, the smallest value among the feasible ones wins
+/// (or among all of them when none is feasible); cells without any point stay `NaN`.
+pub fn porkchop_grid(points: &[PorkchopPoint], metric: PorkchopMetric) -> Grid {
+    let departures = grid_axis(points.iter().map(|p| p.depart_et));
+    let arrivals = grid_axis(points.iter().map(|p| p.arrive_et));
+    let mut grid = Grid::empty(departures, arrivals);
+    for point in points {
+        let value = metric.value(point);
+        if !value.is_finite() {
+            continue;
+        }
+        let (Some(row), Some(col)) = (
+            axis_index(&grid.depart_et, point.depart_et),
+            axis_index(&grid.arrive_et, point.arrive_et),
+        ) else {
+            continue;
+        };
+        let idx = grid.index(row, col);
+        let slot = grid.values[idx];
+        let replace = match (grid.feasible[idx], point.feasible) {
+            (false, true) => true,
+            (true, false) => false,
+            _ => slot.is_nan() || value < slot,
+        };
+        if replace {
+            grid.values[idx] = value;
+            grid.feasible[idx] = point.feasible;
+        }
+    }
+    grid
+}
+
+/// Write `points` as a whitespace-separated departure×arrival matrix of `metric`.
+///
+/// After a `#` comment line naming the metric, the first row holds the column count followed by
+/// the arrival ETs, and each following row starts with its departure ET (gnuplot's
+/// `nonuniform matrix` layout). Cells with no feasible transfer are written as `NaN`; see
+/// [`porkchop_grid`] for how branches sharing a cell are reduced.
+pub fn write_grid(
+    writer: &mut dyn Write,
+    points: &[PorkchopPoint],
+    metric: PorkchopMetric,
+) -> io::Result<()> {
+    let grid = porkchop_grid(points, metric);
+    writeln!(
+        writer,
+        "# rows: depart_et, columns: arrive_et, values: {}",
+        metric.label()
+    )?;
+    write!(writer, "{}", grid.arrive_et.len())?;
+    for arrive_et in &grid.arrive_et {
+        write!(writer, " {arrive_et:.3}")?;
+    }
+    writeln!(writer)?;
+    for (row, depart_et) in grid.depart_et.iter().enumerate() {
+        write!(writer, "{depart_et:.3}")?;
+        for col in 0..grid.arrive_et.len() {
+            let idx = grid.index(row, col);
+            let value = if grid.feasible[idx] {
+                grid.values[idx]
+            } else {
+                f64::NAN
+            };
+            write!(writer, " {value:.6}")?;
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// Sorted distinct epochs, merging values within [`TIME_GROUP_TOLERANCE_S`].
+fn grid_axis(epochs: impl Iterator<Item = f64>) -> Vec<f64> {
+    let mut axis: Vec<f64> = epochs.filter(|et| et.is_finite()).collect();
+    axis.sort_by(f64::total_cmp);
+    axis.dedup_by(|later, earlier| (*later - *earlier).abs() <= TIME_GROUP_TOLERANCE_S);
+    axis
+}
+
+fn axis_index(axis: &[f64], et: f64) -> Option<usize> {
+    let idx = axis.partition_point(|&v| v < et - TIME_GROUP_TOLERANCE_S);
+    axis.get(idx)
+        .filter(|&&v| (v - et).abs() <= TIME_GROUP_TOLERANCE_S)
+        .map(|_| idx)
+}
+
 pub fn save_window_dataset(path: &Path, dataset: &WindowDataset) -> Result<(), WindowError> {
     if let Some(parent) = path.parent() {
         if !parent.as_os_str().is_empty() {
@@ -715,10 +1214,163 @@ pub fn analyze_departure(
         .or(backward_candidate)
         .unwrap_or_else(|| baseline.clone());
 
+    let faster = dataset
+        .pareto
+        .iter()
+        .filter(|s| s.dv_total_km_s <= total_dv_km_s && s.tof_days() < recommended.tof_days())
+        .min_by(|a, b| {
+            a.tof_days()
+                .total_cmp(&b.tof_days())
+                .then(a.dv_total_km_s.total_cmp(&b.dv_total_km_s))
+        })
+        .cloned();
+
     Some(WindowSuggestion {
         baseline,
         recommended,
         user_total_dv_km_s: total_dv_km_s,
         threshold_dv_km_s: threshold,
+        faster,
+    })
+}
+
+/// Sweep departure epochs with the time of flight held fixed, keeping cells under `max_dv_km_s`.
+///
+/// Each arrival is departure + `tof_days`, so the arrival epochs span `depart_window` shifted by
+/// the TOF. Both Lambert branches are tried and the cheaper one is kept. Escape and capture burns
+/// are sized at each endpoint's parking radius with the catalog GMs; no vehicle is involved, so
+/// `max_dv_km_s` is the only budget.
+///
+/// A `tof_days` that is not finite and strictly positive fails with
+/// [`WindowError::InvalidTimeOfFlight`] before any ephemeris lookup.
+pub fn fixed_tof_windows(
+    origin: TransferEndpoint<'_>,
+    destination: TransferEndpoint<'_>,
+    tof_days: f64,
+    depart_window: &TimeWindow,
+    max_dv_km_s: f64,
+) -> Result<Vec<WindowSample>, WindowError> {
+    if !(tof_days.is_finite() && tof_days > 0.0) {
+        return Err(WindowError::InvalidTimeOfFlight(tof_days));
+    }
+    let tof = tof_days * 86_400.0;
+    ensure_distinct_bodies(origin.body, destination.body)?;
+
+    // Shifting the departure window by the TOF keeps both sample vectors index-aligned.
+    let arrival_window = TimeWindow {
+        start_et: depart_window.start_et + tof,
+        end_et: depart_window.end_et + tof,
+        step_seconds: depart_window.step_seconds,
+    };
+    let mut states = SpiceStates;
+    ensure_bodies_available(
+        origin,
+        destination,
+        &mut states,
+        depart_window.start_et,
+        arrival_window.start_et,
+    )?;
+    let grid = sample_windows(
+        origin,
+        destination,
+        depart_window,
+        &arrival_window,
+        &mut states,
+    )?;
+
+    let constants = ConstantsSet::default();
+    let mut samples = Vec::new();
+    for (idx, (dep_sample, arr_sample)) in grid.departure.iter().zip(&grid.arrival).enumerate() {
+        let (Some(dep_state), Some(arr_state)) =
+            (dep_sample.state.as_ref(), arr_sample.state.as_ref())
+        else {
+            continue;
+        };
+        let origin_rel_state = grid
+            .origin_rel
+            .as_ref()
+            .and_then(|samples| samples.get(idx))
+            .and_then(|sample| sample.state.as_ref());
+        let destination_rel_state = grid
+            .destination_rel
+            .as_ref()
+            .and_then(|samples| samples.get(idx))
+            .and_then(|sample| sample.state.as_ref());
+
+        let best = [PorkchopPath::Short, PorkchopPath::Long]
+            .into_iter()
+            .filter_map(|path| {
+                evaluate_branch(dep_state, arr_state, tof, constants.mu_sun_km3_s2, path)
+            })
+            .filter_map(|branch| {
+                let vinf_dep = norm3(&vinf_vector_for_body(
+                    origin.parent,
+                    &branch.vinf_dep_vec,
+                    origin_rel_state,
+                )?);
+                let vinf_arr = norm3(&vinf_vector_for_body(
+                    destination.parent,
+                    &branch.vinf_arr_vec,
+                    destination_rel_state,
+                )?);
+                let dv_dep =
+                    burn_from_vinf(constants.body_mu(origin.body), origin.rpark_km, vinf_dep);
+                let dv_arr = burn_from_vinf(
+                    constants.body_mu(destination.body),
+                    destination.rpark_km,
+                    vinf_arr,
+                );
+                Some(WindowSample {
+                    depart_et: dep_sample.et,
+                    depart_utc: dep_sample.utc.clone(),
+                    arrive_et: arr_sample.et,
+                    arrive_utc: arr_sample.utc.clone(),
+                    dv_total_km_s: dv_dep + dv_arr,
+                    dv_depart_km_s: dv_dep,
+                    dv_arrive_km_s: dv_arr,
+                    c3_km2_s2: c3_from_vinf(vinf_dep),
+                })
+            })
+            .min_by(|a, b| {
+                a.dv_total_km_s
+                    .partial_cmp(&b.dv_total_km_s)
+                    .unwrap_or(Ordering::Equal)
+            });
+
+        if let Some(best) = best.filter(|sample| sample.dv_total_km_s <= max_dv_km_s) {
+            samples.push(best);
+        }
+    }
+
+    Ok(samples)
+}
+
+/// Summarise departure C3 across dataset samples whose total delta-v is at most `max_dv_km_s`.
+///
+/// Returns `None` when no sample falls under the threshold.
+pub fn c3_statistics(dataset: &WindowDataset, max_dv_km_s: f64) -> Option<C3Stats> {
+    let mut c3_values: Vec<f64> = dataset
+        .samples
+        .iter()
+        .filter(|sample| sample.dv_total_km_s <= max_dv_km_s && sample.c3_km2_s2.is_finite())
+        .map(|sample| sample.c3_km2_s2)
+        .collect();
+    if c3_values.is_empty() {
+        return None;
+    }
+    c3_values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+    let count = c3_values.len();
+    let median = if count.is_multiple_of(2) {
+        0.5 * (c3_values[count / 2 - 1] + c3_values[count / 2])
+    } else {
+        c3_values[count / 2]
+    };
+
+    Some(C3Stats {
+        sample_count: count,
+        min_km2_s2: c3_values[0],
+        median_km2_s2: median,
+        max_km2_s2: c3_values[count - 1],
     })
 }
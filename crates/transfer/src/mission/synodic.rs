@@ -0,0 +1,211 @@
+//! Synodic periods and next-launch-window estimates from the bodies' osculating heliocentric
+//! orbits, for departures outside any precomputed window dataset.
+
+use std::f64::consts::PI;
+
+use solar_config::{ConstantsSet, PlanetConfig};
+use solar_core::vector::cross;
+use solar_ephem_spice::{self as ephemeris, StateVector};
+use solar_impulsive::lambert;
+use solar_impulsive::transfers::{hohmann_checked, hohmann_phase_angle, time_to_phase_alignment};
+use solar_orbits::{dot, norm3, sub};
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+const FRAME: &str = "ECLIPJ2000";
+/// Half-width of the departure scan around the phase-angle estimate, as a fraction of the
+/// synodic period.
+const REFINE_HALF_SPAN_FRACTION: f64 = 0.1;
+/// Departure samples across the refinement scan.
+const REFINE_STEPS: usize = 40;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SynodicError {
+    #[error("ephemeris lookup failed: {0}")]
+    Ephemeris(#[from] ephemeris::EphemerisError),
+    #[error("{0} is not on a bound heliocentric orbit")]
+    Unbound(String),
+    #[error("{0} and {1} share a heliocentric orbit period; their phasing never repeats")]
+    CoOrbital(String, String),
+    #[error("no Lambert solution near the estimated {0}→{1} window")]
+    NoTransfer(String, String),
+}
+
+/// Next low-energy departure found by [`next_launch_window`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LaunchWindowEstimate {
+    pub depart_et: f64,
+    /// Hohmann time of flight between the two osculating orbits, used for every candidate.
+    pub tof_days: f64,
+    /// Combined departure + arrival v-infinity of the best Lambert branch at `depart_et` (km/s).
+    pub vinf_total_km_s: f64,
+    pub synodic_period_days: f64,
+}
+
+/// Synodic period (days) of two orbits with the given periods; `None` when they are equal.
+pub fn synodic_period(period_a_days: f64, period_b_days: f64) -> Option<f64> {
+    let rate = (1.0 / period_a_days - 1.0 / period_b_days).abs();
+    (rate.is_finite() && rate > 1e-12).then(|| 1.0 / rate)
+}
+
+/// Osculating heliocentric orbital period (days) of `body` at `epoch_et`.
+///
+/// Moons use their parent's heliocentric orbit.
+pub fn orbital_period_days(
+    body: &PlanetConfig,
+    epoch_et: f64,
+    constants: &ConstantsSet,
+) -> Result<f64, SynodicError> {
+    let a = heliocentric_semi_major_axis(body, epoch_et, constants)?;
+    Ok(2.0 * PI * (a.powi(3) / constants.mu_sun_km3_s2).sqrt() / SECONDS_PER_DAY)
+}
+
+/// Synodic period (days) between `origin` and `destination`, from their osculating periods
+/// at `epoch_et`.
+pub fn synodic_period_days(
+    origin: &PlanetConfig,
+    destination: &PlanetConfig,
+    epoch_et: f64,
+    constants: &ConstantsSet,
+) -> Result<f64, SynodicError> {
+    synodic_period(
+        orbital_period_days(origin, epoch_et, constants)?,
+        orbital_period_days(destination, epoch_et, constants)?,
+    )
+    .ok_or_else(|| co_orbital(origin, destination))
+}
+
+/// Estimate the first low-energy departure from `origin` to `destination` after `after_et`.
+///
+/// The current heliocentric phase angle is advanced at the synodic rate of the two osculating
+/// orbits until it reaches the Hohmann phase angle; departures within a tenth of a synodic
+/// period either side of that epoch (never before `after_et`) are then scanned at the Hohmann
+/// TOF and the lowest combined Lambert v-infinity kept. Eccentric or inclined orbits shift the
+/// true optimum, so treat the result as a bracket centre for a porkchop search rather than the
+/// optimum itself.
+pub fn next_launch_window(
+    origin: &PlanetConfig,
+    destination: &PlanetConfig,
+    after_et: f64,
+    constants: &ConstantsSet,
+) -> Result<LaunchWindowEstimate, SynodicError> {
+    let mu_sun = constants.mu_sun_km3_s2;
+    let origin_target = heliocentric_target(origin);
+    let destination_target = heliocentric_target(destination);
+    let a1 = heliocentric_semi_major_axis(origin, after_et, constants)?;
+    let a2 = heliocentric_semi_major_axis(destination, after_et, constants)?;
+    let synodic_days = synodic_period(
+        2.0 * PI * (a1.powi(3) / mu_sun).sqrt() / SECONDS_PER_DAY,
+        2.0 * PI * (a2.powi(3) / mu_sun).sqrt() / SECONDS_PER_DAY,
+    )
+    .ok_or_else(|| co_orbital(origin, destination))?;
+
+    let origin_state = heliocentric_state(&origin_target, after_et)?;
+    let destination_state = heliocentric_state(&destination_target, after_et)?;
+    let wait_seconds = time_to_phase_alignment(
+        phase_angle(&origin_state, &destination_state),
+        hohmann_phase_angle(a1, a2, mu_sun),
+        a1,
+        a2,
+        mu_sun,
+    )
+    .ok_or_else(|| co_orbital(origin, destination))?;
+    let tof_seconds = hohmann_checked(a1, a2, mu_sun)
+        .map_err(|_| SynodicError::Unbound(origin.name.clone()))?
+        .tof_seconds;
+
+    let centre = after_et + wait_seconds;
+    let half_span = REFINE_HALF_SPAN_FRACTION * synodic_days * SECONDS_PER_DAY;
+    let start = (centre - half_span).max(after_et);
+    let step = (centre + half_span - start) / REFINE_STEPS as f64;
+    let mut best: Option<(f64, f64)> = None;
+    for i in 0..=REFINE_STEPS {
+        let depart_et = start + step * i as f64;
+        let departure = heliocentric_state(&origin_target, depart_et)?;
+        let arrival = heliocentric_state(&destination_target, depart_et + tof_seconds)?;
+        let Some(score) = lambert_vinf_total(&departure, &arrival, tof_seconds, mu_sun) else {
+            continue;
+        };
+        if best.is_none_or(|(_, current)| score < current) {
+            best = Some((depart_et, score));
+        }
+    }
+
+    let (depart_et, vinf_total) = best
+        .ok_or_else(|| SynodicError::NoTransfer(origin.name.clone(), destination.name.clone()))?;
+    Ok(LaunchWindowEstimate {
+        depart_et,
+        tof_days: tof_seconds / SECONDS_PER_DAY,
+        vinf_total_km_s: vinf_total,
+        synodic_period_days: synodic_days,
+    })
+}
+
+fn heliocentric_target(body: &PlanetConfig) -> String {
+    ephemeris::normalize_heliocentric_target_name(
+        body.parent_spice.as_deref().unwrap_or(&body.spice_name),
+    )
+}
+
+fn heliocentric_state(target: &str, et: f64) -> Result<StateVector, ephemeris::EphemerisError> {
+    ephemeris::state_vector_et(target, "SUN", FRAME, "NONE", et)
+}
+
+fn heliocentric_semi_major_axis(
+    body: &PlanetConfig,
+    epoch_et: f64,
+    constants: &ConstantsSet,
+) -> Result<f64, SynodicError> {
+    let elements = ephemeris::osculating_elements_et(
+        &heliocentric_target(body),
+        "SUN",
+        FRAME,
+        epoch_et,
+        constants.mu_sun_km3_s2,
+    )?;
+    let a = elements.semi_major_axis_km;
+    if a.is_finite() && a > 0.0 {
+        Ok(a)
+    } else {
+        Err(SynodicError::Unbound(body.name.clone()))
+    }
+}
+
+fn co_orbital(origin: &PlanetConfig, destination: &PlanetConfig) -> SynodicError {
+    SynodicError::CoOrbital(origin.name.clone(), destination.name.clone())
+}
+
+/// Angle (rad) the destination leads the origin by, measured in the origin's direction of
+/// motion and wrapped to `(-π, π]`.
+fn phase_angle(origin: &StateVector, destination: &StateVector) -> f64 {
+    let h = cross(&origin.position_km, &origin.velocity_km_s);
+    let h_norm = norm3(&h);
+    if h_norm == 0.0 {
+        return 0.0;
+    }
+    let sin = dot(&cross(&origin.position_km, &destination.position_km), &h) / h_norm;
+    sin.atan2(dot(&origin.position_km, &destination.position_km))
+}
+
+fn lambert_vinf_total(
+    departure: &StateVector,
+    arrival: &StateVector,
+    tof_seconds: f64,
+    mu_sun: f64,
+) -> Option<f64> {
+    [true, false]
+        .into_iter()
+        .filter_map(|short| {
+            let (v1, v2) = lambert::solve(
+                departure.position_km,
+                arrival.position_km,
+                tof_seconds,
+                mu_sun,
+                short,
+            )
+            .ok()?;
+            let score = norm3(&sub(&v1, &departure.velocity_km_s))
+                + norm3(&sub(&v2, &arrival.velocity_km_s));
+            score.is_finite().then_some(score)
+        })
+        .min_by(f64::total_cmp)
+}
@@ -0,0 +1,357 @@
+use std::fs;
+
+use solar_travel_calculator::config::{
+    AerobrakeSetting, ConfigError, DE440_MU_SUN_KM3_S2, PlanetConfig, VehiclePropulsionConfig,
+    load_constants, load_planets, load_planets_with_overrides, load_scenario, load_vehicle_configs,
+};
+
+#[test]
+fn vehicle_catalog_loads_multi_document_yaml_stream() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("vehicles.yaml");
+    fs::write(
+        &path,
+        r#"---
+name: Tug A
+dry_mass_kg: 1000.0
+propellant_mass_kg: 500.0
+propulsion:
+  type: continuous
+  max_thrust_newtons: 1.5
+  isp_seconds: 3000.0
+---
+- name: Stage B
+  dry_mass_kg: 2000.0
+  propellant_mass_kg: 8000.0
+  propulsion:
+    type: impulsive
+    max_delta_v_km_s: 6.0
+    isp_seconds: 450.0
+- name: Stage C
+  dry_mass_kg: 3000.0
+  propellant_mass_kg: 9000.0
+  propulsion:
+    type: impulsive
+    max_delta_v_km_s: 5.0
+    isp_seconds: 380.0
+---
+"#,
+    )
+    .expect("write catalog");
+
+    let vehicles = load_vehicle_configs(&path).expect("multi-document catalog");
+    let names: Vec<&str> = vehicles.iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(names, ["Tug A", "Stage B", "Stage C"]);
+    assert!(matches!(
+        vehicles[0].propulsion,
+        VehiclePropulsionConfig::Continuous { .. }
+    ));
+}
+
+#[test]
+fn constants_file_overrides_body_gm_and_keeps_default_sun() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("constants.toml");
+    fs::write(
+        &path,
+        r#"[body_mu_km3_s2]
+"MARS BARYCENTER" = 42828.37
+"#,
+    )
+    .expect("write constants");
+
+    let constants = load_constants(&path).expect("constants");
+    assert_eq!(constants.mu_sun_km3_s2, DE440_MU_SUN_KM3_S2);
+    assert_eq!(
+        constants.body_mu_km3_s2.get("MARS BARYCENTER"),
+        Some(&42_828.37)
+    );
+}
+
+#[test]
+fn duplicate_names_are_rejected_case_insensitively() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let body = |name: &str| {
+        format!(
+            "name = \"{name}\"\nspice_name = \"MARS BARYCENTER\"\nmu_km3_s2 = 42828.37\n\
+             radius_km = 3389.5\nsoi_radius_km = 577000.0\ndefault_parking_altitude_km = 400.0\n\
+             surface_gravity_m_s2 = 3.72\nmass_kg = 6.417e23\n"
+        )
+    };
+    fs::write(dir.path().join("mars.toml"), body("MARS")).expect("write mars");
+    fs::write(dir.path().join("mars_edited.toml"), body("Mars")).expect("write copy");
+
+    match load_planets(dir.path()) {
+        Err(ConfigError::DuplicateName { kind, name }) => {
+            assert_eq!(kind, "body");
+            assert_eq!(name, "Mars");
+        }
+        other => panic!("expected duplicate name error, got {other:?}"),
+    }
+
+    assert!(load_planets("configs/bodies").is_ok());
+    assert!(load_vehicle_configs("configs/vehicles").is_ok());
+}
+
+#[test]
+fn scenario_manifest_resolves_referenced_and_inline_catalogs() {
+    let scenario = load_scenario("configs/runs/earth_mars_2026.toml").expect("example scenario");
+    assert_eq!(scenario.origin().name, "EARTH");
+    assert_eq!(scenario.destination().name, "MARS");
+    assert_eq!(
+        scenario.vehicle().map(|v| v.name.as_str()),
+        Some("Starship V4 Concept")
+    );
+    assert_eq!(scenario.mission.aerobrake, AerobrakeSetting::Full);
+    assert!(scenario.planets.len() >= 10);
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("scenario.yaml");
+    let manifest = |vehicle: &str| {
+        format!(
+            r#"mission:
+  from: earth
+  to: MARS
+  depart: "2030-01-01T00:00:00"
+  vehicle: {vehicle}
+planets:
+  - name: EARTH
+    spice_name: EARTH
+    mu_km3_s2: 398600.435436
+    radius_km: 6378.137
+    soi_radius_km: 924000.0
+    default_parking_altitude_km: 300.0
+    surface_gravity_m_s2: 9.81
+    mass_kg: 5.972e24
+  - name: MARS
+    spice_name: MARS BARYCENTER
+    mu_km3_s2: 42828.375214
+    radius_km: 3389.5
+    soi_radius_km: 577000.0
+    default_parking_altitude_km: 400.0
+    surface_gravity_m_s2: 3.71
+    mass_kg: 6.4171e23
+vehicles: {vehicles}
+constants:
+  mu_sun_km3_s2: 1.32712440041e11
+"#,
+            vehicles = fs::canonicalize("configs/vehicles")
+                .expect("vehicle catalog")
+                .display()
+        )
+    };
+
+    fs::write(&path, manifest("Ion Tug Mk1")).expect("write scenario");
+    let scenario = load_scenario(&path).expect("inline scenario");
+    assert_eq!(scenario.destination().spice_name, "MARS BARYCENTER");
+    assert!(
+        scenario
+            .origin()
+            .kernel_dependencies
+            .contains(&"de440s.bsp".to_string())
+    );
+    assert_eq!(scenario.constants.mu_sun_km3_s2, 1.327_124_400_41e11);
+    assert_eq!(scenario.mission.aerobrake, AerobrakeSetting::None);
+
+    fs::write(&path, manifest("Warp Shuttle")).expect("write scenario");
+    match load_scenario(&path) {
+        Err(ConfigError::UnresolvedReference {
+            field,
+            catalog,
+            name,
+        }) => {
+            assert_eq!(field, "vehicle");
+            assert_eq!(catalog, "vehicle");
+            assert_eq!(name, "Warp Shuttle");
+        }
+        other => panic!("expected unresolved reference, got {other:?}"),
+    }
+}
+
+#[test]
+fn inconsistent_planet_records_report_every_issue() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("broken.toml");
+    fs::write(
+        &path,
+        r#"name = "Typo"
+spice_name = "MARS BARYCENTER"
+mu_km3_s2 = -42828.37
+radius_km = 3389.5
+soi_radius_km = 3000.0
+default_parking_altitude_km = 400.0
+surface_gravity_m_s2 = 3.72
+mass_kg = 6.417e23
+
+[atmosphere]
+exists = true
+scale_height_km = 0.0
+surface_density_kg_m3 = 0.02
+"#,
+    )
+    .expect("write body");
+
+    match load_planets(&path) {
+        Err(ConfigError::Invalid { body, issues }) => {
+            assert_eq!(body, "Typo");
+            assert_eq!(issues.len(), 3, "{issues:?}");
+            assert!(issues[0].contains("mu_km3_s2"));
+            assert!(issues[1].contains("soi_radius_km"));
+            assert!(issues[2].contains("scale_height_km"));
+        }
+        other => panic!("expected invalid body error, got {other:?}"),
+    }
+
+    let planets = load_planets("configs/bodies").expect("catalog is consistent");
+    let mut mars = planets
+        .into_iter()
+        .find(|p| p.name == "MARS")
+        .expect("mars");
+    assert!(mars.validate().is_ok());
+    mars.surface_gravity_m_s2 = 9.81;
+    match mars.validate() {
+        Err(ConfigError::Invalid { issues, .. }) => {
+            assert_eq!(issues.len(), 1);
+            assert!(issues[0].contains("surface_gravity_m_s2"));
+        }
+        other => panic!("expected gravity mismatch, got {other:?}"),
+    }
+}
+
+#[test]
+fn planet_overrides_replace_only_listed_fields() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("overrides.yaml");
+    fs::write(
+        &path,
+        r#"- name: mars
+  default_parking_altitude_km: 250.0
+- name: Earth
+  atmosphere:
+    exists: true
+    scale_height_km: 7.2
+    surface_density_kg_m3: 1.2
+"#,
+    )
+    .expect("write overrides");
+
+    let base = load_planets("configs/bodies").expect("base catalog");
+    let merged = load_planets_with_overrides("configs/bodies".as_ref(), path.as_path())
+        .expect("merged catalog");
+    assert_eq!(merged.len(), base.len());
+
+    let find = |planets: &[PlanetConfig], name: &str| {
+        planets
+            .iter()
+            .find(|p| p.name == name)
+            .cloned()
+            .expect("body present")
+    };
+    let (mars_base, mars) = (find(&base, "MARS"), find(&merged, "MARS"));
+    assert_eq!(mars.default_parking_altitude_km, 250.0);
+    assert_eq!(mars.mu_km3_s2, mars_base.mu_km3_s2);
+    assert_eq!(mars.kernel_dependencies, mars_base.kernel_dependencies);
+    assert!(mars.entry_target.is_some());
+    let earth = find(&merged, "EARTH");
+    assert_eq!(
+        earth.atmosphere.as_ref().map(|a| a.scale_height_km),
+        Some(7.2)
+    );
+    assert_eq!(
+        earth.default_parking_altitude_km,
+        find(&base, "EARTH").default_parking_altitude_km
+    );
+
+    fs::write(&path, "name: Vulcan\nradius_km: 1000.0\n").expect("write overrides");
+    match load_planets_with_overrides("configs/bodies".as_ref(), path.as_path()) {
+        Err(ConfigError::UnknownOverride { kind, name }) => {
+            assert_eq!(kind, "body");
+            assert_eq!(name, "Vulcan");
+        }
+        other => panic!("expected unknown override error, got {other:?}"),
+    }
+}
+
+#[test]
+fn catalog_directory_mixes_toml_yaml_and_json_in_name_order() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let vehicle = |name: &str| {
+        format!(
+            "name = \"{name}\"\ndry_mass_kg = 1000.0\npropellant_mass_kg = 500.0\n\n\
+             [propulsion]\ntype = \"impulsive\"\nmax_delta_v_km_s = 4.0\nisp_seconds = 320.0\n"
+        )
+    };
+    fs::write(dir.path().join("b_stage.toml"), vehicle("B Stage")).expect("write toml");
+    fs::write(
+        dir.path().join("a_tugs.json"),
+        r#"[
+  {"name": "A1 Tug", "dry_mass_kg": 800.0, "propellant_mass_kg": 200.0,
+   "propulsion": {"type": "continuous", "max_thrust_newtons": 0.5, "isp_seconds": 3000.0}},
+  {"name": "A2 Tug", "dry_mass_kg": 900.0, "propellant_mass_kg": 300.0,
+   "propulsion": {"type": "continuous", "max_thrust_newtons": 0.7, "isp_seconds": 2500.0}}
+]"#,
+    )
+    .expect("write json");
+    fs::write(
+        dir.path().join("c_lander.yaml"),
+        r#"name: C Lander
+dry_mass_kg: 3000.0
+propellant_mass_kg: 6000.0
+propulsion:
+  type: impulsive
+  max_delta_v_km_s: 3.0
+  isp_seconds: 310.0
+"#,
+    )
+    .expect("write yaml");
+    fs::write(dir.path().join("notes.txt"), "not a record").expect("write notes");
+
+    let vehicles = load_vehicle_configs(dir.path()).expect("mixed catalog");
+    let names: Vec<&str> = vehicles.iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(names, ["A1 Tug", "A2 Tug", "B Stage", "C Lander"]);
+
+    let constants_path = dir.path().join("constants.json");
+    fs::write(
+        &constants_path,
+        r#"{"body_mu_km3_s2": {"EARTH": 398600.4}}"#,
+    )
+    .expect("write constants");
+    let constants = load_constants(&constants_path).expect("json constants");
+    assert_eq!(constants.mu_sun_km3_s2, DE440_MU_SUN_KM3_S2);
+
+    fs::write(dir.path().join("d_broken.json"), "{\"name\": ").expect("write broken");
+    assert!(matches!(
+        load_vehicle_configs(dir.path()),
+        Err(ConfigError::Json(_))
+    ));
+}
+
+#[test]
+fn numeric_spice_ids_infer_system_kernels() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let moon = |name: &str, spice: &str| {
+        format!(
+            "name = \"{name}\"\nspice_name = \"{spice}\"\nmu_km3_s2 = 3202.739\n\
+             radius_km = 1560.8\nsoi_radius_km = 67100.0\ndefault_parking_altitude_km = 75.0\n\
+             surface_gravity_m_s2 = 1.314\nmass_kg = 4.8e22\n"
+        )
+    };
+    fs::write(dir.path().join("europa.toml"), moon("Europa by ID", "502")).expect("write europa");
+    fs::write(dir.path().join("titan.toml"), moon("Titan by ID", " 606 ")).expect("write titan");
+    fs::write(dir.path().join("oberon.toml"), moon("Oberon by ID", "704")).expect("write oberon");
+
+    let planets = load_planets(dir.path()).expect("numeric catalog");
+    let kernels = |name: &str| {
+        planets
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| p.kernel_dependencies.clone())
+            .expect("body present")
+    };
+    assert!(kernels("Europa by ID").contains(&"jup365.bsp".to_string()));
+    assert!(kernels("Titan by ID").contains(&"sat455.bsp".to_string()));
+    assert_eq!(
+        kernels("Oberon by ID"),
+        ["de440s.bsp", "naif0012.tls", "pck00011.tpc"]
+    );
+}
@@ -1,8 +1,10 @@
 use std::sync::{Mutex, OnceLock};
 
+use solar_travel_calculator::core::constants::MU_SUN_KM3_S2;
 use solar_travel_calculator::ephemeris;
 use solar_travel_calculator::ephemeris::EphemerisError;
-use solar_travel_calculator::ephemeris::kernels::KERNEL_CATALOG;
+use solar_travel_calculator::ephemeris::kernels::{KERNEL_CATALOG, KernelDescriptor, KernelKind};
+use solar_travel_calculator::transfer::kernel_constants;
 
 const SPEED_OF_LIGHT_KM_S: f64 = 299_792.458;
 const AU_KM: f64 = 149_597_870.7;
@@ -115,3 +117,592 @@ fn state_vector_et_matches_string_version() {
         assert!((s1.velocity_km_s[i] - s2.velocity_km_s[i]).abs() < 1e-12);
     }
 }
+
+#[test]
+fn analytic_sun_ssb_offset_is_bounded() {
+    // Sun-SSB separation stays within ~2.2 solar radii; speed within ~16 m/s.
+    for days in [-3_650.0_f64, 0.0, 1_000.0, 9_131.0] {
+        let state = ephemeris::analytic::sun_ssb_approx(days * 86_400.0);
+        let distance = (state.position_km[0].powi(2)
+            + state.position_km[1].powi(2)
+            + state.position_km[2].powi(2))
+        .sqrt();
+        let speed = (state.velocity_km_s[0].powi(2)
+            + state.velocity_km_s[1].powi(2)
+            + state.velocity_km_s[2].powi(2))
+        .sqrt();
+        assert!(
+            distance < 1.7e6,
+            "Sun-SSB distance should be under 1.7e6 km (got {distance} km)"
+        );
+        assert!(
+            speed < 0.02,
+            "Sun-SSB speed should be under 20 m/s (got {speed} km/s)"
+        );
+    }
+}
+
+#[test]
+fn analytic_sun_ssb_tracks_spice() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    let et = ephemeris::epoch_seconds("2024 JAN 01 00:00:00 TDB").expect("epoch");
+    let spice =
+        ephemeris::state_vector_et("SUN", "SOLAR SYSTEM BARYCENTER", "ECLIPJ2000", "NONE", et)
+            .expect("Sun-SSB state should resolve");
+    let approx = ephemeris::analytic::sun_ssb_approx(et);
+
+    let error = ((spice.position_km[0] - approx.position_km[0]).powi(2)
+        + (spice.position_km[1] - approx.position_km[1]).powi(2)
+        + (spice.position_km[2] - approx.position_km[2]).powi(2))
+    .sqrt();
+    assert!(
+        error < 3.0e5,
+        "analytic Sun-SSB position should agree with SPICE within 3e5 km (error {error} km)"
+    );
+}
+
+#[test]
+fn body_id_resolves_known_names_only() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    assert_eq!(ephemeris::body_id("MARS BARYCENTER").unwrap(), Some(4));
+    assert_eq!(ephemeris::body_id("europa").unwrap(), Some(502));
+    assert_eq!(ephemeris::body_id("JUPTER").unwrap(), None);
+}
+
+#[test]
+fn sun_radec_from_earth_is_near_origin_at_equinox() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    let (ra, dec) = ephemeris::radec("SUN", "EARTH", "2024 MAR 20 03:06:00 UTC", "LT+S")
+        .expect("RA/Dec should resolve");
+    let ra_wrapped = if ra > std::f64::consts::PI {
+        ra - std::f64::consts::TAU
+    } else {
+        ra
+    };
+    assert!(
+        ra_wrapped.abs() < 0.01,
+        "Sun RA at equinox ~0 (got {ra} rad)"
+    );
+    assert!(dec.abs() < 0.01, "Sun Dec at equinox ~0 (got {dec} rad)");
+}
+
+#[test]
+fn aberration_corrections_map_to_spice_tokens() {
+    use ephemeris::AberrationCorrection as Abcorr;
+
+    let tokens: Vec<&str> = [
+        Abcorr::None,
+        Abcorr::LightTime,
+        Abcorr::LightTimeStellar,
+        Abcorr::ConvergedNewtonian,
+        Abcorr::ConvergedNewtonianStellar,
+        Abcorr::TransmissionLightTime,
+        Abcorr::TransmissionLightTimeStellar,
+    ]
+    .into_iter()
+    .map(Abcorr::spice_token)
+    .collect();
+    assert_eq!(tokens, ["NONE", "LT", "LT+S", "CN", "CN+S", "XLT", "XLT+S"]);
+    assert_eq!(Abcorr::default().to_string(), "NONE");
+}
+
+#[test]
+fn corrected_query_matches_string_token() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    let et = ephemeris::epoch_seconds("2030-01-01T00:00:00").expect("epoch");
+    let typed = ephemeris::state_vector_et_corrected(
+        "MARS BARYCENTER",
+        "EARTH",
+        "J2000",
+        ephemeris::AberrationCorrection::LightTimeStellar,
+        et,
+    )
+    .expect("typed");
+    let raw = ephemeris::state_vector_et("MARS BARYCENTER", "EARTH", "J2000", "LT+S", et).unwrap();
+    assert_eq!(typed.position_km, raw.position_km);
+    assert!(typed.light_time_seconds > 0.0);
+}
+
+#[test]
+fn earth_heliocentric_elements_are_near_circular() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    let elements = ephemeris::osculating_elements(
+        "EARTH BARYCENTER",
+        "SUN",
+        "ECLIPJ2000",
+        "2030-01-01T00:00:00",
+        132_712_440_041.939_4,
+    )
+    .expect("elements");
+    assert!((elements.semi_major_axis_km / 1.496e8 - 1.0).abs() < 0.01);
+    assert!(elements.eccentricity < 0.03);
+    assert!(elements.inclination_rad.to_degrees() < 0.1);
+    assert!((0.0..std::f64::consts::TAU).contains(&elements.mean_anomaly_rad));
+}
+
+#[test]
+fn spice_elements_convert_back_to_the_sampled_state() {
+    use solar_travel_calculator::orbits::{norm3, state_from_elements, sub};
+
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    let mu_sun = 132_712_440_041.939_4;
+    let et = ephemeris::epoch_seconds("2030-01-01T00:00:00").expect("epoch");
+    let elements =
+        ephemeris::osculating_elements_et("MARS BARYCENTER", "SUN", "ECLIPJ2000", et, mu_sun)
+            .expect("elements");
+    let state = ephemeris::state_vector_et("MARS BARYCENTER", "SUN", "ECLIPJ2000", "NONE", et)
+        .expect("state");
+    let (position, velocity) = state_from_elements(&elements, mu_sun).expect("conic state");
+    assert!(norm3(&sub(&position, &state.position_km)) < 1e-3);
+    assert!(norm3(&sub(&velocity, &state.velocity_km_s)) < 1e-9);
+}
+
+#[test]
+fn batch_state_vectors_match_single_queries_and_report_failing_index() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    let start = ephemeris::epoch_seconds("2030-01-01T00:00:00").expect("epoch");
+    let epochs: Vec<f64> = (0..5).map(|k| start + k as f64 * 86_400.0).collect();
+    let batch =
+        ephemeris::state_vectors_et("MARS BARYCENTER", "SUN", "ECLIPJ2000", "NONE", &epochs)
+            .expect("batch");
+    assert_eq!(batch.len(), epochs.len());
+    for (state, &et) in batch.iter().zip(&epochs) {
+        let single =
+            ephemeris::state_vector_et("MARS BARYCENTER", "SUN", "ECLIPJ2000", "NONE", et).unwrap();
+        assert_eq!(state.position_km, single.position_km);
+    }
+
+    // Far outside de440s coverage.
+    let mut bad = epochs.clone();
+    bad[3] = 1.0e12;
+    let err = ephemeris::state_vectors_et("MARS BARYCENTER", "SUN", "ECLIPJ2000", "NONE", &bad)
+        .expect_err("uncovered epoch");
+    assert!(
+        matches!(err, ephemeris::EphemerisError::BatchEpoch { index: 3, .. }),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn body_fixed_state_preserves_range_and_rejects_unknown_frames() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    let epoch = "2030-01-01T00:00:00";
+    let inertial =
+        ephemeris::state_vector("SUN", "MARS", "J2000", "NONE", epoch).expect("inertial state");
+    let fixed =
+        ephemeris::state_vector_from_frame("SUN", "MARS", "J2000", "NONE", epoch, "IAU_MARS")
+            .expect("Mars-fixed state");
+    let norm = |v: [f64; 3]| v.iter().map(|c| c * c).sum::<f64>().sqrt();
+    let range = norm(inertial.position_km);
+    assert!((norm(fixed.position_km) - range).abs() < 1e-6 * range);
+    // The Sun sweeps across the Mars-fixed sky at range × rotation rate × cos(subsolar latitude),
+    // and the subsolar latitude stays within Mars's ~25° obliquity.
+    let sweep = range * std::f64::consts::TAU / 88_642.7;
+    let ratio = norm(fixed.velocity_km_s) / sweep;
+    assert!((0.85..1.01).contains(&ratio), "sweep ratio {ratio}");
+
+    let err =
+        ephemeris::state_vector_from_frame("SUN", "MARS", "J2000", "NONE", epoch, "IAU_VULCAN")
+            .expect_err("unknown frame");
+    assert!(
+        matches!(&err, ephemeris::EphemerisError::UnknownFrame { frame } if frame == "IAU_VULCAN"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn ephemeris_cache_memoizes_exact_and_quantized_epochs() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    let et = ephemeris::epoch_seconds("2030-01-01T00:00:00").expect("epoch");
+    let exact = ephemeris::cache::EphemerisCache::new();
+    let first = exact
+        .cached_state_vector_et("EARTH", "SUN", "ECLIPJ2000", et)
+        .expect("state");
+    exact
+        .cached_state_vector_et("EARTH", "SUN", "ECLIPJ2000", et)
+        .expect("state");
+    exact
+        .cached_state_vector_et("EARTH", "SUN", "ECLIPJ2000", et + 10.0)
+        .expect("state");
+    assert_eq!(exact.len(), 2);
+
+    let coarse = ephemeris::cache::EphemerisCache::with_quantum(60.0);
+    coarse
+        .cached_state_vector_et("EARTH", "SUN", "ECLIPJ2000", et)
+        .expect("state");
+    let nearby = coarse
+        .cached_state_vector_et("EARTH", "SUN", "ECLIPJ2000", et + 10.0)
+        .expect("state");
+    assert_eq!(coarse.len(), 1);
+    assert_eq!(nearby.position_km, first.position_km);
+
+    coarse.clear();
+    assert!(coarse.is_empty());
+}
+
+#[test]
+fn cached_states_persist_samples_across_instances() {
+    use solar_travel_calculator::ephemeris::StateVector;
+    use solar_travel_calculator::ephemeris::cache::{CachedStates, StateProvider};
+
+    struct Counting(usize);
+    impl StateProvider for Counting {
+        fn state(
+            &mut self,
+            _target: &str,
+            _observer: &str,
+            _frame: &str,
+            et: f64,
+        ) -> Result<StateVector, EphemerisError> {
+            self.0 += 1;
+            Ok(StateVector {
+                position_km: [et, 2.0 * et, 3.0],
+                velocity_km_s: [0.1, 0.2, 0.3],
+                light_time_seconds: 4.0,
+            })
+        }
+    }
+
+    struct Offline;
+    impl StateProvider for Offline {
+        fn state(
+            &mut self,
+            _target: &str,
+            _observer: &str,
+            _frame: &str,
+            _et: f64,
+        ) -> Result<StateVector, EphemerisError> {
+            Err(EphemerisError::Spice {
+                message: "offline".to_string(),
+            })
+        }
+    }
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let epochs = [0.0, 864_000.0, 1_728_000.5];
+
+    let mut first = CachedStates::new(dir.path(), Counting(0));
+    for et in epochs {
+        first
+            .state("MARS BARYCENTER", "SUN", "ECLIPJ2000", et)
+            .expect("miss");
+    }
+    first
+        .state("MARS BARYCENTER", "SUN", "ECLIPJ2000", epochs[1])
+        .expect("hit");
+    first.flush().expect("flush");
+    let path = first.path_for("MARS BARYCENTER", "SUN", "ECLIPJ2000");
+    assert!(path.ends_with("MARS_BARYCENTER_SUN_ECLIPJ2000.bin"));
+    drop(first);
+
+    let mut second = CachedStates::new(dir.path(), Offline);
+    let state = second
+        .state("MARS BARYCENTER", "SUN", "ECLIPJ2000", epochs[2])
+        .expect("served from disk");
+    assert_eq!(state.position_km, [epochs[2], 2.0 * epochs[2], 3.0]);
+    assert_eq!(state.light_time_seconds, 4.0);
+    assert!(
+        second
+            .state("MARS BARYCENTER", "SUN", "ECLIPJ2000", 1.0)
+            .is_err()
+    );
+    assert!(second.state("EARTH", "SUN", "ECLIPJ2000", 0.0).is_err());
+}
+
+#[test]
+fn kernel_headers_must_match_their_kind() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let descriptor = |kind: KernelKind| {
+        KERNEL_CATALOG
+            .iter()
+            .find(|d| d.kind == kind)
+            .expect("catalog covers kind")
+    };
+    let write = |name: &str, contents: &[u8]| {
+        let path = dir.path().join(name);
+        std::fs::write(&path, contents).expect("write kernel");
+        path
+    };
+
+    let lsk = write("naif.tls", b"KPL/LSK\n\\begindata\n");
+    let spk = write("planets.bsp", b"DAF/SPK \x00\x00\x00\x02");
+    let legacy_spk = write("legacy.bsp", b"NAIF/DAF\x00");
+    let empty = write("empty.bsp", b"");
+    let truncated = write("short.tpc", b"KPL");
+
+    assert!(ephemeris::verify_kernel_at(descriptor(KernelKind::Lsk), &lsk).is_ok());
+    assert!(ephemeris::verify_kernel_at(descriptor(KernelKind::Spk), &spk).is_ok());
+    assert!(ephemeris::verify_kernel_at(descriptor(KernelKind::Spk), &legacy_spk).is_ok());
+    for (kind, path) in [
+        (KernelKind::Spk, &empty),
+        (KernelKind::Pck, &truncated),
+        (KernelKind::Lsk, &spk),
+    ] {
+        match ephemeris::verify_kernel_at(descriptor(kind), path) {
+            Err(EphemerisError::CorruptKernel {
+                name,
+                path: reported,
+            }) => {
+                assert_eq!(name, descriptor(kind).filename);
+                assert_eq!(&reported, path);
+            }
+            other => panic!("expected corrupt kernel for {kind:?}, got {other:?}"),
+        }
+    }
+    assert!(matches!(
+        ephemeris::verify_kernel_at(descriptor(KernelKind::Fk), &dir.path().join("missing.tf")),
+        Err(EphemerisError::Io { .. })
+    ));
+}
+
+#[test]
+fn kernel_coverage_brackets_de440s_range() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    let mars_id = ephemeris::body_id("MARS BARYCENTER")
+        .expect("body lookup")
+        .expect("Mars barycenter is known");
+    let coverage = ephemeris::kernel_coverage(mars_id).expect("coverage");
+    assert!(!coverage.is_empty());
+    assert!(
+        coverage
+            .windows(2)
+            .all(|pair| pair[0].end_et <= pair[1].start_et)
+    );
+
+    let now = ephemeris::epoch_seconds("2026 JAN 01 00:00:00 TDB").expect("epoch");
+    assert!(ephemeris::is_covered(mars_id, now).expect("coverage query"));
+    // de440s stops in 2650.
+    let far_future = ephemeris::epoch_seconds("3000 JAN 01 00:00:00 TDB").expect("epoch");
+    assert!(!ephemeris::is_covered(mars_id, far_future).expect("coverage query"));
+}
+
+#[test]
+fn misspelt_body_names_suggest_the_closest_known_body() {
+    assert_eq!(ephemeris::suggest_body_name("JUPTER"), Some("JUPITER"));
+    assert_eq!(ephemeris::suggest_body_name("ganymed"), Some("GANYMEDE"));
+    assert_eq!(
+        ephemeris::suggest_body_name("mars barycentre"),
+        Some("MARS BARYCENTER")
+    );
+    assert_eq!(ephemeris::suggest_body_name("EARTH"), None);
+    assert_eq!(ephemeris::suggest_body_name("XYZZY"), None);
+}
+
+#[test]
+fn resolve_body_accepts_integer_ids_and_rejects_unknown_names() {
+    assert_eq!(ephemeris::resolve_body("599").unwrap(), 599);
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+    assert_eq!(ephemeris::resolve_body("jupiter barycenter").unwrap(), 5);
+    assert_eq!(ephemeris::body_name(399).unwrap(), "EARTH");
+    match ephemeris::resolve_body("JUPTER") {
+        Err(EphemerisError::UnknownBody { name, suggestion }) => {
+            assert_eq!(name, "JUPTER");
+            assert_eq!(suggestion.as_deref(), Some("JUPITER"));
+        }
+        other => panic!("expected UnknownBody, got {other:?}"),
+    }
+}
+
+#[test]
+fn format_epoch_iso_matches_calendar_format() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+    let et = ephemeris::epoch_seconds("2026-01-01T00:00:00").unwrap();
+    assert_eq!(
+        ephemeris::format_epoch_iso(et).unwrap(),
+        "2026-01-01T00:00:00.000"
+    );
+    assert_eq!(
+        ephemeris::format_epoch(et).unwrap(),
+        "2026 JAN 01 00:00:00.000"
+    );
+}
+
+#[test]
+fn julian_date_conversions_round_trip() {
+    assert_eq!(ephemeris::et_from_jd_tdb(2_451_545.0), 0.0);
+    assert_eq!(ephemeris::jd_tdb_from_et(86_400.0), 2_451_546.0);
+    assert_eq!(ephemeris::mjd_tdb_from_et(0.0), 51_544.5);
+    for et in [-3.2e9, -1.0, 0.0, 12_345.678, 8.1e8, 3.2e9] {
+        let via_jd = ephemeris::et_from_jd_tdb(ephemeris::jd_tdb_from_et(et));
+        let via_mjd = ephemeris::et_from_mjd_tdb(ephemeris::mjd_tdb_from_et(et));
+        assert!(
+            (via_jd - et).abs() < 1e-4,
+            "JD round trip drifted for {et}: {via_jd}"
+        );
+        assert!(
+            (via_mjd - et).abs() < 1e-4,
+            "MJD round trip drifted for {et}: {via_mjd}"
+        );
+    }
+}
+
+#[test]
+fn range_rate_projects_velocity_onto_line_of_sight() {
+    let state = ephemeris::StateVector {
+        position_km: [3.0, 4.0, 0.0],
+        velocity_km_s: [1.0, 0.0, 7.0],
+        light_time_seconds: 0.0,
+    };
+    let (range, rate) = state.range_and_rate();
+    assert!((range - 5.0).abs() < 1e-12);
+    assert!((rate - 0.6).abs() < 1e-12);
+
+    let tangential = ephemeris::StateVector {
+        velocity_km_s: [-4.0, 3.0, 0.0],
+        ..state
+    };
+    assert!(tangential.range_and_rate().1.abs() < 1e-12);
+}
+
+#[test]
+fn earth_mars_range_rate_matches_finite_difference() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+    let (range, rate) = ephemeris::range_rate(
+        "MARS BARYCENTER",
+        "EARTH",
+        "ECLIPJ2000",
+        "LT",
+        "2026-01-01T00:00:00",
+    )
+    .expect("range rate");
+    let (later, _) = ephemeris::range_rate(
+        "MARS BARYCENTER",
+        "EARTH",
+        "ECLIPJ2000",
+        "LT",
+        "2026-01-01T00:01:00",
+    )
+    .expect("range rate one minute later");
+    assert!(range > 0.3 * AU_KM && range < 2.7 * AU_KM);
+    assert!(((later - range) / 60.0 - rate).abs() < 1e-3);
+}
+
+#[test]
+fn kernel_constants_match_pooled_sun_gm_or_fall_back_to_de440() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+    let constants = kernel_constants(&[]).expect("kernel constants");
+    match ephemeris::body_gm(10) {
+        Ok(mu_sun) => {
+            assert_eq!(constants.mu_sun_km3_s2, mu_sun);
+            assert!((mu_sun / MU_SUN_KM3_S2 - 1.0).abs() < 1e-8);
+        }
+        Err(EphemerisError::MissingGm { id }) => {
+            assert_eq!(id, 10);
+            assert_eq!(constants.mu_sun_km3_s2, MU_SUN_KM3_S2);
+        }
+        Err(err) => panic!("unexpected GM lookup error: {err}"),
+    }
+}
+
+#[test]
+fn furnish_additional_rejects_missing_files_before_touching_spice() {
+    let missing = std::path::Path::new("data/spice/no_such_spacecraft.bsp");
+    match ephemeris::furnish_additional(missing) {
+        Err(EphemerisError::UnusableKernel { path, .. }) => assert_eq!(path, missing),
+        other => panic!("expected UnusableKernel, got {other:?}"),
+    }
+}
+
+#[test]
+fn reinitialize_reloads_the_catalog_and_accepts_extra_kernels() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+    let before = ephemeris::state_vector_et("MARS BARYCENTER", "SUN", "ECLIPJ2000", "NONE", 0.0)
+        .expect("state before reload");
+
+    ephemeris::reinitialize(KERNEL_CATALOG).expect("reload catalog");
+    let leap_seconds = KERNEL_CATALOG
+        .iter()
+        .find(|descriptor| descriptor.kind == KernelKind::Lsk)
+        .expect("catalog LSK");
+    ephemeris::furnish_additional(&leap_seconds.local_path()).expect("furnish extra kernel");
+
+    let after = ephemeris::state_vector_et("MARS BARYCENTER", "SUN", "ECLIPJ2000", "NONE", 0.0)
+        .expect("state after reload");
+    assert_eq!(before.position_km, after.position_km);
+}
+
+#[test]
+fn kernel_coverage_follows_the_loaded_kernel_pool() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+    const MARS_BODY: i32 = 499;
+    let without_satellites: Vec<KernelDescriptor> = KERNEL_CATALOG
+        .iter()
+        .filter(|descriptor| descriptor.filename != "mar099.bsp")
+        .copied()
+        .collect();
+    let satellites = KERNEL_CATALOG
+        .iter()
+        .find(|descriptor| descriptor.filename == "mar099.bsp")
+        .expect("catalog Mars satellite SPK");
+
+    ephemeris::reinitialize(&without_satellites).expect("reload without mar099");
+    assert!(
+        ephemeris::kernel_coverage(MARS_BODY)
+            .expect("coverage")
+            .is_empty()
+    );
+
+    // A kernel outside the startup set still counts once it is furnished.
+    ephemeris::furnish_additional(&satellites.local_path()).expect("furnish mar099");
+    let coverage = ephemeris::kernel_coverage(MARS_BODY).expect("coverage");
+    ephemeris::reinitialize(KERNEL_CATALOG).expect("restore catalog");
+    assert!(!coverage.is_empty());
+}
@@ -0,0 +1,55 @@
+use serde_json::Value;
+use solar_travel_calculator::export::trajectory::{write_czml, write_geojson};
+use std::fs;
+
+fn sample_track() -> Vec<(f64, [f64; 3])> {
+    vec![
+        (0.0, [1.5e8, 0.0, 0.0]),
+        (86_400.0, [1.49e8, 2.5e6, 1.0e3]),
+        (172_800.0, [1.47e8, 5.0e6, 2.0e3]),
+    ]
+}
+
+#[test]
+fn geojson_track_is_a_linestring_with_frame_and_times() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("track.geojson");
+    write_geojson(&path, &sample_track(), None).expect("geojson");
+
+    let doc: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(doc["type"], "Feature");
+    assert_eq!(doc["geometry"]["type"], "LineString");
+    let coordinates = doc["geometry"]["coordinates"].as_array().unwrap();
+    assert_eq!(coordinates.len(), 3);
+    assert_eq!(coordinates[1][1].as_f64(), Some(2.5e6));
+    assert_eq!(doc["properties"]["frame"], "ECLIPJ2000");
+    assert_eq!(doc["properties"]["units"], "km");
+    assert_eq!(doc["properties"]["et_seconds"][2].as_f64(), Some(172_800.0));
+}
+
+#[test]
+fn czml_positions_are_time_tagged_metres_from_epoch() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("track.czml");
+    let track: Vec<_> = sample_track()
+        .into_iter()
+        .map(|(et, position)| (et + 8.0e8, position))
+        .collect();
+    write_czml(&path, &track, "2025-05-08T12:00:00.000", Some("J2000")).expect("czml");
+
+    let doc: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    let packets = doc.as_array().unwrap();
+    assert_eq!(packets[0]["id"], "document");
+    let position = &packets[1]["position"];
+    assert_eq!(position["epoch"], "2025-05-08T12:00:00.000");
+    let cartesian: Vec<f64> = position["cartesian"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_f64().unwrap())
+        .collect();
+    assert_eq!(cartesian.len(), 12);
+    assert_eq!(&cartesian[..4], &[0.0, 1.5e11, 0.0, 0.0]);
+    assert_eq!(cartesian[4], 86_400.0);
+    assert_eq!(packets[1]["properties"]["frame"], "J2000");
+}
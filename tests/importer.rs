@@ -0,0 +1,364 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use solar_travel_calculator::config::load_planets;
+use solar_travel_calculator::ephemeris::kernels::{KernelDescriptor, KernelKind};
+use solar_travel_calculator::importer::{
+    ImportError, KernelStatus, RetryPolicy, download_kernel_resumable, download_kernel_to,
+    download_kernels_parallel_to, download_kernels_with_policy_to,
+    download_kernels_with_progress_to, partial_path, required_kernels_for,
+    unknown_kernel_dependencies, validator_path,
+};
+
+const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+/// Serve `body` to each of `requests` connections, then shut down.
+fn serve(body: &'static [u8], requests: usize) -> String {
+    serve_responses(vec![(200, body); requests])
+}
+
+/// Answer successive connections with the given `(status, body)` pairs, then shut down.
+fn serve_responses(responses: Vec<(u16, &'static [u8])>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    thread::spawn(move || {
+        for (stream, (status, body)) in listener.incoming().zip(responses) {
+            let mut stream = stream.expect("accept");
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = stream.read(&mut buf).expect("read request");
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            write!(
+                stream,
+                "HTTP/1.1 {status} Test\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            )
+            .expect("write headers");
+            stream.write_all(body).expect("write body");
+        }
+    });
+    format!("http://{addr}/kernel.bsp")
+}
+
+/// `Range` and `If-Range` headers of one request.
+type RangeHeaders = (Option<String>, Option<String>);
+
+/// Serve `body` once with `ETag: "abc-v1"`, honouring a `Range: bytes=N-` header with a 206 when
+/// `ranges` is set and otherwise answering 200 with everything. The request's `Range` and
+/// `If-Range` headers (if any) are reported back.
+fn serve_ranged(body: &'static [u8], ranges: bool) -> (String, mpsc::Receiver<RangeHeaders>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().expect("accept");
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = stream.read(&mut buf).expect("read request");
+            if n == 0 {
+                break;
+            }
+            request.extend_from_slice(&buf[..n]);
+        }
+        let request = String::from_utf8_lossy(&request).into_owned();
+        let header = |wanted: &str| {
+            request.lines().find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                name.eq_ignore_ascii_case(wanted)
+                    .then(|| value.trim().to_string())
+            })
+        };
+        let (range, if_range) = (header("range"), header("if-range"));
+        let start = range.as_deref().filter(|_| ranges).and_then(|value| {
+            value
+                .strip_prefix("bytes=")?
+                .strip_suffix('-')?
+                .parse()
+                .ok()
+        });
+        let (status, content) = match start {
+            Some(start) => ("206 Partial Content", &body[start..]),
+            None => ("200 OK", body),
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nETag: \"abc-v1\"\r\nConnection: close\r\n\r\n",
+            content.len()
+        )
+        .expect("write headers");
+        stream.write_all(content).expect("write body");
+        tx.send((range, if_range)).expect("report range");
+    });
+    (format!("http://{addr}/kernel.bsp"), rx)
+}
+
+fn descriptor(url: String, sha256: &'static str) -> KernelDescriptor {
+    KernelDescriptor {
+        filename: "kernel.bsp",
+        url: Box::leak(url.into_boxed_str()),
+        kind: KernelKind::Spk,
+        description: "test kernel",
+        sha256: Some(sha256),
+    }
+}
+
+#[test]
+fn checksum_mismatch_discards_partial_download() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let dest = dir.path().join("kernel.bsp");
+    let url = serve(b"<html>mirror error</html>", 1);
+
+    let err = download_kernel_to(&descriptor(url, ABC_SHA256), &dest).expect_err("bad bytes");
+    match err {
+        ImportError::Checksum {
+            path,
+            expected,
+            actual,
+        } => {
+            assert_eq!(path, dest);
+            assert_eq!(expected, ABC_SHA256);
+            assert_ne!(actual, ABC_SHA256);
+        }
+        other => panic!("expected checksum error, got {other:?}"),
+    }
+    assert!(!dest.exists());
+    assert!(!partial_path(&dest).exists());
+}
+
+#[test]
+fn verified_download_is_promoted_into_place() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let dest = dir.path().join("kernel.bsp");
+    let url = serve(b"abc", 1);
+
+    download_kernel_to(&descriptor(url, ABC_SHA256), &dest).expect("verified download");
+    assert_eq!(std::fs::read(&dest).expect("kernel"), b"abc");
+    assert!(!partial_path(&dest).exists());
+    assert_eq!(
+        partial_path(&dest).file_name().unwrap(),
+        "kernel.bsp.partial"
+    );
+}
+
+#[test]
+fn parallel_downloads_keep_descriptor_order_and_skip_present_kernels() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(dir.path().join("b.bsp"), b"cached").expect("seed kernel");
+    let url = serve(b"abc", 3);
+    let kernel = |filename: &'static str| KernelDescriptor {
+        filename,
+        ..descriptor(url.clone(), ABC_SHA256)
+    };
+    let descriptors = [
+        kernel("a.bsp"),
+        kernel("b.bsp"),
+        kernel("c.bsp"),
+        kernel("c.tf"),
+    ];
+
+    let statuses =
+        download_kernels_parallel_to(dir.path(), &descriptors, 3).expect("parallel download");
+    let names: Vec<String> = statuses
+        .iter()
+        .map(|status| match status {
+            KernelStatus::Downloaded { path, .. } => {
+                format!("new {}", path.file_name().unwrap().display())
+            }
+            KernelStatus::AlreadyPresent(path) => {
+                format!("skip {}", path.file_name().unwrap().display())
+            }
+            other => panic!("unexpected status {other:?}"),
+        })
+        .collect();
+    assert_eq!(names, ["new a.bsp", "skip b.bsp", "new c.bsp", "new c.tf"]);
+    assert_eq!(std::fs::read(dir.path().join("b.bsp")).unwrap(), b"cached");
+    assert_eq!(std::fs::read(dir.path().join("c.tf")).unwrap(), b"abc");
+}
+
+#[test]
+fn parallel_download_reports_network_failure() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    // Bind then drop a listener so the port refuses connections.
+    let closed = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let descriptors = [descriptor(
+        format!("http://{closed}/kernel.bsp"),
+        ABC_SHA256,
+    )];
+
+    let err = download_kernels_parallel_to(dir.path(), &descriptors, 2).expect_err("refused");
+    assert!(matches!(err, ImportError::Network(_)), "{err:?}");
+    assert!(!partial_path(&dir.path().join("kernel.bsp")).exists());
+}
+
+#[test]
+fn transient_server_errors_are_retried_with_attempt_count() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let url = serve_responses(vec![(503, b"busy"), (502, b"busy"), (200, b"abc")]);
+    let policy = RetryPolicy {
+        max_attempts: 3,
+        base_delay: Duration::from_millis(1),
+    };
+
+    let statuses =
+        download_kernels_with_policy_to(dir.path(), &[descriptor(url, ABC_SHA256)], &policy)
+            .expect("third attempt succeeds");
+    match &statuses[..] {
+        [KernelStatus::Downloaded { path, attempts }] => {
+            assert_eq!(*attempts, 3);
+            assert_eq!(std::fs::read(path).unwrap(), b"abc");
+        }
+        other => panic!("unexpected statuses {other:?}"),
+    }
+}
+
+#[test]
+fn client_errors_fail_without_retrying() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    // A retry would hit the second response and succeed.
+    let url = serve_responses(vec![(404, b"missing"), (200, b"abc")]);
+    let policy = RetryPolicy {
+        max_attempts: 5,
+        base_delay: Duration::from_millis(1),
+    };
+
+    let err = download_kernels_with_policy_to(dir.path(), &[descriptor(url, ABC_SHA256)], &policy)
+        .expect_err("404 is final");
+    match err {
+        ImportError::Network(err) => assert_eq!(err.status().map(|s| s.as_u16()), Some(404)),
+        other => panic!("unexpected error {other:?}"),
+    }
+    assert!(!dir.path().join("kernel.bsp").exists());
+}
+
+#[test]
+fn resumable_download_appends_ranged_response() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let dest = dir.path().join("kernel.bsp");
+    std::fs::write(partial_path(&dest), b"ab").expect("seed partial");
+    let (url, range) = serve_ranged(b"abc", true);
+
+    download_kernel_resumable(&descriptor(url, ABC_SHA256), &dest, true).expect("resume");
+    assert_eq!(range.recv().unwrap().0.as_deref(), Some("bytes=2-"));
+    assert_eq!(std::fs::read(&dest).unwrap(), b"abc");
+    assert!(!partial_path(&dest).exists());
+}
+
+#[test]
+fn resumable_download_restarts_when_range_is_ignored() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let dest = dir.path().join("kernel.bsp");
+    std::fs::write(partial_path(&dest), b"stale").expect("seed partial");
+    let (url, range) = serve_ranged(b"abc", false);
+
+    download_kernel_resumable(&descriptor(url, ABC_SHA256), &dest, true).expect("restart");
+    assert_eq!(range.recv().unwrap().0.as_deref(), Some("bytes=5-"));
+    assert_eq!(std::fs::read(&dest).unwrap(), b"abc");
+}
+
+#[test]
+fn unverified_partial_without_validator_restarts_from_scratch() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let dest = dir.path().join("kernel.bsp");
+    std::fs::write(partial_path(&dest), b"xy").expect("seed partial");
+    let (url, headers) = serve_ranged(b"abc", true);
+    let kernel = KernelDescriptor {
+        sha256: None,
+        ..descriptor(url, ABC_SHA256)
+    };
+
+    download_kernel_resumable(&kernel, &dest, true).expect("restart");
+    assert_eq!(headers.recv().unwrap(), (None, None));
+    assert_eq!(std::fs::read(&dest).unwrap(), b"abc");
+    assert!(!partial_path(&dest).exists());
+    assert!(!validator_path(&dest).exists());
+}
+
+#[test]
+fn unverified_partial_resumes_with_recorded_validator() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let dest = dir.path().join("kernel.bsp");
+    std::fs::write(partial_path(&dest), b"ab").expect("seed partial");
+    std::fs::write(validator_path(&dest), "\"abc-v1\"").expect("seed validator");
+    let (url, headers) = serve_ranged(b"abc", true);
+    let kernel = KernelDescriptor {
+        sha256: None,
+        ..descriptor(url, ABC_SHA256)
+    };
+
+    download_kernel_resumable(&kernel, &dest, true).expect("resume");
+    let (range, if_range) = headers.recv().unwrap();
+    assert_eq!(range.as_deref(), Some("bytes=2-"));
+    assert_eq!(if_range.as_deref(), Some("\"abc-v1\""));
+    assert_eq!(std::fs::read(&dest).unwrap(), b"abc");
+    assert!(!validator_path(&dest).exists());
+}
+
+#[test]
+fn progress_is_reported_in_chunks_with_content_length() {
+    const INTERVAL: u64 = 256 * 1024;
+    let dir = tempfile::tempdir().expect("tempdir");
+    let body: &'static [u8] = Box::leak(vec![7u8; 600 * 1024].into_boxed_slice());
+    let kernel = KernelDescriptor {
+        sha256: None,
+        ..descriptor(serve(body, 1), ABC_SHA256)
+    };
+
+    let mut reports = Vec::new();
+    download_kernels_with_progress_to(
+        dir.path(),
+        std::slice::from_ref(&kernel),
+        &RetryPolicy::none(),
+        |descriptor, done, total| {
+            assert_eq!(descriptor.filename, "kernel.bsp");
+            reports.push((done, total));
+        },
+    )
+    .expect("download");
+
+    let len = body.len() as u64;
+    assert_eq!(reports.last(), Some(&(len, Some(len))));
+    assert!(reports.iter().all(|(_, total)| *total == Some(len)));
+    let intermediate: Vec<u64> = reports[..reports.len() - 1]
+        .iter()
+        .map(|(done, _)| *done)
+        .collect();
+    assert_eq!(intermediate.len(), 2, "{reports:?}");
+    assert!(intermediate[0] >= INTERVAL && intermediate[1] - intermediate[0] >= INTERVAL);
+}
+
+#[test]
+fn required_kernels_cover_only_the_selected_bodies() {
+    let planets = load_planets("configs/bodies").expect("catalog");
+    let mut selected: Vec<_> = planets
+        .into_iter()
+        .filter(|p| p.name == "EARTH" || p.name == "MARS")
+        .collect();
+
+    let filenames: Vec<&str> = required_kernels_for(&selected)
+        .iter()
+        .map(|descriptor| descriptor.filename)
+        .collect();
+    assert_eq!(
+        filenames,
+        ["de440s.bsp", "mar099.bsp", "naif0012.tls", "pck00011.tpc"]
+    );
+    assert!(unknown_kernel_dependencies(&selected).is_empty());
+
+    selected[0]
+        .kernel_dependencies
+        .push("custom_probe.bsp".to_string());
+    assert_eq!(required_kernels_for(&selected).len(), 4);
+    assert_eq!(unknown_kernel_dependencies(&selected), ["custom_probe.bsp"]);
+}
@@ -1,6 +1,10 @@
-use solar_travel_calculator::impulsive::transfers::{bi_elliptic, hohmann};
+use solar_travel_calculator::core::constants::MU_SUN_KM3_S2 as MU_SUN;
+use solar_travel_calculator::impulsive::transfers::{
+    TransferError, bi_elliptic, bi_elliptic_checked, bi_elliptic_optimal, combined_plane_change,
+    hohmann, hohmann_checked, hohmann_phase_angle, hohmann_with_plane_change,
+    time_to_phase_alignment,
+};
 
-const MU_SUN: f64 = 1.327_124_400_18e11; // km^3 / s^2
 const AU_KM: f64 = 149_597_870.7; // km
 
 #[test]
@@ -31,6 +35,30 @@ fn bielliptic_can_outperform_hohmann_for_large_ratios() {
     assert!(bi.dv_total_km_s < ho.dv_total_km_s);
 }
 
+#[test]
+fn bielliptic_optimum_respects_the_hohmann_crossover() {
+    let r1 = 1.0 * AU_KM;
+    let max_rb = 1_000.0 * AU_KM;
+
+    let below = bi_elliptic_optimal(r1, 10.0 * r1, MU_SUN, max_rb).expect("ratio 10");
+    assert!(!below.beats_hohmann);
+    assert!(below.transfer.dv_total_km_s >= below.hohmann.dv_total_km_s * (1.0 - 1e-9));
+    assert!((below.transfer.rb_km - 10.0 * r1).abs() / r1 < 1e-6);
+
+    let above = bi_elliptic_optimal(r1, 20.0 * r1, MU_SUN, max_rb).expect("ratio 20");
+    assert!(above.beats_hohmann);
+    assert!(above.transfer.dv_total_km_s < above.hohmann.dv_total_km_s);
+    // Larger rb keeps helping beyond the crossover, so the optimum sits at the bound.
+    assert!((above.transfer.rb_km - max_rb).abs() / max_rb < 1e-6);
+    let fixed = bi_elliptic(r1, 20.0 * r1, 50.0 * r1, MU_SUN);
+    assert!(above.transfer.dv_total_km_s <= fixed.dv_total_km_s);
+
+    assert!(matches!(
+        bi_elliptic_optimal(r1, 20.0 * r1, MU_SUN, 5.0 * r1),
+        Err(TransferError::ApoapsisBoundTooLow { .. })
+    ));
+}
+
 #[test]
 fn hohmann_earth_mars_reasonable_numbers() {
     // Semi-major axis ratios approximated by mean orbital radii in AU
@@ -60,3 +88,74 @@ fn hohmann_earth_venus_reasonable_numbers() {
     let days = h.tof_seconds / 86_400.0;
     assert!((days - 145.0).abs() < 5.0, "tof_days = {}", days);
 }
+
+#[test]
+fn hohmann_phase_angle_and_alignment_for_earth_mars() {
+    let r1 = 1.0 * AU_KM;
+    let r2 = 1.524 * AU_KM;
+    let required = hohmann_phase_angle(r1, r2, MU_SUN);
+    // Mars must lead Earth by roughly 44 degrees at a Hohmann departure.
+    assert!((required.to_degrees() - 44.3).abs() < 1.0);
+
+    // Already aligned: no wait. Just past alignment: nearly a full synodic period.
+    let wait_now = time_to_phase_alignment(required, required, r1, r2, MU_SUN).unwrap();
+    assert!(wait_now.abs() < 1e-6);
+    let wait_next = time_to_phase_alignment(required - 1e-3, required, r1, r2, MU_SUN).unwrap();
+    let synodic_days = wait_next / 86_400.0;
+    assert!((synodic_days - 780.0).abs() < 10.0, "{synodic_days}");
+
+    assert!(time_to_phase_alignment(0.0, required, r1, r1, MU_SUN).is_none());
+}
+
+#[test]
+fn checked_transfers_reject_non_positive_inputs() {
+    let r1 = 1.0 * AU_KM;
+    let r2 = 1.524 * AU_KM;
+
+    assert_eq!(hohmann_checked(r1, r2, MU_SUN), Ok(hohmann(r1, r2, MU_SUN)));
+    assert_eq!(
+        hohmann_checked(r1, r2, 0.0),
+        Err(TransferError::NonPositiveInput {
+            name: "mu_km3_s2",
+            value: 0.0
+        })
+    );
+    assert!(hohmann_checked(-r1, r2, MU_SUN).is_err());
+    assert!(hohmann_checked(r1, f64::NAN, MU_SUN).is_err());
+
+    let rb = 20.0 * AU_KM;
+    assert_eq!(
+        bi_elliptic_checked(r1, r2, rb, MU_SUN),
+        Ok(bi_elliptic(r1, r2, rb, MU_SUN))
+    );
+    assert!(bi_elliptic_checked(r1, r2, rb, -MU_SUN).is_err());
+}
+
+#[test]
+fn plane_change_burns_follow_vector_geometry() {
+    let v = 7.5;
+    assert!((combined_plane_change(v, v + 1.0, 0.0) - 1.0).abs() < 1e-12);
+    let pure = combined_plane_change(v, v, 30f64.to_radians());
+    assert!((pure - 2.0 * v * 15f64.to_radians().sin()).abs() < 1e-12);
+
+    let r1 = 1.0 * AU_KM;
+    let r2 = 39.5 * AU_KM; // Pluto-like distance
+    let delta_i = 17f64.to_radians();
+    let coplanar = hohmann_with_plane_change(r1, r2, MU_SUN, 0.0, 0.5).expect("coplanar");
+    let ho = hohmann(r1, r2, MU_SUN);
+    assert!((coplanar.dv_total_km_s - ho.dv_total_km_s).abs() < 1e-9);
+
+    let early = hohmann_with_plane_change(r1, r2, MU_SUN, delta_i, 1.0).expect("all at departure");
+    let late = hohmann_with_plane_change(r1, r2, MU_SUN, delta_i, 0.0).expect("all at arrival");
+    assert!(early.dv_total_km_s > ho.dv_total_km_s);
+    // Plane changes are cheapest at the slow, outer burn.
+    assert!(late.dv_total_km_s < early.dv_total_km_s);
+    assert!(early.optimal_split_fraction < 0.5);
+    assert!(early.optimal_dv_total_km_s <= late.dv_total_km_s + 1e-12);
+    assert_eq!(early.optimal_split_fraction, late.optimal_split_fraction);
+
+    assert!(matches!(
+        hohmann_with_plane_change(r1, r2, MU_SUN, delta_i, 1.5),
+        Err(TransferError::SplitOutOfRange(_))
+    ));
+}
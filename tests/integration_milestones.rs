@@ -4,16 +4,16 @@ use std::time::Instant;
 use assert_cmd::Command;
 use csv::Reader;
 
-use solar_travel_calculator::config::{load_planets, load_vehicle_configs};
+use solar_travel_calculator::config::{ConstantsSet, load_planets, load_vehicle_configs};
+use solar_travel_calculator::core::constants::MU_SUN_KM3_S2 as MU_SUN;
 use solar_travel_calculator::ephemeris::{self, EphemerisError};
 use solar_travel_calculator::impulsive::{lambert, transfers as impulsive};
 use solar_travel_calculator::mission::arrival::{AerobrakingOption, ArrivalConfig};
-use solar_travel_calculator::mission::departure::DepartureConfig;
+use solar_travel_calculator::mission::departure::{DepartureConfig, VInfinityTarget};
 use solar_travel_calculator::mission::interplanetary::{InterplanetaryConfig, plan_interplanetary};
 use solar_travel_calculator::mission::{MissionConfig, plan_mission};
 use solar_travel_calculator::transfer::vehicle;
 
-const MU_SUN: f64 = 1.327_124_400_18e11; // km^3 / s^2
 const AU_KM: f64 = 149_597_870.7; // km
 const SPEED_OF_LIGHT_KM_S: f64 = 299_792.458; // km/s
 
@@ -117,8 +117,9 @@ fn milestone_v02_impulsive_transfers() {
         origin_body: origin.spice_name.clone(),
         parking_altitude_km: origin.default_parking_altitude_km,
         departure_epoch: "2026 JAN 01 00:00:00 TDB".to_string(),
-        required_v_infinity: Some(3.2),
+        required_v_infinity: Some(VInfinityTarget::Magnitude(3.2)),
         propulsion_mode: vehicle.propulsion.clone(),
+        parking_inclination_deg: None,
     };
 
     let cruise = InterplanetaryConfig {
@@ -127,6 +128,8 @@ fn milestone_v02_impulsive_transfers() {
         departure_epoch: "2026 JAN 01 00:00:00 TDB".to_string(),
         arrival_epoch: Some("2026 OCT 01 00:00:00 TDB".to_string()),
         propulsion_mode: vehicle.propulsion.clone(),
+        warm_start_tof_days: None,
+        parking_altitudes_km: None,
     };
 
     let arrival = ArrivalConfig {
@@ -137,6 +140,7 @@ fn milestone_v02_impulsive_transfers() {
         aerobraking: Some(AerobrakingOption::Partial {
             periapsis_altitude_km: 80.0,
         }),
+        max_heat_flux_w_m2: None,
     };
 
     let mission = MissionConfig {
@@ -146,6 +150,7 @@ fn milestone_v02_impulsive_transfers() {
         departure,
         cruise,
         arrival,
+        constants: ConstantsSet::default(),
     };
 
     let profile = plan_mission(mission).expect("mission profile");
@@ -193,7 +198,7 @@ fn milestone_v02_impulsive_transfers() {
             "MARS_BARYCENTER" => "MARS BARYCENTER",
             other => panic!("unexpected body {other}"),
         };
-        let et = (jd - 2451545.0) * 86_400.0;
+        let et = ephemeris::et_from_jd_tdb(jd);
         let state =
             ephemeris::state_vector_et(target, "SOLAR SYSTEM BARYCENTER", "ECLIPJ2000", "NONE", et)
                 .expect("state vector from horizons epoch");
@@ -312,6 +317,11 @@ fn milestone_v02_impulsive_transfers() {
         "CLI did not report expected Hohmann line. Output:\n{}",
         stdout
     );
+    assert!(
+        stdout.contains("Hohmann vs plan: parking-orbit Δv = "),
+        "CLI did not compare the plan against Hohmann. Output:\n{}",
+        stdout
+    );
 
     let depart_grid: Vec<f64> = (0..3)
         .map(|i| depart_et + i as f64 * 30.0 * 86_400.0)
@@ -399,10 +409,18 @@ fn milestone_v03_continuous_low_thrust() {
         departure_epoch: "2026 JAN 01 00:00:00 TDB".to_string(),
         arrival_epoch: Some("2026 OCT 01 00:00:00 TDB".to_string()),
         propulsion_mode: vehicle.propulsion.clone(),
+        warm_start_tof_days: None,
+        parking_altitudes_km: None,
     };
 
-    let plan =
-        plan_interplanetary(&vehicle, &cruise, &origin, &destination).expect("continuous plan");
+    let plan = plan_interplanetary(
+        &vehicle,
+        &cruise,
+        &origin,
+        &destination,
+        &ConstantsSet::default(),
+    )
+    .expect("continuous plan");
     let prop_used = plan.propellant_used_kg.expect("propellant");
     assert!(prop_used >= 0.0);
     assert!(prop_used <= vehicle.propellant_mass_kg);
@@ -441,27 +459,298 @@ fn milestone_v03_continuous_low_thrust() {
 }
 
 #[test]
-#[ignore = "Milestone v0.4 pending"]
 fn milestone_v04_flyby_geometry() {
-    // TODO: replace mocked data with real flyby solver once milestone v0.4 lands.
+    use solar_travel_calculator::mission::flyby::solve_flyby;
+    use solar_travel_calculator::orbits::flyby_max_turn_angle;
+
+    let mu: f64 = 398_600.441_8;
+    let rp_min = 6_678.0;
+    // v∞² = μ / rp gives a 60° turn at the minimum periapsis.
+    let vinf = (mu / rp_min).sqrt();
+    let rotated = |deg: f64| {
+        let angle = deg.to_radians();
+        [vinf * angle.cos(), vinf * angle.sin(), 0.0]
+    };
+    let vinf_in = [vinf, 0.0, 0.0];
+
+    let reachable = solve_flyby(vinf_in, rotated(40.0), mu, rp_min).expect("reachable flyby");
+    assert!(reachable.feasible);
+    assert!((reachable.turn_angle_rad.to_degrees() - 40.0).abs() < 1e-9);
+    assert!(reachable.periapsis_radius_km > rp_min);
+    assert!(reachable.delta_v_deficit_km_s < 1e-9);
+    let turn_at_rp = flyby_max_turn_angle(vinf, mu, reachable.periapsis_radius_km);
+    assert!((turn_at_rp.to_degrees() - 40.0).abs() < 1e-9);
+
+    let too_sharp = solve_flyby(vinf_in, rotated(90.0), mu, rp_min).expect("sharp flyby");
+    assert!(!too_sharp.feasible);
+    assert_eq!(too_sharp.periapsis_radius_km, rp_min);
+    assert!((too_sharp.turn_angle_rad.to_degrees() - 60.0).abs() < 1e-9);
+    let expected_deficit = 2.0 * vinf * 15f64.to_radians().sin();
+    assert!((too_sharp.delta_v_deficit_km_s - expected_deficit).abs() < 1e-9);
+
+    let faster = [(vinf + 1.0) * 0.5, (vinf + 1.0) * 3f64.sqrt() / 2.0, 0.0];
+    let mismatched = solve_flyby(vinf_in, faster, mu, rp_min).expect("magnitude mismatch");
+    assert!(!mismatched.feasible);
+    assert!((mismatched.delta_v_deficit_km_s - 1.0).abs() < 1e-9);
+
+    let straight = solve_flyby(vinf_in, vinf_in, mu, rp_min).expect("no turn");
+    assert!(straight.feasible);
+    assert!(straight.periapsis_radius_km.is_infinite());
+    assert!(solve_flyby([0.0; 3], vinf_in, mu, rp_min).is_err());
 }
 
 #[test]
-#[ignore = "Milestone v0.5 pending"]
 fn milestone_v05_low_thrust_3d() {
-    // TODO: exercise adaptive RK 3D integrator once implemented.
+    use solar_travel_calculator::ephemeris::StateVector;
+    use solar_travel_calculator::mission::interplanetary::propagate_tangential;
+    use solar_travel_calculator::propulsion::{PropulsionMode, Vehicle};
+
+    let coasting = |max_thrust_newtons: f64| Vehicle {
+        name: "probe".to_string(),
+        dry_mass_kg: 1_000.0,
+        propellant_mass_kg: 500.0,
+        propulsion: PropulsionMode::Continuous {
+            max_thrust_newtons,
+            isp_seconds: 3_000.0,
+            max_acceleration_m_s2: None,
+            power_limited: false,
+            reference_distance_au: 1.0,
+        },
+        aero: None,
+        stages: Vec::new(),
+    };
+    // Slightly eccentric, inclined heliocentric orbit at 1 AU.
+    let v_circ = (MU_SUN / AU_KM).sqrt();
+    let initial = StateVector {
+        position_km: [AU_KM, 0.0, 0.0],
+        velocity_km_s: [0.5, 1.1 * v_circ * 0.9, 1.1 * v_circ * 0.1],
+        light_time_seconds: 0.0,
+    };
+    let tof = 150.0 * 86_400.0;
+
+    let ballistic =
+        propagate_tangential(&coasting(0.0), initial, MU_SUN, tof, true).expect("coast arc");
+    let energy = |r: &[f64; 3], v: &[f64; 3]| {
+        0.5 * (v[0] * v[0] + v[1] * v[1] + v[2] * v[2])
+            - MU_SUN / (r[0] * r[0] + r[1] * r[1] + r[2] * r[2]).sqrt()
+    };
+    let momentum = |r: &[f64; 3], v: &[f64; 3]| {
+        [
+            r[1] * v[2] - r[2] * v[1],
+            r[2] * v[0] - r[0] * v[2],
+            r[0] * v[1] - r[1] * v[0],
+        ]
+    };
+    let e0 = energy(&initial.position_km, &initial.velocity_km_s);
+    let h0 = momentum(&initial.position_km, &initial.velocity_km_s);
+    let h0_mag = (h0[0] * h0[0] + h0[1] * h0[1] + h0[2] * h0[2]).sqrt();
+    for sample in &ballistic {
+        assert!((energy(&sample.position_km, &sample.velocity_km_s) - e0).abs() < 1e-8 * e0.abs());
+        let h = momentum(&sample.position_km, &sample.velocity_km_s);
+        for axis in 0..3 {
+            assert!((h[axis] - h0[axis]).abs() < 1e-8 * h0_mag);
+        }
+        assert_eq!(sample.mass_kg, 1_500.0);
+    }
+
+    // The coast must coincide with the Lambert arc through its endpoints.
+    let last = ballistic.last().expect("final sample");
+    assert!((last.time_s - tof).abs() < 1e-6);
+    let (v1, v2) = lambert::solve(initial.position_km, last.position_km, tof, MU_SUN, true)
+        .expect("lambert arc");
+    for axis in 0..3 {
+        assert!((v1[axis] - initial.velocity_km_s[axis]).abs() < 1e-5);
+        assert!((v2[axis] - last.velocity_km_s[axis]).abs() < 1e-5);
+    }
+
+    // Prograde thrust raises the orbit and burns propellant at the rocket-equation rate.
+    let thrusting =
+        propagate_tangential(&coasting(0.5), initial, MU_SUN, tof, true).expect("thrust arc");
+    let end = thrusting.last().expect("final sample");
+    assert!(energy(&end.position_km, &end.velocity_km_s) > e0);
+    let expected_propellant = 0.5 / (3_000.0 * 9.806_65) * tof;
+    assert!((1_500.0 - end.mass_kg - expected_propellant).abs() < 1e-6 * expected_propellant);
 }
 
 #[test]
-#[ignore = "Milestone v0.6 pending"]
 fn milestone_v06_window_search() {
-    // TODO: validate optimizer window search outputs once milestone v0.6 lands.
+    use solar_travel_calculator::mission::porkchop::load_window_dataset;
+
+    let mut cmd = Command::cargo_bin("window").expect("window bin");
+    cmd.args([
+        "--from",
+        "EARTH",
+        "--to",
+        "MARS",
+        "--depart-start",
+        "2026-01-01T00:00:00",
+        "--min-tof",
+        "400",
+        "--max-tof",
+        "100",
+    ]);
+    cmd.assert().failure();
+
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let output_path = dir.path().join("earth_mars_window.json");
+    let mut cmd = Command::cargo_bin("window").expect("window bin");
+    cmd.args([
+        "--from",
+        "EARTH",
+        "--to",
+        "MARS",
+        "--depart-start",
+        "2026-01-01T00:00:00",
+        "--span-days",
+        "365",
+        "--step-days",
+        "10",
+        "--min-tof",
+        "120",
+        "--max-tof",
+        "400",
+        "--output",
+        output_path.to_str().unwrap(),
+    ]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let stdout = String::from_utf8(output).expect("utf8 stdout");
+    assert!(stdout.contains("Best departure"), "stdout:\n{stdout}");
+
+    let dataset = load_window_dataset(&output_path).expect("window dataset");
+    assert!(!dataset.samples.is_empty());
+    let best = dataset.baseline_sample().expect("best sample");
+    // The 2026 Earth–Mars opportunity opens in the autumn.
+    let window_open = ephemeris::epoch_seconds("2026-08-01T00:00:00").unwrap();
+    let window_close = ephemeris::epoch_seconds("2027-02-01T00:00:00").unwrap();
+    assert!(
+        (window_open..window_close).contains(&best.depart_et),
+        "best departure {} outside the 2026 opportunity",
+        best.depart_utc
+    );
+    assert!(best.dv_total_km_s < 8.0, "best Δv {}", best.dv_total_km_s);
 }
 
 #[test]
-#[ignore = "Milestone v0.7 pending"]
 fn milestone_v07_multi_leg() {
-    // TODO: test multi-leg itinerary solver once milestone v0.7 lands.
+    use solar_travel_calculator::core::constants::G0;
+    use solar_travel_calculator::mission::itinerary::{
+        ItineraryError, LegEpoch, LegSpec, leg_reports, plan_itinerary,
+    };
+    use solar_travel_calculator::propulsion::{PropulsionMode, Vehicle};
+
+    let planets = load_planets("configs/bodies").expect("planets catalog");
+    let find = |name: &str| planets.iter().find(|p| p.name == name).unwrap();
+    let (earth, venus) = (find("EARTH"), find("VENUS"));
+    let constants = ConstantsSet::default();
+    let tug = Vehicle {
+        name: "tug".to_string(),
+        dry_mass_kg: 1_000.0,
+        propellant_mass_kg: 1_000.0,
+        propulsion: PropulsionMode::Impulsive {
+            max_delta_v_km_s: 10.0,
+            isp_seconds: 450.0,
+            max_thrust_newtons: None,
+        },
+        aero: None,
+        stages: Vec::new(),
+    };
+
+    // Mass carries over between legs: the second leg runs the tug dry and the third gets nothing.
+    let reports = leg_reports(&tug, [3.0, 0.5, 2.0]);
+    assert_eq!(reports.len(), 3);
+    let after_first = tug.initial_mass_kg() * (-3_000.0 / (450.0 * G0)).exp();
+    assert!(reports[0].feasible);
+    assert!((reports[0].mass_after_kg - after_first).abs() < 1e-9);
+    assert!(!reports[1].feasible);
+    assert!((reports[1].mass_after_kg - tug.dry_mass_kg).abs() < 1e-9);
+    assert!(!reports[2].feasible);
+    assert_eq!(reports[2].propellant_used_kg, 0.0);
+    for pair in reports.windows(2) {
+        let expected = pair[0].mass_after_kg - pair[1].propellant_used_kg;
+        assert!((pair[1].mass_after_kg - expected).abs() < 1e-9);
+    }
+
+    let lone = [LegSpec {
+        body: earth,
+        epoch: LegEpoch::Fixed(0.0),
+        altitude_km: earth.default_parking_altitude_km,
+    }];
+    assert!(matches!(
+        plan_itinerary(&lone, &tug, &constants),
+        Err(ItineraryError::TooFewBodies(1))
+    ));
+
+    let _lock = guard().lock().unwrap_or_else(|e| e.into_inner());
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    let depart = ephemeris::epoch_seconds("2026 OCT 01 00:00:00 TDB").expect("epoch");
+    let day = 86_400.0;
+    let sequence = [
+        LegSpec {
+            body: earth,
+            epoch: LegEpoch::Fixed(depart),
+            altitude_km: earth.default_parking_altitude_km,
+        },
+        LegSpec {
+            body: venus,
+            epoch: LegEpoch::Window {
+                start_et: depart + 100.0 * day,
+                end_et: depart + 200.0 * day,
+                step_days: 10.0,
+            },
+            altitude_km: 300.0,
+        },
+        LegSpec {
+            body: earth,
+            epoch: LegEpoch::Window {
+                start_et: depart + 300.0 * day,
+                end_et: depart + 500.0 * day,
+                step_days: 10.0,
+            },
+            altitude_km: earth.default_parking_altitude_km,
+        },
+    ];
+    let itinerary = plan_itinerary(&sequence, &tug, &constants).expect("itinerary");
+
+    assert_eq!(itinerary.legs.len(), 2);
+    assert_eq!(itinerary.flybys.len(), 1);
+    assert_eq!(itinerary.legs[0].depart_et, depart);
+    assert_eq!(itinerary.legs[0].arrive_et, itinerary.legs[1].depart_et);
+    assert!(itinerary.legs.iter().all(|leg| leg.tof_days > 0.0));
+
+    let flyby = &itinerary.flybys[0];
+    assert_eq!(flyby.body, "VENUS");
+    assert!(flyby.geometry.periapsis_radius_km >= venus.radius_km + 300.0);
+    assert_eq!(
+        itinerary.is_feasible(),
+        itinerary.flybys.iter().all(|f| f.geometry.feasible)
+    );
+    let total = itinerary.departure_dv_km_s
+        + itinerary.arrival_dv_km_s
+        + flyby.geometry.delta_v_deficit_km_s;
+    assert!((itinerary.total_dv_km_s() - total).abs() < 1e-12);
+
+    assert_eq!(itinerary.reports.len(), 2);
+    let charged: f64 = itinerary.reports.iter().map(|r| r.delta_v_km_s).sum();
+    assert!((charged - itinerary.total_dv_km_s()).abs() < 1e-9);
+    assert_eq!(
+        itinerary.reports,
+        leg_reports(
+            &tug,
+            itinerary.reports.iter().map(|report| report.delta_v_km_s)
+        )
+    );
+    assert_eq!(
+        itinerary.first_infeasible_leg(),
+        itinerary.reports.iter().position(|report| !report.feasible)
+    );
 }
 
 #[test]
@@ -1,6 +1,6 @@
+use solar_travel_calculator::core::constants::MU_SUN_KM3_S2 as MU_SUN;
 use solar_travel_calculator::impulsive::lambert;
 
-const MU_SUN: f64 = 1.327_124_400_18e11; // km^3 / s^2
 const AU_KM: f64 = 149_597_870.7; // km
 
 #[test]
@@ -32,3 +32,145 @@ fn lambert_quarter_orbit_matches_expected_velocity() {
 fn vector_dot(a: &[f64; 3], b: &[f64; 3]) -> f64 {
     a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
 }
+
+/// Position and velocity on a heliocentric ellipse with periapsis on +x, in the ecliptic plane.
+fn ellipse_state(a: f64, e: f64, true_anomaly: f64) -> ([f64; 3], [f64; 3]) {
+    let p = a * (1.0 - e * e);
+    let r = p / (1.0 + e * true_anomaly.cos());
+    let h = (MU_SUN * p).sqrt();
+    let (s, c) = true_anomaly.sin_cos();
+    (
+        [r * c, r * s, 0.0],
+        [-MU_SUN / h * s, MU_SUN / h * (e + c), 0.0],
+    )
+}
+
+/// Time from periapsis to `true_anomaly` on the ellipse.
+fn time_since_periapsis(a: f64, e: f64, true_anomaly: f64) -> f64 {
+    let ecc_anomaly = 2.0 * (((1.0 - e) / (1.0 + e)).sqrt() * (true_anomaly / 2.0).tan()).atan();
+    let mean_anomaly = ecc_anomaly - e * ecc_anomaly.sin();
+    mean_anomaly.rem_euclid(std::f64::consts::TAU) * (a.powi(3) / MU_SUN).sqrt()
+}
+
+#[test]
+fn multirev_recovers_earth_mars_ellipse_after_one_revolution() {
+    // Hohmann-like ellipse touching 1 AU and 1.524 AU, flown for one full orbit plus 150°.
+    let rp = AU_KM;
+    let ra = 1.524 * AU_KM;
+    let a = 0.5 * (rp + ra);
+    let e = (ra - rp) / (ra + rp);
+    let nu2 = 150f64.to_radians();
+    let period = std::f64::consts::TAU * (a.powi(3) / MU_SUN).sqrt();
+    let tof = period + time_since_periapsis(a, e, nu2);
+
+    let (r1, v1_true) = ellipse_state(a, e, 0.0);
+    let (r2, v2_true) = ellipse_state(a, e, nu2);
+
+    let matches: Vec<bool> = [true, false]
+        .into_iter()
+        .map(|left| {
+            let (v1, v2) =
+                lambert::solve_multirev(r1, r2, tof, MU_SUN, 1, left).expect("1-rev solution");
+            let err = |x: [f64; 3], y: [f64; 3]| {
+                ((x[0] - y[0]).powi(2) + (x[1] - y[1]).powi(2) + (x[2] - y[2]).powi(2)).sqrt()
+            };
+            err(v1, v1_true) < 1e-6 && err(v2, v2_true) < 1e-6
+        })
+        .collect();
+    assert_eq!(
+        matches.iter().filter(|m| **m).count(),
+        1,
+        "exactly one branch should reproduce the reference orbit: {matches:?}"
+    );
+
+    // Too short for two revolutions of anything reaching Mars's distance.
+    assert!(lambert::solve_multirev(r1, r2, 0.5 * tof, MU_SUN, 2, true).is_err());
+}
+
+#[test]
+fn all_branches_include_zero_and_multi_revolution_solutions() {
+    let r1 = [AU_KM, 0.0, 0.0];
+    let r2 = [0.0, 1.524 * AU_KM, 0.0];
+    let tof = 3.0 * 365.25 * 86_400.0;
+
+    let solutions = lambert::solve_all_branches(r1, r2, tof, MU_SUN, 3);
+    let has = |revolutions: u32, branch: lambert::Branch| {
+        solutions
+            .iter()
+            .any(|s| s.revolutions == revolutions && s.branch == branch)
+    };
+    assert!(has(0, lambert::Branch::Short) && has(0, lambert::Branch::Long));
+    assert!(has(1, lambert::Branch::Left) && has(1, lambert::Branch::Right));
+    assert!(solutions.iter().all(|s| s.revolutions <= 3));
+    for solution in &solutions {
+        let v1 = solution.v1_km_s;
+        let energy = vector_dot(&v1, &v1) / 2.0 - MU_SUN / AU_KM;
+        assert!(energy.is_finite(), "{solution:?}");
+    }
+}
+
+#[test]
+fn zero_rev_recovers_short_and_long_way_arcs_on_an_ellipse() {
+    let rp = AU_KM;
+    let ra = 1.524 * AU_KM;
+    let a = 0.5 * (rp + ra);
+    let e = (ra - rp) / (ra + rp);
+    let (r1, v1_true) = ellipse_state(a, e, 0.0);
+
+    for (nu2_deg, short) in [(150.0, true), (250.0, false)] {
+        let nu2 = f64::to_radians(nu2_deg);
+        let (r2, v2_true) = ellipse_state(a, e, nu2);
+        let tof = time_since_periapsis(a, e, nu2);
+        let solution = lambert::solve_with_diagnostics(
+            r1,
+            r2,
+            tof,
+            MU_SUN,
+            short,
+            lambert::DEFAULT_TOLERANCE,
+            lambert::DEFAULT_MAX_ITERATIONS,
+        )
+        .expect("zero-rev solution");
+        assert!(solution.iterations > 0 && solution.iterations <= lambert::DEFAULT_MAX_ITERATIONS);
+        for i in 0..3 {
+            assert!(
+                (solution.v1_km_s[i] - v1_true[i]).abs() < 1e-5,
+                "{nu2_deg}°: {solution:?}"
+            );
+            assert!(
+                (solution.v2_km_s[i] - v2_true[i]).abs() < 1e-5,
+                "{nu2_deg}°: {solution:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn solver_failures_are_classified() {
+    let r1 = [AU_KM, 0.0, 0.0];
+    let r2 = [0.0, 1.524 * AU_KM, 0.0];
+    let tof = 200.0 * 86_400.0;
+
+    let capped = lambert::solve_with_diagnostics(r1, r2, tof, MU_SUN, true, 1e-12, 3)
+        .expect_err("three iterations cannot converge");
+    match capped {
+        lambert::LambertSolverError::MaxIterations { achieved_residual } => {
+            assert!(achieved_residual.is_finite() && achieved_residual.abs() > 1e-12)
+        }
+        other => panic!("expected MaxIterations, got {other:?}"),
+    }
+
+    let opposite = [-AU_KM, 0.0, 0.0];
+    assert!(matches!(
+        lambert::solve(r1, opposite, tof, MU_SUN, true),
+        Err(lambert::LambertSolverError::Degenerate(_))
+    ));
+    assert!(matches!(
+        lambert::solve(r1, r2, -1.0, MU_SUN, true),
+        Err(lambert::LambertSolverError::Degenerate(_))
+    ));
+    assert!(matches!(
+        lambert::solve_multirev(r1, r2, tof, MU_SUN, 3, true),
+        Err(lambert::LambertSolverError::BelowMinimumTime { revolutions: 3, .. })
+    ));
+}
@@ -0,0 +1,219 @@
+use solar_travel_calculator::core::constants::G0;
+use solar_travel_calculator::lowthrust::{
+    ConstantAccelInputs, ThrottledInputs, constant_accel_profile, constant_accel_profile_adaptive,
+    throttled_profile,
+};
+use solar_travel_calculator::propulsion::PropulsionMode;
+
+#[test]
+fn constant_accel_profile_reports_mass_ratio_and_payload_fraction() {
+    let inputs = ConstantAccelInputs {
+        acceleration_m_s2: 0.01,
+        isp_seconds: 3_000.0,
+        initial_mass_kg: 10_000.0,
+        dry_mass_kg: 4_000.0,
+    };
+    let summary = constant_accel_profile(&inputs, 30.0 * 86_400.0).expect("feasible profile");
+
+    let expected_ratio = inputs.initial_mass_kg / summary.final_mass_kg;
+    assert!((summary.mass_ratio - expected_ratio).abs() < 1e-12);
+    assert!(summary.mass_ratio > 1.0);
+    assert!((summary.payload_fraction - 0.4).abs() < 1e-12);
+}
+
+#[test]
+fn adaptive_profile_hits_midpoint_and_meets_tolerance() {
+    let inputs = ConstantAccelInputs {
+        acceleration_m_s2: 0.01,
+        isp_seconds: 3_000.0,
+        initial_mass_kg: 10_000.0,
+        dry_mass_kg: 4_000.0,
+    };
+    let total = 30.0 * 86_400.0;
+    let fixed = constant_accel_profile(&inputs, total).expect("fixed profile");
+    let coarse = constant_accel_profile_adaptive(&inputs, total, 1e-3).expect("coarse profile");
+    let fine = constant_accel_profile_adaptive(&inputs, total, 1e-6).expect("fine profile");
+
+    assert!(coarse.samples.len() < fixed.samples.len());
+    assert!(fine.samples.len() > coarse.samples.len());
+    assert!((coarse.total_distance_m - fixed.total_distance_m).abs() < 1e-6);
+
+    for summary in [&coarse, &fine] {
+        let samples = &summary.samples;
+        assert!(samples.windows(2).all(|w| w[1].time_s > w[0].time_s));
+        assert!(samples.iter().any(|s| s.time_s == 0.5 * total));
+        let last = samples.last().expect("endpoint");
+        assert_eq!(last.time_s, total);
+        assert_eq!(last.velocity_m_s, 0.0);
+    }
+
+    // Linear interpolation between adjacent samples tracks the analytic distance.
+    let a = inputs.acceleration_m_s2;
+    let exact_distance = |t: f64| {
+        let half = 0.5 * total;
+        if t <= half {
+            0.5 * a * t * t
+        } else {
+            let tau = t - half;
+            0.5 * a * half * half + a * half * tau - 0.5 * a * tau * tau
+        }
+    };
+    for w in fine.samples.windows(2) {
+        let mid = 0.5 * (w[0].time_s + w[1].time_s);
+        let interpolated = 0.5 * (w[0].distance_m + w[1].distance_m);
+        assert!((interpolated - exact_distance(mid)).abs() <= 1e-6 * fine.total_distance_m * 1.01);
+    }
+
+    assert!(constant_accel_profile_adaptive(&inputs, total, 0.0).is_none());
+    assert!(constant_accel_profile_adaptive(&inputs, total, f64::NAN).is_none());
+}
+
+#[test]
+fn exhaust_velocity_is_isp_times_g0_for_propulsive_modes() {
+    let ion = PropulsionMode::Continuous {
+        max_thrust_newtons: 0.5,
+        isp_seconds: 3_000.0,
+        max_acceleration_m_s2: None,
+        power_limited: false,
+        reference_distance_au: 1.0,
+    };
+    let chemical = PropulsionMode::Impulsive {
+        max_delta_v_km_s: 6.0,
+        isp_seconds: 380.0,
+        max_thrust_newtons: None,
+    };
+
+    assert_eq!(ion.exhaust_velocity_m_s(), Some(3_000.0 * G0));
+    assert_eq!(chemical.exhaust_velocity_m_s(), Some(380.0 * G0));
+    assert_eq!(PropulsionMode::Hybrid.exhaust_velocity_m_s(), None);
+}
+
+#[test]
+fn power_limited_thrust_falls_off_beyond_reference_distance() {
+    let solar_electric = PropulsionMode::Continuous {
+        max_thrust_newtons: 2.0,
+        isp_seconds: 3_000.0,
+        max_acceleration_m_s2: None,
+        power_limited: true,
+        reference_distance_au: 1.0,
+    };
+
+    assert_eq!(solar_electric.available_thrust_newtons(0.7), Some(2.0));
+    assert_eq!(solar_electric.available_thrust_newtons(1.0), Some(2.0));
+    let at_jupiter = solar_electric
+        .available_thrust_newtons(5.2)
+        .expect("continuous thrust");
+    assert!((at_jupiter - 2.0 / (5.2 * 5.2)).abs() < 1e-12);
+
+    let unlimited = PropulsionMode::Continuous {
+        max_thrust_newtons: 2.0,
+        isp_seconds: 3_000.0,
+        max_acceleration_m_s2: None,
+        power_limited: false,
+        reference_distance_au: 1.0,
+    };
+    assert_eq!(unlimited.available_thrust_newtons(5.2), Some(2.0));
+}
+
+#[test]
+fn telemetry_decimation_keeps_endpoints_and_peak_velocity() {
+    use solar_travel_calculator::export::continuous::{Sample, decimate_samples};
+
+    let samples: Vec<Sample> = (0..1_000)
+        .map(|i| Sample {
+            time_s: i as f64 * 3_600.0,
+            distance_m: i as f64 * 1.0e6,
+            velocity_m_s: if i == 517 { 9_999.0 } else { i as f64 },
+            mass_kg: 1_000.0 - i as f64 * 0.1,
+        })
+        .collect();
+
+    let thinned = decimate_samples(&samples, 50);
+    assert!(thinned.len() <= 50);
+    assert!(thinned.len() >= 45);
+    assert_eq!(thinned.first().unwrap().time_s, 0.0);
+    assert_eq!(thinned.last().unwrap().time_s, 999.0 * 3_600.0);
+    assert!(thinned.iter().any(|s| s.velocity_m_s == 9_999.0));
+    assert!(thinned.windows(2).all(|w| w[0].time_s < w[1].time_s));
+
+    assert_eq!(decimate_samples(&samples, 1).len(), 3);
+    assert_eq!(decimate_samples(&samples[..10], 50).len(), 10);
+}
+
+fn throttled(throttle_profile: Vec<(f64, f64)>) -> ThrottledInputs {
+    ThrottledInputs {
+        max_thrust_newtons: 50.0,
+        isp_seconds: 3_000.0,
+        initial_mass_kg: 10_000.0,
+        dry_mass_kg: 4_000.0,
+        throttle_profile,
+    }
+}
+
+#[test]
+fn throttled_profile_at_full_throttle_follows_the_rocket_equation() {
+    let inputs = throttled(vec![(0.0, 1.0)]);
+    let total = 30.0 * 86_400.0;
+    let summary = throttled_profile(&inputs, total).expect("feasible profile");
+
+    let ve = 3_000.0 * G0;
+    let final_mass = 10_000.0 - 50.0 / ve * total;
+    assert!((summary.final_mass_kg - final_mass).abs() < 1e-6);
+    assert_eq!(summary.burn_time_total_s, total);
+    let dv = ve * (10_000.0 / final_mass).ln();
+    assert!((summary.max_velocity_m_s - dv).abs() < 1e-6);
+    assert!((summary.dv_total_km_s - dv / 1_000.0).abs() < 1e-9);
+
+    // Overdrawn tanks and out-of-range throttles are rejected.
+    assert!(throttled_profile(&inputs, 1_000.0 * 86_400.0).is_none());
+    assert!(throttled_profile(&throttled(vec![(0.0, 1.5)]), total).is_none());
+    assert!(throttled_profile(&throttled(vec![(0.5, 1.0), (0.2, -1.0)]), total).is_none());
+}
+
+#[test]
+fn bang_coast_bang_schedule_coasts_between_burns() {
+    let total = 30.0 * 86_400.0;
+    let coast = throttled_profile(
+        &throttled(vec![(0.0, 1.0), (0.25, 0.0), (0.75, -1.0)]),
+        total,
+    )
+    .expect("bang-coast-bang profile");
+    let bang_bang = throttled_profile(&throttled(vec![(0.0, 1.0), (0.5, -1.0)]), total)
+        .expect("bang-bang profile");
+
+    assert!((coast.burn_time_total_s - 0.5 * total).abs() < 1e-6);
+    assert!(coast.propellant_used_kg < bang_bang.propellant_used_kg);
+
+    let at = |time: f64| {
+        coast
+            .samples
+            .iter()
+            .find(|sample| sample.time_s == time)
+            .expect("breakpoint sample")
+    };
+    let coast_start = at(0.25 * total);
+    let coast_end = at(0.75 * total);
+    assert_eq!(coast_start.mass_kg, coast_end.mass_kg);
+    assert_eq!(coast_start.velocity_m_s, coast_end.velocity_m_s);
+    let coasted = coast_end.distance_m - coast_start.distance_m;
+    assert!((coasted - coast_start.velocity_m_s * 0.5 * total).abs() < 1e-3);
+    assert!((coast.max_velocity_m_s - coast_start.velocity_m_s).abs() < 1e-9);
+
+    // The lighter vehicle brakes harder, so it ends retrograde by the rocket-equation difference.
+    let ve = 3_000.0 * G0;
+    let last = coast.samples.last().expect("endpoint");
+    let expected = coast_start.velocity_m_s - ve * (coast_end.mass_kg / last.mass_kg).ln();
+    assert!(expected < 0.0);
+    assert!((last.velocity_m_s - expected).abs() < 1e-6);
+}
+
+#[test]
+fn throttled_mode_reports_isp_and_full_thrust() {
+    let mode = PropulsionMode::Throttled {
+        max_thrust_newtons: 100.0,
+        isp_seconds: 3_000.0,
+        throttle_profile: vec![(0.0, 1.0), (0.5, -1.0)],
+    };
+    assert_eq!(mode.exhaust_velocity_m_s(), Some(3_000.0 * G0));
+    assert_eq!(mode.available_thrust_newtons(5.0), Some(100.0));
+}
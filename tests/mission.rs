@@ -1,12 +1,23 @@
 use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
 
-use solar_travel_calculator::config::{load_planets, load_vehicle_configs};
-use solar_travel_calculator::ephemeris::EphemerisError;
+use solar_travel_calculator::config::{
+    ConstantsSet, PlanetConfig, load_planets, load_vehicle_configs,
+};
+use solar_travel_calculator::ephemeris::{EphemerisError, epoch_seconds};
 use solar_travel_calculator::mission::arrival::{AerobrakingOption, ArrivalConfig};
-use solar_travel_calculator::mission::departure::DepartureConfig;
+use solar_travel_calculator::mission::departure::{DepartureConfig, VInfinityTarget};
 use solar_travel_calculator::mission::interplanetary::InterplanetaryConfig;
-use solar_travel_calculator::mission::{MissionConfig, plan_mission};
-use solar_travel_calculator::transfer::vehicle;
+use solar_travel_calculator::mission::porkchop::{
+    PorkchopRequest, TimeWindow, TransferEndpoint, WINDOW_DATASET_VERSION, WindowDataset,
+    WindowError, WindowSample, c3_statistics, generate, generate_parallel,
+};
+use solar_travel_calculator::mission::synodic::{next_launch_window, synodic_period};
+use solar_travel_calculator::mission::{
+    MissionConfig, fixed_tof_windows, plan_mission, plan_mission_timed,
+};
+use solar_travel_calculator::orbits::{c3_from_vinf, specific_energy_from_vinf, vinf_from_c3};
+use solar_travel_calculator::transfer::{PropulsionMode, Vehicle, vehicle};
 
 fn guard() -> &'static Mutex<()> {
     static GUARD: OnceLock<Mutex<()>> = OnceLock::new();
@@ -27,6 +38,82 @@ fn ensure_kernels_or_skip() -> Option<()> {
     }
 }
 
+/// Catalog inputs shared by the kernel-backed Earth→Mars porkchop tests.
+struct EarthMarsGrid {
+    planets: Vec<PlanetConfig>,
+    vehicle: Vehicle,
+    constants: ConstantsSet,
+}
+
+impl EarthMarsGrid {
+    fn load() -> Self {
+        let planets = load_planets("configs/bodies").expect("planets catalog");
+        let vehicle = load_vehicle_configs("configs/vehicles")
+            .expect("vehicles catalog")
+            .iter()
+            .map(|cfg| vehicle::from_config(cfg).expect("convert vehicle"))
+            .find(|v| matches!(v.propulsion, PropulsionMode::Impulsive { .. }))
+            .expect("impulsive vehicle");
+        Self {
+            planets,
+            vehicle,
+            constants: ConstantsSet::default(),
+        }
+    }
+
+    fn body(&self, name: &str) -> &PlanetConfig {
+        self.planets.iter().find(|p| p.name == name).unwrap()
+    }
+
+    /// `name` at its default parking orbit.
+    fn endpoint(&self, name: &str) -> TransferEndpoint<'_> {
+        let body = self.body(name);
+        TransferEndpoint {
+            body,
+            parent: None,
+            rpark_km: body.radius_km + body.default_parking_altitude_km,
+        }
+    }
+
+    /// Direct transfer between default parking orbits that ignores vehicle limits and keeps
+    /// every cell; tests override only the fields they exercise.
+    fn request(
+        &self,
+        departure_window: TimeWindow,
+        arrival_window: TimeWindow,
+    ) -> PorkchopRequest<'_> {
+        let origin = self.body("EARTH");
+        let destination = self.body("MARS");
+        PorkchopRequest {
+            origin_body: origin,
+            origin_parent: None,
+            destination_body: destination,
+            destination_parent: None,
+            vehicle: &self.vehicle,
+            rpark_depart_km: origin.radius_km + origin.default_parking_altitude_km,
+            rpark_arrive_km: destination.radius_km + destination.default_parking_altitude_km,
+            departure_window,
+            arrival_window,
+            long_path_only: false,
+            ignore_vehicle_limits: true,
+            include_infeasible: true,
+            constants: &self.constants,
+            state_cache_dir: None,
+            max_revolutions: 0,
+            plane_change_penalty: false,
+        }
+    }
+}
+
+/// Window from `start_days` to `end_days` after `epoch_et`, sampled every `step_days`.
+fn days_window(epoch_et: f64, start_days: f64, end_days: f64, step_days: f64) -> TimeWindow {
+    TimeWindow {
+        start_et: epoch_et + start_days * 86_400.0,
+        end_et: epoch_et + end_days * 86_400.0,
+        step_seconds: step_days * 86_400.0,
+    }
+}
+
 #[test]
 fn stub_mission_planner_runs() {
     let _lock = guard().lock().unwrap();
@@ -53,8 +140,9 @@ fn stub_mission_planner_runs() {
         origin_body: origin.spice_name.clone(),
         parking_altitude_km: origin.default_parking_altitude_km,
         departure_epoch: "2025 OCT 14 23:28:58 TDB".to_string(),
-        required_v_infinity: Some(3.2),
+        required_v_infinity: Some(VInfinityTarget::Magnitude(3.2)),
         propulsion_mode: propulsion_mode.clone(),
+        parking_inclination_deg: None,
     };
 
     let cruise = InterplanetaryConfig {
@@ -63,6 +151,8 @@ fn stub_mission_planner_runs() {
         departure_epoch: "2025 OCT 14 23:28:58 TDB".to_string(),
         arrival_epoch: Some("2026 APR 12 23:28:58 TDB".to_string()),
         propulsion_mode: propulsion_mode.clone(),
+        warm_start_tof_days: None,
+        parking_altitudes_km: None,
     };
 
     let arrival = ArrivalConfig {
@@ -73,6 +163,7 @@ fn stub_mission_planner_runs() {
         aerobraking: Some(AerobrakingOption::Partial {
             periapsis_altitude_km: 80.0,
         }),
+        max_heat_flux_w_m2: None,
     };
 
     let profile = plan_mission(MissionConfig {
@@ -82,6 +173,7 @@ fn stub_mission_planner_runs() {
         departure,
         cruise,
         arrival,
+        constants: ConstantsSet::default(),
     })
     .expect("mission planner should return placeholder results");
 
@@ -89,3 +181,564 @@ fn stub_mission_planner_runs() {
     assert!(profile.cruise.time_of_flight_days > 0.0);
     assert!(profile.arrival.delta_v_required >= 0.0);
 }
+
+#[test]
+fn fixed_tof_windows_hold_transfer_time() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    let grid = EarthMarsGrid::load();
+    let start_et = epoch_seconds("2026 SEP 01 00:00:00 TDB").expect("epoch");
+    let departure_window = days_window(start_et, 0.0, 120.0, 10.0);
+    let (earth, mars) = (grid.endpoint("EARTH"), grid.endpoint("MARS"));
+
+    for tof_days in [0.0, -30.0, f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            fixed_tof_windows(earth, mars, tof_days, &departure_window, 8.0),
+            Err(WindowError::InvalidTimeOfFlight(_))
+        ));
+    }
+
+    let max_dv = 8.0;
+    let samples =
+        fixed_tof_windows(earth, mars, 210.0, &departure_window, max_dv).expect("fixed-TOF sweep");
+    assert!(
+        !samples.is_empty(),
+        "2026 Earth-Mars window should admit 210-day transfers under {max_dv} km/s"
+    );
+    for sample in &samples {
+        assert!((sample.tof_days() - 210.0).abs() < 1e-6);
+        assert!(sample.dv_total_km_s <= max_dv);
+    }
+}
+
+#[test]
+fn c3_statistics_respects_delta_v_threshold() {
+    let sample = |dv: f64, c3: f64| WindowSample {
+        depart_et: 0.0,
+        depart_utc: String::new(),
+        arrive_et: 200.0 * 86_400.0,
+        arrive_utc: String::new(),
+        dv_total_km_s: dv,
+        dv_depart_km_s: dv * 0.6,
+        dv_arrive_km_s: dv * 0.4,
+        c3_km2_s2: c3,
+    };
+    let dataset = WindowDataset {
+        version: WINDOW_DATASET_VERSION,
+        origin_spice: "EARTH".to_string(),
+        destination_spice: "MARS BARYCENTER".to_string(),
+        depart_start_et: 0.0,
+        depart_end_et: 0.0,
+        step_days: 1.0,
+        min_tof_days: 100.0,
+        max_tof_days: 300.0,
+        vehicle: "Test Stage".to_string(),
+        rpark_depart_km: 6_578.0,
+        rpark_arrive_km: 3_690.0,
+        constants_fingerprint: ConstantsSet::default().fingerprint(),
+        min_dv_total_km_s: Some(5.6),
+        samples: vec![
+            sample(5.6, 9.0),
+            sample(5.9, 12.0),
+            sample(6.1, 15.0),
+            sample(6.4, 20.0),
+            sample(9.0, 60.0),
+        ],
+        pareto: Vec::new(),
+    };
+
+    let stats = c3_statistics(&dataset, 6.5).expect("samples under threshold");
+    assert_eq!(stats.sample_count, 4);
+    assert_eq!(stats.min_km2_s2, 9.0);
+    assert_eq!(stats.median_km2_s2, 13.5);
+    assert_eq!(stats.max_km2_s2, 20.0);
+
+    assert!(c3_statistics(&dataset, 5.0).is_none());
+}
+
+#[test]
+fn timed_mission_planner_reports_phase_breakdown() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    let planets = load_planets("configs/bodies").expect("planets catalog");
+    let vehicles_cfg = load_vehicle_configs("configs/vehicles").expect("vehicles catalog");
+    let chemical = vehicles_cfg
+        .iter()
+        .map(|cfg| vehicle::from_config(cfg).expect("convert vehicle"))
+        .find(|v| matches!(v.propulsion, PropulsionMode::Impulsive { .. }))
+        .expect("impulsive vehicle");
+    let origin = planets.iter().find(|p| p.name == "EARTH").unwrap().clone();
+    let destination = planets.iter().find(|p| p.name == "MARS").unwrap().clone();
+    let propulsion_mode = chemical.propulsion.clone();
+
+    let config = MissionConfig {
+        vehicle: chemical,
+        departure: DepartureConfig {
+            origin_body: origin.spice_name.clone(),
+            parking_altitude_km: origin.default_parking_altitude_km,
+            departure_epoch: "2026 NOV 01 00:00:00 TDB".to_string(),
+            required_v_infinity: None,
+            propulsion_mode: propulsion_mode.clone(),
+            parking_inclination_deg: None,
+        },
+        cruise: InterplanetaryConfig {
+            departure_body: origin.spice_name.clone(),
+            destination_body: destination.spice_name.clone(),
+            departure_epoch: "2026 NOV 01 00:00:00 TDB".to_string(),
+            arrival_epoch: None,
+            propulsion_mode: propulsion_mode.clone(),
+            warm_start_tof_days: None,
+            parking_altitudes_km: None,
+        },
+        arrival: ArrivalConfig {
+            destination_body: destination.spice_name.clone(),
+            target_parking_altitude_km: destination.default_parking_altitude_km,
+            encounter_epoch: "2027 JUN 01 00:00:00 TDB".to_string(),
+            propulsion_mode,
+            aerobraking: None,
+            max_heat_flux_w_m2: None,
+        },
+        origin,
+        destination,
+        constants: ConstantsSet::default(),
+    };
+
+    let (profile, timings) = plan_mission_timed(config);
+    profile.expect("timed mission planner should succeed");
+
+    assert!(timings.cruise_spice > Duration::ZERO);
+    assert_eq!(timings.cruise_spice + timings.cruise_math, timings.cruise);
+    assert!(timings.departure + timings.cruise + timings.arrival <= timings.total);
+}
+
+#[test]
+fn c3_and_vinf_conversions_round_trip() {
+    for vinf in [0.0, 2.9, 3.6, 11.2] {
+        let c3 = c3_from_vinf(vinf);
+        assert!((vinf_from_c3(c3) - vinf).abs() < 1e-12);
+        assert!((specific_energy_from_vinf(vinf) - 0.5 * c3).abs() < 1e-12);
+    }
+    assert_eq!(vinf_from_c3(-4.0), 0.0);
+}
+
+#[test]
+fn porkchop_points_report_consistent_c3_and_vinf() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    let grid = EarthMarsGrid::load();
+    let depart_et = epoch_seconds("2026 OCT 01 00:00:00 TDB").expect("epoch");
+    let request = grid.request(
+        days_window(depart_et, 0.0, 40.0, 20.0),
+        days_window(depart_et, 180.0, 260.0, 40.0),
+    );
+
+    let points = generate(&request).expect("porkchop grid");
+    assert!(!points.is_empty());
+    for point in points.iter().filter(|p| p.vinf_depart_km_s.is_finite()) {
+        let expected = c3_from_vinf(point.vinf_depart_km_s);
+        assert!((point.c3_km2_s2 - expected).abs() <= 1e-9 * expected.max(1.0));
+    }
+}
+
+#[test]
+fn plane_change_penalty_tracks_relative_inclination() {
+    use solar_travel_calculator::ephemeris::StateVector;
+    use solar_travel_calculator::mission::porkchop::relative_inclination_rad;
+
+    let state = |incl_deg: f64| {
+        let i = incl_deg.to_radians();
+        StateVector {
+            position_km: [1.0e8, 0.0, 0.0],
+            velocity_km_s: [0.0, 30.0 * i.cos(), 30.0 * i.sin()],
+            light_time_seconds: 0.0,
+        }
+    };
+    let incl = relative_inclination_rad(&state(0.0), &state(17.0));
+    assert!((incl.to_degrees() - 17.0).abs() < 1e-9);
+    assert_eq!(relative_inclination_rad(&state(5.0), &state(5.0)), 0.0);
+
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    let grid = EarthMarsGrid::load();
+    let depart_et = epoch_seconds("2026 OCT 01 00:00:00 TDB").expect("epoch");
+    let coplanar = PorkchopRequest {
+        include_infeasible: false,
+        ..grid.request(
+            days_window(depart_et, 0.0, 40.0, 20.0),
+            days_window(depart_et, 180.0, 260.0, 40.0),
+        )
+    };
+    let penalised = PorkchopRequest {
+        plane_change_penalty: true,
+        ..coplanar.clone()
+    };
+
+    let flat = generate(&coplanar).expect("coplanar grid");
+    let inclined = generate(&penalised).expect("penalised grid");
+    assert_eq!(flat.len(), inclined.len());
+    for (a, b) in flat.iter().zip(&inclined) {
+        assert_eq!(a.relative_inclination_deg, b.relative_inclination_deg);
+        // Mars' orbit sits within a few degrees of Earth's.
+        assert!(a.relative_inclination_deg > 0.0 && a.relative_inclination_deg < 5.0);
+        assert_eq!(a.dv_depart_km_s, b.dv_depart_km_s);
+        assert!(b.dv_arrive_km_s >= a.dv_arrive_km_s);
+    }
+}
+
+#[test]
+fn parallel_porkchop_matches_serial_grid() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    let grid = EarthMarsGrid::load();
+    let depart_et = epoch_seconds("2026 OCT 01 00:00:00 TDB").expect("epoch");
+    let request = grid.request(
+        days_window(depart_et, 0.0, 90.0, 10.0),
+        days_window(depart_et, 150.0, 330.0, 20.0),
+    );
+
+    let serial = generate(&request).expect("serial grid");
+    let parallel = generate_parallel(&request).expect("parallel grid");
+    assert_eq!(serial.len(), parallel.len());
+    for (s, p) in serial.iter().zip(&parallel) {
+        assert_eq!((s.depart_et, s.arrive_et), (p.depart_et, p.arrive_et));
+        assert_eq!(s.dv_total_km_s.to_bits(), p.dv_total_km_s.to_bits());
+        assert_eq!(s.lambert_path, p.lambert_path);
+    }
+}
+
+#[test]
+fn multirev_branches_never_worsen_porkchop_cells() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    let grid = EarthMarsGrid::load();
+    let depart_et = epoch_seconds("2026 OCT 01 00:00:00 TDB").expect("epoch");
+    let direct = grid.request(
+        days_window(depart_et, 0.0, 40.0, 20.0),
+        // Long enough for one full heliocentric revolution before arrival.
+        days_window(depart_et, 700.0, 900.0, 50.0),
+    );
+    let multirev = PorkchopRequest {
+        max_revolutions: 1,
+        ..direct.clone()
+    };
+
+    let direct_points = generate(&direct).expect("direct grid");
+    let multirev_points = generate(&multirev).expect("multi-rev grid");
+    assert_eq!(direct_points.len(), multirev_points.len());
+    for (d, m) in direct_points.iter().zip(&multirev_points) {
+        if d.feasible {
+            assert!(m.dv_total_km_s <= d.dv_total_km_s + 1e-9);
+        }
+    }
+}
+
+#[test]
+fn feasible_only_requests_drop_infeasible_cells() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    let grid = EarthMarsGrid::load();
+    let depart_et = epoch_seconds("2026 OCT 01 00:00:00 TDB").expect("epoch");
+    let full_request = PorkchopRequest {
+        ignore_vehicle_limits: false,
+        ..grid.request(
+            days_window(depart_et, 0.0, 60.0, 20.0),
+            days_window(depart_et, 30.0, 330.0, 60.0),
+        )
+    };
+    let compact_request = PorkchopRequest {
+        include_infeasible: false,
+        ..full_request.clone()
+    };
+
+    let full = generate(&full_request).expect("full grid");
+    let compact = generate(&compact_request).expect("feasible-only grid");
+
+    assert!(compact.iter().all(|p| p.feasible));
+    assert_eq!(compact.len(), full.iter().filter(|p| p.feasible).count());
+}
+
+#[test]
+fn time_window_epochs_are_endpoint_inclusive_and_counted() {
+    let day = 86_400.0;
+    let even = TimeWindow {
+        start_et: 1_000.0,
+        end_et: 1_000.0 + 60.0 * day,
+        step_seconds: 20.0 * day,
+    };
+    let epochs: Vec<f64> = even.epochs().collect();
+    assert_eq!(even.len(), 4);
+    assert_eq!(epochs.len(), even.len());
+    assert_eq!(epochs.last().copied(), Some(even.end_et));
+
+    let uneven = TimeWindow {
+        end_et: 1_000.0 + 70.0 * day,
+        ..even.clone()
+    };
+    assert_eq!(uneven.len(), 4);
+    assert_eq!(uneven.epochs().last(), Some(1_000.0 + 60.0 * day));
+
+    let inverted = TimeWindow {
+        start_et: 10.0 * day,
+        end_et: 0.0,
+        step_seconds: day,
+    };
+    assert!(inverted.is_empty());
+    assert_eq!(inverted.epochs().count(), 0);
+}
+
+#[test]
+fn flyby_screening_respects_turn_limit_and_departure_cost() {
+    use solar_travel_calculator::mission::flyby::{FlybyScreenRequest, screen_single_flyby};
+
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+
+    let planets = load_planets("configs/bodies").expect("load planets");
+    let find = |name: &str| planets.iter().find(|p| p.name == name).unwrap();
+    let (earth, mars, jupiter) = (find("EARTH"), find("MARS"), find("JUPITER"));
+    let constants = ConstantsSet::default();
+    let screening = screen_single_flyby(&FlybyScreenRequest {
+        origin: earth,
+        flyby: mars,
+        destination: jupiter,
+        departure_et: epoch_seconds("2026 OCT 01 00:00:00 TDB").expect("epoch"),
+        direct_tof_days: 900.0,
+        min_flyby_altitude_km: mars.default_parking_altitude_km,
+        constants: &constants,
+    })
+    .expect("screening");
+
+    assert!(screening.direct_vinf_depart_km_s > 0.0);
+    assert!(screening.direct_vinf_arrive_km_s > 0.0);
+    if let Some(assist) = &screening.assist {
+        assert!(assist.turn_angle_deg <= assist.max_turn_angle_deg);
+        assert!(assist.vinf_depart_km_s <= screening.direct_vinf_depart_km_s);
+        assert!(assist.flyby_et < assist.arrival_et);
+    }
+}
+
+#[test]
+fn porkchop_grid_writes_departure_by_arrival_matrix() {
+    use solar_travel_calculator::mission::porkchop::{
+        PorkchopMetric, PorkchopPath, PorkchopPoint, write_grid,
+    };
+
+    let point = |depart_et: f64, arrive_et: f64, dv: f64, feasible: bool| PorkchopPoint {
+        depart_et,
+        arrive_et,
+        depart_utc: String::new(),
+        arrive_utc: String::new(),
+        tof_days: (arrive_et - depart_et) / 86_400.0,
+        c3_km2_s2: 10.0,
+        vinf_depart_km_s: 0.0,
+        vinf_arrive_km_s: 0.0,
+        dv_depart_km_s: 0.0,
+        dv_arrive_km_s: 0.0,
+        dv_total_km_s: dv,
+        propellant_used_kg: 0.0,
+        burn_time_s: 0.0,
+        final_mass_kg: 0.0,
+        lambert_path: PorkchopPath::Short,
+        feasible,
+        relative_inclination_deg: 0.0,
+    };
+    let points = vec![
+        point(0.0, 86_400.0, 5.0, true),
+        point(0.0, 86_400.0, 4.0, true),
+        point(0.0, 172_800.0, 6.0, false),
+        point(86_400.0, 172_800.0, 3.5, true),
+    ];
+
+    let mut out = Vec::new();
+    write_grid(&mut out, &points, PorkchopMetric::DvTotal).expect("grid");
+    let text = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "# rows: depart_et, columns: arrive_et, values: dv_total_km_s",
+            "2 86400.000 172800.000",
+            "0.000 4.000000 NaN",
+            "86400.000 NaN 3.500000",
+        ]
+    );
+
+    let mut out = Vec::new();
+    write_grid(&mut out, &points, PorkchopMetric::Tof).expect("grid");
+    let text = String::from_utf8(out).unwrap();
+    assert!(
+        text.lines()
+            .nth(2)
+            .unwrap()
+            .starts_with("0.000 1.000000 NaN")
+    );
+}
+
+#[test]
+fn window_pareto_front_keeps_faster_but_pricier_transfers() {
+    use solar_travel_calculator::mission::porkchop::{analyze_departure, pareto_front};
+
+    let sample = |depart_days: f64, tof_days: f64, dv: f64| WindowSample {
+        depart_et: depart_days * 86_400.0,
+        depart_utc: format!("D+{depart_days}"),
+        arrive_et: (depart_days + tof_days) * 86_400.0,
+        arrive_utc: String::new(),
+        dv_total_km_s: dv,
+        dv_depart_km_s: dv * 0.6,
+        dv_arrive_km_s: dv * 0.4,
+        c3_km2_s2: 10.0,
+    };
+    let group = vec![
+        sample(0.0, 300.0, 5.6),
+        sample(0.0, 150.0, 9.0),
+        sample(0.0, 200.0, 6.5),
+        sample(0.0, 250.0, 7.0),
+        sample(0.0, 350.0, 5.8),
+    ];
+    let front = pareto_front(&group);
+    let tofs: Vec<f64> = front.iter().map(|s| s.tof_days()).collect();
+    assert_eq!(tofs, [150.0, 200.0, 300.0]);
+    assert!(
+        front
+            .windows(2)
+            .all(|w| w[1].dv_total_km_s < w[0].dv_total_km_s)
+    );
+
+    let dataset = WindowDataset {
+        version: WINDOW_DATASET_VERSION,
+        origin_spice: "EARTH".to_string(),
+        destination_spice: "MARS BARYCENTER".to_string(),
+        depart_start_et: 0.0,
+        depart_end_et: 0.0,
+        step_days: 1.0,
+        min_tof_days: 100.0,
+        max_tof_days: 400.0,
+        vehicle: "Test Stage".to_string(),
+        rpark_depart_km: 6_578.0,
+        rpark_arrive_km: 3_690.0,
+        constants_fingerprint: ConstantsSet::default().fingerprint(),
+        min_dv_total_km_s: Some(5.6),
+        samples: vec![sample(0.0, 300.0, 5.6)],
+        pareto: front,
+    };
+    let suggestion = analyze_departure(&dataset, 0.0, 7.5, 1.1).expect("above threshold");
+    let faster = suggestion
+        .faster
+        .expect("faster option within the user's Δv");
+    assert_eq!(faster.tof_days(), 200.0);
+
+    // Caches written before the `pareto` field existed still load.
+    let mut json = serde_json::to_value(&dataset).unwrap();
+    json.as_object_mut().unwrap().remove("pareto");
+    let old: WindowDataset = serde_json::from_value(json).unwrap();
+    assert!(old.pareto.is_empty());
+}
+
+#[test]
+fn window_dataset_matches_only_its_vehicle_parking_radii_and_constants() {
+    let dataset = WindowDataset {
+        version: WINDOW_DATASET_VERSION,
+        origin_spice: "EARTH".to_string(),
+        destination_spice: "MARS BARYCENTER".to_string(),
+        depart_start_et: 0.0,
+        depart_end_et: 0.0,
+        step_days: 1.0,
+        min_tof_days: 100.0,
+        max_tof_days: 400.0,
+        vehicle: "Test Stage".to_string(),
+        rpark_depart_km: 6_578.0,
+        rpark_arrive_km: 3_690.0,
+        constants_fingerprint: ConstantsSet::default().fingerprint(),
+        min_dv_total_km_s: None,
+        samples: Vec::new(),
+        pareto: Vec::new(),
+    };
+    let constants = ConstantsSet::default();
+    let mut kernel_gms = constants.clone();
+    kernel_gms.mu_sun_km3_s2 *= 1.0 + 1e-12;
+    let mut override_gms = constants.clone();
+    override_gms
+        .body_mu_km3_s2
+        .insert("MARS BARYCENTER".to_string(), 42_828.37);
+    assert_ne!(constants.fingerprint(), kernel_gms.fingerprint());
+    assert_ne!(constants.fingerprint(), override_gms.fingerprint());
+
+    assert!(dataset.matches_parameters("Test Stage", 6_578.0, 3_690.0, &constants));
+    assert!(!dataset.matches_parameters("Other Stage", 6_578.0, 3_690.0, &constants));
+    assert!(!dataset.matches_parameters("Test Stage", 6_678.0, 3_690.0, &constants));
+    assert!(!dataset.matches_parameters("Test Stage", 6_578.0, 3_790.0, &constants));
+    assert!(!dataset.matches_parameters("Test Stage", 6_578.0, 3_690.0, &kernel_gms));
+    assert!(!dataset.matches_parameters("Test Stage", 6_578.0, 3_690.0, &override_gms));
+
+    // Caches written before the fields existed never match a real request.
+    let mut json = serde_json::to_value(&dataset).unwrap();
+    let object = json.as_object_mut().unwrap();
+    for key in [
+        "vehicle",
+        "rpark_depart_km",
+        "rpark_arrive_km",
+        "constants_fingerprint",
+    ] {
+        object.remove(key);
+    }
+    let old: WindowDataset = serde_json::from_value(json).unwrap();
+    assert!(!old.matches_parameters("Test Stage", 6_578.0, 3_690.0, &constants));
+}
+
+#[test]
+fn synodic_period_matches_earth_mars() {
+    let synodic = synodic_period(365.256, 686.98).expect("distinct periods");
+    assert!((synodic - 779.9).abs() < 0.5, "{synodic}");
+    assert_eq!(synodic_period(686.98, 365.256), Some(synodic));
+    assert!(synodic_period(365.256, 365.256).is_none());
+}
+
+#[test]
+fn next_launch_window_finds_the_late_2026_mars_opportunity() {
+    let _lock = guard().lock().unwrap();
+    if ensure_kernels_or_skip().is_none() {
+        return;
+    }
+    let planets = load_planets("configs/bodies").expect("planets catalog");
+    let earth = planets.iter().find(|p| p.name == "EARTH").unwrap();
+    let mars = planets.iter().find(|p| p.name == "MARS").unwrap();
+    let constants = ConstantsSet::default();
+
+    let after = epoch_seconds("2026 JAN 01 00:00:00 TDB").unwrap();
+    let window = next_launch_window(earth, mars, after, &constants).expect("window estimate");
+    assert!((window.synodic_period_days - 780.0).abs() < 15.0);
+    // The 2026 Earth→Mars window opens around November.
+    let days_after = (window.depart_et - after) / 86_400.0;
+    assert!((250.0..400.0).contains(&days_after), "{days_after}");
+
+    let next = next_launch_window(earth, mars, window.depart_et + 86_400.0 * 60.0, &constants)
+        .expect("following window");
+    let gap_days = (next.depart_et - window.depart_et) / 86_400.0;
+    assert!(
+        (gap_days - window.synodic_period_days).abs() < 120.0,
+        "{gap_days}"
+    );
+}
@@ -0,0 +1,197 @@
+use solar_travel_calculator::config::load_planets;
+use solar_travel_calculator::orbits::{
+    OrbitalElements, capture_delta_v_j2, elements_from_state, escape_delta_v, escape_delta_v_j2,
+    finite_burn_correction, flyby_max_turn_angle, norm3, propagate_kepler, spiral_capture_delta_v,
+    spiral_escape_delta_v, spiral_time, state_from_elements, sub,
+};
+
+/// Earth GM (km³/s²).
+const MU_EARTH: f64 = 398_600.441_8;
+
+#[test]
+fn flyby_turn_limit_matches_hyperbola_geometry() {
+    let mu = MU_EARTH;
+    let rp = 6_678.0;
+    // v∞² = μ / rp gives e = 2 and a 60° turn.
+    let vinf = (mu / rp).sqrt();
+    assert!((flyby_max_turn_angle(vinf, mu, rp).to_degrees() - 60.0).abs() < 1e-9);
+    assert!((flyby_max_turn_angle(0.0, mu, rp).to_degrees() - 180.0).abs() < 1e-9);
+    assert!(flyby_max_turn_angle(2.0 * vinf, mu, rp) < flyby_max_turn_angle(vinf, mu, rp));
+}
+
+#[test]
+fn spiral_estimates_follow_edelbaum_and_rocket_equation() {
+    let mu = MU_EARTH;
+    let leo = 6_778.0;
+    let geo = 42_164.0;
+    let v_leo = (mu / leo).sqrt();
+
+    // Escaping to infinity costs the full starting circular speed.
+    assert!((spiral_escape_delta_v(mu, leo, f64::INFINITY) - v_leo).abs() < 1e-12);
+    let leo_to_geo = spiral_escape_delta_v(mu, leo, geo);
+    assert!((leo_to_geo - (v_leo - (mu / geo).sqrt())).abs() < 1e-12);
+    assert!((spiral_capture_delta_v(mu, geo, leo) - leo_to_geo).abs() < 1e-12);
+
+    let thrust = 0.5;
+    let mass = 1_000.0;
+    let isp = 3_000.0;
+    let duration = spiral_time(mu, thrust, mass, isp, leo, f64::INFINITY).unwrap();
+    let ve = isp * 9.806_65;
+    let propellant = mass * (1.0 - (-v_leo * 1_000.0 / ve).exp());
+    assert!((duration - propellant * ve / thrust).abs() < 1e-6);
+    // Mass depletion makes the spiral shorter than a constant-acceleration estimate.
+    assert!(duration < v_leo * 1_000.0 * mass / thrust);
+    assert!(spiral_time(mu, 0.0, mass, isp, leo, geo).is_none());
+}
+
+#[test]
+fn j2_escape_correction_is_first_order_and_vanishes_for_spheres() {
+    let mu = MU_EARTH;
+    let radius = 6_378.136_3;
+    let leo = 6_778.0;
+    let j2 = 1.082_63e-3;
+    let vinf = 3.0;
+
+    let spherical = escape_delta_v(mu, leo, vinf);
+    assert_eq!(
+        escape_delta_v_j2(mu, leo, vinf, 0.0, radius, 0.3),
+        spherical
+    );
+
+    // Equatorial orbit: the circular speed rises by ~0.75 k and the periapsis speed by less.
+    let k = j2 * (radius / leo).powi(2);
+    let v_circ = (mu / leo * (1.0 + 1.5 * k)).sqrt();
+    let v_hyp = (vinf * vinf + 2.0 * mu / leo * (1.0 + 0.5 * k)).sqrt();
+    let equatorial = escape_delta_v_j2(mu, leo, vinf, j2, radius, 0.0);
+    assert!((equatorial - (v_hyp - v_circ)).abs() < 1e-12);
+    assert!(equatorial < spherical && spherical - equatorial < 0.01);
+    assert_eq!(
+        capture_delta_v_j2(mu, leo, vinf, j2, radius, 0.0),
+        equatorial
+    );
+
+    // The orbit-averaged J2 term vanishes at sin² i = 2/3.
+    let critical = (2.0_f64 / 3.0).sqrt().asin();
+    assert!((escape_delta_v_j2(mu, leo, vinf, j2, radius, critical) - spherical).abs() < 1e-12);
+
+    let planets = load_planets("configs/bodies").expect("planets catalog");
+    let earth = planets.iter().find(|p| p.name == "EARTH").unwrap();
+    assert_eq!(earth.j2, j2);
+    assert!(planets.iter().any(|p| p.j2 == 0.0));
+}
+
+#[test]
+fn kepler_propagation_closes_orbits_and_conserves_flyby_invariants() {
+    let mu = MU_EARTH;
+    let r = 7_000.0;
+    let v = (mu / r).sqrt();
+    let period = std::f64::consts::TAU * (r.powi(3) / mu).sqrt();
+
+    // A circular orbit returns to its start after one (or several) periods.
+    let (position, velocity) = propagate_kepler([r, 0.0, 0.0], [0.0, v, 0.0], mu, period);
+    assert!(norm3(&[position[0] - r, position[1], position[2]]) < 1e-6);
+    assert!(norm3(&[velocity[0], velocity[1] - v, velocity[2]]) < 1e-9);
+    let (quarter, _) = propagate_kepler([r, 0.0, 0.0], [0.0, v, 0.0], mu, 5.25 * period);
+    assert!(norm3(&[quarter[0], quarter[1] - r, quarter[2]]) < 1e-5);
+
+    // Hyperbolic flyby from periapsis: symmetric in time, energy and angular momentum conserved.
+    let rp = 7_000.0;
+    let vinf = 4.0;
+    let vp = (vinf * vinf + 2.0 * mu / rp).sqrt();
+    let (periapsis, periapsis_velocity) = ([rp, 0.0, 0.0], [0.0, vp, 0.0]);
+    let dt = 3.0 * 86_400.0;
+    let (outbound, outbound_velocity) = propagate_kepler(periapsis, periapsis_velocity, mu, dt);
+    let (inbound, _) = propagate_kepler(periapsis, periapsis_velocity, mu, -dt);
+    assert!((norm3(&outbound) - norm3(&inbound)).abs() < 1e-6 * norm3(&outbound));
+    assert!((outbound[1] + inbound[1]).abs() < 1e-6 * norm3(&outbound));
+
+    let speed = norm3(&outbound_velocity);
+    let energy = 0.5 * speed * speed - mu / norm3(&outbound);
+    assert!((energy - 0.5 * vinf * vinf).abs() < 1e-9);
+    let h = outbound[0] * outbound_velocity[1] - outbound[1] * outbound_velocity[0];
+    assert!((h - rp * vp).abs() < 1e-6 * rp * vp);
+
+    // Propagating back recovers periapsis.
+    let (back, back_velocity) = propagate_kepler(outbound, outbound_velocity, mu, -dt);
+    assert!(norm3(&[back[0] - rp, back[1], back[2]]) < 1e-4);
+    assert!(norm3(&[back_velocity[0], back_velocity[1] - vp, back_velocity[2]]) < 1e-9);
+}
+
+#[test]
+fn elements_round_trip_through_cartesian_states() {
+    let mu = MU_EARTH;
+    let cases = [
+        // Molniya-like ellipse, a hyperbolic departure (before periapsis) and a retrograde
+        // circular equatorial orbit.
+        (26_600.0, 0.74, 63.4, 40.0, 270.0, 1.2),
+        (-12_000.0, 1.6, 28.5, 300.0, 45.0, -2.5),
+        (7_000.0, 0.0, 180.0, 0.0, 0.0, 0.7),
+    ];
+    for (a, e, inclination_deg, raan_deg, argp_deg, mean_anomaly) in cases {
+        let elements = OrbitalElements {
+            semi_major_axis_km: a,
+            eccentricity: e,
+            inclination_rad: f64::to_radians(inclination_deg),
+            raan_rad: f64::to_radians(raan_deg),
+            argument_of_periapsis_rad: f64::to_radians(argp_deg),
+            mean_anomaly_rad: mean_anomaly,
+        };
+        let (position, velocity) = state_from_elements(&elements, mu).expect("conic state");
+        let speed = norm3(&velocity);
+        assert!((0.5 * speed * speed - mu / norm3(&position) + mu / (2.0 * a)).abs() < 1e-9);
+
+        let recovered = elements_from_state(position, velocity, mu);
+        assert!((recovered.semi_major_axis_km - a).abs() < 1e-6 * a.abs());
+        assert!((recovered.eccentricity - e).abs() < 1e-9);
+        assert!((recovered.inclination_rad - elements.inclination_rad).abs() < 1e-9);
+        let (again, again_velocity) = state_from_elements(&recovered, mu).expect("round trip");
+        assert!(norm3(&sub(&again, &position)) < 1e-6);
+        assert!(norm3(&sub(&again_velocity, &velocity)) < 1e-9);
+    }
+
+    // Advancing the mean anomaly matches propagating the state.
+    let (a, e) = (26_600.0, 0.74);
+    let elements = |mean_anomaly_rad: f64| OrbitalElements {
+        semi_major_axis_km: a,
+        eccentricity: e,
+        inclination_rad: 1.1,
+        raan_rad: 0.4,
+        argument_of_periapsis_rad: 4.7,
+        mean_anomaly_rad,
+    };
+    let (position, velocity) = state_from_elements(&elements(0.3), mu).unwrap();
+    let dt = 3_600.0;
+    let later = state_from_elements(&elements(0.3 + (mu / a.powi(3)).sqrt() * dt), mu).unwrap();
+    let propagated = propagate_kepler(position, velocity, mu, dt);
+    assert!(norm3(&sub(&later.0, &propagated.0)) < 1e-5);
+
+    let parabolic = OrbitalElements {
+        semi_major_axis_km: f64::INFINITY,
+        eccentricity: 1.0,
+        ..elements(0.0)
+    };
+    assert!(state_from_elements(&parabolic, mu).is_none());
+}
+
+#[test]
+fn finite_burn_correction_adds_gravity_loss_for_low_thrust() {
+    let mu = MU_EARTH;
+    let leo = 6_678.0;
+    let ideal = 3.6;
+    let mass = 100_000.0;
+    let isp = 450.0;
+
+    let high = finite_burn_correction(ideal, mu, leo, 5.0e6, mass, isp).unwrap();
+    let low = finite_burn_correction(ideal, mu, leo, 2.0e5, mass, isp).unwrap();
+    assert!(high.delta_v_km_s >= ideal);
+    assert!(high.gravity_loss_km_s(ideal) < 0.01);
+    assert!(low.gravity_loss_km_s(ideal) > high.gravity_loss_km_s(ideal));
+    assert!(low.burn_duration_s > high.burn_duration_s);
+    assert!(low.burn_arc_rad > high.burn_arc_rad);
+
+    // The reported duration is consistent with the rocket equation at the corrected delta-v.
+    let ve = isp * 9.806_65;
+    let propellant = mass * (1.0 - (-low.delta_v_km_s * 1_000.0 / ve).exp());
+    assert!((low.burn_duration_s - propellant * ve / 2.0e5).abs() < 1e-6);
+    assert!(finite_burn_correction(ideal, mu, leo, 0.0, mass, isp).is_none());
+}
@@ -1,10 +1,25 @@
 use std::sync::{Mutex, OnceLock};
 
-use solar_travel_calculator::config::{load_planets, load_vehicle_configs};
-use solar_travel_calculator::mission::MissionConfig;
-use solar_travel_calculator::mission::arrival::{AerobrakingOption, ArrivalConfig, plan_arrival};
-use solar_travel_calculator::mission::departure::{DepartureConfig, plan_departure};
-use solar_travel_calculator::mission::interplanetary::{InterplanetaryConfig, plan_interplanetary};
+use solar_travel_calculator::config::{ConstantsSet, load_planets, load_vehicle_configs};
+use solar_travel_calculator::core::constants::G0;
+use solar_travel_calculator::core::constants::MU_SUN_KM3_S2 as MU_SUN;
+use solar_travel_calculator::ephemeris::source::{EphemerisSource, FixedEphemerisSource};
+use solar_travel_calculator::ephemeris::{self, StateVector};
+use solar_travel_calculator::mission::arrival::{
+    AerobrakingOption, ArrivalConfig, plan_aerobraking_campaign, plan_arrival,
+};
+use solar_travel_calculator::mission::departure::{
+    DepartureConfig, VInfinityTarget, departure_phasing, plan_departure,
+};
+use solar_travel_calculator::mission::interplanetary::{
+    InterplanetaryConfig, InterplanetaryError, InterplanetaryPlan, plan_interplanetary,
+    plan_interplanetary_with_source,
+};
+use solar_travel_calculator::mission::{
+    GM_MISMATCH_TOLERANCE, MissionConfig, MissionError, gm_mismatches, plan_mission_with_cruise,
+    plan_mission_with_source,
+};
+use solar_travel_calculator::propulsion::PropulsionMode;
 use solar_travel_calculator::transfer::vehicle;
 
 fn guard() -> &'static Mutex<()> {
@@ -52,8 +67,9 @@ fn earth_mars_setup(
         origin_body: origin.spice_name.clone(),
         parking_altitude_km: origin.default_parking_altitude_km,
         departure_epoch: "2025 OCT 14 23:28:58 TDB".to_string(),
-        required_v_infinity: Some(3.2),
+        required_v_infinity: Some(VInfinityTarget::Magnitude(3.2)),
         propulsion_mode: propulsion_mode.clone(),
+        parking_inclination_deg: None,
     };
 
     let cruise_cfg = InterplanetaryConfig {
@@ -62,18 +78,107 @@ fn earth_mars_setup(
         departure_epoch: "2025 OCT 14 23:28:58 TDB".to_string(),
         arrival_epoch: Some("2026 APR 12 23:28:58 TDB".to_string()),
         propulsion_mode: propulsion_mode.clone(),
+        warm_start_tof_days: None,
+        parking_altitudes_km: None,
     };
 
     let arrival_cfg = ArrivalConfig {
         destination_body: destination.spice_name.clone(),
         target_parking_altitude_km: destination.default_parking_altitude_km,
         encounter_epoch: "2026 APR 12 23:28:58 TDB".to_string(),
-        propulsion_mode: propulsion_mode,
+        propulsion_mode,
         aerobraking: None,
+        max_heat_flux_w_m2: None,
     };
 
-    let cruise =
-        plan_interplanetary(&vehicle, &cruise_cfg, &origin, &destination).expect("interplanetary");
+    let cruise = plan_interplanetary(
+        &vehicle,
+        &cruise_cfg,
+        &origin,
+        &destination,
+        &ConstantsSet::default(),
+    )
+    .expect("interplanetary");
+
+    (
+        MissionConfig {
+            vehicle,
+            origin,
+            destination,
+            departure: departure_cfg,
+            cruise: cruise_cfg,
+            arrival: arrival_cfg,
+            constants: ConstantsSet::default(),
+        },
+        cruise,
+    )
+}
+
+const AU_KM: f64 = 149_597_870.7;
+
+/// Heliocentric circular-orbit state in the ecliptic plane at the given longitude.
+fn circular_state(radius_au: f64, longitude_deg: f64) -> StateVector {
+    let radius_km = radius_au * AU_KM;
+    let speed = (MU_SUN / radius_km).sqrt();
+    let (sin_l, cos_l) = longitude_deg.to_radians().sin_cos();
+    StateVector {
+        position_km: [radius_km * cos_l, radius_km * sin_l, 0.0],
+        velocity_km_s: [-speed * sin_l, speed * cos_l, 0.0],
+        light_time_seconds: 0.0,
+    }
+}
+
+/// Earth→Mars setup with injected circular-orbit states; needs no SPICE kernels.
+fn earth_mars_offline_setup(vehicle_name: &str) -> (MissionConfig, InterplanetaryPlan) {
+    let planets = load_planets("configs/bodies").expect("planets catalog");
+    let vehicles_cfg = load_vehicle_configs("configs/vehicles").expect("vehicles catalog");
+    let vehicles: Vec<_> = vehicles_cfg
+        .iter()
+        .map(|cfg| vehicle::from_config(cfg).expect("convert vehicle"))
+        .collect();
+
+    let origin = planets.iter().find(|p| p.name == "EARTH").unwrap().clone();
+    let destination = planets.iter().find(|p| p.name == "MARS").unwrap().clone();
+    let vehicle = vehicles
+        .iter()
+        .find(|v| v.name.to_uppercase().contains(&vehicle_name.to_uppercase()))
+        .unwrap_or(&vehicles[0])
+        .clone();
+    let propulsion_mode = vehicle.propulsion.clone();
+
+    let departure_cfg = DepartureConfig {
+        origin_body: origin.spice_name.clone(),
+        parking_altitude_km: origin.default_parking_altitude_km,
+        departure_epoch: "2025 OCT 14 23:28:58 TDB".to_string(),
+        required_v_infinity: Some(VInfinityTarget::Magnitude(3.2)),
+        propulsion_mode: propulsion_mode.clone(),
+        parking_inclination_deg: None,
+    };
+
+    let cruise_cfg = InterplanetaryConfig {
+        departure_body: origin.spice_name.clone(),
+        destination_body: destination.spice_name.clone(),
+        departure_epoch: "2025 OCT 14 23:28:58 TDB".to_string(),
+        arrival_epoch: None,
+        propulsion_mode: propulsion_mode.clone(),
+        warm_start_tof_days: None,
+        parking_altitudes_km: None,
+    };
+
+    let arrival_cfg = ArrivalConfig {
+        destination_body: destination.spice_name.clone(),
+        target_parking_altitude_km: destination.default_parking_altitude_km,
+        encounter_epoch: "2026 APR 12 23:28:58 TDB".to_string(),
+        propulsion_mode,
+        aerobraking: None,
+        max_heat_flux_w_m2: None,
+    };
+
+    let cruise = InterplanetaryPlan::from_states(
+        circular_state(1.0, 21.0),
+        circular_state(1.524, 161.0),
+        210.0,
+    );
 
     (
         MissionConfig {
@@ -83,6 +188,7 @@ fn earth_mars_setup(
             departure: departure_cfg,
             cruise: cruise_cfg,
             arrival: arrival_cfg,
+            constants: ConstantsSet::default(),
         },
         cruise,
     )
@@ -90,17 +196,14 @@ fn earth_mars_setup(
 
 #[test]
 fn departure_delta_v_is_positive() {
-    let _lock = guard().lock().unwrap();
-    if ensure_kernels_or_skip().is_none() {
-        return;
-    }
-    let (config, cruise) = earth_mars_setup("Ion");
+    let (config, cruise) = earth_mars_offline_setup("Ion");
     let departure = plan_departure(
         &config.vehicle,
         &config.departure,
         &config.origin,
         &config.cruise,
         &cruise,
+        &config.constants,
     )
     .expect("departure");
 
@@ -108,13 +211,65 @@ fn departure_delta_v_is_positive() {
     assert!(departure.hyperbolic_excess_km_s > 0.0);
 }
 
+#[test]
+fn departure_phasing_reports_plane_change_beyond_parking_inclination() {
+    let mu: f64 = 398_600.441_8;
+    let radius = 6_778.0;
+    let inclination = 28.5_f64.to_radians();
+    let asymptote = |declination_deg: f64| {
+        let (sin, cos) = declination_deg.to_radians().sin_cos();
+        [3.0 * cos * 0.6, 3.0 * cos * 0.8, 3.0 * sin]
+    };
+
+    // Within reach: no penalty, and the optimal plane contains the asymptote.
+    let reachable = departure_phasing(asymptote(20.0), inclination, mu, radius).expect("phasing");
+    assert_eq!(reachable.plane_change_rad, 0.0);
+    assert_eq!(reachable.plane_change_penalty_km_s, 0.0);
+    let (sin_raan, cos_raan) = reachable.optimal_raan_rad.sin_cos();
+    let normal = [
+        sin_raan * inclination.sin(),
+        -cos_raan * inclination.sin(),
+        inclination.cos(),
+    ];
+    let direction = asymptote(20.0).map(|c| c / 3.0);
+    let out_of_plane: f64 = (0..3).map(|k| normal[k] * direction[k]).sum();
+    assert!(out_of_plane.abs() < 1e-12);
+
+    // Out of reach: the remaining 11.5° is folded into the escape burn.
+    let steep = departure_phasing(asymptote(40.0), inclination, mu, radius).expect("phasing");
+    let turn = 11.5_f64.to_radians();
+    assert!((steep.plane_change_rad - turn).abs() < 1e-12);
+    let vc = (mu / radius).sqrt();
+    let vp = (9.0 + 2.0 * mu / radius).sqrt();
+    let combined = (vc * vc + vp * vp - 2.0 * vc * vp * turn.cos()).sqrt();
+    assert!((steep.plane_change_penalty_km_s - (combined - (vp - vc))).abs() < 1e-12);
+    assert!(steep.asymptote_true_anomaly_rad > std::f64::consts::FRAC_PI_2);
+
+    // The planner only runs the check for an inclined parking orbit.
+    let (mut config, cruise) = earth_mars_offline_setup("Starship");
+    let plan = |config: &solar_travel_calculator::mission::MissionConfig| {
+        plan_departure(
+            &config.vehicle,
+            &config.departure,
+            &config.origin,
+            &config.cruise,
+            &cruise,
+            &config.constants,
+        )
+        .expect("departure")
+    };
+    let free = plan(&config);
+    assert_eq!(free.plane_change_penalty_km_s, 0.0);
+    assert!(free.optimal_parking_raan_rad.is_none());
+    config.departure.parking_inclination_deg = Some(28.5);
+    let inclined = plan(&config);
+    assert!(inclined.optimal_parking_raan_rad.is_some());
+    assert!(inclined.plane_change_penalty_km_s >= 0.0);
+}
+
 #[test]
 fn aerobraking_reduces_capture_delta_v() {
-    let _lock = guard().lock().unwrap();
-    if ensure_kernels_or_skip().is_none() {
-        return;
-    }
-    let (mut config, cruise) = earth_mars_setup("Starship");
+    let (mut config, cruise) = earth_mars_offline_setup("Starship");
     let propulsive = plan_arrival(
         &config.vehicle,
         &config.arrival,
@@ -122,6 +277,7 @@ fn aerobraking_reduces_capture_delta_v() {
         &config.cruise,
         None,
         &cruise,
+        &config.constants,
     )
     .expect("arrival");
 
@@ -135,6 +291,7 @@ fn aerobraking_reduces_capture_delta_v() {
         &config.cruise,
         config.arrival.aerobraking,
         &cruise,
+        &config.constants,
     )
     .expect("arrival aero");
 
@@ -157,3 +314,644 @@ fn continuous_solver_consumes_propellant() {
     assert!(prop_used >= 0.0);
     assert!(prop_used <= config.vehicle.propellant_mass_kg);
 }
+
+#[test]
+fn departure_vector_target_never_beats_minimum_magnitude() {
+    let (mut config, cruise) = earth_mars_offline_setup("Starship");
+    let unconstrained = plan_departure(
+        &config.vehicle,
+        &config.departure,
+        &config.origin,
+        &config.cruise,
+        &cruise,
+        &config.constants,
+    )
+    .expect("departure");
+
+    config.departure.required_v_infinity = Some(VInfinityTarget::Vector([0.0, 0.0, 25.0]));
+    let constrained = plan_departure(
+        &config.vehicle,
+        &config.departure,
+        &config.origin,
+        &config.cruise,
+        &cruise,
+        &config.constants,
+    )
+    .expect("departure with asymptote target");
+
+    assert!(constrained.hyperbolic_excess_km_s + 1e-9 >= unconstrained.hyperbolic_excess_km_s);
+}
+
+#[test]
+fn offline_mission_plan_runs_without_kernels() {
+    let (config, cruise) = earth_mars_offline_setup("Starship");
+    let profile = plan_mission_with_cruise(&config, cruise).expect("offline mission plan");
+
+    assert!(profile.departure.delta_v_required > 0.0);
+    assert!(profile.departure.hyperbolic_excess_km_s > 0.0);
+    assert!(profile.arrival.delta_v_required > 0.0);
+    assert!((profile.cruise.time_of_flight_days - 210.0).abs() < 1e-12);
+}
+
+#[test]
+fn departure_uses_body_gm_from_constants_set() {
+    let (mut config, cruise) = earth_mars_offline_setup("Starship");
+    let baseline = plan_mission_with_cruise(&config, cruise.clone()).expect("baseline plan");
+
+    let earth_mu = config.origin.mu_km3_s2;
+    config
+        .constants
+        .body_mu_km3_s2
+        .insert(config.origin.spice_name.to_lowercase(), 2.0 * earth_mu);
+    let heavier = plan_mission_with_cruise(&config, cruise).expect("override plan");
+
+    assert_eq!(
+        heavier.departure.hyperbolic_excess_km_s,
+        baseline.departure.hyperbolic_excess_km_s
+    );
+    assert!(heavier.departure.delta_v_required > baseline.departure.delta_v_required);
+    assert_eq!(
+        heavier.arrival.delta_v_required,
+        baseline.arrival.delta_v_required
+    );
+}
+
+#[test]
+fn aerobrake_pass_reports_atmospheric_time_and_capture_period() {
+    use solar_travel_calculator::aerobrake::{
+        AerobrakeRequest, AtmosphereModel, PlanetEntryContext, SUTTON_GRAVES_K,
+        SUTTON_GRAVES_K_CO2, VehicleEntryContext, simulate_ballistic_pass,
+    };
+
+    let request = AerobrakeRequest {
+        planet: PlanetEntryContext {
+            mu_m3_s2: 4.282_837e13,
+            radius_m: 3_389_500.0,
+            atmosphere: AtmosphereModel::Exponential {
+                rho0: 0.020,
+                scale_height: 11_100.0,
+            },
+            target_periapsis_altitude_m: 75_000.0,
+            exit_altitude_m: 150_000.0,
+            sutton_graves_k: SUTTON_GRAVES_K_CO2,
+        },
+        vehicle: VehicleEntryContext {
+            ballistic_coefficient_kg_m2: 100.0,
+            lift_to_drag: None,
+            nose_radius_m: 1.0,
+            bank_angle_schedule: None,
+        },
+        initial_vinf_m_s: 1_000.0,
+    };
+    let result = simulate_ballistic_pass(&request).expect("aerobrake pass");
+
+    assert!(result.atmospheric_time_s > 0.0);
+    assert!(result.atmospheric_time_s < 3_600.0);
+    let period = result
+        .pass_duration_s
+        .expect("drag at periapsis captures the vehicle");
+    assert!(period > result.atmospheric_time_s);
+
+    assert!(result.peak_heat_flux_w_m2 > 0.0);
+    assert!(result.integrated_heat_load_j_m2 > 0.0);
+    assert!(
+        result.integrated_heat_load_j_m2
+            <= result.peak_heat_flux_w_m2 * result.atmospheric_time_s * (1.0 + 1e-9)
+    );
+
+    // A blunter nose lowers stagnation heating as 1/√r_n.
+    let mut blunt = request.clone();
+    blunt.vehicle.nose_radius_m = 4.0;
+    let blunt = simulate_ballistic_pass(&blunt).expect("blunt pass");
+    assert!(
+        (blunt.peak_heat_flux_w_m2 * 2.0 - result.peak_heat_flux_w_m2).abs()
+            < 1e-6 * result.peak_heat_flux_w_m2
+    );
+    // Heating scales with the atmosphere's own coefficient; air runs cooler than CO₂.
+    let mut air = request.clone();
+    air.planet.sutton_graves_k = SUTTON_GRAVES_K;
+    let air = simulate_ballistic_pass(&air).expect("air pass");
+    let ratio = air.peak_heat_flux_w_m2 / result.peak_heat_flux_w_m2;
+    assert!((ratio - SUTTON_GRAVES_K / SUTTON_GRAVES_K_CO2).abs() < 1e-9);
+    assert_eq!(air.delta_v_drag_m_s, result.delta_v_drag_m_s);
+}
+
+#[test]
+fn layered_atmosphere_matches_exponential_and_varies_scale_height() {
+    use solar_travel_calculator::aerobrake::{
+        AerobrakeError, AerobrakeRequest, AtmosphereModel, PlanetEntryContext, VehicleEntryContext,
+        simulate_ballistic_pass,
+    };
+
+    let exponential = PlanetEntryContext::exponential(
+        4.282_837e13,
+        3_389_500.0,
+        0.020,
+        11_100.0,
+        75_000.0,
+        150_000.0,
+    );
+    assert_eq!(
+        exponential.atmosphere,
+        AtmosphereModel::Exponential {
+            rho0: 0.020,
+            scale_height: 11_100.0,
+        }
+    );
+    let mut single_layer = exponential.clone();
+    single_layer.atmosphere = AtmosphereModel::Layered {
+        layers: vec![(0.0, 0.020, 11_100.0)],
+    };
+
+    let request = |planet: PlanetEntryContext| AerobrakeRequest {
+        planet,
+        vehicle: VehicleEntryContext {
+            ballistic_coefficient_kg_m2: 100.0,
+            lift_to_drag: None,
+            nose_radius_m: 1.0,
+            bank_angle_schedule: None,
+        },
+        initial_vinf_m_s: 1_000.0,
+    };
+    let a = simulate_ballistic_pass(&request(exponential.clone())).expect("exponential pass");
+    let b = simulate_ballistic_pass(&request(single_layer)).expect("layered pass");
+    assert_eq!(a.delta_v_drag_m_s, b.delta_v_drag_m_s);
+
+    // A thick lower layer with a short scale height under a slowly decaying upper layer.
+    let layered = AtmosphereModel::Layered {
+        layers: vec![(50_000.0, 1.0e-3, 20_000.0), (0.0, 65.0, 15_900.0)],
+    };
+    assert_eq!(layered.density(0.0), 65.0);
+    assert_eq!(layered.density(50_000.0), 1.0e-3);
+    let upper = layered.density(70_000.0);
+    assert!((upper - 1.0e-3 * (-1.0f64).exp()).abs() < 1e-15);
+    assert!(layered.density(-1_000.0) > 65.0);
+
+    let mut invalid = exponential;
+    invalid.atmosphere = AtmosphereModel::Layered { layers: Vec::new() };
+    assert!(matches!(
+        simulate_ballistic_pass(&request(invalid)),
+        Err(AerobrakeError::MissingAtmosphere)
+    ));
+}
+
+#[test]
+fn bank_angle_schedule_steers_lifting_aerocapture() {
+    use solar_travel_calculator::aerobrake::{
+        AerobrakeError, AerobrakeRequest, AtmosphereModel, PlanetEntryContext, SUTTON_GRAVES_K_CO2,
+        VehicleEntryContext, simulate_ballistic_pass,
+    };
+
+    let request = |lift_to_drag: Option<f64>, schedule: Option<Vec<(f64, f64)>>| AerobrakeRequest {
+        planet: PlanetEntryContext {
+            mu_m3_s2: 4.282_837e13,
+            radius_m: 3_389_500.0,
+            atmosphere: AtmosphereModel::Exponential {
+                rho0: 0.020,
+                scale_height: 11_100.0,
+            },
+            target_periapsis_altitude_m: 75_000.0,
+            exit_altitude_m: 150_000.0,
+            sutton_graves_k: SUTTON_GRAVES_K_CO2,
+        },
+        vehicle: VehicleEntryContext {
+            ballistic_coefficient_kg_m2: 100.0,
+            lift_to_drag,
+            nose_radius_m: 1.0,
+            bank_angle_schedule: schedule,
+        },
+        initial_vinf_m_s: 3_000.0,
+    };
+
+    let conic = simulate_ballistic_pass(&request(None, None)).expect("conic pass");
+    let unlifted =
+        simulate_ballistic_pass(&request(None, Some(vec![(0.0, 0.0)]))).expect("integrated pass");
+    let lift_up = simulate_ballistic_pass(&request(Some(0.25), Some(vec![(0.0, 0.0)])))
+        .expect("lift-up pass");
+    let lift_down = simulate_ballistic_pass(&request(
+        Some(0.25),
+        Some(vec![(0.0, std::f64::consts::PI)]),
+    ))
+    .expect("lift-down pass");
+
+    // Without lift the integrated trajectory reproduces the conic drag estimate.
+    assert!(
+        (unlifted.delta_v_drag_m_s - conic.delta_v_drag_m_s).abs() < 0.1 * conic.delta_v_drag_m_s
+    );
+    for pass in [&conic, &unlifted, &lift_up, &lift_down] {
+        assert!(pass.exit_flight_path_angle_rad > 0.0);
+    }
+    // Lift up holds the vehicle higher; lift down drives it deeper into the atmosphere.
+    assert!(lift_up.periapsis_altitude_m > unlifted.periapsis_altitude_m);
+    assert!(lift_up.delta_v_drag_m_s < unlifted.delta_v_drag_m_s);
+    assert!(lift_down.periapsis_altitude_m < unlifted.periapsis_altitude_m);
+    assert!(lift_down.delta_v_drag_m_s > unlifted.delta_v_drag_m_s);
+
+    let mut too_deep = request(Some(0.25), Some(vec![(0.0, std::f64::consts::PI)]));
+    too_deep.planet.target_periapsis_altitude_m = 60_000.0;
+    assert!(matches!(
+        simulate_ballistic_pass(&too_deep),
+        Err(AerobrakeError::SurfaceImpact)
+    ));
+}
+
+#[test]
+fn heat_flux_ceiling_rejects_aerobrake_pass() {
+    let (mut config, cruise) = earth_mars_offline_setup("Starship");
+    config.arrival.aerobraking = Some(AerobrakingOption::Partial {
+        periapsis_altitude_km: 80.0,
+    });
+    let unconstrained = plan_arrival(
+        &config.vehicle,
+        &config.arrival,
+        &config.destination,
+        &config.cruise,
+        config.arrival.aerobraking,
+        &cruise,
+        &config.constants,
+    )
+    .expect("arrival aero");
+    let report = unconstrained
+        .aerobrake_report
+        .as_ref()
+        .expect("aerobrake report");
+    assert!(report.peak_heat_flux_w_m2 > 0.0);
+
+    config.arrival.max_heat_flux_w_m2 = Some(0.5 * report.peak_heat_flux_w_m2);
+    let capped = plan_arrival(
+        &config.vehicle,
+        &config.arrival,
+        &config.destination,
+        &config.cruise,
+        config.arrival.aerobraking,
+        &cruise,
+        &config.constants,
+    )
+    .expect("arrival capped");
+    assert!(capped.aerobrake_report.is_none());
+    assert!(capped.delta_v_required >= unconstrained.delta_v_required);
+}
+
+#[test]
+fn aerobraking_campaign_lowers_apoapsis_within_limits() {
+    let (config, _) = earth_mars_offline_setup("Starship");
+    let mars = &config.destination;
+
+    let campaign = plan_aerobraking_campaign(
+        &config.vehicle,
+        mars,
+        &config.constants,
+        33_000.0,
+        2_000.0,
+        110.0,
+        None,
+    )
+    .expect("campaign");
+    assert!(campaign.passes.len() > 1);
+    assert!(campaign.total_days > 0.0);
+    for pass in &campaign.passes {
+        assert!(pass.delta_v_drag_km_s > 0.0);
+        assert!(pass.peak_dynamic_pressure_pa <= 80_000.0);
+        assert!(pass.peak_deceleration_m_s2 <= 39.24);
+    }
+
+    // Skimming deep enough to breach the limits forces periapsis-raise trims.
+    let deep = plan_aerobraking_campaign(
+        &config.vehicle,
+        mars,
+        &config.constants,
+        33_000.0,
+        2_000.0,
+        20.0,
+        None,
+    )
+    .expect("deep campaign");
+    assert!(deep.trim_burns_dv_km_s > 0.0);
+    assert!(deep.passes[0].periapsis_altitude_m > 20_000.0);
+
+    // A heat-flux cap below the unconstrained peak is enforced on every pass.
+    let peak_flux = campaign
+        .passes
+        .iter()
+        .map(|pass| pass.peak_heat_flux_w_m2)
+        .fold(0.0, f64::max);
+    let cap = 0.8 * peak_flux;
+    let cooled = plan_aerobraking_campaign(
+        &config.vehicle,
+        mars,
+        &config.constants,
+        33_000.0,
+        2_000.0,
+        110.0,
+        Some(cap),
+    )
+    .expect("heat-limited campaign");
+    assert!(cooled.trim_burns_dv_km_s > 0.0);
+    assert!(
+        cooled
+            .passes
+            .iter()
+            .all(|pass| pass.peak_heat_flux_w_m2 <= cap)
+    );
+
+    assert!(
+        plan_aerobraking_campaign(
+            &config.vehicle,
+            &config.origin,
+            &config.constants,
+            2_000.0,
+            33_000.0,
+            110.0,
+            None,
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn identical_origin_and_destination_is_rejected() {
+    let (mut config, cruise) = earth_mars_offline_setup("Starship");
+    config.destination = config.origin.clone();
+
+    let err = plan_mission_with_cruise(&config, cruise).expect_err("same-body mission");
+    assert!(
+        matches!(err, MissionError::IdenticalBodies(ref body) if *body == config.origin.spice_name)
+    );
+}
+
+#[test]
+fn vehicle_without_escape_budget_is_rejected_up_front() {
+    let (mut config, cruise) = earth_mars_offline_setup("Chemical");
+    config.vehicle.propellant_mass_kg = 1_000.0;
+
+    let err = plan_mission_with_cruise(&config, cruise).expect_err("underfuelled vehicle");
+    let MissionError::InsufficientDeltaV {
+        required_km_s,
+        available_km_s,
+        ..
+    } = err
+    else {
+        panic!("unexpected error: {err}");
+    };
+    assert!(available_km_s < required_km_s);
+    assert!((available_km_s - config.vehicle.total_delta_v_km_s().unwrap()).abs() < 1e-12);
+}
+
+#[test]
+fn ballistic_cruise_reports_arc_length_and_sweep() {
+    let (_, cruise) = earth_mars_offline_setup("Starship");
+
+    // Endpoints are 140 degrees apart on circular orbits of 1 and 1.524 AU.
+    let sweep = cruise.sweep_angle_deg.expect("sweep angle");
+    assert!((sweep - 140.0).abs() < 1e-6, "{sweep}");
+
+    let arc_au = cruise.arc_length_km.expect("arc length") / AU_KM;
+    let chord_au = {
+        let d = [
+            cruise.arrival_state.position_km[0] - cruise.departure_state.position_km[0],
+            cruise.arrival_state.position_km[1] - cruise.departure_state.position_km[1],
+            cruise.arrival_state.position_km[2] - cruise.departure_state.position_km[2],
+        ];
+        (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt() / AU_KM
+    };
+    assert!(arc_au > chord_au);
+    // Bounded by the sweep traced at the outer radius.
+    assert!(arc_au < 1.524 * 140.0_f64.to_radians());
+}
+
+#[test]
+fn impulsive_cruise_sizes_burns_with_rocket_equation() {
+    let (config, cruise) = earth_mars_offline_setup("Starship");
+    let altitudes = (
+        config.origin.default_parking_altitude_km,
+        config.destination.default_parking_altitude_km,
+    );
+    let burns = cruise
+        .size_impulsive_burns(
+            &config.vehicle,
+            &config.origin,
+            &config.destination,
+            altitudes,
+            &config.constants,
+        )
+        .expect("impulsive burns");
+
+    assert!(burns.vinf_depart_km_s > 0.0 && burns.vinf_arrive_km_s > 0.0);
+    assert!(burns.dv_depart_km_s > 0.0 && burns.dv_arrive_km_s > 0.0);
+    assert_eq!(burns.shortfall_km_s, 0.0);
+
+    let ve = config.vehicle.propulsion.exhaust_velocity_m_s().unwrap();
+    let m0 = config.vehicle.initial_mass_kg();
+    let m2 = m0 * (-(burns.dv_depart_km_s + burns.dv_arrive_km_s) * 1_000.0 / ve).exp();
+    assert!((burns.propellant_total_kg() - (m0 - m2)).abs() < 1e-6 * m0);
+
+    // A higher capture orbit lowers the arrival burn and the propellant it needs.
+    let higher = cruise
+        .size_impulsive_burns(
+            &config.vehicle,
+            &config.origin,
+            &config.destination,
+            (altitudes.0, altitudes.1 + 10_000.0),
+            &config.constants,
+        )
+        .expect("impulsive burns");
+    assert_eq!(higher.dv_depart_km_s, burns.dv_depart_km_s);
+    assert!(higher.dv_arrive_km_s < burns.dv_arrive_km_s);
+    assert!(higher.propellant_total_kg() < burns.propellant_total_kg());
+}
+
+#[test]
+fn gm_mismatches_flag_catalog_values_beyond_tolerance() {
+    let planets = load_planets("configs/bodies").expect("planets catalog");
+    let earth = planets.iter().find(|p| p.name == "EARTH").expect("Earth");
+    let mars = planets.iter().find(|p| p.name == "MARS").expect("Mars");
+
+    let mut constants = ConstantsSet::default();
+    assert!(gm_mismatches(&planets, &constants, GM_MISMATCH_TOLERANCE).is_empty());
+
+    // A kernel Earth GM 0.1% off is reported; a Mars GM within tolerance is not.
+    constants
+        .body_mu_km3_s2
+        .insert(earth.spice_name.clone(), earth.mu_km3_s2 * 1.001);
+    constants
+        .body_mu_km3_s2
+        .insert(mars.spice_name.clone(), mars.mu_km3_s2 * (1.0 + 1e-9));
+    let mismatches = gm_mismatches(&planets, &constants, GM_MISMATCH_TOLERANCE);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].body, "EARTH");
+    assert_eq!(mismatches[0].catalog_mu_km3_s2, earth.mu_km3_s2);
+    assert!((mismatches[0].relative_difference() - 0.001 / 1.001).abs() < 1e-12);
+}
+
+/// Circular Earth and Mars tracks every two days from ET 0, with Mars leading Earth by
+/// `mars_lead_deg` at the start (44.3° is the Hohmann phase angle, making ET 0 the optimal
+/// departure).
+fn circular_tracks_source(departure_label: &str, mars_lead_deg: f64) -> FixedEphemerisSource {
+    let mut source = FixedEphemerisSource::new();
+    source.insert_epoch(departure_label, 0.0);
+    for (body, radius_au, longitude0_deg) in [("EARTH", 1.0, 0.0), ("MARS", 1.524, mars_lead_deg)] {
+        let target = ephemeris::normalize_heliocentric_target_name(body);
+        let rate_deg_per_day =
+            (MU_SUN / (radius_au * AU_KM).powi(3)).sqrt().to_degrees() * 86_400.0;
+        for day in (0..=700).step_by(2) {
+            let longitude = longitude0_deg + rate_deg_per_day * day as f64;
+            source.insert_state(
+                &target,
+                "SUN",
+                "ECLIPJ2000",
+                day as f64 * 86_400.0,
+                circular_state(radius_au, longitude),
+            );
+        }
+    }
+    source
+}
+
+#[test]
+fn fixed_source_plans_a_full_mission_without_kernels() {
+    let label = "2030 JAN 01 00:00:00 TDB";
+    let source = circular_tracks_source(label, 44.3);
+
+    // Between samples the Hermite interpolant stays on the circular orbit.
+    let earth = ephemeris::normalize_heliocentric_target_name("EARTH");
+    let midway = source
+        .state_vector_et(&earth, "SUN", "ECLIPJ2000", "NONE", 86_400.0)
+        .expect("interpolated state");
+    let radius = (midway.position_km[0].powi(2) + midway.position_km[1].powi(2)).sqrt();
+    assert!((radius / AU_KM - 1.0).abs() < 1e-6);
+    assert!(
+        source
+            .state_vector_et(&earth, "SUN", "ECLIPJ2000", "NONE", -1.0)
+            .is_err()
+    );
+    assert_eq!(source.epoch_seconds(label).expect("labelled epoch"), 0.0);
+    assert_eq!(source.format_epoch(0.0).expect("label"), label);
+
+    let (mut config, _) = earth_mars_offline_setup("Starship");
+    config.departure.departure_epoch = label.to_string();
+    config.cruise.departure_epoch = label.to_string();
+    config.departure.required_v_infinity = None;
+
+    let first = plan_mission_with_source(&config, &source).expect("offline mission");
+    let second = plan_mission_with_source(&config, &source).expect("offline mission");
+    // Earth→Mars Hohmann: ~259 days, ~2.9 km/s departure v-infinity.
+    assert!((first.cruise.time_of_flight_days - 259.0).abs() < 30.0);
+    assert!((first.departure.hyperbolic_excess_km_s - 2.95).abs() < 0.5);
+    assert!(first.departure.delta_v_required > 0.0 && first.arrival.delta_v_required > 0.0);
+    assert_eq!(
+        first.cruise.time_of_flight_days,
+        second.cruise.time_of_flight_days
+    );
+    assert_eq!(
+        first.departure.delta_v_required,
+        second.departure.delta_v_required
+    );
+}
+
+#[test]
+fn continuous_cruise_aims_at_the_destination_on_the_arrival_epoch() {
+    // Mars leads by 70°, so a Hohmann-like spiral only meets it after ~56 days of coasting.
+    let departure = "2030 JAN 01 00:00:00 TDB";
+    let arrival = "2030 NOV 12 00:00:00 TDB";
+    let mut source = circular_tracks_source(departure, 70.0);
+    source.insert_epoch(arrival, 315.0 * 86_400.0);
+
+    let (config, _) = earth_mars_offline_setup("Ion");
+    let mut cruise = config.cruise.clone();
+    cruise.departure_epoch = departure.to_string();
+    let plan = |cruise: &InterplanetaryConfig| {
+        plan_interplanetary_with_source(
+            &source,
+            &config.vehicle,
+            cruise,
+            &config.origin,
+            &config.destination,
+            &config.constants,
+        )
+        .expect("continuous cruise")
+    };
+
+    // Without an arrival epoch the leg stops at Mars' radius, far from Mars itself.
+    let free = plan(&cruise);
+    let free_miss = free.miss_distance_km.expect("free-arrival miss distance");
+    assert!(free_miss > 0.2 * AU_KM, "free miss {free_miss:.0} km");
+
+    cruise.arrival_epoch = Some(arrival.to_string());
+    let aimed = plan(&cruise);
+    let aimed_miss = aimed.miss_distance_km.expect("aimed miss distance");
+    assert!((aimed.time_of_flight_days - 315.0).abs() < 1e-6);
+    assert!(aimed_miss < 10_000.0, "aimed miss {aimed_miss:.0} km");
+    let mars = source
+        .state_vector_et(
+            &ephemeris::normalize_heliocentric_target_name("MARS"),
+            "SUN",
+            "ECLIPJ2000",
+            "NONE",
+            315.0 * 86_400.0,
+        )
+        .expect("Mars at arrival");
+    assert_eq!(aimed.arrival_state.position_km, mars.position_km);
+}
+
+#[test]
+fn throttled_cruise_flies_its_schedule_over_the_arrival_epoch() {
+    let departure = "2030 JAN 01 00:00:00 TDB";
+    let arrival = "2030 SEP 17 00:00:00 TDB";
+    let tof_s = 259.0 * 86_400.0;
+    let mut source = circular_tracks_source(departure, 44.3);
+    source.insert_epoch(arrival, tof_s);
+
+    let (mut config, _) = earth_mars_offline_setup("Ion");
+    let throttled = |throttle_profile| PropulsionMode::Throttled {
+        max_thrust_newtons: 3_000.0,
+        isp_seconds: 4_000.0,
+        throttle_profile,
+    };
+    // Coast for a tenth of the cruise, burn at full throttle for a hundredth, then coast.
+    config.vehicle.propulsion = throttled(vec![(0.0, 0.0), (0.1, 1.0), (0.11, 0.0)]);
+    let mut cruise = config.cruise.clone();
+    cruise.departure_epoch = departure.to_string();
+    cruise.propulsion_mode = config.vehicle.propulsion.clone();
+    let plan = |vehicle: &_, cruise: &InterplanetaryConfig| {
+        plan_interplanetary_with_source(
+            &source,
+            vehicle,
+            cruise,
+            &config.origin,
+            &config.destination,
+            &config.constants,
+        )
+    };
+
+    assert!(matches!(
+        plan(&config.vehicle, &cruise),
+        Err(InterplanetaryError::ArrivalEpochRequired)
+    ));
+
+    cruise.arrival_epoch = Some(arrival.to_string());
+    let flown = plan(&config.vehicle, &cruise).expect("throttled cruise");
+    assert!((flown.time_of_flight_days - 259.0).abs() < 1e-6);
+    let burn_s = 0.01 * tof_s;
+    let expected = 3_000.0 / (4_000.0 * G0) * burn_s;
+    let used = flown.propellant_used_kg.expect("propellant used");
+    assert!(
+        (used - expected).abs() < 1e-6 * expected,
+        "{used} vs {expected}"
+    );
+    let initial_mass = config.vehicle.initial_mass_kg();
+    for sample in &flown.trajectory {
+        if sample.time_s <= 0.1 * tof_s {
+            assert_eq!(sample.mass_kg, initial_mass);
+        } else if sample.time_s >= 0.11 * tof_s {
+            assert!((sample.mass_kg - (initial_mass - used)).abs() < 1e-9);
+        }
+    }
+    assert!(flown.miss_distance_km.is_some_and(|miss| miss > 0.0));
+
+    let mut unsorted = config.vehicle.clone();
+    unsorted.propulsion = throttled(vec![(0.5, 1.0), (0.2, 0.0)]);
+    assert!(matches!(
+        plan(&unsorted, &cruise),
+        Err(InterplanetaryError::InvalidThrottleProfile)
+    ));
+}
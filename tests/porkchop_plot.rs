@@ -1,14 +1,10 @@
 use assert_cmd::Command;
 use std::fs::{self, File};
 use std::io::Write;
+use std::path::Path;
 
-#[test]
-fn porkchop_plot_renders_png() {
-    let dir = tempfile::tempdir().expect("tempdir");
-    let csv_path = dir.path().join("pork.csv");
-    let png_path = dir.path().join("pork.png");
-
-    let mut file = File::create(&csv_path).expect("csv create");
+fn write_sample_csv(path: &Path) {
+    let mut file = File::create(path).expect("csv create");
     writeln!(
         file,
         "depart_et,arrive_et,depart_utc,arrive_utc,tof_days,c3_km2_s2,vinf_dep_km_s,vinf_arr_km_s,dv_dep_km_s,dv_arr_km_s,dv_total_km_s,lambert_path,feasible,origin_body,dest_body,rpark_dep_km,rpark_arr_km"
@@ -30,6 +26,378 @@ fn porkchop_plot_renders_png() {
         )
         .unwrap();
     }
+}
+
+#[test]
+fn porkchop_plot_renders_png() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let csv_path = dir.path().join("pork.csv");
+    let png_path = dir.path().join("pork.png");
+
+    write_sample_csv(&csv_path);
+
+    Command::cargo_bin("porkchop_plot")
+        .expect("porkchop_plot bin")
+        .args([
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--output",
+            png_path.to_str().unwrap(),
+            "--metric",
+            "dv_total",
+            "--width",
+            "400",
+            "--height",
+            "300",
+        ])
+        .assert()
+        .success();
+
+    let metadata = fs::metadata(png_path).expect("png metadata");
+    assert!(metadata.len() > 0, "PNG output should not be empty");
+}
+
+#[test]
+fn porkchop_plot_renders_depart_tof_axes() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let csv_path = dir.path().join("pork.csv");
+    let png_path = dir.path().join("pork_tof.png");
+    write_sample_csv(&csv_path);
+
+    Command::cargo_bin("porkchop_plot")
+        .expect("porkchop_plot bin")
+        .args([
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--output",
+            png_path.to_str().unwrap(),
+            "--axes",
+            "depart-tof",
+            "--width",
+            "400",
+            "--height",
+            "300",
+        ])
+        .assert()
+        .success();
+
+    let metadata = fs::metadata(png_path).expect("png metadata");
+    assert!(metadata.len() > 0, "PNG output should not be empty");
+}
+
+#[test]
+fn porkchop_plot_renders_inverted_tof_heatmap() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let csv_path = dir.path().join("pork.csv");
+    let png_path = dir.path().join("pork_tof_metric.png");
+    write_sample_csv(&csv_path);
+
+    Command::cargo_bin("porkchop_plot")
+        .expect("porkchop_plot bin")
+        .args([
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--output",
+            png_path.to_str().unwrap(),
+            "--metric",
+            "tof",
+            "--invert",
+            "--width",
+            "400",
+            "--height",
+            "300",
+        ])
+        .assert()
+        .success();
+
+    let metadata = fs::metadata(png_path).expect("png metadata");
+    assert!(metadata.len() > 0, "PNG output should not be empty");
+}
+
+#[test]
+fn porkchop_plot_renders_each_style() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let csv_path = dir.path().join("pork.csv");
+    write_sample_csv(&csv_path);
+
+    for style in ["filled", "contours", "both"] {
+        let png_path = dir.path().join(format!("pork_{style}.png"));
+        Command::cargo_bin("porkchop_plot")
+            .expect("porkchop_plot bin")
+            .args([
+                "--input",
+                csv_path.to_str().unwrap(),
+                "--output",
+                png_path.to_str().unwrap(),
+                "--style",
+                style,
+                "--width",
+                "400",
+                "--height",
+                "300",
+            ])
+            .assert()
+            .success();
+        assert!(fs::metadata(&png_path).expect("png metadata").len() > 0);
+    }
+}
+
+#[test]
+fn porkchop_plot_marks_a_mission_point() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let csv_path = dir.path().join("pork.csv");
+    let png_path = dir.path().join("pork_marked.png");
+    write_sample_csv(&csv_path);
+
+    let base_args = [
+        "--input",
+        csv_path.to_str().unwrap(),
+        "--output",
+        png_path.to_str().unwrap(),
+        "--width",
+        "400",
+        "--height",
+        "300",
+    ];
+
+    // Both ends of the mission are needed to place the marker.
+    Command::cargo_bin("porkchop_plot")
+        .expect("porkchop_plot bin")
+        .args(base_args)
+        .args(["--mark-depart", "2003-03-02T12:00:00"])
+        .assert()
+        .failure();
+
+    if solar_travel_calculator::ephemeris::load_default_kernels().is_err() {
+        eprintln!("Skipping mission marker rendering: SPICE kernels unavailable");
+        return;
+    }
+    // The departure lies inside the sample grid; the arrival is far past its end.
+    Command::cargo_bin("porkchop_plot")
+        .expect("porkchop_plot bin")
+        .args(base_args)
+        .args([
+            "--mark-depart",
+            "2003-03-02T12:00:00",
+            "--mark-arrive",
+            "2010-01-01T00:00:00",
+        ])
+        .assert()
+        .success();
+    assert!(fs::metadata(&png_path).expect("png metadata").len() > 0);
+}
+
+#[test]
+fn porkchop_plot_renders_log_scaled_c3() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let csv_path = dir.path().join("pork.csv");
+    let png_path = dir.path().join("pork_log.png");
+    write_sample_csv(&csv_path);
+
+    Command::cargo_bin("porkchop_plot")
+        .expect("porkchop_plot bin")
+        .args([
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--output",
+            png_path.to_str().unwrap(),
+            "--metric",
+            "c3",
+            "--scale",
+            "log",
+            "--width",
+            "400",
+            "--height",
+            "300",
+        ])
+        .assert()
+        .success();
+    assert!(fs::metadata(&png_path).expect("png metadata").len() > 0);
+}
+
+#[test]
+fn porkchop_plot_writes_svg_and_png() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let csv_path = dir.path().join("pork.csv");
+    write_sample_csv(&csv_path);
+
+    // Formats inferred from the extension, then SVG forced onto an unrelated one.
+    let svg_path = dir.path().join("pork.svg");
+    let png_path = dir.path().join("pork_inferred.png");
+    let forced_path = dir.path().join("pork_forced.img");
+    for (output, format) in [
+        (&svg_path, None),
+        (&png_path, None),
+        (&forced_path, Some("svg")),
+    ] {
+        let mut cmd = Command::cargo_bin("porkchop_plot").expect("porkchop_plot bin");
+        cmd.args([
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--output",
+            output.to_str().unwrap(),
+            "--width",
+            "400",
+            "--height",
+            "300",
+        ]);
+        if let Some(format) = format {
+            cmd.args(["--format", format]);
+        }
+        cmd.assert().success();
+    }
+
+    let svg = fs::read_to_string(&svg_path).expect("svg output");
+    assert!(svg.contains("<svg"), "SVG output should be an SVG document");
+    let png = fs::read(&png_path).expect("png output");
+    assert!(png.starts_with(b"\x89PNG"), "PNG output should be a PNG");
+    let forced = fs::read_to_string(&forced_path).expect("forced svg output");
+    assert!(
+        forced.contains("<svg"),
+        "--format svg should override the extension"
+    );
+}
+
+#[test]
+fn exported_epochs_are_quantized_to_resolution() {
+    use solar_travel_calculator::export::porkchop::{Record, quantize_et};
+
+    let record = Record {
+        depart_et: 815_011_200.000_000_1,
+        arrive_et: 833_155_200.499_999_9,
+        depart_utc: "DUTC",
+        arrive_utc: "AUTC",
+        tof_days: 210.0,
+        c3: 12.0,
+        vinf_dep: 3.0,
+        vinf_arr: 2.5,
+        dv_dep: 3.6,
+        dv_arr: 2.1,
+        dv_total: 5.7,
+        propellant_used_kg: 0.0,
+        burn_time_s: 0.0,
+        final_mass_kg: 0.0,
+        path: "short",
+        feasible: true,
+        origin_body: "EARTH",
+        dest_body: "MARS",
+        rpark_dep_km: 6778.0,
+        rpark_arr_km: 3778.0,
+        et_resolution_s: 1.0,
+        depart_index: 0,
+        arrive_index: 0,
+        revolutions: 0,
+    };
+    let mut out = Vec::new();
+    record.write_to(&mut out).expect("write record");
+    let line = String::from_utf8(out).expect("utf8");
+    assert!(line.starts_with("815011200,833155200,"), "{line}");
+
+    assert_eq!(quantize_et(100.26, 0.5), 100.5);
+    assert_eq!(quantize_et(100.26, 0.0), 100.26);
+}
+
+#[test]
+fn exported_rows_carry_grid_indices() {
+    use solar_travel_calculator::export::porkchop::{Record, grid_index, write_header};
+
+    let start = 815_011_200.0;
+    let step = 5.0 * 86_400.0;
+    assert_eq!(grid_index(start, start, step), 0);
+    assert_eq!(grid_index(start + 3.0 * step + 0.4, start, step), 3);
+    assert_eq!(grid_index(start - step, start, step), 0);
+    assert_eq!(grid_index(start + step, start, 0.0), 0);
+
+    let record = Record {
+        depart_et: start + 2.0 * step,
+        arrive_et: start + 40.0 * step,
+        depart_utc: "DUTC",
+        arrive_utc: "AUTC",
+        tof_days: 190.0,
+        c3: 12.0,
+        vinf_dep: 3.0,
+        vinf_arr: 2.5,
+        dv_dep: 3.6,
+        dv_arr: 2.1,
+        dv_total: 5.7,
+        propellant_used_kg: 0.0,
+        burn_time_s: 0.0,
+        final_mass_kg: 0.0,
+        path: "short",
+        feasible: true,
+        origin_body: "EARTH",
+        dest_body: "MARS",
+        rpark_dep_km: 6778.0,
+        rpark_arr_km: 3778.0,
+        et_resolution_s: 1.0,
+        depart_index: grid_index(start + 2.0 * step, start, step),
+        arrive_index: grid_index(start + 40.0 * step, start + 30.0 * step, step),
+        revolutions: 0,
+    };
+    let mut out = Vec::new();
+    write_header(&mut out).expect("write header");
+    record.write_to(&mut out).expect("write record");
+    let text = String::from_utf8(out).expect("utf8");
+    let mut lines = text.lines();
+    let header: Vec<&str> = lines.next().expect("header").split(',').collect();
+    let row: Vec<&str> = lines.next().expect("row").split(',').collect();
+    assert_eq!(header.len(), row.len());
+
+    let column = |name: &str| header.iter().position(|h| *h == name).expect(name);
+    assert_eq!(row[column("depart_index")], "2");
+    assert_eq!(row[column("arrive_index")], "10");
+}
+
+#[test]
+fn porkchop_plot_renders_exported_rows_with_revolutions() {
+    use solar_travel_calculator::export::porkchop::{Record, write_header};
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let csv_path = dir.path().join("pork_revs.csv");
+    let png_path = dir.path().join("pork_revs.png");
+
+    let mut out = Vec::new();
+    write_header(&mut out).expect("write header");
+    for i in 0..3u32 {
+        let depart_et = 1.0e8 + i as f64 * 10_000.0;
+        let path = if i == 0 { "short" } else { "1rev-left" };
+        let record = Record {
+            depart_et,
+            arrive_et: depart_et + 200_000.0,
+            depart_utc: "DUTC",
+            arrive_utc: "AUTC",
+            tof_days: 150.0 + i as f64,
+            c3: 12.0,
+            vinf_dep: 3.0,
+            vinf_arr: 2.5,
+            dv_dep: 3.6,
+            dv_arr: 2.1,
+            dv_total: 5.7 + i as f64 * 0.1,
+            propellant_used_kg: 0.0,
+            burn_time_s: 0.0,
+            final_mass_kg: 0.0,
+            path,
+            feasible: true,
+            origin_body: "EARTH",
+            dest_body: "MARS",
+            rpark_dep_km: 6778.0,
+            rpark_arr_km: 3778.0,
+            et_resolution_s: 1.0,
+            depart_index: i as usize,
+            arrive_index: 0,
+            revolutions: i.min(1),
+        };
+        record.write_to(&mut out).expect("write record");
+    }
+    let text = String::from_utf8(out).expect("utf8");
+    let header: Vec<&str> = text.lines().next().expect("header").split(',').collect();
+    let last: Vec<&str> = text.lines().last().expect("row").split(',').collect();
+    let revs = header
+        .iter()
+        .position(|h| *h == "revolutions")
+        .expect("revolutions column");
+    assert_eq!(last[revs], "1");
+    fs::write(&csv_path, &text).expect("csv write");
 
     Command::cargo_bin("porkchop_plot")
         .expect("porkchop_plot bin")
@@ -51,3 +419,61 @@ fn porkchop_plot_renders_png() {
     let metadata = fs::metadata(png_path).expect("png metadata");
     assert!(metadata.len() > 0, "PNG output should not be empty");
 }
+
+#[test]
+fn binary_grid_round_trips_and_renders() {
+    use solar_travel_calculator::export::porkchop::{
+        Grid, GridMetadata, read_binary, read_binary_from, write_binary,
+    };
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let grid_path = dir.path().join("pork.grid");
+    let png_path = dir.path().join("pork_grid.png");
+
+    let depart_et: Vec<f64> = (0..3).map(|i| 1.0e8 + i as f64 * 86_400.0).collect();
+    let arrive_et: Vec<f64> = (0..4).map(|i| 1.2e8 + i as f64 * 86_400.0).collect();
+    let mut grid = Grid::empty(depart_et, arrive_et);
+    for row in 0..3 {
+        for col in 0..4 {
+            let idx = grid.index(row, col);
+            grid.values[idx] = 4.0 + row as f64 * 0.1 + col as f64 * 0.05;
+            grid.feasible[idx] = idx != 5;
+        }
+    }
+    let metadata = GridMetadata {
+        metric: "dv_total_km_s".to_string(),
+        origin_body: "EARTH".to_string(),
+        dest_body: "MARS".to_string(),
+    };
+    write_binary(&grid_path, &grid, &metadata).expect("write binary grid");
+
+    let (read_grid, read_metadata) = read_binary(&grid_path).expect("read binary grid");
+    assert_eq!(read_grid, grid);
+    assert_eq!(read_metadata, metadata);
+
+    // Version-2 readers may append header fields; a version-1 reader must skip them.
+    let mut bytes = fs::read(&grid_path).unwrap();
+    let header_len = u32::from_le_bytes(bytes[12..16].try_into().unwrap());
+    bytes[8..12].copy_from_slice(&2u32.to_le_bytes());
+    bytes[12..16].copy_from_slice(&(header_len + 3).to_le_bytes());
+    let header_end = 16 + header_len as usize;
+    bytes.splice(header_end..header_end, [1u8, 2, 3]);
+    let (future_grid, _) = read_binary_from(&mut bytes.as_slice()).expect("newer header");
+    assert_eq!(future_grid, grid);
+
+    Command::cargo_bin("porkchop_plot")
+        .expect("porkchop_plot bin")
+        .args([
+            "--input",
+            grid_path.to_str().unwrap(),
+            "--output",
+            png_path.to_str().unwrap(),
+            "--width",
+            "400",
+            "--height",
+            "300",
+        ])
+        .assert()
+        .success();
+    assert!(fs::metadata(png_path).expect("png metadata").len() > 0);
+}
@@ -0,0 +1,175 @@
+use std::fs;
+
+use solar_travel_calculator::config::{VehicleConfig, load_vehicle_configs};
+use solar_travel_calculator::core::constants::G0;
+use solar_travel_calculator::propulsion::{PropulsionMode, Stage, Vehicle};
+use solar_travel_calculator::transfer::vehicle;
+
+fn impulsive(isp_seconds: f64) -> PropulsionMode {
+    PropulsionMode::Impulsive {
+        max_delta_v_km_s: 10.0,
+        isp_seconds,
+        max_thrust_newtons: None,
+    }
+}
+
+fn two_stage() -> Vehicle {
+    Vehicle {
+        name: "Two Stage".to_string(),
+        dry_mass_kg: 2_000.0,
+        propellant_mass_kg: 8_000.0,
+        propulsion: impulsive(450.0),
+        aero: None,
+        stages: vec![Stage {
+            dry_mass_kg: 5_000.0,
+            propellant_mass_kg: 40_000.0,
+            propulsion: impulsive(300.0),
+        }],
+    }
+}
+
+#[test]
+fn single_stage_burn_follows_the_rocket_equation() {
+    let mut single = two_stage();
+    single.stages.clear();
+    assert_eq!(single.initial_mass_kg(), 10_000.0);
+
+    let mut staging = single.staging();
+    let burn = staging.burn(2.0).expect("impulsive burn");
+    let expected = 10_000.0 * (1.0 - (-2_000.0 / (450.0 * G0)).exp());
+    assert!((burn.propellant_kg - expected).abs() < 1e-6);
+    assert_eq!(burn.stages_jettisoned, 0);
+    assert_eq!(burn.shortfall_km_s, 0.0);
+    assert!((staging.mass_kg() - (10_000.0 - expected)).abs() < 1e-6);
+}
+
+#[test]
+fn staging_drops_spent_dry_mass_and_switches_isp() {
+    let vehicle = two_stage();
+    assert_eq!(vehicle.initial_mass_kg(), 55_000.0);
+    assert_eq!(vehicle.total_propellant_kg(), 48_000.0);
+
+    let ve1 = 300.0 * G0;
+    let ve2 = 450.0 * G0;
+    let stage1_dv = ve1 * (55_000.0_f64 / 15_000.0).ln();
+    let core_dv = 1_000.0;
+
+    let mut staging = vehicle.staging();
+    let burn = staging
+        .burn((stage1_dv + core_dv) / 1_000.0)
+        .expect("staged burn");
+    assert_eq!(burn.stages_jettisoned, 1);
+    assert_eq!(burn.shortfall_km_s, 0.0);
+    let core_propellant = 10_000.0 * (1.0 - (-core_dv / ve2).exp());
+    assert!((burn.propellant_kg - (40_000.0 + core_propellant)).abs() < 1e-6);
+    assert!((staging.mass_kg() - (10_000.0 - core_propellant)).abs() < 1e-6);
+
+    // Asking for more than the core holds leaves a shortfall instead of failing.
+    let mut staging = vehicle.staging();
+    let over = staging.burn(20.0).expect("over-budget burn");
+    assert!(over.shortfall_km_s > 0.0);
+    assert!((over.propellant_kg - vehicle.total_propellant_kg()).abs() < 1e-6);
+    assert!((staging.mass_kg() - vehicle.dry_mass_kg).abs() < 1e-6);
+}
+
+#[test]
+fn total_delta_v_sums_each_stage_rocket_equation() {
+    // Stage 1: 300 s from 55 t to 15 t; core: 450 s from 10 t to 2 t.
+    let expected = (300.0 * G0 * (55.0_f64 / 15.0).ln() + 450.0 * G0 * 5.0_f64.ln()) / 1_000.0;
+    let budget = two_stage().total_delta_v_km_s().expect("impulsive budget");
+    assert!((budget - expected).abs() < 1e-9);
+    assert!((budget - 10.925).abs() < 1e-3);
+
+    let mut hybrid = two_stage();
+    hybrid.propulsion = PropulsionMode::Hybrid;
+    assert_eq!(hybrid.total_delta_v_km_s(), None);
+}
+
+#[test]
+fn payload_and_mass_ratio_follow_the_rocket_equation() {
+    let mut single = two_stage();
+    single.stages.clear();
+    let ve = 450.0 * G0;
+    let ratio = (3_000.0 / ve).exp();
+
+    let mass_ratio = single.mass_ratio_for_dv(3.0).expect("feasible burn");
+    assert!((mass_ratio - ratio).abs() < 1e-9);
+    // (dry + prop + P) / (dry + P) = R  ⇒  P = prop / (R - 1) - dry.
+    let payload = single.deliverable_payload_kg(3.0).expect("payload");
+    assert!((payload - (8_000.0 / (ratio - 1.0) - 2_000.0)).abs() < 1e-3);
+
+    // Beyond the vehicle's budget the burn is infeasible rather than a panic.
+    assert_eq!(single.mass_ratio_for_dv(20.0), None);
+    assert_eq!(single.deliverable_payload_kg(20.0), None);
+}
+
+fn catalog_entry(name: &str, dry_mass_kg: f64, propellant_mass_kg: f64) -> VehicleConfig {
+    let toml = format!(
+        r#"name = "{name}"
+dry_mass_kg = {dry_mass_kg:.1}
+propellant_mass_kg = {propellant_mass_kg:.1}
+
+[propulsion]
+type = "impulsive"
+max_delta_v_km_s = 6.0
+isp_seconds = 350.0
+"#
+    );
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("vehicle.toml");
+    fs::write(&path, toml).expect("write vehicle");
+    load_vehicle_configs(&path)
+        .expect("vehicle catalog")
+        .remove(0)
+}
+
+#[test]
+fn rank_orders_by_payload_and_drops_infeasible_vehicles() {
+    let catalog = [
+        catalog_entry("Small", 1_000.0, 9_000.0),
+        catalog_entry("Tiny", 1_000.0, 1_000.0),
+        catalog_entry("Large", 5_000.0, 45_000.0),
+    ];
+    let ranked = vehicle::rank(&catalog, 4.0);
+    let names: Vec<_> = ranked.iter().map(|r| r.vehicle.name.as_str()).collect();
+    assert_eq!(names, ["Large", "Small"]);
+    assert!(ranked[0].payload_kg > ranked[1].payload_kg);
+    assert!((ranked[0].mass_ratio - ranked[1].mass_ratio).abs() < 1e-9);
+}
+
+#[test]
+fn vehicle_catalog_parses_lower_stages() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("launcher.toml");
+    fs::write(
+        &path,
+        r#"name = "Launcher"
+dry_mass_kg = 2000.0
+propellant_mass_kg = 8000.0
+
+[propulsion]
+type = "impulsive"
+max_delta_v_km_s = 6.0
+isp_seconds = 450.0
+
+[[stages]]
+dry_mass_kg = 5000.0
+propellant_mass_kg = 40000.0
+
+[stages.propulsion]
+type = "impulsive"
+max_delta_v_km_s = 4.0
+isp_seconds = 300.0
+"#,
+    )
+    .expect("write vehicle");
+
+    let configs = load_vehicle_configs(&path).expect("vehicle catalog");
+    let launcher = vehicle::from_config(&configs[0]).expect("convert vehicle");
+    assert_eq!(launcher.stages.len(), 1);
+    assert_eq!(launcher.initial_mass_kg(), 55_000.0);
+    assert!(matches!(
+        launcher.stages[0].propulsion,
+        PropulsionMode::Impulsive { isp_seconds, .. } if isp_seconds == 300.0
+    ));
+}
@@ -0,0 +1,45 @@
+use solar_travel_calculator::core::units::{Km, KmPerSec, Meters, Vector3Km};
+
+#[test]
+fn unit_newtypes_convert_and_combine_like_the_free_functions() {
+    let altitude = Km(400.0);
+    assert_eq!(Meters::from(altitude), Meters(400_000.0));
+    assert_eq!(Km::from(Meters(6_378_000.0)) + altitude, Km(6_778.0));
+    assert_eq!(altitude / Km(200.0), 2.0);
+    assert_eq!(KmPerSec::from_m_per_s(7_670.0).to_m_per_s(), 7_670.0);
+    assert_eq!(Km(7.0) / KmPerSec(3.5), 2.0);
+
+    let r1 = Vector3Km([3.0, 0.0, 0.0]);
+    let r2 = Vector3Km::from([Km(0.0), Km(4.0), Km(0.0)]);
+    assert_eq!((r2 - r1).norm(), Km(5.0));
+    assert_eq!(r1.dot(&r2), 0.0);
+    assert_eq!(r1.cross(&r2), [0.0, 0.0, 12.0]);
+    assert_eq!(2.0 * r1 + r2, Vector3Km([6.0, 4.0, 0.0]));
+    assert_eq!(r2.unit(), Some([0.0, 1.0, 0.0]));
+    assert_eq!(Vector3Km::default().unit(), None);
+    assert_eq!(r1.to_meters()[0], Meters(3_000.0));
+}
+
+#[test]
+fn angle_helpers_wrap_convert_and_clamp() {
+    use std::f64::consts::{PI, TAU};
+
+    use solar_travel_calculator::core::angles::{
+        acos_clamped, deg_to_rad, rad_to_deg, wrap_to_2pi, wrap_to_pi,
+    };
+
+    assert!((deg_to_rad(180.0) - PI).abs() < 1e-15);
+    assert!((rad_to_deg(deg_to_rad(37.5)) - 37.5).abs() < 1e-12);
+
+    assert!((wrap_to_2pi(-PI / 2.0) - 1.5 * PI).abs() < 1e-12);
+    assert!((wrap_to_2pi(5.0 * TAU + 1.0) - 1.0).abs() < 1e-9);
+    assert_eq!(wrap_to_2pi(-1e-18), 0.0);
+    assert!((wrap_to_pi(1.5 * PI) + PI / 2.0).abs() < 1e-12);
+    assert_eq!(wrap_to_pi(PI), PI);
+    assert_eq!(wrap_to_pi(-PI), PI);
+
+    // Round-off just past ±1 must not turn into NaN.
+    assert_eq!(acos_clamped(1.0 + 1e-15), 0.0);
+    assert_eq!(acos_clamped(-1.0 - 1e-15), PI);
+    assert!(acos_clamped(f64::NAN).is_nan());
+}